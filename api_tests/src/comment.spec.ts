@@ -966,7 +966,7 @@ test("Lock comment", async () => {
       betaPost.post.id,
       comment3.comment_view.comment.id,
     ),
-  ).rejects.toStrictEqual(new LemmyError("locked", statusBadRequest));
+  ).rejects.toStrictEqual(new LemmyError("comment_locked", statusBadRequest));
 
   // newBeta should still be able to respond to comment1
   expect(
@@ -0,0 +1,80 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_comment_response,
+  context::LemmyContext,
+  notify::NotifyData,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_community_mod_action,
+};
+use lemmy_db_schema::source::comment::{Comment, CommentUpdateForm};
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{ApproveComment, CommentResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn approve_comment(
+  Json(data): Json<ApproveComment>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let comment_id = data.comment_id;
+  let local_instance_id = local_user_view.person.instance_id;
+
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+
+  check_community_mod_action(
+    &local_user_view,
+    &orig_comment.community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+
+  // Only publish the comment once, approving it again is a no-op
+  if orig_comment.comment.pending_approval {
+    let form = CommentUpdateForm {
+      pending_approval: Some(false),
+      ..Default::default()
+    };
+    let comment = Comment::update(&mut context.pool(), comment_id, &form).await?;
+
+    // Send the notifications and activity which were held back when the comment was created.
+    // Comments which are still scheduled or delayed are announced by the scheduled tasks instead,
+    // so that they aren't sent twice.
+    let publish =
+      comment.scheduled_publish_time_at.is_none() && comment.pending_federation_until.is_none();
+    if publish {
+      let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+      NotifyData::new(
+        orig_comment.post,
+        Some(comment.clone()),
+        orig_comment.creator,
+        orig_comment.community,
+        !local_site.disable_email_notifications,
+      )
+      .send(&context);
+
+      ActivityChannel::submit_activity(SendActivityData::CreateComment(comment), &context)?;
+    }
+  }
+
+  build_comment_response(
+    &context,
+    comment_id,
+    local_user_view.into(),
+    local_instance_id,
+  )
+  .await
+  .map(Json)
+}
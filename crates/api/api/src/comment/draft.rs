@@ -0,0 +1,81 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
+use lemmy_db_schema::source::{
+  comment::Comment,
+  comment_draft::{CommentDraft, CommentDraftForm},
+  post::Post,
+};
+use lemmy_db_views_comment::api::{
+  CommentDraftResponse,
+  DeleteCommentDraft,
+  GetCommentDraft,
+  SaveCommentDraft,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::is_valid_body_field,
+};
+
+/// Drafts are stored as written, markdown is only processed once the comment is created.
+pub async fn save_comment_draft(
+  Json(data): Json<SaveCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentDraftResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  is_valid_body_field(&data.content, false)?;
+
+  // Make sure the post and parent exist, so that the draft can be published later
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+  if let Some(parent_id) = data.parent_id {
+    let parent = Comment::read(&mut context.pool(), parent_id).await?;
+    if parent.post_id != post.id {
+      Err(LemmyErrorType::CommentParentNotOnPost)?
+    }
+  }
+
+  let form = CommentDraftForm::new(
+    local_user_view.person.id,
+    post.id,
+    data.parent_id,
+    data.content,
+  );
+  let draft = CommentDraft::save(&mut context.pool(), &form).await?;
+
+  Ok(Json(CommentDraftResponse { draft: Some(draft) }))
+}
+
+pub async fn get_comment_draft(
+  Query(data): Query<GetCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentDraftResponse>> {
+  let draft = CommentDraft::read(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.post_id,
+    data.parent_id,
+  )
+  .await?;
+
+  Ok(Json(CommentDraftResponse { draft }))
+}
+
+pub async fn delete_comment_draft(
+  Json(data): Json<DeleteCommentDraft>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  CommentDraft::delete(
+    &mut context.pool(),
+    local_user_view.person.id,
+    data.post_id,
+    data.parent_id,
+  )
+  .await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
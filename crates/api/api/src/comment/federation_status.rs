@@ -0,0 +1,28 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::source::comment::Comment;
+use lemmy_db_views_comment::{
+  CommentFederationTarget,
+  api::{GetCommentFederationStatus, GetCommentFederationStatusResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+/// Shows for each remote instance if the comment was delivered, or why delivery is failing.
+pub async fn get_comment_federation_status(
+  Query(data): Query<GetCommentFederationStatus>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetCommentFederationStatusResponse>> {
+  let comment = Comment::read(&mut context.pool(), data.comment_id).await?;
+
+  // Only the creator and admins can see this
+  if comment.creator_id != local_user_view.person.id {
+    is_admin(&local_user_view)?;
+  }
+
+  let targets = CommentFederationTarget::list_for_comment(&mut context.pool(), &comment).await?;
+
+  Ok(Json(GetCommentFederationStatusResponse { targets }))
+}
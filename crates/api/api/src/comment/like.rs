@@ -7,6 +7,7 @@ use lemmy_api_utils::{
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_bot_account,
+    check_community_not_read_only,
     check_community_user_action,
     check_local_user_valid,
     check_local_vote_mode,
@@ -27,7 +28,7 @@ use lemmy_db_views_comment::{
 };
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
-use lemmy_utils::error::LemmyResult;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
 use std::ops::Deref;
 
 pub async fn like_comment(
@@ -66,6 +67,15 @@ pub async fn like_comment(
     &mut context.pool(),
   )
   .await?;
+  check_community_not_read_only(
+    &local_user_view,
+    &orig_comment.community,
+    &mut context.pool(),
+  )
+  .await?;
+  if data.is_upvote == Some(false) && !orig_comment.community.downvotes_enabled {
+    Err(LemmyErrorType::DownvotesDisabled)?
+  }
 
   // Remove any likes first
   CommentActions::remove_like(&mut context.pool(), my_person_id, comment_id).await?;
@@ -127,3 +137,127 @@ pub async fn like_comment(
     .await?,
   ))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{
+        Community,
+        CommunityActions,
+        CommunityInsertForm,
+        CommunityModeratorForm,
+        CommunityUpdateForm,
+      },
+      person::Person,
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_downvotes_disabled() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let creator = LocalUserView::create_test_user(pool, "downvoted", "", false).await?;
+    let voter = LocalUserView::create_test_user(pool, "downvoter", "", false).await?;
+    let community_form = CommunityInsertForm {
+      downvotes_enabled: Some(false),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "positive_vibes".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("vibes".to_string(), creator.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(creator.person.id, post.id, "hot take".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let vote = |is_upvote| CreateCommentLike {
+      comment_id: comment.id,
+      is_upvote: Some(is_upvote),
+    };
+
+    // Downvotes are rejected, upvotes still work
+    let res = like_comment(Json(vote(false)), context.clone(), voter.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::DownvotesDisabled),
+      res.err().map(|e| e.error_type)
+    );
+    let res = like_comment(Json(vote(true)), context.clone(), voter.clone()).await?;
+    assert_eq!(1, res.comment_view.comment.score);
+
+    // Once enabled, downvotes are allowed
+    let form = CommunityUpdateForm {
+      downvotes_enabled: Some(true),
+      ..Default::default()
+    };
+    Community::update(pool, community.id, &form).await?;
+    let res = like_comment(Json(vote(false)), context.clone(), voter.clone()).await?;
+    assert_eq!(-1, res.comment_view.comment.score);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, creator.person.id).await?;
+    Person::delete(pool, voter.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_vote_in_read_only_community() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let voter = LocalUserView::create_test_user(pool, "read_only_voter", "", false).await?;
+    let moderator =
+      LocalUserView::create_test_user(pool, "read_only_voting_mod", "", false).await?;
+    let community_form = CommunityInsertForm {
+      read_only: Some(true),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "read_only_votes".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm::new(community.id, moderator.person.id);
+    CommunityActions::join(pool, &form).await?;
+    let post_form = PostInsertForm::new("archived".to_string(), moderator.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(moderator.person.id, post.id, "old news".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let vote = CreateCommentLike {
+      comment_id: comment.id,
+      is_upvote: Some(true),
+    };
+    let res = like_comment(Json(vote), context.clone(), voter.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommunityIsReadOnly),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Mods can still vote
+    let res = like_comment(Json(vote), context.clone(), moderator.clone()).await?;
+    assert_eq!(1, res.comment_view.comment.score);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, voter.person.id).await?;
+    Person::delete(pool, moderator.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
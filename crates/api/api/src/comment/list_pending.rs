@@ -0,0 +1,40 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_of_any_or_admin_action};
+use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
+use lemmy_db_views_comment::{CommentView, api::ListPendingComments, impls::CommentQuery};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_pending_comments(
+  Query(data): Query<ListPendingComments>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<CommentView>>> {
+  check_community_mod_of_any_or_admin_action(&local_user_view, &mut context.pool()).await?;
+  let site = SiteView::read_local(&mut context.pool()).await?.site;
+
+  // Admins can see the queue of all communities
+  let listing_type = if local_user_view.local_user.admin {
+    ListingType::All
+  } else {
+    ListingType::ModeratorView
+  };
+
+  let comments = CommentQuery {
+    listing_type: Some(listing_type),
+    sort: Some(CommentSortType::Old),
+    community_id: data.community_id,
+    pending_approval_only: Some(true),
+    local_user: Some(&local_user_view.local_user),
+    page_cursor: data.page_cursor,
+    limit: data.limit,
+    ..Default::default()
+  }
+  .list(&site, &mut context.pool())
+  .await?;
+
+  Ok(Json(comments))
+}
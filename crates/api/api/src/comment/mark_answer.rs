@@ -0,0 +1,86 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_comment_response,
+  context::LemmyContext,
+  notify::notify_mod_action,
+  utils::{
+    check_comment_deleted_or_removed,
+    check_community_mod_action,
+    check_community_user_action,
+  },
+};
+use lemmy_db_schema::source::{
+  comment::Comment,
+  modlog::{Modlog, ModlogInsertForm},
+};
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{CommentResponse, MarkCommentAsAnswer},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn mark_comment_as_answer(
+  Json(data): Json<MarkCommentAsAnswer>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let comment_id = data.comment_id;
+  let local_instance_id = local_user_view.person.instance_id;
+
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+
+  check_community_user_action(
+    &local_user_view,
+    &orig_comment.community,
+    &mut context.pool(),
+  )
+  .await?;
+
+  // Removed or deleted comments can't become the answer, but can still be unmarked
+  if data.answer {
+    check_comment_deleted_or_removed(&orig_comment.comment)?;
+  }
+
+  // Besides mods, the creator of the post can pick the answer to their question
+  let is_post_creator = local_user_view.person.id == orig_comment.post.creator_id;
+  if !is_post_creator {
+    check_community_mod_action(
+      &local_user_view,
+      &orig_comment.community,
+      false,
+      &mut context.pool(),
+    )
+    .await?;
+  }
+
+  let comment =
+    Comment::mark_as_answer(&mut context.pool(), &orig_comment.comment, data.answer).await?;
+
+  // The post creator picking an answer is not a mod action, so it doesn't go in the modlog
+  if !is_post_creator {
+    let form = ModlogInsertForm::mod_mark_comment_as_answer(
+      local_user_view.person.id,
+      &comment,
+      data.answer,
+    );
+    let action = Modlog::create(&mut context.pool(), &[form]).await?;
+    notify_mod_action(action.clone(), &context);
+  }
+
+  build_comment_response(
+    &context,
+    comment_id,
+    local_user_view.into(),
+    local_instance_id,
+  )
+  .await
+  .map(Json)
+}
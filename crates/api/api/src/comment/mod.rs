@@ -1,5 +1,14 @@
+pub mod approve;
 pub mod distinguish;
+pub mod draft;
+pub mod federation_status;
 pub mod like;
 pub mod list_comment_likes;
+pub mod list_pending;
 pub mod lock;
+pub mod mark_answer;
+pub mod move_subtree;
+pub mod pin_in_community;
+pub mod preview;
+pub mod reaction;
 pub mod save;
@@ -0,0 +1,90 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_comment_response,
+  context::LemmyContext,
+  notify::notify_mod_action,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::check_community_mod_action,
+};
+use lemmy_db_schema::source::{
+  comment::Comment,
+  community::Community,
+  modlog::{Modlog, ModlogInsertForm},
+  post::Post,
+};
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{CommentResponse, MoveCommentSubtree},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn move_comment_subtree(
+  Json(data): Json<MoveCommentSubtree>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let local_instance_id = local_user_view.person.instance_id;
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    data.comment_id,
+    None,
+    local_instance_id,
+  )
+  .await?;
+  let target_post = Post::read(&mut context.pool(), data.target_post_id).await?;
+  let target_community = Community::read(&mut context.pool(), target_post.community_id).await?;
+
+  // Need to be a mod of both communities
+  check_community_mod_action(
+    &local_user_view,
+    &orig_comment.community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+  check_community_mod_action(
+    &local_user_view,
+    &target_community,
+    false,
+    &mut context.pool(),
+  )
+  .await?;
+
+  let new_parent = match data.new_parent_id {
+    Some(id) => Some(Comment::read(&mut context.pool(), id).await?),
+    None => None,
+  };
+  let comments = Comment::move_subtree(
+    &mut context.pool(),
+    &orig_comment.comment,
+    target_post.id,
+    new_parent.as_ref(),
+    local_site.max_comment_depth.try_into()?,
+  )
+  .await?;
+  let comment = comments.first().ok_or(LemmyErrorType::NotFound)?;
+
+  let form = ModlogInsertForm::mod_move_comment(local_user_view.person.id, comment, &data.reason);
+  let action = Modlog::create(&mut context.pool(), &[form]).await?;
+  notify_mod_action(action.clone(), &context);
+
+  // Only local comments can be updated over federation
+  for comment in comments.iter().filter(|c| c.local) {
+    ActivityChannel::submit_activity(SendActivityData::UpdateComment(comment.clone()), &context)?;
+  }
+
+  build_comment_response(
+    &context,
+    data.comment_id,
+    local_user_view.into(),
+    local_instance_id,
+  )
+  .await
+  .map(Json)
+}
@@ -0,0 +1,51 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  build_response::build_comment_response,
+  context::LemmyContext,
+  utils::check_community_mod_action,
+};
+use lemmy_db_schema::source::community::{Community, CommunityUpdateForm};
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{CommentResponse, PinCommentInCommunity},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn pin_comment_in_community(
+  Json(data): Json<PinCommentInCommunity>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let comment_id = data.comment_id;
+  let local_instance_id = local_user_view.person.instance_id;
+
+  let orig_comment =
+    CommentView::read(&mut context.pool(), comment_id, None, local_instance_id).await?;
+  let community = &orig_comment.community;
+
+  check_community_mod_action(&local_user_view, community, false, &mut context.pool()).await?;
+  if data.pinned && !orig_comment.creator_is_moderator && !orig_comment.creator_is_admin {
+    Err(LemmyErrorType::OnlyModCommentsCanBePinned)?
+  }
+
+  // Unpinning only has an effect if this comment is the pinned one
+  if data.pinned || community.pinned_comment_id == Some(comment_id) {
+    let form = CommunityUpdateForm {
+      pinned_comment_id: Some(data.pinned.then_some(comment_id)),
+      ..Default::default()
+    };
+    Community::update(&mut context.pool(), community.id, &form).await?;
+  }
+
+  build_comment_response(
+    &context,
+    comment_id,
+    local_user_view.into(),
+    local_instance_id,
+  )
+  .await
+  .map(Json)
+}
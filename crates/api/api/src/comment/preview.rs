@@ -0,0 +1,73 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{get_url_blocklist, process_markdown, slur_regex},
+};
+use lemmy_db_views_comment::api::{PreviewComment, PreviewCommentResponse};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::{markdown::markdown_to_html, validation::is_valid_body_field},
+};
+
+/// Runs comment content through the same processing as when creating a comment, so that clients
+/// can show exactly what will be stored.
+pub async fn preview_comment(
+  Json(data): Json<PreviewComment>,
+  context: Data<LemmyContext>,
+  _local_user_view: LocalUserView,
+) -> LemmyResult<Json<PreviewCommentResponse>> {
+  let slur_regex = slur_regex(&context).await?;
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let content = process_markdown(&data.content, &slur_regex, &url_blocklist, &context).await?;
+  is_valid_body_field(&content, false)?;
+  let html = markdown_to_html(&content);
+
+  Ok(Json(PreviewCommentResponse { content, html }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{source::person::Person, test_data::TestData};
+  use lemmy_diesel_utils::traits::Crud;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_preview_comment() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+    let user = LocalUserView::create_test_user(pool, "comment_previewer", "", false).await?;
+
+    let content = "[a link](https://example.com/path/123?utm_medium=social&id=123) \
+      <script>alert('xss');</script>"
+      .to_string();
+    let preview = preview_comment(
+      Json(PreviewComment { content }),
+      context.clone(),
+      user.clone(),
+    )
+    .await?
+    .0;
+
+    // Tracking parameters are removed from the stored content, and html is escaped when rendered
+    assert_eq!(
+      "[a link](https://example.com/path/123?id=123) <script>alert('xss');</script>",
+      preview.content
+    );
+    assert!(
+      preview
+        .html
+        .contains("href=\"https://example.com/path/123?id=123\"")
+    );
+    assert!(!preview.html.contains("<script>"));
+
+    Person::delete(pool, user.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
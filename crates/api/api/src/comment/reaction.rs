@@ -0,0 +1,105 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_bot_account, check_community_user_action, check_local_user_valid},
+};
+use lemmy_db_schema::source::comment_reaction::{CommentReaction, CommentReactionForm};
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{AddCommentReaction, CommentResponse, RemoveCommentReaction},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn add_comment_reaction(
+  Json(data): Json<AddCommentReaction>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  check_bot_account(&local_user_view.person)?;
+  if !context
+    .settings()
+    .allowed_comment_reactions
+    .contains(&data.emoji)
+  {
+    Err(LemmyErrorType::ReactionNotAllowed)?
+  }
+
+  let comment_id = data.comment_id;
+  let local_instance_id = local_user_view.person.instance_id;
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+  check_community_user_action(
+    &local_user_view,
+    &orig_comment.community,
+    &mut context.pool(),
+  )
+  .await?;
+
+  let form = CommentReactionForm::new(comment_id, local_user_view.person.id, data.emoji.clone());
+  CommentReaction::add(&mut context.pool(), &form).await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::ReactToComment {
+      object_id: orig_comment.comment.ap_id,
+      actor: local_user_view.person.clone(),
+      community: orig_comment.community,
+      emoji: data.emoji,
+      add: true,
+    },
+    &context,
+  )?;
+
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+
+  Ok(Json(CommentResponse { comment_view }))
+}
+
+pub async fn remove_comment_reaction(
+  Json(data): Json<RemoveCommentReaction>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  check_local_user_valid(&local_user_view)?;
+
+  let comment_id = data.comment_id;
+  let form = CommentReactionForm::new(comment_id, local_user_view.person.id, data.emoji.clone());
+  let removed = CommentReaction::remove(&mut context.pool(), &form).await?;
+
+  let local_instance_id = local_user_view.person.instance_id;
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+
+  // Only federate if there was actually a reaction to undo
+  if removed > 0 {
+    ActivityChannel::submit_activity(
+      SendActivityData::ReactToComment {
+        object_id: comment_view.comment.ap_id.clone(),
+        actor: local_user_view.person.clone(),
+        community: comment_view.community.clone(),
+        emoji: data.emoji,
+        add: false,
+      },
+      &context,
+    )?;
+  }
+
+  Ok(Json(CommentResponse { comment_view }))
+}
@@ -0,0 +1,69 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+use lemmy_db_views_community::CommunityView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::{
+  CommunityActiveUser,
+  api::{GetCommunityActiveUsers, GetCommunityActiveUsersResponse},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::validation::check_active_users_window,
+};
+
+pub async fn get_community_active_users(
+  Query(data): Query<GetCommunityActiveUsers>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetCommunityActiveUsersResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_instance_id = site_view.site.instance_id;
+
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+  check_active_users_window(data.window_seconds)?;
+
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(data.community_id),
+  )
+  .await
+  .is_ok();
+
+  let community_view = CommunityView::read(
+    &mut context.pool(),
+    data.community_id,
+    local_user_view.as_ref().map(|u| &u.local_user),
+    is_mod_or_admin,
+  )
+  .await?;
+
+  // Content of private communities is only visible to approved followers
+  let is_follower = community_view
+    .community_actions
+    .is_some_and(|a| a.follow_state == Some(CommunityFollowerState::Accepted));
+  if community_view.community.visibility == CommunityVisibility::Private
+    && !is_follower
+    && !is_mod_or_admin
+  {
+    Err(LemmyErrorType::NotFound)?
+  }
+
+  let active_users = CommunityActiveUser::list_for_community(
+    &mut context.pool(),
+    data.community_id,
+    data.window_seconds,
+    local_user_view.as_ref().map(|u| u.person.id),
+    local_instance_id,
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(GetCommunityActiveUsersResponse { active_users }))
+}
@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::source::community::{Community, CommunityActions, CommunityMemberForm};
+use lemmy_db_views_community::api::AddCommunityMember;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+/// Membership is only enforced by the local instance, so it isn't federated.
+pub async fn add_community_member(
+  Json(data): Json<AddCommunityMember>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  if data.added {
+    let form = CommunityMemberForm::new(community.id, data.person_id);
+    CommunityActions::add_member(&mut context.pool(), &form).await?;
+  } else {
+    CommunityActions::remove_member(&mut context.pool(), data.person_id, community.id).await?;
+  }
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -88,7 +88,8 @@ pub async fn add_mod_to_community(
   // Note: in case a remote mod is added, this returns the old moderators list, it will only get
   //       updated once we receive an activity from the community (like `Announce/Add/Moderator`)
   let community_id = data.community_id;
-  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
+  let moderators =
+    CommunityModeratorView::for_community_inline(&mut context.pool(), community_id).await?;
 
   ActivityChannel::submit_activity(
     SendActivityData::AddModToCommunity {
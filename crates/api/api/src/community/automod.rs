@@ -0,0 +1,101 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::source::{
+  community::Community,
+  community_automod_rule::{
+    CommunityAutomodRule,
+    CommunityAutomodRuleInsertForm,
+    CommunityAutomodRuleUpdateForm,
+  },
+};
+use lemmy_db_views_community::api::{
+  CreateCommunityAutomodRule,
+  DeleteCommunityAutomodRule,
+  EditCommunityAutomodRule,
+  ListCommunityAutomodRules,
+  ListCommunityAutomodRulesResponse,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::{
+  error::LemmyResult,
+  utils::validation::{build_and_check_regex, check_api_elements_count},
+};
+
+pub async fn create_community_automod_rule(
+  Json(data): Json<CreateCommunityAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityAutomodRule>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods can create automod rules
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let existing =
+    CommunityAutomodRule::list_for_community(&mut context.pool(), community.id).await?;
+  check_api_elements_count(existing.len())?;
+  build_and_check_regex(Some(&data.pattern))?;
+
+  let form = CommunityAutomodRuleInsertForm::new(community.id, data.pattern, data.action);
+  let rule = CommunityAutomodRule::create(&mut context.pool(), &form).await?;
+
+  Ok(Json(rule))
+}
+
+pub async fn edit_community_automod_rule(
+  Json(data): Json<EditCommunityAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunityAutomodRule>> {
+  let rule = CommunityAutomodRule::read(&mut context.pool(), data.rule_id).await?;
+  let community = Community::read(&mut context.pool(), rule.community_id).await?;
+
+  // Verify that only mods can update automod rules
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  if let Some(pattern) = &data.pattern {
+    build_and_check_regex(Some(pattern))?;
+  }
+
+  let form = CommunityAutomodRuleUpdateForm {
+    pattern: data.pattern,
+    action: data.action,
+  };
+  let rule = CommunityAutomodRule::update(&mut context.pool(), data.rule_id, &form).await?;
+
+  Ok(Json(rule))
+}
+
+pub async fn delete_community_automod_rule(
+  Json(data): Json<DeleteCommunityAutomodRule>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let rule = CommunityAutomodRule::read(&mut context.pool(), data.rule_id).await?;
+  let community = Community::read(&mut context.pool(), rule.community_id).await?;
+
+  // Verify that only mods can delete automod rules
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  CommunityAutomodRule::delete(&mut context.pool(), data.rule_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+pub async fn list_community_automod_rules(
+  Query(data): Query<ListCommunityAutomodRules>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListCommunityAutomodRulesResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // The rules are only visible to mods, so that they can't be easily worked around
+  check_community_mod_action(&local_user_view, &community, true, &mut context.pool()).await?;
+
+  let rules = CommunityAutomodRule::list_for_community(&mut context.pool(), community.id).await?;
+
+  Ok(Json(ListCommunityAutomodRulesResponse { rules }))
+}
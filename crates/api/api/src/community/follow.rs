@@ -37,3 +37,78 @@ pub async fn follow_community(
     discussion_languages,
   }))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      community::{CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+      local_user::{LocalUser, LocalUserUpdateForm},
+      person::Person,
+    },
+    test_data::TestData,
+  };
+  use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+  use lemmy_email::community::send_new_pending_follower_email_to_mods;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pending_follow_emails_mods() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let moderator = LocalUserView::create_test_user(pool, "pending_follow_mod", "", false).await?;
+    let form = LocalUserUpdateForm {
+      email: Some(Some("pending_follow_mod@example.com".to_string())),
+      send_notifications_to_email: Some(true),
+      email_pending_community_followers: Some(true),
+      ..Default::default()
+    };
+    LocalUser::update(pool, moderator.local_user.id, &form).await?;
+    let follower = LocalUserView::create_test_user(pool, "pending_follower", "", false).await?;
+
+    let community_form = CommunityInsertForm {
+      visibility: Some(CommunityVisibility::Private),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "pending_follow_email".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm::new(community.id, moderator.person.id);
+    CommunityActions::join(pool, &form).await?;
+
+    // Following the private community needs approval, so the mod is emailed about it
+    let follow = FollowCommunity {
+      community_id: community.id,
+      follow: true,
+    };
+    let res = follow_community(Json(follow), context.clone(), follower.clone()).await?;
+    assert_eq!(
+      Some(CommunityFollowerState::ApprovalRequired),
+      res
+        .community_view
+        .community_actions
+        .and_then(|a| a.follow_state)
+    );
+    let sent = send_new_pending_follower_email_to_mods(
+      &follower.person.name,
+      &community,
+      pool,
+      context.settings(),
+    )
+    .await?;
+    assert_eq!(1, sent);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, moderator.person.id).await?;
+    Person::delete(pool, follower.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -13,17 +13,23 @@ use lemmy_db_schema::{
 };
 use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
 use lemmy_db_views_community_moderator::CommunityPersonBanView;
-use lemmy_utils::error::LemmyResult;
+use lemmy_email::community::send_new_pending_follower_email_to_mods;
+use lemmy_utils::{error::LemmyResult, spawn_try_task};
 
+pub mod active_users;
+pub mod add_member;
 pub mod add_mod;
+pub mod automod;
 pub mod ban;
 pub mod block;
 pub mod follow;
 pub mod multi_community_follow;
 pub mod pending_follows;
 pub mod random;
+pub mod settings_backup;
 pub mod tag;
 pub mod transfer;
+pub mod update_comment_sort;
 pub mod update_notifications;
 
 pub(super) async fn do_follow_community(
@@ -55,6 +61,21 @@ pub(super) async fn do_follow_community(
 
     // Write to db
     CommunityActions::follow(&mut context.pool(), &form).await?;
+
+    if community.local && follow_state == CommunityFollowerState::ApprovalRequired {
+      let (person_name, community, context) =
+        (person.name.clone(), community.clone(), context.clone());
+      spawn_try_task(async move {
+        send_new_pending_follower_email_to_mods(
+          &person_name,
+          &community,
+          &mut context.pool(),
+          context.settings(),
+        )
+        .await?;
+        Ok(())
+      });
+    }
   } else {
     CommunityActions::unfollow(&mut context.pool(), person.id, community.id).await?;
   }
@@ -0,0 +1,415 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{
+    check_community_mod_action,
+    check_local_user_valid,
+    check_nsfw_allowed,
+    get_url_blocklist,
+    is_admin,
+    process_markdown_opt,
+    slur_regex,
+  },
+};
+use lemmy_db_schema::{
+  impls::actor_language::validate_post_language,
+  source::{
+    actor_language::{CommunityLanguage, SiteLanguage},
+    community::{Community, CommunityUpdateForm},
+    community_automod_rule::{CommunityAutomodRule, CommunityAutomodRuleInsertForm},
+    language::Language,
+    tag::{Tag, TagInsertForm},
+  },
+};
+use lemmy_db_schema_file::enums::CommunityVisibility;
+use lemmy_db_views_community::api::{
+  COMMUNITY_SETTINGS_BACKUP_VERSION,
+  CommunityAutomodRuleBackup,
+  CommunityIdQuery,
+  CommunitySettingsBackup,
+  CommunityTagBackup,
+  ImportCommunitySettings,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::{SiteView, api::SuccessResponse};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::{
+  error::{LemmyErrorType, LemmyResult},
+  utils::{
+    slurs::{check_slurs, check_slurs_opt},
+    validation::{
+      build_and_check_regex,
+      check_api_elements_count,
+      description_length_check,
+      is_valid_actor_name,
+      is_valid_body_field,
+      is_valid_display_name,
+    },
+  },
+};
+use std::collections::HashMap;
+use url::Url;
+
+pub async fn export_community_settings(
+  Query(data): Query<CommunityIdQuery>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommunitySettingsBackup>> {
+  let community = Community::read(&mut context.pool(), data.id).await?;
+
+  // Verify that only mods can export the settings
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let backup = community_settings_backup(community, &mut context.pool()).await?;
+  Ok(Json(backup))
+}
+
+async fn community_settings_backup(
+  community: Community,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<CommunitySettingsBackup> {
+  let language_codes: HashMap<_, _> = Language::read_all(pool)
+    .await?
+    .into_iter()
+    .map(|l| (l.id, l.code))
+    .collect();
+  let mut discussion_languages = CommunityLanguage::read(pool, community.id)
+    .await?
+    .iter()
+    .flat_map(|l| language_codes.get(l).cloned())
+    .collect::<Vec<_>>();
+  discussion_languages.sort();
+
+  let mut tags = Tag::read_for_community(pool, community.id)
+    .await?
+    .into_iter()
+    .map(|t| CommunityTagBackup {
+      name: t.name,
+      display_name: t.display_name,
+      description: t.description,
+    })
+    .collect::<Vec<_>>();
+  tags.sort_by(|a, b| a.name.cmp(&b.name));
+
+  let automod_rules = CommunityAutomodRule::list_for_community(pool, community.id)
+    .await?
+    .into_iter()
+    .map(|r| CommunityAutomodRuleBackup {
+      pattern: r.pattern,
+      action: r.action,
+    })
+    .collect();
+
+  Ok(CommunitySettingsBackup {
+    version: COMMUNITY_SETTINGS_BACKUP_VERSION,
+    title: Some(community.title),
+    sidebar: community.sidebar,
+    description: community.description,
+    nsfw: Some(community.nsfw),
+    posting_restricted_to_mods: Some(community.posting_restricted_to_mods),
+    visibility: Some(community.visibility),
+    discussion_languages,
+    primary_language: community
+      .primary_language_id
+      .and_then(|l| language_codes.get(&l).cloned()),
+    tags,
+    automod_rules,
+  })
+}
+
+/// Applies a settings backup to a community. To avoid overwriting an established community by
+/// accident, this is only possible as long as the community has no posts. Languages which aren't
+/// available on this instance are skipped, as are tags and automod rules which already exist. All
+/// changes are made in a single transaction, so a failed import leaves the community unchanged.
+pub async fn import_community_settings(
+  Json(data): Json<ImportCommunitySettings>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let backup = data.backup;
+  if backup.version > COMMUNITY_SETTINGS_BACKUP_VERSION {
+    Err(LemmyErrorType::UnsupportedBackupVersion)?
+  }
+
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+
+  // Verify that only mods can import the settings
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+  if community.posts > 0 {
+    Err(LemmyErrorType::CommunityNotEmpty)?
+  }
+
+  check_api_elements_count(
+    backup.discussion_languages.len() + backup.tags.len() + backup.automod_rules.len(),
+  )?;
+
+  // Validate everything before making any changes
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  let slur_regex = slur_regex(&context).await?;
+  let url_blocklist = get_url_blocklist(&context).await?;
+  let title = backup.title.as_ref().map(|t| t.trim().to_string());
+  check_slurs_opt(&title, &slur_regex)?;
+  if let Some(title) = &title {
+    is_valid_display_name(title)?;
+  }
+  let sidebar =
+    process_markdown_opt(&backup.sidebar, &slur_regex, &url_blocklist, &context).await?;
+  if let Some(sidebar) = &sidebar {
+    is_valid_body_field(sidebar, false)?;
+  }
+  check_slurs_opt(&backup.description, &slur_regex)?;
+  check_nsfw_allowed(backup.nsfw, Some(&local_site))?;
+  if matches!(
+    backup.visibility,
+    Some(CommunityVisibility::Private | CommunityVisibility::Unlisted)
+  ) {
+    is_admin(&local_user_view)?;
+  }
+  for tag in &backup.tags {
+    is_valid_actor_name(&tag.name)?;
+    if let Some(desc) = &tag.description {
+      description_length_check(desc)?;
+      check_slurs(desc, &slur_regex)?;
+    }
+  }
+  for rule in &backup.automod_rules {
+    build_and_check_regex(Some(&rule.pattern))?;
+  }
+
+  let pool = &mut context.pool();
+  let community_id = community.id;
+  let language_ids: HashMap<_, _> = Language::read_all(pool)
+    .await?
+    .into_iter()
+    .map(|l| (l.code, l.id))
+    .collect();
+  let site_languages = SiteLanguage::read_local_raw(pool).await?;
+  let discussion_languages = backup
+    .discussion_languages
+    .iter()
+    .flat_map(|code| language_ids.get(code).copied())
+    .filter(|l| site_languages.contains(l))
+    .collect::<Vec<_>>();
+  let primary_language_id = backup
+    .primary_language
+    .and_then(|code| language_ids.get(&code).copied());
+
+  let existing_tags = Tag::read_for_community(pool, community_id)
+    .await?
+    .into_iter()
+    .map(|t| t.name)
+    .collect::<Vec<_>>();
+  let mut tag_forms = vec![];
+  for tag in backup.tags {
+    if existing_tags.contains(&tag.name) {
+      continue;
+    }
+    let ap_id = Url::parse(&format!("{}/tag/{}", community.ap_id, &tag.name))?;
+    tag_forms.push(TagInsertForm {
+      ap_id: ap_id.into(),
+      name: tag.name,
+      display_name: tag.display_name,
+      description: tag.description,
+      community_id,
+      deleted: Some(false),
+    });
+  }
+
+  let existing_patterns = CommunityAutomodRule::list_for_community(pool, community_id)
+    .await?
+    .into_iter()
+    .map(|r| r.pattern)
+    .collect::<Vec<_>>();
+  let rule_forms = backup
+    .automod_rules
+    .into_iter()
+    .filter(|r| !existing_patterns.contains(&r.pattern))
+    .map(|r| CommunityAutomodRuleInsertForm::new(community_id, r.pattern, r.action))
+    .collect::<Vec<_>>();
+
+  let conn = &mut get_conn(pool).await?;
+  let community = conn
+    .run_transaction(|conn| {
+      async move {
+        if !discussion_languages.is_empty() {
+          CommunityLanguage::update(&mut conn.into(), discussion_languages, community_id).await?;
+        }
+        let mut primary_language_id = primary_language_id;
+        if validate_post_language(&mut conn.into(), primary_language_id, community_id)
+          .await
+          .is_err()
+        {
+          primary_language_id = None;
+        }
+
+        for form in tag_forms {
+          Tag::create(&mut conn.into(), &form).await?;
+        }
+        for form in rule_forms {
+          CommunityAutomodRule::create(&mut conn.into(), &form).await?;
+        }
+
+        let form = CommunityUpdateForm {
+          title,
+          sidebar: sidebar.map(Some),
+          description: backup.description.map(Some),
+          nsfw: backup.nsfw,
+          posting_restricted_to_mods: backup.posting_restricted_to_mods,
+          visibility: backup.visibility,
+          primary_language_id: primary_language_id.map(Some),
+          ..Default::default()
+        };
+        Community::update(&mut conn.into(), community_id, &form).await
+      }
+      .scope_boxed()
+    })
+    .await?;
+
+  ActivityChannel::submit_activity(
+    SendActivityData::UpdateCommunity(local_user_view.person.clone(), community),
+    &context,
+  )?;
+
+  Ok(Json(Default::default()))
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    newtypes::LanguageId,
+    source::{
+      community::{CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+      person::Person,
+    },
+    test_data::TestData,
+    traits::Followable,
+  };
+  use lemmy_db_schema_file::enums::AutomodAction;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_settings_export_import() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let mod_user = LocalUserView::create_test_user(pool, "community_backup_mod", "", false).await?;
+    let other_user =
+      LocalUserView::create_test_user(pool, "community_backup_other", "", false).await?;
+
+    let mut communities = vec![];
+    for name in ["backup_source", "backup_target"] {
+      let form = CommunityInsertForm {
+        sidebar: Some("the rules".to_string()),
+        nsfw: Some(true),
+        ..CommunityInsertForm::new(
+          data.instance.id,
+          name.to_string(),
+          name.to_string(),
+          "pubkey".to_string(),
+        )
+      };
+      let community = Community::create(pool, &form).await?;
+      let form = CommunityModeratorForm::new(community.id, mod_user.person.id);
+      CommunityActions::join(pool, &form).await?;
+      communities.push(community);
+    }
+    let (source, target) = (&communities[0], &communities[1]);
+
+    CommunityLanguage::update(pool, vec![LanguageId(2), LanguageId(3)], source.id).await?;
+    let form = CommunityUpdateForm {
+      primary_language_id: Some(Some(LanguageId(3))),
+      ..Default::default()
+    };
+    Community::update(pool, source.id, &form).await?;
+    let form = TagInsertForm {
+      ap_id: Url::parse(&format!("{}/tag/news", source.ap_id))?.into(),
+      name: "news".to_string(),
+      display_name: Some("News".to_string()),
+      description: None,
+      community_id: source.id,
+      deleted: Some(false),
+    };
+    Tag::create(pool, &form).await?;
+    let form =
+      CommunityAutomodRuleInsertForm::new(source.id, "badword".into(), AutomodAction::Remove);
+    CommunityAutomodRule::create(pool, &form).await?;
+
+    let query = Query(CommunityIdQuery { id: source.id });
+    let backup = export_community_settings(query, context.clone(), mod_user.clone())
+      .await?
+      .0;
+    assert_eq!(COMMUNITY_SETTINGS_BACKUP_VERSION, backup.version);
+    assert_eq!(2, backup.discussion_languages.len());
+    assert_eq!(1, backup.tags.len());
+    assert_eq!(1, backup.automod_rules.len());
+
+    // Only mods of the community can export or import its settings
+    let query = Query(CommunityIdQuery { id: source.id });
+    let denied = export_community_settings(query, context.clone(), other_user.clone()).await;
+    assert!(denied.is_err());
+    let import = ImportCommunitySettings {
+      community_id: target.id,
+      backup: backup.clone(),
+    };
+    let denied = import_community_settings(Json(import), context.clone(), other_user.clone());
+    assert!(denied.await.is_err());
+
+    // Newer backup versions can't be imported
+    let import = ImportCommunitySettings {
+      community_id: target.id,
+      backup: CommunitySettingsBackup {
+        version: COMMUNITY_SETTINGS_BACKUP_VERSION + 1,
+        ..backup.clone()
+      },
+    };
+    let unsupported = import_community_settings(Json(import), context.clone(), mod_user.clone());
+    assert_eq!(
+      Some(LemmyErrorType::UnsupportedBackupVersion),
+      unsupported.await.err().map(|e| e.error_type)
+    );
+
+    let import = ImportCommunitySettings {
+      community_id: target.id,
+      backup: backup.clone(),
+    };
+    import_community_settings(Json(import), context.clone(), mod_user.clone()).await?;
+
+    // Exporting the target now gives the same settings
+    let query = Query(CommunityIdQuery { id: target.id });
+    let imported = export_community_settings(query, context.clone(), mod_user.clone())
+      .await?
+      .0;
+    assert_eq!(backup, imported);
+
+    // Importing again doesn't duplicate tags or automod rules
+    let import = ImportCommunitySettings {
+      community_id: target.id,
+      backup: backup.clone(),
+    };
+    import_community_settings(Json(import), context.clone(), mod_user.clone()).await?;
+    let query = Query(CommunityIdQuery { id: target.id });
+    let reimported = export_community_settings(query, context.clone(), mod_user.clone())
+      .await?
+      .0;
+    assert_eq!(backup, reimported);
+
+    Community::delete(pool, source.id).await?;
+    Community::delete(pool, target.id).await?;
+    Person::delete(pool, mod_user.person.id).await?;
+    Person::delete(pool, other_user.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
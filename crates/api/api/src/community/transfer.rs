@@ -4,10 +4,11 @@ use diesel_async::scoped_futures::ScopedFutureExt;
 use lemmy_api_utils::{
   context::LemmyContext,
   notify::notify_mod_action,
-  utils::{check_community_user_action, is_admin, is_top_mod},
+  utils::{check_community_user_action, comment_downvotes_enabled, is_admin, is_top_mod},
 };
 use lemmy_db_schema::source::{
   community::{Community, CommunityActions, CommunityModeratorForm},
+  community_allowed_domain::CommunityAllowedDomain,
   modlog::{Modlog, ModlogInsertForm},
 };
 use lemmy_db_views_community::{
@@ -16,6 +17,7 @@ use lemmy_db_views_community::{
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
@@ -94,7 +96,13 @@ pub async fn transfer_community(
   .await?;
 
   let community_id = data.community_id;
-  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
+  let moderators =
+    CommunityModeratorView::for_community_inline(&mut context.pool(), community_id).await?;
+
+  let allowed_link_domains =
+    CommunityAllowedDomain::read(&mut context.pool(), community_view.community.id).await?;
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  let downvotes_enabled = comment_downvotes_enabled(&community_view.community, &local_site);
 
   // Return the jwt
   Ok(Json(GetCommunityResponse {
@@ -102,5 +110,7 @@ pub async fn transfer_community(
     site: None,
     moderators,
     discussion_languages: vec![],
+    allowed_link_domains,
+    downvotes_enabled,
   }))
 }
@@ -0,0 +1,28 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::community::{Community, CommunityActions};
+use lemmy_db_views_community::api::UpdateCommunityCommentSort;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn update_community_comment_sort(
+  Json(data): Json<UpdateCommunityCommentSort>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure the community exists
+  Community::read(&mut context.pool(), data.community_id).await?;
+
+  CommunityActions::update_comment_sort_type(
+    data.community_id,
+    local_user_view.person.id,
+    data.sort,
+    &mut context.pool(),
+  )
+  .await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
@@ -7,18 +7,40 @@ use crate::federation::{
 };
 use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
-use lemmy_api_utils::{context::LemmyContext, utils::check_private_instance};
-use lemmy_db_schema::source::comment::Comment;
-use lemmy_db_views_comment::{CommentSlimView, CommentView, api::GetComments, impls::CommentQuery};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_schema::{
+  newtypes::{CommunityId, PostId},
+  source::{comment::Comment, community::CommunityActions, post::Post},
+  utils::FETCH_LIMIT_MAX,
+};
+use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
+use lemmy_db_views_comment::{
+  CommentSlimView,
+  CommentView,
+  api::{
+    GetCommentSiblings,
+    GetCommentSiblingsResponse,
+    GetComments,
+    GetCommentsResponse,
+    GetPersonCommentsInCommunity,
+    GetTopCommentsPerPost,
+    GetTopCommentsPerPostResponse,
+  },
+  impls::CommentQuery,
+};
+use lemmy_db_views_community::CommunityView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::{pagination::PagedResponse, traits::Crud};
 use lemmy_utils::error::LemmyResult;
 
 /// A common fetcher for both the CommentView, and CommentSlimView.
-async fn list_comments_common(
+pub async fn list_comments_common(
   data: GetComments,
-  context: Data<LemmyContext>,
+  context: &Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
 ) -> LemmyResult<PagedResponse<CommentView>> {
   let site_view = SiteView::read_local(&mut context.pool()).await?;
@@ -29,13 +51,20 @@ async fn list_comments_common(
   let community_id = resolve_community_identifier(
     &data.community_name,
     data.community_id,
-    &context,
+    context,
     &local_user_view,
   )
   .await?;
   let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  let community_sort = if data.sort.is_none() {
+    community_comment_sort_type(community_id, data.post_id, &local_user_view, context).await?
+  } else {
+    None
+  };
   let sort = Some(comment_sort_type_with_default(
-    data.sort, local_user, local_site,
+    data.sort.or(community_sort),
+    local_user,
+    local_site,
   ));
   let time_range_seconds =
     post_time_range_seconds_with_default(data.time_range_seconds, local_user, local_site);
@@ -65,26 +94,69 @@ async fn list_comments_common(
     listing_type,
     sort,
     time_range_seconds,
+    created_after: data.created_after,
     max_depth,
+    branch_sort: data.branch_sort,
+    leaf_only: data.leaf_only,
+    edited_only: data.edited_only,
+    edited_after: data.edited_after,
+    saved_only: data.saved_only,
+    saved_sort: data.saved_sort,
+    language_mismatch_only: data.language_mismatch_only,
+    needs_translation_only: data.needs_translation_only,
+    min_community_activity_seconds: data.min_community_activity_seconds,
+    creator_software: data.creator_software,
+    show_blocked_instances: data.show_blocked_instances,
+    moddable_only: data.moddable_only,
+    tag_id: data.tag_id,
+    search_term: data.search_term,
     community_id,
     parent_path,
     post_id,
     local_user,
     page_cursor: data.page_cursor,
     limit,
+    ..Default::default()
   }
   .list(&site_view.site, &mut context.pool())
   .await
 }
 
+/// The comment sort which the user stored for the listed community, if any. It takes precedence
+/// over their default comment sort.
+async fn community_comment_sort_type(
+  community_id: Option<CommunityId>,
+  post_id: Option<PostId>,
+  local_user_view: &Option<LocalUserView>,
+  context: &LemmyContext,
+) -> LemmyResult<Option<CommentSortType>> {
+  let Some(local_user_view) = local_user_view else {
+    return Ok(None);
+  };
+  let community_id = match (community_id, post_id) {
+    (Some(community_id), _) => community_id,
+    (None, Some(post_id)) => Post::read(&mut context.pool(), post_id).await?.community_id,
+    (None, None) => return Ok(None),
+  };
+  let actions =
+    CommunityActions::read(&mut context.pool(), community_id, local_user_view.person.id)
+      .await
+      .ok();
+  Ok(actions.and_then(|a| a.comment_sort_type))
+}
+
 pub async fn list_comments(
   Query(data): Query<GetComments>,
   context: Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
-) -> LemmyResult<Json<PagedResponse<CommentView>>> {
-  let common = list_comments_common(data, context, local_user_view).await?;
+) -> LemmyResult<Json<GetCommentsResponse>> {
+  if data.slim.unwrap_or_default() {
+    let Json(slim) = list_comments_slim(Query(data), context, local_user_view).await?;
+    return Ok(Json(GetCommentsResponse::Slim(slim)));
+  }
+  let common = list_comments_common(data, &context, local_user_view).await?;
 
-  Ok(Json(common))
+  Ok(Json(GetCommentsResponse::Full(common)))
 }
 
 pub async fn list_comments_slim(
@@ -92,13 +164,18 @@ pub async fn list_comments_slim(
   context: Data<LemmyContext>,
   local_user_view: Option<LocalUserView>,
 ) -> LemmyResult<Json<PagedResponse<CommentSlimView>>> {
-  let common = list_comments_common(data, context, local_user_view).await?;
+  let include_child_previews = data.include_child_previews.unwrap_or_default();
+  let my_person_id = local_user_view.as_ref().map(|l| l.person.id);
+  let common = list_comments_common(data, &context, local_user_view).await?;
 
-  let data = common
+  let mut data: Vec<_> = common
     .items
     .into_iter()
     .map(CommentView::map_to_slim)
     .collect();
+  if include_child_previews {
+    CommentSlimView::fill_top_child_previews(&mut data, my_person_id, &mut context.pool()).await?;
+  }
   let res = PagedResponse {
     items: data,
     next_page: common.next_page,
@@ -107,3 +184,409 @@ pub async fn list_comments_slim(
 
   Ok(Json(res))
 }
+
+pub async fn list_person_comments_in_community(
+  Query(data): Query<GetPersonCommentsInCommunity>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<PagedResponse<CommentView>>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = &site_view.local_site;
+
+  check_private_instance(&local_user_view, local_site)?;
+
+  // Make sure that the community is visible to the viewer
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(data.community_id),
+  )
+  .await
+  .is_ok();
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  CommunityView::read(
+    &mut context.pool(),
+    data.community_id,
+    local_user,
+    is_mod_or_admin,
+  )
+  .await?;
+
+  let sort = Some(comment_sort_type_with_default(
+    data.sort, local_user, local_site,
+  ));
+  let limit = Some(fetch_limit_with_default(data.limit, local_user, local_site));
+
+  let comments = CommentQuery {
+    listing_type: Some(ListingType::All),
+    sort,
+    creator_id: Some(data.person_id),
+    community_id: Some(data.community_id),
+    local_user,
+    page_cursor: data.page_cursor,
+    limit,
+    ..Default::default()
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?;
+
+  Ok(Json(comments))
+}
+
+pub async fn get_top_comments_per_post(
+  Query(data): Query<GetTopCommentsPerPost>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetTopCommentsPerPostResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  // Make sure that the community is visible to the viewer
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(data.community_id),
+  )
+  .await
+  .is_ok();
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  CommunityView::read(
+    &mut context.pool(),
+    data.community_id,
+    local_user,
+    is_mod_or_admin,
+  )
+  .await?;
+
+  let comments = CommentView::top_per_post(
+    &mut context.pool(),
+    data.community_id,
+    data.window_seconds,
+    data.limit,
+    local_user,
+    site_view.site.instance_id,
+  )
+  .await?;
+
+  Ok(Json(GetTopCommentsPerPostResponse { comments }))
+}
+
+pub async fn get_comment_siblings(
+  Query(data): Query<GetCommentSiblings>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetCommentSiblingsResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = &site_view.local_site;
+  check_private_instance(&local_user_view, local_site)?;
+
+  let comment = Comment::read(&mut context.pool(), data.comment_id).await?;
+  let community_sort = if data.sort.is_none() {
+    community_comment_sort_type(None, Some(comment.post_id), &local_user_view, &context).await?
+  } else {
+    None
+  };
+  let local_user = local_user_view.as_ref().map(|u| &u.local_user);
+  let sort = Some(comment_sort_type_with_default(
+    data.sort.or(community_sort),
+    local_user,
+    local_site,
+  ));
+
+  let comments = CommentQuery {
+    listing_type: Some(ListingType::All),
+    sort,
+    post_id: Some(comment.post_id),
+    sibling_path: Some(comment.path),
+    local_user,
+    limit: Some(FETCH_LIMIT_MAX.try_into()?),
+    ..Default::default()
+  }
+  .list(&site_view.site, &mut context.pool())
+  .await?
+  .items;
+  let position = comments.iter().position(|c| c.comment.id == comment.id);
+
+  Ok(Json(GetCommentSiblingsResponse { comments, position }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    newtypes::InstanceId,
+    source::{
+      comment::{CommentActions, CommentInsertForm, CommentLikeForm},
+      community::{Community, CommunityInsertForm},
+      person::{Person, PersonActions, PersonBlockForm},
+      post::PostInsertForm,
+    },
+    test_data::TestData,
+    traits::{Blockable, Likeable},
+  };
+  use lemmy_diesel_utils::connection::DbPool;
+  use lemmy_utils::error::LemmyErrorType;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_community_comment_sort() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let mut user = LocalUserView::create_test_user(pool, "sorter", "", false).await?;
+    user.local_user.default_comment_sort_type = CommentSortType::New;
+    let voter = LocalUserView::create_test_user(pool, "sort_voter", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "comment_sort".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("sort".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    // The older comment has the higher score
+    let form = CommentInsertForm::new(user.person.id, post.id, "top".to_string());
+    let top = Comment::create(pool, &form, None).await?;
+    CommentActions::like(pool, &CommentLikeForm::new(voter.person.id, top.id, true)).await?;
+    let form = CommentInsertForm::new(user.person.id, post.id, "new".to_string());
+    let new = Comment::create(pool, &form, None).await?;
+
+    let first_comment = |sort| {
+      let form = GetComments {
+        post_id: Some(post.id),
+        sort,
+        ..Default::default()
+      };
+      let context = context.clone();
+      let user = user.clone();
+      async move {
+        let GetCommentsResponse::Full(res) =
+          list_comments(Query(form), context, Some(user)).await?.0
+        else {
+          Err(LemmyErrorType::NotFound)?
+        };
+        LemmyResult::Ok(res.items.first().map(|c| c.comment.id))
+      }
+    };
+
+    // The global default is used without a community preference
+    assert_eq!(Some(new.id), first_comment(None).await?);
+
+    // The community preference takes precedence over it
+    CommunityActions::update_comment_sort_type(
+      community.id,
+      user.person.id,
+      Some(CommentSortType::Top),
+      pool,
+    )
+    .await?;
+    assert_eq!(Some(top.id), first_comment(None).await?);
+
+    // An explicit sort still wins
+    assert_eq!(
+      Some(new.id),
+      first_comment(Some(CommentSortType::New)).await?
+    );
+
+    // Removing the preference goes back to the global default
+    CommunityActions::update_comment_sort_type(community.id, user.person.id, None, pool).await?;
+    assert_eq!(Some(new.id), first_comment(None).await?);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_comments_slim() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "slim_reader", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "slim_comments".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("slim".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    for content in ["first", "second"] {
+      let form = CommentInsertForm::new(user.person.id, post.id, content.to_string());
+      Comment::create(pool, &form, None).await?;
+    }
+    // Comments from blocked users stay hidden in the slim list too
+    let blocked = LocalUserView::create_test_user(pool, "slim_blocked", "", false).await?;
+    let form = CommentInsertForm::new(blocked.person.id, post.id, "blocked".to_string());
+    Comment::create(pool, &form, None).await?;
+    let form = PersonBlockForm::new(user.person.id, blocked.person.id);
+    PersonActions::block(pool, &form).await?;
+
+    let list = |slim| {
+      let form = GetComments {
+        post_id: Some(post.id),
+        slim,
+        ..Default::default()
+      };
+      list_comments(Query(form), context.clone(), Some(user.clone()))
+    };
+    let full = list(None).await?.0;
+    let slim = list(Some(true)).await?.0;
+    let (GetCommentsResponse::Full(full_page), GetCommentsResponse::Slim(slim_page)) =
+      (&full, &slim)
+    else {
+      Err(LemmyErrorType::NotFound)?
+    };
+
+    // Same comments, but without the post and community
+    let full_ids: Vec<_> = full_page.items.iter().map(|c| c.comment.id).collect();
+    let slim_ids: Vec<_> = slim_page.items.iter().map(|c| c.comment.id).collect();
+    assert_eq!(2, full_ids.len());
+    assert_eq!(full_ids, slim_ids);
+    let full_json = serde_json::to_value(&full)?;
+    let slim_json = serde_json::to_value(&slim)?;
+    assert!(full_json["items"][0].get("community").is_some());
+    assert!(slim_json["items"][0].get("community").is_none());
+    assert!(slim_json["items"][0].get("post").is_none());
+    assert!(slim_json.to_string().len() < full_json.to_string().len());
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  /// Creates a community with one comment each from `person` and `other`, and returns the
+  /// community along with the comment of `person`.
+  async fn community_with_comments(
+    pool: &mut DbPool<'_>,
+    instance_id: InstanceId,
+    name: &str,
+    person: &Person,
+    other: &Person,
+  ) -> LemmyResult<(Community, Comment)> {
+    let form = CommunityInsertForm::new(
+      instance_id,
+      name.to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &form).await?;
+    let post_form = PostInsertForm::new(name.to_string(), other.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let form = CommentInsertForm::new(other.id, post.id, "theirs".to_string());
+    Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(person.id, post.id, "mine".to_string());
+    let comment = Comment::create(pool, &form, None).await?;
+    Ok((community, comment))
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_person_comments_in_community() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let reviewer = LocalUserView::create_test_user(pool, "activity_reviewer", "", false).await?;
+    let person = LocalUserView::create_test_user(pool, "reviewed_person", "", false).await?;
+    let other = LocalUserView::create_test_user(pool, "other_person", "", false).await?;
+    let (community, comment) = community_with_comments(
+      pool,
+      data.instance.id,
+      "reviewed_community",
+      &person.person,
+      &other.person,
+    )
+    .await?;
+    let (elsewhere, _) = community_with_comments(
+      pool,
+      data.instance.id,
+      "elsewhere",
+      &person.person,
+      &other.person,
+    )
+    .await?;
+
+    // Neither the comments of others in the community, nor the person's comments elsewhere
+    let form = GetPersonCommentsInCommunity {
+      person_id: person.person.id,
+      community_id: community.id,
+      sort: None,
+      page_cursor: None,
+      limit: None,
+    };
+    let res = list_person_comments_in_community(Query(form), context.clone(), Some(reviewer))
+      .await?
+      .0;
+    let ids: Vec<_> = res.items.iter().map(|c| c.comment.id).collect();
+    assert_eq!(vec![comment.id], ids);
+
+    Community::delete(pool, community.id).await?;
+    Community::delete(pool, elsewhere.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_top_comments_per_post() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "digest_author", "", false).await?;
+    let voter = LocalUserView::create_test_user(pool, "digest_voter", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "best_of".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let mut comments = vec![];
+    for title in ["first", "second"] {
+      let post_form = PostInsertForm::new(title.to_string(), user.person.id, community.id);
+      let post = Post::create(pool, &post_form).await?;
+      for content in ["one", "two"] {
+        let form = CommentInsertForm::new(user.person.id, post.id, content.to_string());
+        comments.push(Comment::create(pool, &form, None).await?);
+      }
+    }
+    let [first_one, _, second_one, second_two] = comments.as_slice() else {
+      panic!("wrong number of comments");
+    };
+
+    // Upvote a comment of the first post, and downvote one of the second
+    for (comment, is_upvote) in [(first_one, true), (second_one, false)] {
+      let form = CommentLikeForm::new(voter.person.id, comment.id, is_upvote);
+      CommentActions::like(pool, &form).await?;
+    }
+
+    let form = GetTopCommentsPerPost {
+      community_id: community.id,
+      window_seconds: Some(3600),
+      limit: None,
+    };
+    let res = get_top_comments_per_post(Query(form), context.clone(), None)
+      .await?
+      .0;
+    let top: Vec<_> = res
+      .comments
+      .iter()
+      .map(|c| (c.comment.id, c.comment.score))
+      .collect();
+    assert_eq!(vec![(first_one.id, 1), (second_two.id, 0)], top);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -12,6 +12,8 @@ pub mod list_posts;
 pub mod read_community;
 pub mod read_multi_community;
 pub mod read_person;
+pub mod read_person_karma;
+pub mod resolve_comment;
 pub mod resolve_object;
 pub mod search;
 pub mod user_settings_backup;
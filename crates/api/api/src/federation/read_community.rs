@@ -3,9 +3,17 @@ use activitypub_federation::config::Data;
 use actix_web::web::{Json, Query};
 use lemmy_api_utils::{
   context::LemmyContext,
-  utils::{check_private_instance, is_mod_or_admin_opt, read_site_for_actor},
+  utils::{
+    check_private_instance,
+    comment_downvotes_enabled,
+    is_mod_or_admin_opt,
+    read_site_for_actor,
+  },
+};
+use lemmy_db_schema::source::{
+  actor_language::CommunityLanguage,
+  community_allowed_domain::CommunityAllowedDomain,
 };
-use lemmy_db_schema::source::actor_language::CommunityLanguage;
 use lemmy_db_views_community::{
   CommunityView,
   api::{GetCommunity, GetCommunityResponse},
@@ -22,17 +30,18 @@ pub async fn get_community(
 ) -> LemmyResult<Json<GetCommunityResponse>> {
   let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
 
-  if data.name.is_none() && data.id.is_none() {
-    Err(LemmyErrorType::NoIdGiven)?
-  }
-
   check_private_instance(&local_user_view, &local_site)?;
 
   let local_user = local_user_view.as_ref().map(|u| &u.local_user);
 
-  let community_id = resolve_community_identifier(&data.name, data.id, &context, &local_user_view)
-    .await?
-    .ok_or(LemmyErrorType::NoIdGiven)?;
+  let community_id = match data {
+    GetCommunity::Id(id) => id,
+    GetCommunity::Name(name) => {
+      resolve_community_identifier(&Some(name), None, &context, &local_user_view)
+        .await?
+        .ok_or(LemmyErrorType::NoIdGiven)?
+    }
+  };
 
   let is_mod_or_admin = is_mod_or_admin_opt(
     &mut context.pool(),
@@ -50,17 +59,53 @@ pub async fn get_community(
   )
   .await?;
 
-  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community_id).await?;
+  let moderators =
+    CommunityModeratorView::for_community_inline(&mut context.pool(), community_id).await?;
 
   let site = read_site_for_actor(community_view.community.ap_id.clone(), &context).await?;
 
   let community_id = community_view.community.id;
   let discussion_languages = CommunityLanguage::read(&mut context.pool(), community_id).await?;
 
+  let allowed_link_domains =
+    CommunityAllowedDomain::read(&mut context.pool(), community_view.community.id).await?;
+  let downvotes_enabled = comment_downvotes_enabled(&community_view.community, &local_site);
+
   Ok(Json(GetCommunityResponse {
     community_view,
     site,
     moderators,
     discussion_languages,
+    allowed_link_domains,
+    downvotes_enabled,
   }))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::newtypes::CommunityId;
+
+  #[test]
+  fn test_get_community_params() -> LemmyResult<()> {
+    let parse = |query: &str| Query::<GetCommunity>::from_query(query).map(Query::into_inner);
+
+    assert_eq!(GetCommunity::Id(CommunityId(5)), parse("id=5")?);
+    assert_eq!(
+      GetCommunity::Name("star_trek@xyz.tld".to_string()),
+      parse("name=star_trek%40xyz.tld")?
+    );
+    // Exactly one of them is required
+    assert!(parse("").is_err());
+    assert!(parse("id=5&name=star_trek").is_err());
+
+    // Same format as JSON
+    let id = GetCommunity::Id(CommunityId(5));
+    assert_eq!(r#"{"id":5}"#, serde_json::to_string(&id)?);
+    assert_eq!(id, serde_json::from_str(r#"{"id":5}"#)?);
+    let name = GetCommunity::Name("star_trek".to_string());
+    assert_eq!(r#"{"name":"star_trek"}"#, serde_json::to_string(&name)?);
+    assert!(serde_json::from_str::<GetCommunity>(r#"{"id":5,"name":"star_trek"}"#).is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,55 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_admin},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::{
+  PersonCommunityKarma,
+  PersonView,
+  api::{GetPersonKarmaBreakdown, GetPersonKarmaBreakdownResponse},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn read_person_karma_breakdown(
+  Query(data): Query<GetPersonKarmaBreakdown>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetPersonKarmaBreakdownResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_site = site_view.local_site;
+  let local_instance_id = site_view.site.instance_id;
+  let my_person_id = local_user_view.as_ref().map(|l| l.person.id);
+
+  check_private_instance(&local_user_view, &local_site)?;
+
+  let is_admin = local_user_view
+    .as_ref()
+    .map(|l| is_admin(l).is_ok())
+    .unwrap_or_default();
+  let person = PersonView::read(
+    &mut context.pool(),
+    data.person_id,
+    my_person_id,
+    local_instance_id,
+    is_admin,
+  )
+  .await?
+  .person;
+
+  let communities = PersonCommunityKarma::list_for_person(
+    &mut context.pool(),
+    person.id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    data.limit,
+  )
+  .await?;
+
+  Ok(Json(GetPersonKarmaBreakdownResponse {
+    post_score: person.post_score,
+    comment_score: person.comment_score,
+    communities,
+  }))
+}
@@ -0,0 +1,117 @@
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{
+  build_response::build_comment_response,
+  context::LemmyContext,
+  utils::{check_private_instance, get_url_blocklist},
+};
+use lemmy_apub_objects::objects::comment::ApubComment;
+use lemmy_db_views_comment::api::{CommentResponse, ResolveComment};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::{LemmyErrorExt2, LemmyErrorType, LemmyResult};
+use url::Url;
+
+pub async fn resolve_comment(
+  Query(data): Query<ResolveComment>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<CommentResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  let local_instance_id = site_view.site.instance_id;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  let comment = resolve_comment_internal(&data.ap_id, &local_user_view, &context).await?;
+
+  Ok(Json(
+    build_comment_response(&context, comment.id, local_user_view, local_instance_id).await?,
+  ))
+}
+
+/// Only authenticated users may fetch remote comments, and only if they aren't on the URL
+/// blocklist. Otherwise the comment has to be stored locally already.
+async fn resolve_comment_internal(
+  ap_id: &str,
+  local_user_view: &Option<LocalUserView>,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<ApubComment> {
+  let url = Url::parse(ap_id)?;
+  let is_authenticated = local_user_view.as_ref().is_some_and(|l| !l.banned);
+
+  if is_authenticated || cfg!(debug_assertions) {
+    if get_url_blocklist(context).await?.is_match(url.as_str()) {
+      Err(LemmyErrorType::BlockedUrl)?
+    }
+    ObjectId::<ApubComment>::from(url)
+      .dereference(context)
+      .await
+  } else {
+    ObjectId::<ApubComment>::from(url)
+      .dereference_local(context)
+      .await
+  }
+  .with_lemmy_type(LemmyErrorType::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
+      person::Person,
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_resolve_local_comment() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let creator = LocalUserView::create_test_user(pool, "comment_resolver", "", false).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "resolve_comment".to_string(),
+        "test".to_string(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("Test".to_string(), creator.person.id, community.id),
+    )
+    .await?;
+    let comment = Comment::create(
+      pool,
+      &CommentInsertForm::new(creator.person.id, post.id, "resolve me".to_string()),
+      None,
+    )
+    .await?;
+
+    // Resolvable by its ap_id, without and with authentication
+    let res = resolve_comment_internal(comment.ap_id.as_str(), &None, &context).await?;
+    assert_eq!(comment.id, res.id);
+    let res =
+      resolve_comment_internal(comment.ap_id.as_str(), &Some(creator.clone()), &context).await?;
+    assert_eq!(comment.id, res.id);
+
+    // Not a valid ap_id
+    let res = resolve_comment_internal("not a url", &None, &context).await;
+    assert!(res.is_err());
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, creator.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -1,6 +1,15 @@
 use activitypub_federation::{config::Data, fetch::object_id::ObjectId, traits::Object};
-use actix_web::web::Json;
-use futures::{StreamExt, future::try_join_all};
+use actix_web::{
+  HttpResponse,
+  web::{Bytes, Json},
+};
+use futures::{
+  Stream,
+  StreamExt,
+  TryStreamExt,
+  future::{ready, try_join_all},
+  stream,
+};
 use itertools::Itertools;
 use lemmy_api_utils::{context::LemmyContext, utils::check_local_user_valid};
 use lemmy_apub_objects::objects::{
@@ -9,38 +18,56 @@ use lemmy_apub_objects::objects::{
   person::ApubPerson,
   post::ApubPost,
 };
-use lemmy_db_schema::{
-  source::{
-    actor_language::LocalUserLanguage,
-    comment::{CommentActions, CommentSavedForm},
-    community::{CommunityActions, CommunityBlockForm, CommunityFollowerForm},
-    instance::{Instance, InstanceActions, InstanceCommunitiesBlockForm, InstancePersonsBlockForm},
-    keyword_block::LocalUserKeywordBlock,
-    language::Language,
-    local_user::{LocalUser, LocalUserUpdateForm},
-    person::{Person, PersonActions, PersonBlockForm, PersonUpdateForm},
-    post::{PostActions, PostSavedForm},
+use lemmy_db_schema::source::{
+  actor_language::LocalUserLanguage,
+  comment::{CommentActions, CommentSavedForm},
+  community::{CommunityActions, CommunityBlockForm, CommunityFollowerForm},
+  instance::{Instance, InstanceActions, InstanceCommunitiesBlockForm, InstancePersonsBlockForm},
+  keyword_block::LocalUserKeywordBlock,
+  language::Language,
+  local_user::{LocalUser, LocalUserUpdateForm},
+  person::{Person, PersonActions, PersonBlockForm, PersonUpdateForm},
+  post::{PostActions, PostSavedForm},
+  settings_import_failure::{SettingsImportFailure, SettingsImportFailureForm},
+};
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{
+    CommunityFollowerState,
+    SettingsImportKind::{self, *},
   },
-  traits::{Blockable, Followable, Saveable},
 };
-use lemmy_db_schema_file::enums::CommunityFollowerState;
+use lemmy_db_views_community_follower::CommunityFollowerView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::{
-  api::{SuccessResponse, UserSettingsBackup},
-  impls::user_backup_list_to_user_settings_backup,
+  api::{SettingsImportStatusResponse, SuccessResponse, UserSettingsBackup},
+  impls::{user_backup_list_to_user_settings_backup, user_settings_backup_without_lists},
 };
-use lemmy_diesel_utils::traits::Crud;
+use lemmy_diesel_utils::{dburl::DbUrl, traits::Crud};
 use lemmy_utils::{
   error::LemmyResult,
   spawn_try_task,
   utils::validation::{check_api_elements_count, check_blocking_keywords_are_valid},
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, future::Future};
-use tracing::info;
+use tracing::{info, warn};
+use url::Url;
 
 const PARALLELISM: usize = 10;
 
+/// Number of list items which are fetched at once for streaming exports.
+const EXPORT_CHUNK_SIZE: i64 = 1000;
+
+/// The lists in [UserSettingsBackup] which streaming exports write in chunks.
+const EXPORT_LISTS: [SettingsImportKind; 5] = [
+  FollowedCommunity,
+  SavedPost,
+  SavedComment,
+  BlockedCommunity,
+  BlockedPerson,
+];
+
 pub async fn export_settings(
   local_user_view: LocalUserView,
   context: Data<LemmyContext>,
@@ -51,6 +78,212 @@ pub async fn export_settings(
   Ok(Json(settings))
 }
 
+/// Same as [export_settings], but fetches and writes the lists of followed communities, saved
+/// items and blocked users in chunks, to avoid holding them in memory at once. The output can be
+/// imported with [import_settings] as usual.
+pub async fn export_settings_streaming(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<HttpResponse> {
+  let body = export_settings_stream(local_user_view, context, EXPORT_CHUNK_SIZE).await?;
+  Ok(
+    HttpResponse::Ok()
+      .content_type("application/json")
+      .streaming(body.map_err(actix_web::Error::from)),
+  )
+}
+
+/// The fields of [UserSettingsBackup] which streaming exports write before the lists.
+#[derive(Serialize)]
+struct UserSettingsBackupHead {
+  display_name: Option<String>,
+  bio: Option<String>,
+  avatar: Option<Url>,
+  banner: Option<Url>,
+  matrix_id: Option<String>,
+  bot_account: Option<bool>,
+  settings: Option<LocalUser>,
+  blocked_instances_communities: Vec<String>,
+  blocked_instances_persons: Vec<String>,
+  blocking_keywords: Vec<String>,
+  discussion_languages: Vec<String>,
+}
+
+impl From<UserSettingsBackup> for UserSettingsBackupHead {
+  fn from(backup: UserSettingsBackup) -> Self {
+    UserSettingsBackupHead {
+      display_name: backup.display_name,
+      bio: backup.bio,
+      avatar: backup.avatar,
+      banner: backup.banner,
+      matrix_id: backup.matrix_id,
+      bot_account: backup.bot_account,
+      settings: backup.settings,
+      blocked_instances_communities: backup.blocked_instances_communities,
+      blocked_instances_persons: backup.blocked_instances_persons,
+      blocking_keywords: backup.blocking_keywords,
+      discussion_languages: backup.discussion_languages,
+    }
+  }
+}
+
+async fn export_settings_stream(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+  chunk_size: i64,
+) -> LemmyResult<impl Stream<Item = LemmyResult<Bytes>>> {
+  let person_id = local_user_view.person.id;
+  let head: UserSettingsBackupHead =
+    user_settings_backup_without_lists(local_user_view, &mut context.pool())
+      .await?
+      .into();
+
+  // Write all other fields first, and leave the object open for the lists
+  let fields: serde_json::Map<String, serde_json::Value> =
+    serde_json::from_value(serde_json::to_value(head)?)?;
+  let head = fields
+    .iter()
+    .map(|(name, value)| Ok(format!("{}:{value}", serde_json::to_string(name)?)))
+    .collect::<LemmyResult<Vec<_>>>()?
+    .join(",");
+
+  let lists = stream::iter(EXPORT_LISTS)
+    .flat_map(move |kind| export_list(kind, person_id, context.clone(), chunk_size));
+  let body = stream::once(ready(Ok(Bytes::from(format!("{{{head}")))))
+    .chain(lists)
+    .chain(stream::once(ready(Ok(Bytes::from_static(b"}")))));
+
+  // The response is already sent at this point, so a database error can't change the status.
+  // Stop at the first error instead, so that the download is cut off and can't be mistaken for a
+  // complete backup with missing items.
+  Ok(body.scan(false, |failed, item| {
+    if *failed {
+      return ready(None);
+    }
+    if let Err(e) = &item {
+      warn!("Streaming settings export failed: {e}");
+      *failed = true;
+    }
+    ready(Some(item))
+  }))
+}
+
+/// Writes one of the lists as object field, fetching `chunk_size` items at a time.
+fn export_list(
+  kind: SettingsImportKind,
+  person_id: PersonId,
+  context: Data<LemmyContext>,
+  chunk_size: i64,
+) -> impl Stream<Item = LemmyResult<Bytes>> {
+  let name = export_list_name(kind);
+  let items = stream::try_unfold(Some(0), move |after_id| {
+    let context = context.clone();
+    async move {
+      match after_id {
+        Some(after_id) => export_list_chunk(kind, person_id, after_id, chunk_size, &context).await,
+        None => Ok(None),
+      }
+    }
+  });
+  stream::once(ready(Ok(Bytes::from(format!(",\"{name}\":[")))))
+    .chain(items)
+    .chain(stream::once(ready(Ok(Bytes::from_static(b"]")))))
+}
+
+/// Returns the next chunk of the list after the given id, and the id to continue from if there
+/// may be more items.
+async fn export_list_chunk(
+  kind: SettingsImportKind,
+  person_id: PersonId,
+  after_id: i32,
+  chunk_size: i64,
+  context: &LemmyContext,
+) -> LemmyResult<Option<(Bytes, Option<i32>)>> {
+  let chunk =
+    LocalUser::export_backup_chunk(&mut context.pool(), person_id, kind, after_id, chunk_size)
+      .await?;
+  let Some((last_id, _)) = chunk.last() else {
+    return Ok(None);
+  };
+  // Only a full chunk can be followed by more items
+  let next_after_id = (chunk.len() == usize::try_from(chunk_size)?).then_some(*last_id);
+
+  let urls = chunk
+    .iter()
+    .map(|(_, url)| serde_json::to_string(url.inner()))
+    .collect::<Result<Vec<_>, _>>()?
+    .join(",");
+  // Ids start at 1, so anything else means there was a chunk before
+  let separator = if after_id == 0 { "" } else { "," };
+  Ok(Some((
+    Bytes::from(format!("{separator}{urls}")),
+    next_after_id,
+  )))
+}
+
+/// Name of the field in [UserSettingsBackup] which holds the given list.
+fn export_list_name(kind: SettingsImportKind) -> &'static str {
+  match kind {
+    FollowedCommunity => "followed_communities",
+    SavedPost => "saved_posts",
+    SavedComment => "saved_comments",
+    BlockedCommunity => "blocked_communities",
+    BlockedPerson => "blocked_users",
+  }
+}
+
+/// Exports the followed communities as OPML document, for use with feed readers.
+pub async fn export_communities_opml(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<HttpResponse> {
+  let communities =
+    CommunityFollowerView::for_person(&mut context.pool(), local_user_view.person.id)
+      .await?
+      .into_iter()
+      .map(|f| (f.community.title, f.community.ap_id))
+      .collect::<Vec<_>>();
+  let opml = generate_opml(&local_user_view.person.name, &communities);
+
+  Ok(HttpResponse::Ok().content_type("text/x-opml").body(opml))
+}
+
+/// Generates the OPML document from a list of community titles and actor urls.
+fn generate_opml(person_name: &str, communities: &[(String, DbUrl)]) -> String {
+  let outlines = communities
+    .iter()
+    .map(|(title, ap_id)| {
+      format!(
+        r#"    <outline type="rss" text="{title}" title="{title}" htmlUrl="{url}" xmlUrl="{url}"/>"#,
+        title = escape_xml(title),
+        url = escape_xml(ap_id.as_str()),
+      )
+    })
+    .join("\n");
+  format!(
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<opml version="2.0">
+  <head>
+    <title>Communities followed by {}</title>
+  </head>
+  <body>
+{outlines}
+  </body>
+</opml>
+"#,
+    escape_xml(person_name)
+  )
+}
+
+fn escape_xml(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
 pub async fn import_settings(
   Json(data): Json<UserSettingsBackup>,
   local_user_view: LocalUserView,
@@ -83,6 +316,23 @@ pub async fn import_settings(
       .map(|s| s.send_notifications_to_email),
     show_bot_accounts: data.settings.as_ref().map(|s| s.show_bot_accounts),
     show_read_posts: data.settings.as_ref().map(|s| s.show_read_posts),
+    show_read_comments: data.settings.as_ref().map(|s| s.show_read_comments),
+    email_pending_community_followers: data
+      .settings
+      .as_ref()
+      .map(|s| s.email_pending_community_followers),
+    receive_community_welcome_messages: data
+      .settings
+      .as_ref()
+      .map(|s| s.receive_community_welcome_messages),
+    collapse_comments_account_age_days: data
+      .settings
+      .as_ref()
+      .map(|s| s.collapse_comments_account_age_days),
+    collapse_comments_below_score: data
+      .settings
+      .as_ref()
+      .map(|s| s.collapse_comments_below_score),
     open_links_in_new_tab: data.settings.as_ref().map(|s| s.open_links_in_new_tab),
     blur_nsfw: data.settings.as_ref().map(|s| s.blur_nsfw),
     infinite_scroll_enabled: data.settings.as_ref().map(|s| s.infinite_scroll_enabled),
@@ -141,91 +391,19 @@ pub async fn import_settings(
       local_user_view.person.name
     );
 
-    let failed_followed_communities = fetch_and_import(
-      data
-        .followed_communities
-        .clone()
-        .into_iter()
-        .map(Into::into)
-        .collect::<Vec<ObjectId<ApubCommunity>>>(),
-      &context,
-      |(followed, context)| async move {
-        let community = followed.dereference(&context).await?;
-        let form =
-          CommunityFollowerForm::new(community.id, person_id, CommunityFollowerState::Pending);
-        CommunityActions::follow(&mut context.pool(), &form).await?;
-        LemmyResult::Ok(())
-      },
-    )
-    .await?;
-
-    let failed_saved_posts = fetch_and_import(
-      data
-        .saved_posts
-        .clone()
-        .into_iter()
-        .map(Into::into)
-        .collect::<Vec<ObjectId<ApubPost>>>(),
-      &context,
-      |(saved, context)| async move {
-        let post = saved.dereference(&context).await?;
-        let form = PostSavedForm::new(post.id, person_id);
-        PostActions::save(&mut context.pool(), &form).await?;
-        LemmyResult::Ok(())
-      },
-    )
-    .await?;
-
-    let failed_saved_comments = fetch_and_import(
-      data
-        .saved_comments
-        .clone()
-        .into_iter()
-        .map(Into::into)
-        .collect::<Vec<ObjectId<ApubComment>>>(),
-      &context,
-      |(saved, context)| async move {
-        let comment = saved.dereference(&context).await?;
-        let form = CommentSavedForm::new(person_id, comment.id);
-        CommentActions::save(&mut context.pool(), &form).await?;
-        LemmyResult::Ok(())
-      },
-    )
-    .await?;
-
-    let failed_community_blocks = fetch_and_import(
-      data
-        .blocked_communities
-        .clone()
-        .into_iter()
-        .map(Into::into)
-        .collect::<Vec<ObjectId<ApubCommunity>>>(),
-      &context,
-      |(blocked, context)| async move {
-        let community = blocked.dereference(&context).await?;
-        let form = CommunityBlockForm::new(community.id, person_id);
-        CommunityActions::block(&mut context.pool(), &form).await?;
-        LemmyResult::Ok(())
-      },
-    )
-    .await?;
-
-    let failed_user_blocks = fetch_and_import(
-      data
-        .blocked_users
-        .clone()
-        .into_iter()
-        .map(Into::into)
-        .collect::<Vec<ObjectId<ApubPerson>>>(),
-      &context,
-      |(blocked, context)| async move {
-        let target = blocked.dereference(&context).await?;
-        let form = PersonBlockForm::new(person_id, target.id);
-        PersonActions::block(&mut context.pool(), &form).await?;
-        LemmyResult::Ok(())
-      },
-    )
-    .await?;
+    // A new import replaces any failures from previous imports
+    SettingsImportFailure::clear(&mut context.pool(), local_user_id).await?;
+
+    let mut failed = vec![];
+    for (kind, urls) in [
+      (FollowedCommunity, data.followed_communities.clone()),
+      (SavedPost, data.saved_posts.clone()),
+      (SavedComment, data.saved_comments.clone()),
+      (BlockedCommunity, data.blocked_communities.clone()),
+      (BlockedPerson, data.blocked_users.clone()),
+    ] {
+      failed.extend(import_items(kind, urls, &local_user_view, &context).await?);
+    }
 
     try_join_all(
       data
@@ -248,22 +426,173 @@ pub async fn import_settings(
     }))
     .await?;
 
+    SettingsImportFailure::create_many(&mut context.pool(), &failed).await?;
+
     info!(
-      "Settings import completed for {}, the following items failed: {failed_followed_communities}, {failed_saved_posts}, {failed_saved_comments}, {failed_community_blocks}, {failed_user_blocks}",
+      "Settings import completed for {}, {} items failed",
+      local_user_view.person.name,
+      failed.len()
+    );
+
+    Ok(())
+  });
+
+  Ok(Json(Default::default()))
+}
+
+/// Lists the items which failed during the last settings import, so that clients can show them.
+pub async fn get_settings_import_status(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SettingsImportStatusResponse>> {
+  let failed =
+    SettingsImportFailure::list(&mut context.pool(), local_user_view.local_user.id).await?;
+  Ok(Json(SettingsImportStatusResponse {
+    count: failed.len().try_into()?,
+    failed,
+  }))
+}
+
+/// Retries fetching only those items which failed during the last settings import.
+pub async fn retry_settings_import(
+  local_user_view: LocalUserView,
+  context: Data<LemmyContext>,
+) -> LemmyResult<Json<SuccessResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let local_user_id = local_user_view.local_user.id;
+  let previous = SettingsImportFailure::list(&mut context.pool(), local_user_id).await?;
+  if previous.is_empty() {
+    return Ok(Json(Default::default()));
+  }
+
+  spawn_try_task(async move {
+    info!(
+      "Retrying {} failed settings import items for {}",
+      previous.len(),
       local_user_view.person.name
     );
 
+    let by_kind = previous
+      .into_iter()
+      .map(|f| (f.kind, f.ap_id.inner().clone()))
+      .into_group_map();
+    let mut failed = vec![];
+    for (kind, urls) in by_kind {
+      let failed_kind = import_items(kind, urls.clone(), &local_user_view, &context).await?;
+      // Only forget the items which were imported now, the others stay stored for another retry
+      let imported: Vec<DbUrl> = urls
+        .into_iter()
+        .map(Into::into)
+        .filter(|url| !failed_kind.iter().any(|f| &f.ap_id == url))
+        .collect();
+      SettingsImportFailure::remove(&mut context.pool(), local_user_id, kind, &imported).await?;
+      failed.extend(failed_kind);
+    }
+
+    info!(
+      "Settings import retry completed for {}, {} items failed",
+      local_user_view.person.name,
+      failed.len()
+    );
+
     Ok(())
   });
 
   Ok(Json(Default::default()))
 }
 
+/// Fetches the given objects and imports them for the user. Returns a failure entry for each
+/// object which couldn't be imported.
+async fn import_items(
+  kind: SettingsImportKind,
+  urls: Vec<Url>,
+  local_user_view: &LocalUserView,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<Vec<SettingsImportFailureForm>> {
+  let person_id = local_user_view.person.id;
+
+  let failed = match kind {
+    FollowedCommunity => {
+      fetch_and_import(
+        urls.into_iter().map(Into::into).collect(),
+        context,
+        |(followed, context): (ObjectId<ApubCommunity>, _)| async move {
+          let community = followed.dereference(&context).await?;
+          let form =
+            CommunityFollowerForm::new(community.id, person_id, CommunityFollowerState::Pending);
+          CommunityActions::follow_if_new(&mut context.pool(), &form).await?;
+          LemmyResult::Ok(())
+        },
+      )
+      .await?
+    }
+    SavedPost => {
+      fetch_and_import(
+        urls.into_iter().map(Into::into).collect(),
+        context,
+        |(saved, context): (ObjectId<ApubPost>, _)| async move {
+          let post = saved.dereference(&context).await?;
+          let form = PostSavedForm::new(post.id, person_id);
+          PostActions::save_if_new(&mut context.pool(), &form).await?;
+          LemmyResult::Ok(())
+        },
+      )
+      .await?
+    }
+    SavedComment => {
+      fetch_and_import(
+        urls.into_iter().map(Into::into).collect(),
+        context,
+        |(saved, context): (ObjectId<ApubComment>, _)| async move {
+          let comment = saved.dereference(&context).await?;
+          let form = CommentSavedForm::new(person_id, comment.id);
+          CommentActions::save_if_new(&mut context.pool(), &form).await?;
+          LemmyResult::Ok(())
+        },
+      )
+      .await?
+    }
+    BlockedCommunity => {
+      fetch_and_import(
+        urls.into_iter().map(Into::into).collect(),
+        context,
+        |(blocked, context): (ObjectId<ApubCommunity>, _)| async move {
+          let community = blocked.dereference(&context).await?;
+          let form = CommunityBlockForm::new(community.id, person_id);
+          CommunityActions::block_if_new(&mut context.pool(), &form).await?;
+          LemmyResult::Ok(())
+        },
+      )
+      .await?
+    }
+    BlockedPerson => {
+      fetch_and_import(
+        urls.into_iter().map(Into::into).collect(),
+        context,
+        |(blocked, context): (ObjectId<ApubPerson>, _)| async move {
+          let target = blocked.dereference(&context).await?;
+          let form = PersonBlockForm::new(person_id, target.id);
+          PersonActions::block_if_new(&mut context.pool(), &form).await?;
+          LemmyResult::Ok(())
+        },
+      )
+      .await?
+    }
+  };
+
+  Ok(
+    failed
+      .into_iter()
+      .map(|url| SettingsImportFailureForm::new(local_user_view.local_user.id, kind, url.into()))
+      .collect(),
+  )
+}
+
 async fn fetch_and_import<Kind, Fut>(
   objects: Vec<ObjectId<Kind>>,
   context: &Data<LemmyContext>,
   import_fn: impl FnMut((ObjectId<Kind>, Data<LemmyContext>)) -> Fut,
-) -> LemmyResult<String>
+) -> LemmyResult<Vec<Url>>
 where
   Kind: Object + Send + Sync + 'static,
   for<'de2> <Kind as Object>::Kind: Deserialize<'de2>,
@@ -290,15 +619,22 @@ where
       failed_items.push(object.inner().clone());
     }
   });
-  Ok(failed_items.into_iter().join(","))
+  Ok(failed_items)
 }
 
 #[cfg(test)]
 #[expect(clippy::indexing_slicing)]
 pub(crate) mod tests {
   use super::*;
-  use crate::federation::user_settings_backup::{export_settings, import_settings};
+  use crate::federation::user_settings_backup::{
+    export_settings,
+    export_settings_stream,
+    get_settings_import_status,
+    import_settings,
+    retry_settings_import,
+  };
   use actix_web::web::Json;
+  use elementtree::Element;
   use lemmy_api_utils::context::LemmyContext;
   use lemmy_db_schema::{
     newtypes::LanguageId,
@@ -388,6 +724,196 @@ pub(crate) mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_settings_export_streaming() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let export_user = LocalUserView::create_test_user(pool, "stream", "stream bio", false).await?;
+    let mut communities = vec![];
+    for name in ["stream_1", "stream_2", "stream_3"] {
+      let community_form = CommunityInsertForm::new(
+        export_user.person.instance_id,
+        name.to_string(),
+        name.to_string(),
+        "pubkey".to_string(),
+      );
+      let community = Community::create(pool, &community_form).await?;
+      let follower_form = CommunityFollowerForm::new(
+        community.id,
+        export_user.person.id,
+        CommunityFollowerState::Accepted,
+      );
+      CommunityActions::follow(pool, &follower_form).await?;
+      communities.push(community);
+    }
+
+    // Use chunks smaller than the list, to check that they are joined correctly
+    let bytes = export_settings_stream(export_user.clone(), context.clone(), 2)
+      .await?
+      .try_collect::<Vec<_>>()
+      .await?
+      .concat();
+    let mut streamed: UserSettingsBackup = serde_json::from_slice(&bytes)?;
+    let mut expected = export_settings(export_user.clone(), context.clone())
+      .await?
+      .0;
+    streamed.followed_communities.sort();
+    expected.followed_communities.sort();
+    assert_eq!(3, streamed.followed_communities.len());
+    assert_eq!(
+      serde_json::to_value(&expected)?,
+      serde_json::to_value(&streamed)?
+    );
+
+    for community in communities {
+      Community::delete(pool, community.id).await?;
+    }
+    Person::delete(pool, export_user.person.id).await?;
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn import_same_backup_twice() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let export_user = LocalUserView::create_test_user(pool, "gustav", "gustav bio", false).await?;
+    let community_form = CommunityInsertForm::new(
+      export_user.person.instance_id,
+      "twice".to_string(),
+      "twice".to_string(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let follower_form = CommunityFollowerForm::new(
+      community.id,
+      export_user.person.id,
+      CommunityFollowerState::Accepted,
+    );
+    CommunityActions::follow(pool, &follower_form).await?;
+
+    let backup = export_settings(export_user.clone(), context.clone()).await?;
+    let import_user = LocalUserView::create_test_user(pool, "greta", "greta bio", false).await?;
+
+    import_settings(Json(backup.0.clone()), import_user.clone(), context.clone()).await?;
+    sleep(Duration::from_millis(1000)).await;
+
+    // The community accepted the follow in the meantime, which a re-import must not reset
+    CommunityActions::follow_accepted(pool, community.id, import_user.person.id).await?;
+
+    import_settings(backup, import_user.clone(), context.clone()).await?;
+    sleep(Duration::from_millis(1000)).await;
+
+    let failures = SettingsImportFailure::list(pool, import_user.local_user.id).await?;
+    assert!(failures.is_empty());
+    let follows = CommunityFollowerView::for_person(pool, import_user.person.id).await?;
+    assert_eq!(1, follows.len());
+    let follow = CommunityActions::read(pool, community.id, import_user.person.id).await?;
+    assert_eq!(Some(CommunityFollowerState::Accepted), follow.follow_state);
+
+    Person::delete(pool, export_user.person.id).await?;
+    Person::delete(pool, import_user.person.id).await?;
+    Community::delete(pool, community.id).await?;
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn import_status_lists_failures() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let import_user = LocalUserView::create_test_user(pool, "status", "status bio", false).await?;
+    let bogus: Url = "http://localhost:1/c/bogus".parse()?;
+    let backup = UserSettingsBackup {
+      followed_communities: vec![bogus.clone()],
+      ..Default::default()
+    };
+    import_settings(Json(backup), import_user.clone(), context.clone()).await?;
+    sleep(Duration::from_millis(1000)).await;
+
+    let status = get_settings_import_status(import_user.clone(), context.clone())
+      .await?
+      .0;
+    assert_eq!(1, status.count);
+    assert_eq!(FollowedCommunity, status.failed[0].kind);
+    assert_eq!(&bogus, status.failed[0].ap_id.inner());
+
+    Person::delete(pool, import_user.person.id).await?;
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn retry_import_keeps_only_failed_items() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let retry_user = LocalUserView::create_test_user(pool, "retry", "retry bio", false).await?;
+    let local_user_id = retry_user.local_user.id;
+
+    // Nothing to retry
+    retry_settings_import(retry_user.clone(), context.clone()).await?;
+    assert!(
+      SettingsImportFailure::list(pool, local_user_id)
+        .await?
+        .is_empty()
+    );
+
+    let target = LocalUserView::create_test_user(pool, "retry_target", "", false).await?;
+    let bogus: Url = "http://localhost:1/u/bogus".parse()?;
+    let forms = [
+      SettingsImportFailureForm::new(local_user_id, BlockedPerson, bogus.clone().into()),
+      SettingsImportFailureForm::new(local_user_id, BlockedPerson, target.person.ap_id.clone()),
+    ];
+    SettingsImportFailure::create_many(pool, &forms).await?;
+    retry_settings_import(retry_user.clone(), context.clone()).await?;
+    sleep(Duration::from_millis(1000)).await;
+
+    // The local person is imported now, the other item still can't be fetched and stays stored
+    let failed = SettingsImportFailure::list(pool, local_user_id).await?;
+    assert_eq!(1, failed.len());
+    assert_eq!(BlockedPerson, failed[0].kind);
+    assert_eq!(&bogus, failed[0].ap_id.inner());
+
+    Person::delete(pool, target.person.id).await?;
+    Person::delete(pool, retry_user.person.id).await?;
+    data.delete(&mut context.pool()).await?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_generate_opml() -> LemmyResult<()> {
+    let community = (
+      "Rust & <friends>".to_string(),
+      Url::parse("https://example.com/c/rust")?.into(),
+    );
+    let opml = generate_opml("alice", &[community]);
+
+    let root = Element::from_reader(opml.as_bytes())?;
+    assert_eq!("opml", root.tag().name());
+    let outline = root
+      .find("body")
+      .and_then(|b| b.find("outline"))
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert_eq!(Some("Rust & <friends>"), outline.get_attr("title"));
+    assert_eq!(
+      Some("https://example.com/c/rust"),
+      outline.get_attr("xmlUrl")
+    );
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn disallow_large_backup() -> LemmyResult<()> {
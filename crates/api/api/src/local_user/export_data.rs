@@ -1,6 +1,7 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::{comment_report::CommentReport, post_report::PostReport};
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_notification::{NotificationData, impls::NotificationQuery};
@@ -9,7 +10,7 @@ use lemmy_db_views_person_liked_combined::impls::PersonLikedCombinedQuery;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_post_comment_combined::PostCommentCombinedView;
 use lemmy_db_views_site::{
-  api::{ExportDataResponse, PostOrCommentOrPrivateMessage},
+  api::{ExportDataResponse, ExportedReport, PostOrCommentOrPrivateMessage},
   impls::user_backup_list_to_user_settings_backup,
 };
 use lemmy_utils::{self, error::LemmyResult};
@@ -85,6 +86,30 @@ pub async fn export_data(
     .map(|cv| cv.community.ap_id.into())
     .collect();
 
+  let post_reports = PostReport::list_for_creator(pool, my_person_id)
+    .await?
+    .into_iter()
+    .map(|(r, ap_id)| ExportedReport {
+      target: ap_id.into(),
+      reason: r.reason,
+      resolved: r.resolved,
+      published_at: r.published_at,
+      updated_at: r.updated_at,
+    })
+    .collect();
+
+  let comment_reports = CommentReport::list_for_creator(pool, my_person_id)
+    .await?
+    .into_iter()
+    .map(|(r, ap_id)| ExportedReport {
+      target: ap_id.into(),
+      reason: r.reason,
+      resolved: r.resolved,
+      published_at: r.published_at,
+      updated_at: r.updated_at,
+    })
+    .collect();
+
   let settings =
     user_backup_list_to_user_settings_backup(local_user_view, &mut context.pool()).await?;
 
@@ -95,5 +120,88 @@ pub async fn export_data(
     read_posts,
     moderates,
     settings,
+    post_reports,
+    comment_reports,
   }))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      comment_report::CommentReportForm,
+      community::{Community, CommunityInsertForm},
+      post::{Post, PostInsertForm},
+      post_report::PostReportForm,
+    },
+    test_data::TestData,
+    traits::Reportable,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_export_reports() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let reporter = LocalUserView::create_test_user(pool, "export_reporter", "", false).await?;
+    let other = LocalUserView::create_test_user(pool, "other_reporter", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "export_reports".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("reported".to_string(), other.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(other.person.id, post.id, "reported".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let post_report_form = |creator_id| PostReportForm {
+      creator_id,
+      post_id: post.id,
+      original_post_name: post.name.clone(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "post reason".to_string(),
+      violates_instance_rules: false,
+    };
+    PostReport::report(pool, &post_report_form(reporter.person.id)).await?;
+    PostReport::report(pool, &post_report_form(other.person.id)).await?;
+    let comment_report_form = CommentReportForm {
+      creator_id: reporter.person.id,
+      comment_id: comment.id,
+      original_comment_text: comment.content.clone(),
+      reason: "comment reason".to_string(),
+      violates_instance_rules: false,
+    };
+    CommentReport::report(pool, &comment_report_form).await?;
+
+    // Only the reports filed by the user are exported
+    let export = export_data(context.clone(), reporter.clone()).await?.0;
+    assert_eq!(1, export.post_reports.len());
+    assert_eq!(
+      Some(post.ap_id.inner()),
+      export.post_reports.first().map(|r| &r.target)
+    );
+    assert_eq!(
+      Some("post reason"),
+      export.post_reports.first().map(|r| r.reason.as_str())
+    );
+    assert_eq!(1, export.comment_reports.len());
+    assert_eq!(
+      Some(comment.ap_id.inner()),
+      export.comment_reports.first().map(|r| &r.target)
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,26 @@
+use actix_web::web::{Data, Json};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::notification::Notification;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_notification::api::{MarkNotificationsAsRead, MarkNotificationsAsReadResponse};
+use lemmy_utils::{error::LemmyResult, utils::validation::check_api_elements_count};
+
+pub async fn mark_notifications_as_read(
+  Json(data): Json<MarkNotificationsAsRead>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<MarkNotificationsAsReadResponse>> {
+  check_api_elements_count(data.notification_ids.len())?;
+
+  let count = Notification::mark_read_by_ids_and_person(
+    &mut context.pool(),
+    &data.notification_ids,
+    local_user_view.person.id,
+    data.read,
+  )
+  .await?;
+
+  Ok(Json(MarkNotificationsAsReadResponse {
+    count: count.try_into()?,
+  }))
+}
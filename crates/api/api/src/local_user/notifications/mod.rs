@@ -1,4 +1,5 @@
 pub mod list;
 pub mod mark_all_read;
 pub mod mark_notification_read;
+pub mod mark_notifications_read;
 pub mod unread_count;
@@ -2,6 +2,7 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use lemmy_api_utils::{
   context::LemmyContext,
+  request::verify_profile_links,
   utils::{check_local_user_valid, get_url_blocklist, process_markdown_opt, slur_regex},
 };
 use lemmy_db_schema::{
@@ -25,6 +26,7 @@ use lemmy_diesel_utils::{
 use lemmy_email::account::send_verification_email;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
+  spawn_try_task,
   utils::validation::{
     check_blocking_keywords_are_valid,
     is_valid_bio_field,
@@ -123,10 +125,16 @@ pub async fn save_user_settings(
 
   // Ignore errors, because 'no fields updated' will return an error.
   // https://github.com/LemmyNet/lemmy/issues/4076
-  Person::update(&mut context.pool(), person_id, &person_form)
+  let updated_person = Person::update(&mut context.pool(), person_id, &person_form)
     .await
     .ok();
 
+  // Re-check which bio links point back to the profile, this requires fetching each link
+  if let (Some(_), Some(person)) = (&person_form.bio, updated_person) {
+    let context = context.clone();
+    spawn_try_task(async move { verify_profile_links(&person, &context).await });
+  }
+
   if let Some(discussion_languages) = data.discussion_languages.clone() {
     LocalUserLanguage::update(&mut context.pool(), discussion_languages, local_user_id).await?;
   }
@@ -149,7 +157,10 @@ pub async fn save_user_settings(
     email,
     show_avatars: data.show_avatars,
     show_read_posts: data.show_read_posts,
+    show_read_comments: data.show_read_comments,
     send_notifications_to_email: data.send_notifications_to_email,
+    email_pending_community_followers: data.email_pending_community_followers,
+    receive_community_welcome_messages: data.receive_community_welcome_messages,
     show_nsfw: data.show_nsfw,
     blur_nsfw: data.blur_nsfw,
     show_bot_accounts: data.show_bot_accounts,
@@ -166,6 +177,10 @@ pub async fn save_user_settings(
     enable_animated_images: data.enable_animated_images,
     enable_private_messages: data.enable_private_messages,
     collapse_bot_comments: data.collapse_bot_comments,
+    collapse_comments_account_age_days: diesel_opt_number_update(
+      data.collapse_comments_account_age_days,
+    ),
+    collapse_comments_below_score: diesel_opt_number_update(data.collapse_comments_below_score),
     auto_mark_fetched_posts_as_read: data.auto_mark_fetched_posts_as_read,
     hide_media: data.hide_media,
     // Update the vote display modes
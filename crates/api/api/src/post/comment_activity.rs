@@ -0,0 +1,54 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{
+  context::LemmyContext,
+  utils::{check_private_instance, is_mod_or_admin_opt},
+};
+use lemmy_db_schema::source::post::Post;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_post::{
+  PostCommentActivity,
+  PostView,
+  api::{GetPostCommentActivity, GetPostCommentActivityResponse},
+};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_utils::error::LemmyResult;
+
+/// Counts the comments on a post per hour or day. Removed comments are only counted for mods.
+pub async fn get_post_comment_activity(
+  Query(data): Query<GetPostCommentActivity>,
+  context: Data<LemmyContext>,
+  local_user_view: Option<LocalUserView>,
+) -> LemmyResult<Json<GetPostCommentActivityResponse>> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  check_private_instance(&local_user_view, &site_view.local_site)?;
+
+  let post = Post::read(&mut context.pool(), data.post_id).await?;
+  let is_mod_or_admin = is_mod_or_admin_opt(
+    &mut context.pool(),
+    local_user_view.as_ref(),
+    Some(post.community_id),
+  )
+  .await
+  .is_ok();
+
+  // Make sure the user can see the post
+  PostView::read(
+    &mut context.pool(),
+    post.id,
+    local_user_view.as_ref().map(|l| &l.local_user),
+    site_view.site.instance_id,
+    is_mod_or_admin,
+  )
+  .await?;
+
+  let activity = PostCommentActivity::list_for_post(
+    &mut context.pool(),
+    post.id,
+    data.bucket.unwrap_or_default(),
+    is_mod_or_admin,
+  )
+  .await?;
+
+  Ok(Json(GetPostCommentActivityResponse { activity }))
+}
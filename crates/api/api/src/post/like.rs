@@ -7,6 +7,7 @@ use lemmy_api_utils::{
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_bot_account,
+    check_community_not_read_only,
     check_community_user_action,
     check_local_user_valid,
     check_local_vote_mode,
@@ -63,6 +64,8 @@ pub async fn like_post(
   let previous_is_upvote = orig_post.post_actions.and_then(|p| p.vote_is_upvote);
 
   check_community_user_action(&local_user_view, &orig_post.community, &mut context.pool()).await?;
+  check_community_not_read_only(&local_user_view, &orig_post.community, &mut context.pool())
+    .await?;
 
   // Remove any likes first
   PostActions::remove_like(&mut context.pool(), my_person_id, post_id).await?;
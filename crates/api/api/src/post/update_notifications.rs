@@ -8,7 +8,7 @@ use lemmy_db_schema::source::{
 };
 use lemmy_db_schema_file::enums::PostNotificationsMode;
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_db_views_post::api::UpdatePostNotifications;
+use lemmy_db_views_post::api::{MutePostNotifications, UpdatePostNotifications};
 use lemmy_db_views_site::api::SuccessResponse;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::error::LemmyResult;
@@ -22,6 +22,7 @@ pub async fn update_post_notifications(
     data.post_id,
     local_user_view.person.id,
     data.mode,
+    data.coalesce,
     &mut context.pool(),
   )
   .await?;
@@ -37,3 +38,21 @@ pub async fn update_post_notifications(
   }
   Ok(Json(SuccessResponse::default()))
 }
+
+pub async fn mute_post_notifications(
+  Json(data): Json<MutePostNotifications>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  // Make sure the post exists
+  Post::read(&mut context.pool(), data.post_id).await?;
+
+  PostActions::update_notifications_muted(
+    data.post_id,
+    local_user_view.person.id,
+    data.muted,
+    &mut context.pool(),
+  )
+  .await?;
+  Ok(Json(SuccessResponse::default()))
+}
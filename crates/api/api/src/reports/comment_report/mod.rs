@@ -1,2 +1,4 @@
 pub mod create;
 pub mod resolve;
+pub mod snooze;
+pub mod subtree;
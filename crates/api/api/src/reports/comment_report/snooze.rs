@@ -0,0 +1,39 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_action};
+use lemmy_db_schema::source::comment_report::CommentReport;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::{
+  ReportCombinedViewInternal,
+  api::{CommentReportResponse, SnoozeCommentReport},
+};
+use lemmy_utils::error::LemmyResult;
+
+/// Hides a comment report from the report queue until the given time, without resolving it
+pub async fn snooze_comment_report(
+  Json(data): Json<SnoozeCommentReport>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentReportResponse>> {
+  let report_id = data.report_id;
+  let person = &local_user_view.person;
+  let report =
+    ReportCombinedViewInternal::read_comment_report(&mut context.pool(), report_id, person).await?;
+
+  check_community_mod_action(
+    &local_user_view,
+    &report.community,
+    true,
+    &mut context.pool(),
+  )
+  .await?;
+
+  CommentReport::update_snoozed(&mut context.pool(), report_id, data.until).await?;
+
+  let comment_report_view =
+    ReportCombinedViewInternal::read_comment_report(&mut context.pool(), report_id, person).await?;
+
+  Ok(Json(CommentReportResponse {
+    comment_report_view,
+  }))
+}
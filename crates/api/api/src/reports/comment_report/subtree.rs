@@ -0,0 +1,84 @@
+use crate::check_report_reason;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use either::Either;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  send_activity::{ActivityChannel, SendActivityData},
+  utils::{check_community_user_action, check_local_user_valid, slur_regex},
+};
+use lemmy_db_schema::source::{comment::Comment, comment_report::CommentReport};
+use lemmy_db_views_comment::CommentView;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::api::{ReportCommentSubtree, ReportCommentSubtreeResponse};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::traits::Crud;
+use lemmy_email::admin::send_new_report_email_to_admins;
+use lemmy_utils::error::LemmyResult;
+
+/// Reports a comment and its replies with the same reason, so that mods can handle them together.
+pub async fn report_comment_subtree(
+  Json(data): Json<ReportCommentSubtree>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ReportCommentSubtreeResponse>> {
+  check_local_user_valid(&local_user_view)?;
+  let reason = data.reason.trim().to_string();
+  let slur_regex = slur_regex(&context).await?;
+  check_report_reason(&reason, &slur_regex)?;
+
+  let person = &local_user_view.person;
+  let comment_view = CommentView::read(
+    &mut context.pool(),
+    data.comment_id,
+    Some(&local_user_view.local_user),
+    person.instance_id,
+  )
+  .await?;
+
+  check_community_user_action(
+    &local_user_view,
+    &comment_view.community,
+    &mut context.pool(),
+  )
+  .await?;
+
+  let reports = CommentReport::report_subtree(
+    &mut context.pool(),
+    &comment_view.comment.path,
+    person.id,
+    &reason,
+    data.violates_instance_rules.unwrap_or_default(),
+    context.settings().max_subtree_reports.into(),
+  )
+  .await?;
+
+  // Email the admins once for the whole subtree
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+  if local_site.reports_email_admins && !reports.is_empty() {
+    send_new_report_email_to_admins(
+      &person.name,
+      &comment_view.creator.name,
+      &mut context.pool(),
+      context.settings(),
+    )
+    .await?;
+  }
+
+  for report in &reports {
+    let comment = Comment::read(&mut context.pool(), report.comment_id).await?;
+    ActivityChannel::submit_activity(
+      SendActivityData::CreateReport {
+        object_id: comment.ap_id.inner().clone(),
+        actor: local_user_view.person.clone(),
+        receiver: Either::Right(comment_view.community.clone()),
+        reason: reason.clone(),
+      },
+      &context,
+    )?;
+  }
+
+  Ok(Json(ReportCommentSubtreeResponse {
+    reports: reports.len().try_into()?,
+  }))
+}
@@ -30,6 +30,8 @@ pub async fn list_reports(
     unresolved_only: data.unresolved_only,
     show_community_rule_violations: data.show_community_rule_violations,
     my_reports_only,
+    sort_by_reporter_trust: data.sort_by_reporter_trust,
+    show_snoozed: data.show_snoozed,
     page_cursor: data.page_cursor,
     limit: data.limit,
   }
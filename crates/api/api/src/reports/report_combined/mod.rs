@@ -1 +1,3 @@
 pub mod list;
+pub mod mod_queue;
+pub mod resolve_all;
@@ -0,0 +1,29 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::check_community_mod_of_any_or_admin_action};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::{ModQueueView, api::GetModQueue, impls::ModQueueQuery};
+use lemmy_db_views_site::SiteView;
+use lemmy_diesel_utils::pagination::PagedResponse;
+use lemmy_utils::error::LemmyResult;
+
+/// Returns the unresolved reports of all communities a user moderates, and for admins also
+/// registration applications
+pub async fn get_mod_queue(
+  Query(data): Query<GetModQueue>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PagedResponse<ModQueueView>>> {
+  check_community_mod_of_any_or_admin_action(&local_user_view, &mut context.pool()).await?;
+  let local_site = SiteView::read_local(&mut context.pool()).await?.local_site;
+
+  let items = ModQueueQuery {
+    type_: data.type_,
+    verified_email_only: Some(local_site.require_email_verification),
+    page_cursor: data.page_cursor,
+    limit: data.limit,
+  }
+  .list(&mut context.pool(), &local_user_view)
+  .await?;
+
+  Ok(Json(items))
+}
@@ -0,0 +1,65 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use diesel_async::scoped_futures::ScopedFutureExt;
+use lemmy_api_utils::{
+  context::LemmyContext,
+  notify::notify_mod_action,
+  utils::check_community_mod_action,
+};
+use lemmy_db_schema::source::{
+  comment_report::CommentReport,
+  community::Community,
+  modlog::{Modlog, ModlogInsertForm},
+  post_report::PostReport,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_report_combined::api::{
+  ResolveAllCommunityReports,
+  ResolveAllCommunityReportsResponse,
+};
+use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
+use lemmy_utils::error::LemmyResult;
+
+/// Resolves all open comment and post reports in a community at once, eg after a raid was cleaned
+/// up. Unlike single reports, the resolution is not federated.
+pub async fn resolve_all_community_reports(
+  Json(data): Json<ResolveAllCommunityReports>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ResolveAllCommunityReportsResponse>> {
+  let community = Community::read(&mut context.pool(), data.community_id).await?;
+  check_community_mod_action(&local_user_view, &community, false, &mut context.pool()).await?;
+
+  let my_person_id = local_user_view.person.id;
+  let community_id = community.id;
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let (res, actions) = conn
+    .run_transaction(|conn| {
+      async move {
+        let comment_reports =
+          CommentReport::resolve_all_for_community(&mut conn.into(), community_id, my_person_id)
+            .await?;
+        let post_reports =
+          PostReport::resolve_all_for_community(&mut conn.into(), community_id, my_person_id)
+            .await?;
+
+        let reason =
+          format!("Resolved {comment_reports} comment reports and {post_reports} post reports");
+        let form =
+          ModlogInsertForm::mod_resolve_community_reports(my_person_id, community_id, &reason);
+        let actions = Modlog::create(&mut conn.into(), &[form]).await?;
+
+        let res = ResolveAllCommunityReportsResponse {
+          comment_reports: comment_reports.try_into()?,
+          post_reports: post_reports.try_into()?,
+        };
+        Ok((res, actions))
+      }
+      .scope_boxed()
+    })
+    .await?;
+  notify_mod_action(actions, &context);
+
+  Ok(Json(res))
+}
@@ -0,0 +1,28 @@
+use actix_web::web::{Data, Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::utils::limit_fetch;
+use lemmy_db_views_comment::{
+  CommentStats,
+  api::{GetCommentStats, GetCommentStatsResponse},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn get_comment_stats(
+  Query(data): Query<GetCommentStats>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<GetCommentStatsResponse>> {
+  // Make sure user is an admin
+  is_admin(&local_user_view)?;
+
+  let stats = CommentStats::read(&mut context.pool()).await?;
+  let limit = limit_fetch(data.limit, None)?;
+  let top_communities = CommentStats::top_communities(&mut context.pool(), limit).await?;
+
+  Ok(Json(GetCommentStatsResponse {
+    stats,
+    removed_ratio: stats.removed_ratio(),
+    top_communities,
+  }))
+}
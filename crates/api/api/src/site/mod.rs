@@ -1,6 +1,7 @@
 pub mod admin_allow_instance;
 pub mod admin_block_instance;
 pub mod admin_list_users;
+pub mod comment_stats;
 pub mod federated_instances;
 pub mod list_all_media;
 pub mod mod_log;
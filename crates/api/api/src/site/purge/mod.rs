@@ -1,4 +1,5 @@
 pub mod comment;
 pub mod community;
+pub mod orphaned_comments;
 pub mod person;
 pub mod post;
@@ -0,0 +1,41 @@
+use activitypub_federation::config::Data;
+use actix_web::web::{Json, Query};
+use lemmy_api_utils::{context::LemmyContext, utils::is_admin};
+use lemmy_db_schema::{source::comment::Comment, utils::limit_fetch};
+use lemmy_db_views_comment::api::{
+  ListOrphanedComments,
+  ListOrphanedCommentsResponse,
+  PurgeOrphanedComments,
+  PurgeOrphanedCommentsResponse,
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn list_orphaned_comments(
+  Query(data): Query<ListOrphanedComments>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<ListOrphanedCommentsResponse>> {
+  is_admin(&local_user_view)?;
+
+  let limit = limit_fetch(data.limit, None)?;
+  let comments = Comment::list_orphaned(&mut context.pool(), limit).await?;
+
+  Ok(Json(ListOrphanedCommentsResponse { comments }))
+}
+
+/// Orphaned comments can't be shown anywhere, so they are purged without federating anything.
+pub async fn purge_orphaned_comments(
+  Json(data): Json<PurgeOrphanedComments>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PurgeOrphanedCommentsResponse>> {
+  is_admin(&local_user_view)?;
+
+  let purged =
+    Comment::purge_orphaned(&mut context.pool(), local_user_view.person.id, &data.reason).await?;
+
+  Ok(Json(PurgeOrphanedCommentsResponse {
+    purged: purged.try_into()?,
+  }))
+}
@@ -1,25 +1,54 @@
 use activitypub_federation::config::Data;
-use actix_web::web::Json;
+use actix_web::web::{Json, Query};
+use diesel_async::scoped_futures::ScopedFutureExt;
 use lemmy_api_utils::{
   context::LemmyContext,
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{is_admin, purge_user_account},
+  utils::{is_admin, purge_post_images, purge_user_account},
 };
 use lemmy_db_schema::{
   source::{
+    comment::Comment,
+    comment_report::CommentReport,
     instance::{InstanceActions, InstanceBanForm},
     local_user::LocalUser,
     modlog::{Modlog, ModlogInsertForm},
     person::Person,
+    post::Post,
+    post_report::PostReport,
   },
   traits::Bannable,
 };
 use lemmy_db_views_local_user::LocalUserView;
-use lemmy_db_views_person::api::PurgePerson;
+use lemmy_db_views_person::api::{PreviewPurgePerson, PreviewPurgePersonResponse, PurgePerson};
 use lemmy_db_views_site::api::SuccessResponse;
-use lemmy_diesel_utils::traits::Crud;
+use lemmy_diesel_utils::{connection::get_conn, traits::Crud};
 use lemmy_utils::error::LemmyResult;
 
+/// Posts and comments are purged in batches of this size, to keep each delete statement small for
+/// accounts with lots of content.
+const PURGE_BATCH_SIZE: i64 = 100;
+
+pub async fn preview_purge_person(
+  Query(data): Query<PreviewPurgePerson>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<PreviewPurgePersonResponse>> {
+  is_admin(&local_user_view)?;
+
+  let pool = &mut context.pool();
+  let posts = Post::count_for_creator(pool, data.person_id).await?;
+  let comments = Comment::count_for_creator_purge(pool, data.person_id).await?;
+  let reports = PostReport::count_for_post_creator(pool, data.person_id).await?
+    + CommentReport::count_for_creator_purge(pool, data.person_id).await?;
+
+  Ok(Json(PreviewPurgePersonResponse {
+    posts,
+    comments,
+    reports,
+  }))
+}
+
 pub async fn purge_person(
   Json(data): Json<PurgePerson>,
   context: Data<LemmyContext>,
@@ -52,6 +81,42 @@ pub async fn purge_person(
     &context,
   )?;
 
+  // The ban above already removes the content on other instances. Purging the posts also purges
+  // the comments of others on them, and reports are removed together with the content.
+  let person_id = data.person_id;
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+  let purged_posts = conn
+    .run_transaction(|conn| {
+      async move {
+        loop {
+          let purged =
+            Comment::purge_batch_for_creator(&mut conn.into(), person_id, PURGE_BATCH_SIZE).await?;
+          if purged == 0 {
+            break;
+          }
+        }
+
+        let mut purged_posts = vec![];
+        loop {
+          let posts =
+            Post::purge_batch_for_creator(&mut conn.into(), person_id, PURGE_BATCH_SIZE).await?;
+          if posts.is_empty() {
+            break;
+          }
+          purged_posts.extend(posts);
+        }
+        Ok(purged_posts)
+      }
+      .scope_boxed()
+    })
+    .await?;
+
+  // Images are only purged once the database changes are committed
+  for post in purged_posts {
+    purge_post_images(post.url, post.thumbnail_url, &context).await;
+  }
+
   // Clear profile data.
   purge_user_account(data.person_id, local_instance_id, &context).await?;
 
@@ -68,3 +133,115 @@ pub async fn purge_person(
 
   Ok(Json(SuccessResponse::default()))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::CommentInsertForm,
+      comment_report::CommentReportForm,
+      community::{Community, CommunityInsertForm},
+      post::PostInsertForm,
+      post_report::PostReportForm,
+    },
+    test_data::TestData,
+    traits::Reportable,
+  };
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_purge_person() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let admin = LocalUserView::create_test_user(pool, "purge_admin", "", true).await?;
+    let spammer = LocalUserView::create_test_user(pool, "purge_spammer", "", false).await?;
+    let other = LocalUserView::create_test_user(pool, "purge_other", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "purge_person".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let post_form = PostInsertForm::new("spam".to_string(), spammer.person.id, community.id);
+    let spam_post = Post::create(pool, &post_form).await?;
+    let post_form = PostInsertForm::new("legit".to_string(), other.person.id, community.id);
+    let other_post = Post::create(pool, &post_form).await?;
+    let form = CommentInsertForm::new(spammer.person.id, spam_post.id, "spam".to_string());
+    Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(spammer.person.id, other_post.id, "spam".to_string());
+    let spam_comment = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(other.person.id, other_post.id, "legit".to_string());
+    let other_comment = Comment::create(pool, &form, None).await?;
+    // Purged together with the spam post
+    let form = CommentInsertForm::new(other.person.id, spam_post.id, "reply".to_string());
+    let reply = Comment::create(pool, &form, None).await?;
+
+    let form = PostReportForm {
+      creator_id: other.person.id,
+      post_id: spam_post.id,
+      original_post_name: spam_post.name.clone(),
+      reason: "spam".to_string(),
+      ..Default::default()
+    };
+    PostReport::report(pool, &form).await?;
+    let form = CommentReportForm {
+      creator_id: other.person.id,
+      comment_id: spam_comment.id,
+      original_comment_text: spam_comment.content.clone(),
+      reason: "spam".to_string(),
+      violates_instance_rules: false,
+    };
+    CommentReport::report(pool, &form).await?;
+
+    let person_id = spammer.person.id;
+    let preview = preview_purge_person(
+      Query(PreviewPurgePerson { person_id }),
+      context.clone(),
+      admin.clone(),
+    )
+    .await?
+    .0;
+    assert_eq!(
+      PreviewPurgePersonResponse {
+        posts: 1,
+        comments: 3,
+        reports: 2,
+      },
+      preview
+    );
+
+    let purge = PurgePerson {
+      person_id,
+      reason: "spam".to_string(),
+    };
+    purge_person(Json(purge), context.clone(), admin.clone()).await?;
+
+    let preview = preview_purge_person(
+      Query(PreviewPurgePerson { person_id }),
+      context.clone(),
+      admin.clone(),
+    )
+    .await?
+    .0;
+    assert_eq!(PreviewPurgePersonResponse::default(), preview);
+
+    // Content of other users is untouched, except for comments on the purged posts
+    assert!(Post::read(pool, other_post.id).await.is_ok());
+    assert!(Comment::read(pool, other_comment.id).await.is_ok());
+    assert!(Comment::read(pool, reply.id).await.is_err());
+
+    Community::delete(pool, community.id).await?;
+    for user in [admin, spammer, other] {
+      Person::delete(pool, user.person.id).await?;
+    }
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
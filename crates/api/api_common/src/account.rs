@@ -10,6 +10,7 @@ pub mod auth {
     CaptchaResponse,
     ChangePassword,
     ExportDataResponse,
+    ExportedReport,
     GenerateTotpSecretResponse,
     GetCaptchaResponse,
     ListLoginsResponse,
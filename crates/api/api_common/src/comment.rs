@@ -1,27 +1,59 @@
 pub use lemmy_db_schema::{
   newtypes::CommentId,
-  source::comment::{Comment, CommentActions, CommentInsertForm},
+  source::{
+    comment::{Comment, CommentActions, CommentInsertForm},
+    comment_draft::CommentDraft,
+  },
 };
 pub use lemmy_db_views_comment::{
+  CommentFederationTarget,
+  CommentPreview,
   CommentSlimView,
   CommentView,
-  api::{CommentResponse, GetComment, GetComments},
+  api::{
+    CommentResponse,
+    GetComment,
+    GetCommentFederationStatus,
+    GetCommentFederationStatusResponse,
+    GetComments,
+    GetCommentsResponse,
+    GetPersonCommentsInCommunity,
+    GetTopCommentsPerPost,
+    GetTopCommentsPerPostResponse,
+    ResolveComment,
+  },
 };
 
 pub mod actions {
   pub use lemmy_db_views_comment::api::{
+    CancelScheduledComment,
+    CommentDraftResponse,
     CreateComment,
     CreateCommentLike,
     DeleteComment,
+    DeleteCommentDraft,
     EditComment,
+    GetCommentDraft,
+    PreviewComment,
+    PreviewCommentResponse,
+    RestoreComment,
     SaveComment,
+    SaveCommentDraft,
   };
 
   pub mod moderation {
     pub use lemmy_db_views_comment::api::{
+      ApproveComment,
       DistinguishComment,
       ListCommentLikes,
+      ListOrphanedComments,
+      ListOrphanedCommentsResponse,
+      ListPendingComments,
+      MarkCommentAsAnswer,
+      MoveCommentSubtree,
       PurgeComment,
+      PurgeOrphanedComments,
+      PurgeOrphanedCommentsResponse,
       RemoveComment,
     };
   }
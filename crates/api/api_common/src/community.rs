@@ -1,12 +1,13 @@
 pub use lemmy_db_schema::{
-  newtypes::{CommunityId, MultiCommunityId, TagId},
+  newtypes::{CommunityAutomodRuleId, CommunityId, MultiCommunityId, TagId},
   source::{
     community::{Community, CommunityActions},
+    community_automod_rule::CommunityAutomodRule,
     multi_community::{MultiCommunity, MultiCommunityFollow},
     tag::{Tag, TagsView},
   },
 };
-pub use lemmy_db_schema_file::enums::CommunityVisibility;
+pub use lemmy_db_schema_file::enums::{AutomodAction, CommunityVisibility};
 pub use lemmy_db_views_community::{
   CommunityView,
   MultiCommunityView,
@@ -22,6 +23,7 @@ pub use lemmy_db_views_community::{
     GetRandomCommunity,
     ListCommunities,
     ListMultiCommunities,
+    UpdateCommunityCommentSort,
     UpdateCommunityNotifications,
     UpdateMultiCommunity,
   },
@@ -40,15 +42,26 @@ pub mod actions {
   pub mod moderation {
     pub use lemmy_db_schema_file::enums::CommunityFollowerState;
     pub use lemmy_db_views_community::api::{
+      AddCommunityMember,
       AddModToCommunity,
       AddModToCommunityResponse,
       ApproveCommunityPendingFollower,
       BanFromCommunity,
+      COMMUNITY_SETTINGS_BACKUP_VERSION,
+      CommunityAutomodRuleBackup,
       CommunityIdQuery,
+      CommunitySettingsBackup,
+      CommunityTagBackup,
+      CreateCommunityAutomodRule,
       CreateCommunityTag,
       DeleteCommunity,
+      DeleteCommunityAutomodRule,
       DeleteCommunityTag,
       EditCommunity,
+      EditCommunityAutomodRule,
+      ImportCommunitySettings,
+      ListCommunityAutomodRules,
+      ListCommunityAutomodRulesResponse,
       PurgeCommunity,
       RemoveCommunity,
       TransferCommunity,
@@ -5,8 +5,12 @@ pub use lemmy_db_schema::{
 };
 pub use lemmy_db_schema_file::enums::{PostListingMode, PostNotificationsMode};
 pub use lemmy_db_views_post::{
+  CommentActivityBucket,
+  PostCommentActivity,
   PostView,
   api::{
+    GetPostCommentActivity,
+    GetPostCommentActivityResponse,
     GetPosts,
     GetSiteMetadata,
     GetSiteMetadataResponse,
@@ -25,6 +29,7 @@ pub mod actions {
     HidePost,
     MarkManyPostsAsRead,
     MarkPostAsRead,
+    MutePostNotifications,
     SavePost,
     UpdatePostNotifications,
   };
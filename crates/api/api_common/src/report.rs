@@ -1,4 +1,5 @@
 pub use lemmy_db_schema::{
+  ModQueueType,
   ReportType,
   newtypes::{CommentReportId, CommunityReportId, PostReportId, PrivateMessageReportId},
   source::{
@@ -11,6 +12,7 @@ pub use lemmy_db_schema::{
 pub use lemmy_db_views_report_combined::{
   CommentReportView,
   CommunityReportView,
+  ModQueueView,
   PostReportView,
   PrivateMessageReportView,
   ReportCombinedView,
@@ -21,14 +23,20 @@ pub use lemmy_db_views_report_combined::{
     CreateCommunityReport,
     CreatePostReport,
     CreatePrivateMessageReport,
+    GetModQueue,
     GetReportCount,
     GetReportCountResponse,
     ListReports,
     PostReportResponse,
     PrivateMessageReportResponse,
+    ReportCommentSubtree,
+    ReportCommentSubtreeResponse,
+    ResolveAllCommunityReports,
+    ResolveAllCommunityReportsResponse,
     ResolveCommentReport,
     ResolveCommunityReport,
     ResolvePostReport,
     ResolvePrivateMessageReport,
+    SnoozeCommentReport,
   },
 };
@@ -0,0 +1,96 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::comment::Comment;
+use lemmy_db_views_comment::{CommentView, api::CancelScheduledComment};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_site::api::SuccessResponse;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn cancel_scheduled_comment(
+  Json(data): Json<CancelScheduledComment>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<SuccessResponse>> {
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    data.comment_id,
+    Some(&local_user_view.local_user),
+    local_user_view.person.instance_id,
+  )
+  .await?;
+
+  // Verify that only the creator can cancel
+  if local_user_view.person.id != orig_comment.creator.id {
+    Err(LemmyErrorType::NoCommentEditAllowed)?
+  }
+
+  // The comment was never federated, so it can simply be removed from the db
+  Comment::delete_scheduled(&mut context.pool(), data.comment_id).await?;
+
+  Ok(Json(SuccessResponse::default()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::{TimeDelta, Utc};
+  use lemmy_db_schema::{
+    source::{
+      comment::CommentInsertForm,
+      community::{Community, CommunityInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_cancel_scheduled_comment() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "canceller", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "cancel_scheduled".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("cancel".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    // A scheduled comment can be cancelled, which deletes it
+    let form = CommentInsertForm {
+      scheduled_publish_time_at: Some(Utc::now() + TimeDelta::hours(1)),
+      ..CommentInsertForm::new(user.person.id, post.id, "scheduled".to_string())
+    };
+    let scheduled = Comment::create(pool, &form, None).await?;
+    let cancel = CancelScheduledComment {
+      comment_id: scheduled.id,
+    };
+    cancel_scheduled_comment(Json(cancel), context.clone(), user.clone()).await?;
+    assert!(Comment::read(pool, scheduled.id).await.is_err());
+
+    // Published comments can't be cancelled
+    let form = CommentInsertForm::new(user.person.id, post.id, "published".to_string());
+    let published = Comment::create(pool, &form, None).await?;
+    let cancel = CancelScheduledComment {
+      comment_id: published.id,
+    };
+    let res = cancel_scheduled_comment(Json(cancel), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentAlreadyPublished),
+      res.err().map(|e| e.error_type)
+    );
+    assert!(Comment::read(pool, published.id).await.is_ok());
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
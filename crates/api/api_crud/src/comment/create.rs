@@ -1,7 +1,9 @@
-use crate::community_use_pending;
+use crate::{comment::check_scheduled_publish_time, community_use_pending};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::{TimeDelta, Utc};
 use lemmy_api_utils::{
+  automod::{apply_automod_rule, automod_holds, automod_removes, matching_automod_rule},
   build_response::build_comment_response,
   context::LemmyContext,
   notify::NotifyData,
@@ -9,32 +11,52 @@ use lemmy_api_utils::{
   send_activity::{ActivityChannel, SendActivityData},
   utils::{
     check_comment_depth,
+    check_community_comment_membership,
+    check_community_not_read_only,
+    check_community_tags,
     check_community_user_action,
     check_post_deleted_or_removed,
+    comment_challenge_required,
     get_url_blocklist,
+    is_auto_locked,
     is_mod_or_admin,
+    is_new_community_member,
     process_markdown,
     slur_regex,
     update_read_comments,
   },
 };
 use lemmy_db_schema::{
-  impls::actor_language::validate_post_language,
+  impls::actor_language::{UNDETERMINED_ID, validate_post_language},
+  newtypes::LanguageId,
   source::{
+    captcha_answer::CaptchaAnswer,
     comment::{Comment, CommentActions, CommentInsertForm, CommentLikeForm},
+    comment_draft::CommentDraft,
+    community::{Community, CommunityActions},
+    community_allowed_domain::CommunityAllowedDomain,
+    local_site::LocalSite,
     notification::Notification,
+    post::Post,
+    tag::CommentTag,
   },
   traits::Likeable,
 };
 use lemmy_db_views_comment::api::{CommentResponse, CreateComment};
+use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_db_views_local_user::LocalUserView;
+use lemmy_db_views_person::impls::PersonQuery;
 use lemmy_db_views_post::PostView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
-  utils::validation::is_valid_body_field,
+  utils::{
+    markdown::markdown_check_for_allowed_domains,
+    validation::{is_valid_body_field, is_valid_comment_min_length},
+  },
 };
+use tracing::warn;
 
 pub async fn create_comment(
   Json(data): Json<CreateComment>,
@@ -67,7 +89,10 @@ pub async fn create_comment(
   let community_id = post_view.community.id;
 
   check_community_user_action(&local_user_view, &post_view.community, &mut context.pool()).await?;
-  check_post_deleted_or_removed(&post)?;
+  check_community_comment_membership(&local_user_view, &post_view.community, &mut context.pool())
+    .await?;
+  check_community_not_read_only(&local_user_view, &post_view.community, &mut context.pool())
+    .await?;
 
   // Fetch the parent, if it exists
   let parent_opt = if let Some(parent_id) = data.parent_id {
@@ -80,11 +105,28 @@ pub async fn create_comment(
   let is_mod_or_admin = is_mod_or_admin(&mut context.pool(), &local_user_view, community_id)
     .await
     .is_ok();
-  // We only need to check the parent comment here as when we lock a
-  // comment we also lock all of its children.
-  let locked = post.locked || parent_opt.as_ref().is_some_and(|p| p.locked);
-  if locked && !is_mod_or_admin {
-    Err(LemmyErrorType::Locked)?
+  check_post_accepts_replies(&post, &post_view.community, is_mod_or_admin)?;
+  // The token is only consumed right before inserting, so that it stays valid if the comment is
+  // rejected for another reason.
+  let challenge_token =
+    if !is_mod_or_admin && comment_challenge_required(&local_user_view.person, &local_site) {
+      let token = data
+        .challenge_token
+        .as_deref()
+        .and_then(|t| uuid::Uuid::parse_str(t).ok())
+        .ok_or(LemmyErrorType::CommentChallengeRequired)?;
+      Some(token)
+    } else {
+      None
+    };
+  if !is_mod_or_admin {
+    is_valid_comment_min_length(&content, post_view.community.min_comment_length)?;
+    let allowed_domains = CommunityAllowedDomain::read(&mut context.pool(), community_id).await?;
+    markdown_check_for_allowed_domains(&data.content, &allowed_domains)?;
+    // Check the whole path, as comments may have been added to a locked subtree via federation.
+    if let Some(parent) = parent_opt.as_ref() {
+      Comment::check_not_in_locked_subtree(&mut context.pool(), &parent.path).await?;
+    }
   }
 
   // If there's a parent_id, check to make sure that comment is in that post
@@ -93,41 +135,126 @@ pub async fn create_comment(
     if parent.post_id != post_id {
       Err(LemmyErrorType::CouldntCreate)?
     }
-    check_comment_depth(parent)?;
+    check_comment_depth(parent, local_site.max_comment_depth.try_into()?)?;
+  }
+
+  // Validate the tags before inserting, so that invalid tags don't leave a comment behind
+  if let Some(tags) = &data.tags {
+    check_community_tags(community_id, tags, &context).await?;
   }
 
+  // Hold comments from new members if the community requires it
+  let pending_approval = if !is_mod_or_admin
+    && post_view.community.require_comment_approval_for_new_members
+  {
+    let approved_comments =
+      Comment::count_approved_in_community(&mut context.pool(), my_person_id, community_id).await?;
+    is_new_community_member(
+      &post_view.community,
+      &local_user_view.person,
+      approved_comments,
+    )
+  } else {
+    false
+  };
+
+  if let Some(publish_at) = data.publish_at {
+    check_scheduled_publish_time(publish_at, &local_user_view, &context).await?;
+  }
+
+  // Hold new comments back from federation for a moment, so that comments which are deleted right
+  // away never leave the instance. Scheduled comments are federated once they are published.
+  let delay_seconds = context.settings().comment_federation_delay_seconds;
+  let pending_federation_until = (delay_seconds > 0 && data.publish_at.is_none())
+    .then(|| Utc::now() + TimeDelta::seconds(delay_seconds.into()));
+  let federation_pending = pending_federation_until.is_some()
+    || community_use_pending(&post_view.community, &context).await;
+
+  // Comments which automod removes or holds for approval are inserted as removed or pending
+  // approval, so that they are never visible before that.
+  let automod_rule = if is_mod_or_admin {
+    None
+  } else {
+    matching_automod_rule(&content, community_id, &context).await?
+  };
+
   let mut comment_form = CommentInsertForm {
-    language_id: data.language_id,
-    federation_pending: Some(community_use_pending(&post_view.community, &context).await),
+    removed: automod_rule.as_ref().map(automod_removes),
+    scheduled_publish_time_at: data.publish_at,
+    language_id: comment_language(data.language_id, post_view.community.primary_language_id),
+    federation_pending: Some(federation_pending),
+    pending_federation_until,
+    pending_approval: Some(pending_approval || automod_rule.as_ref().is_some_and(automod_holds)),
     ..CommentInsertForm::new(my_person_id, data.post_id, content.clone())
   };
   comment_form = plugin_hook_before("local_comment_before_create", comment_form).await?;
   validate_post_language(&mut context.pool(), comment_form.language_id, community_id).await?;
 
+  if let Some(token) = challenge_token {
+    CaptchaAnswer::check_comment_challenge_token(&mut context.pool(), token).await?;
+  }
+
   // Create the comment
   let parent_path = parent_opt.clone().map(|t| t.path);
   let inserted_comment =
     Comment::create(&mut context.pool(), &comment_form, parent_path.as_ref()).await?;
+  Comment::upsert_content_source(&mut context.pool(), inserted_comment.id, &data.content).await?;
+  if let Some(tags) = &data.tags {
+    CommentTag::update(&mut context.pool(), inserted_comment.id, tags).await?;
+  }
+  CommentDraft::delete(
+    &mut context.pool(),
+    local_user_view.person.id,
+    post_id,
+    data.parent_id,
+  )
+  .await?;
+  if let Some(rule) = &automod_rule {
+    apply_automod_rule(&inserted_comment, rule, &context).await?;
+  }
   plugin_hook_after("local_comment_after_create", &inserted_comment);
 
-  NotifyData::new(
-    post.clone(),
-    Some(inserted_comment.clone()),
-    local_user_view.person.clone(),
-    post_view.community,
-    !local_site.disable_email_notifications,
-  )
-  .send(&context);
+  // Comments which were removed, are held for approval, are scheduled or are delayed are neither
+  // announced nor federated yet.
+  let publish = !inserted_comment.removed
+    && !inserted_comment.pending_approval
+    && inserted_comment.scheduled_publish_time_at.is_none()
+    && inserted_comment.pending_federation_until.is_none();
+  if publish {
+    NotifyData::new(
+      post.clone(),
+      Some(inserted_comment.clone()),
+      local_user_view.person.clone(),
+      post_view.community.clone(),
+      !local_site.disable_email_notifications,
+    )
+    .send(&context);
+  }
 
   // You like your own comment by default
   let like_form = CommentLikeForm::new(my_person_id, inserted_comment.id, true);
 
   CommentActions::like(&mut context.pool(), &like_form).await?;
 
-  ActivityChannel::submit_activity(
-    SendActivityData::CreateComment(inserted_comment.clone()),
-    &context,
-  )?;
+  if publish {
+    ActivityChannel::submit_activity(
+      SendActivityData::CreateComment(inserted_comment.clone()),
+      &context,
+    )?;
+    if !is_mod_or_admin {
+      send_welcome_message(
+        &inserted_comment,
+        &post,
+        &post_view.community,
+        &local_user_view,
+        &local_site,
+        &context,
+      )
+      .await
+      .inspect_err(|e| warn!("Failed to send community welcome message: {e}"))
+      .ok();
+    }
+  }
 
   // Update the read comments, so your own new comment doesn't appear as a +1 unread
   update_read_comments(
@@ -163,3 +290,946 @@ pub async fn create_comment(
     .await?,
   ))
 }
+
+/// Replies to deleted or removed posts are rejected, and to locked or too old posts unless the user
+/// is a mod.
+fn check_post_accepts_replies(
+  post: &Post,
+  community: &Community,
+  is_mod_or_admin: bool,
+) -> LemmyResult<()> {
+  if post.removed {
+    Err(LemmyErrorType::PostRemoved)?
+  }
+  check_post_deleted_or_removed(post)?;
+  if is_mod_or_admin {
+    return Ok(());
+  }
+  if post.locked {
+    Err(LemmyErrorType::Locked)?
+  }
+  if is_auto_locked(
+    post.published_at,
+    community.auto_lock_posts_after_seconds,
+    Utc::now(),
+  ) {
+    Err(LemmyErrorType::PostAutoLocked)?
+  }
+  Ok(())
+}
+
+/// Replies to the first comment of a user in a local community with the community's welcome
+/// message. The reply is distinguished and made in the name of the longest serving local mod, or
+/// of the longest serving admin if the community has no local mods.
+async fn send_welcome_message(
+  comment: &Comment,
+  post: &Post,
+  community: &Community,
+  local_user_view: &LocalUserView,
+  local_site: &LocalSite,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let Some(welcome_message) = &community.welcome_message else {
+    return Ok(());
+  };
+  if !community.local
+    || !local_user_view
+      .local_user
+      .receive_community_welcome_messages
+  {
+    return Ok(());
+  }
+
+  // The new comment is already included in the count
+  let person_id = local_user_view.person.id;
+  let approved_comments =
+    Comment::count_approved_in_community(&mut context.pool(), person_id, community.id).await?;
+  if approved_comments > 1 {
+    return Ok(());
+  }
+
+  let moderators = CommunityModeratorView::for_community(&mut context.pool(), community.id).await?;
+  let moderator = match moderators
+    .into_iter()
+    .map(|m| m.moderator)
+    .find(|m| m.local)
+  {
+    Some(moderator) => moderator,
+    None => {
+      // Admins are listed newest first
+      let admins = PersonQuery {
+        admins_only: Some(true),
+        ..Default::default()
+      }
+      .list(
+        None,
+        local_user_view.person.instance_id,
+        &mut context.pool(),
+      )
+      .await?
+      .items;
+      let Some(admin) = admins.into_iter().map(|a| a.person).last() else {
+        return Ok(());
+      };
+      admin
+    }
+  };
+
+  if !CommunityActions::mark_welcomed(&mut context.pool(), community.id, person_id).await? {
+    return Ok(());
+  }
+
+  let form = CommentInsertForm {
+    distinguished: Some(true),
+    language_id: Some(comment.language_id),
+    ..CommentInsertForm::new(moderator.id, post.id, welcome_message.clone())
+  };
+  let reply = Comment::create(&mut context.pool(), &form, Some(&comment.path)).await?;
+
+  NotifyData::new(
+    post.clone(),
+    Some(reply.clone()),
+    moderator,
+    community.clone(),
+    !local_site.disable_email_notifications,
+  )
+  .send(context);
+  ActivityChannel::submit_activity(SendActivityData::CreateComment(reply), context)?;
+  Ok(())
+}
+
+/// The language of a new comment. If the client doesn't set one, the community's primary language
+/// is used.
+fn comment_language(
+  language_id: Option<LanguageId>,
+  primary_language_id: Option<LanguageId>,
+) -> Option<LanguageId> {
+  language_id
+    .filter(|l| *l != UNDETERMINED_ID)
+    .or(primary_language_id)
+    .or(language_id)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    newtypes::{CommentId, TagId},
+    source::{
+      captcha_answer::{CaptchaAnswerForm, CheckCaptchaAnswer},
+      comment_draft::CommentDraftForm,
+      community::{
+        CommunityFollowerForm,
+        CommunityInsertForm,
+        CommunityMemberForm,
+        CommunityModeratorForm,
+      },
+      local_site::LocalSiteUpdateForm,
+      post::{PostInsertForm, PostUpdateForm},
+      tag::{Tag, TagInsertForm},
+    },
+    test_data::TestData,
+    traits::Followable,
+  };
+  use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
+  use lemmy_db_views_comment::{CommentView, impls::CommentQuery};
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_reply_to_locked_or_removed_post() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "replier", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "locked_replies".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm {
+      locked: Some(true),
+      ..PostInsertForm::new("locked".to_string(), user.person.id, community.id)
+    };
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "earlier".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let create = CreateComment {
+      content: "reply".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let res = create_comment(Json(create.clone()), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::Locked),
+      res.err().map(|e| e.error_type)
+    );
+
+    let form = PostUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, post.id, &form).await?;
+    let res = create_comment(Json(create), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::PostRemoved),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Clients can see why replying isn't possible
+    let view =
+      CommentView::read(pool, comment.id, Some(&user.local_user), data.instance.id).await?;
+    assert!(view.post.locked);
+    assert!(view.post.removed);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_content_source() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "source_commenter", "", false).await?;
+    let other = LocalUserView::create_test_user(pool, "source_reader", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "content_source".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("source".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let source = "[a link](https://example.com/path/123?utm_medium=social&id=123)";
+    let create = CreateComment {
+      content: source.to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let comment_view = create_comment(Json(create), context.clone(), user.clone())
+      .await?
+      .0
+      .comment_view;
+
+    // The displayed content is processed, but the creator gets the original markdown back
+    assert_eq!(
+      "[a link](https://example.com/path/123?id=123)",
+      comment_view.comment.content
+    );
+    assert_eq!(Some(source), comment_view.content_source.as_deref());
+
+    // Nobody else sees it
+    let view = CommentView::read(
+      pool,
+      comment_view.comment.id,
+      Some(&other.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!(None, view.content_source);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_reply_in_read_only_community() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "read_only_replier", "", false).await?;
+    let community_form = CommunityInsertForm {
+      read_only: Some(true),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "read_only".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("read only".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "earlier".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let create = CreateComment {
+      content: "reply".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let res = create_comment(Json(create), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommunityIsReadOnly),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Existing content can still be read
+    let view =
+      CommentView::read(pool, comment.id, Some(&user.local_user), data.instance.id).await?;
+    assert_eq!("earlier", view.comment.content);
+    assert!(view.community.read_only);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_scheduled_comment() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "scheduler", "", false).await?;
+    let other = LocalUserView::create_test_user(pool, "schedule_reader", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "scheduled".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("scheduled".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = |publish_at| CreateComment {
+      content: "later".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: Some(publish_at),
+      tags: None,
+      challenge_token: None,
+    };
+
+    // Only times in the near future are allowed
+    let res = create_comment(
+      Json(create(Utc::now() - TimeDelta::hours(1))),
+      context.clone(),
+      user.clone(),
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentScheduleTimeMustBeInFuture),
+      res.err().map(|e| e.error_type)
+    );
+    let res = create_comment(
+      Json(create(Utc::now() + TimeDelta::days(365))),
+      context.clone(),
+      user.clone(),
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentScheduleTimeTooFarInFuture),
+      res.err().map(|e| e.error_type)
+    );
+
+    // The creator sees the scheduled comment
+    let comment_view = create_comment(
+      Json(create(Utc::now() + TimeDelta::hours(1))),
+      context.clone(),
+      user.clone(),
+    )
+    .await?
+    .0
+    .comment_view;
+    assert!(comment_view.comment.scheduled_publish_time_at.is_some());
+    let list = |local_user| CommentQuery {
+      post_id: Some(post.id),
+      local_user,
+      ..Default::default()
+    };
+    let comments = list(Some(&user.local_user)).list(&data.site, pool).await?;
+    assert_eq!(1, comments.len());
+
+    // It is hidden from everyone else until it is published
+    let comments = list(Some(&other.local_user)).list(&data.site, pool).await?;
+    assert_eq!(0, comments.len());
+    let res = CommentView::read(
+      pool,
+      comment_view.comment.id,
+      Some(&other.local_user),
+      data.instance.id,
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::NotFound),
+      res.err().map(|e| e.error_type)
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_comments_require_membership() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let reader = LocalUserView::create_test_user(pool, "membership_reader", "", false).await?;
+    let member = LocalUserView::create_test_user(pool, "membership_member", "", false).await?;
+    let community_form = CommunityInsertForm {
+      visibility: Some(CommunityVisibility::Private),
+      comments_require_membership: Some(true),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "members_only".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    for user in [&reader, &member] {
+      let form = CommunityFollowerForm::new(
+        community.id,
+        user.person.id,
+        CommunityFollowerState::Accepted,
+      );
+      CommunityActions::follow(pool, &form).await?;
+    }
+    let form = CommunityMemberForm::new(community.id, member.person.id);
+    CommunityActions::add_member(pool, &form).await?;
+    let post_form = PostInsertForm::new("members".to_string(), member.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = CreateComment {
+      content: "hello".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let res = create_comment(Json(create.clone()), context.clone(), reader.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::NotACommunityMember),
+      res.err().map(|e| e.error_type)
+    );
+    let comment_view = create_comment(Json(create), context.clone(), member.clone())
+      .await?
+      .0
+      .comment_view;
+
+    // The follower can still read the comment
+    let view = CommentView::read(
+      pool,
+      comment_view.comment.id,
+      Some(&reader.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!("hello", view.comment.content);
+    assert!(view.community.comments_require_membership);
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_min_comment_length() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "low_effort", "", false).await?;
+    let admin = LocalUserView::create_test_user(pool, "min_length_admin", "", true).await?;
+    let community_form = CommunityInsertForm {
+      min_comment_length: Some(5),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "serious".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("serious".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = |content: &str| CreateComment {
+      content: content.to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let res = create_comment(Json(create("lol")), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentTooShort),
+      res.err().map(|e| e.error_type)
+    );
+    create_comment(Json(create("hmmmm")), context.clone(), user.clone()).await?;
+    create_comment(Json(create("indeed")), context.clone(), user.clone()).await?;
+
+    // Admins are exempt
+    create_comment(Json(create("lol")), context.clone(), admin.clone()).await?;
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_max_comment_depth() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+    let form = LocalSiteUpdateForm {
+      max_comment_depth: Some(2),
+      ..Default::default()
+    };
+    LocalSite::update(pool, &form).await?;
+
+    let user = LocalUserView::create_test_user(pool, "deep_replier", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "deep_threads".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("deep".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = |parent_id: Option<CommentId>| CreateComment {
+      content: "reply".to_string(),
+      post_id: post.id,
+      parent_id,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let top_level = create_comment(Json(create(None)), context.clone(), user.clone()).await?;
+    let top_level_id = top_level.comment_view.comment.id;
+
+    // A reply is at the limit of two levels
+    let reply = create_comment(
+      Json(create(Some(top_level_id))),
+      context.clone(),
+      user.clone(),
+    )
+    .await?;
+
+    // A reply to the reply would be the third level
+    let res = create_comment(
+      Json(create(Some(reply.comment_view.comment.id))),
+      context.clone(),
+      user.clone(),
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::MaxCommentDepthReached),
+      res.err().map(|e| e.error_type)
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_allowed_link_domains() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "link_poster", "", false).await?;
+    let admin = LocalUserView::create_test_user(pool, "link_admin", "", true).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "journals_only".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    CommunityAllowedDomain::replace(pool, community.id, vec!["nature.com".to_string()]).await?;
+    let post_form = PostInsertForm::new("journals".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = |content: &str| CreateComment {
+      content: content.to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    create_comment(
+      Json(create("see https://www.nature.com/articles/1")),
+      context.clone(),
+      user.clone(),
+    )
+    .await?;
+    let res = create_comment(
+      Json(create("see [this](https://example.com/blog)")),
+      context.clone(),
+      user.clone(),
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::LinkDomainNotAllowed),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Admins are exempt
+    create_comment(
+      Json(create("see https://example.com/blog")),
+      context.clone(),
+      admin.clone(),
+    )
+    .await?;
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_create_clears_draft() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "drafter", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "drafts".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("drafts".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "parent".to_string());
+    let parent = Comment::create(pool, &comment_form, None).await?;
+
+    let form = CommentDraftForm::new(user.person.id, post.id, None, "top".to_string());
+    CommentDraft::save(pool, &form).await?;
+    let form = CommentDraftForm::new(user.person.id, post.id, Some(parent.id), "re".to_string());
+    CommentDraft::save(pool, &form).await?;
+
+    // Drafts never show up in comment listings
+    let list = || CommentQuery {
+      post_id: Some(post.id),
+      local_user: Some(&user.local_user),
+      ..Default::default()
+    };
+    let comments = list().list(&data.site, pool).await?;
+    assert_eq!(1, comments.len());
+
+    let create = CreateComment {
+      content: "top".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    create_comment(Json(create), context.clone(), user.clone()).await?;
+
+    // Only the draft with the same parent is cleared
+    let draft = CommentDraft::read(pool, user.person.id, post.id, None).await?;
+    assert_eq!(None, draft);
+    let draft = CommentDraft::read(pool, user.person.id, post.id, Some(parent.id)).await?;
+    assert!(draft.is_some());
+    let comments = list().list(&data.site, pool).await?;
+    assert_eq!(2, comments.len());
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_welcome_message() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let moderator = LocalUserView::create_test_user(pool, "welcoming_mod", "", false).await?;
+    let user = LocalUserView::create_test_user(pool, "newcomer", "", false).await?;
+    let community_form = CommunityInsertForm {
+      welcome_message: Some("Welcome here!".to_string()),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "welcoming".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm::new(community.id, moderator.person.id);
+    CommunityActions::join(pool, &form).await?;
+    let post_form = PostInsertForm::new("welcome".to_string(), moderator.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = CreateComment {
+      content: "hello".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token: None,
+    };
+    let first = create_comment(Json(create.clone()), context.clone(), user.clone())
+      .await?
+      .0
+      .comment_view;
+    create_comment(Json(create), context.clone(), user.clone()).await?;
+
+    // Only the first comment gets the welcome reply from the mod
+    let comments = CommentQuery {
+      post_id: Some(post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let welcomes: Vec<_> = comments
+      .items
+      .into_iter()
+      .filter(|c| c.creator.id == moderator.person.id)
+      .collect();
+    assert_eq!(1, welcomes.len());
+    assert_eq!("Welcome here!", welcomes[0].comment.content);
+    assert!(welcomes[0].comment.distinguished);
+    assert_eq!(
+      format!("{}.{}", first.comment.path.0, welcomes[0].comment.id.0),
+      welcomes[0].comment.path.0
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_comment_language() {
+    let english = LanguageId(37);
+    let finnish = LanguageId(41);
+    let primary = Some(english);
+
+    // The primary language is the default
+    assert_eq!(Some(english), comment_language(None, primary));
+    assert_eq!(
+      Some(english),
+      comment_language(Some(UNDETERMINED_ID), primary)
+    );
+    // Explicit values still win
+    assert_eq!(Some(finnish), comment_language(Some(finnish), primary));
+
+    // Without a primary language nothing changes
+    assert_eq!(None, comment_language(None, None));
+    assert_eq!(
+      Some(UNDETERMINED_ID),
+      comment_language(Some(UNDETERMINED_ID), None)
+    );
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_create_with_tags() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "comment_tagger", "", false).await?;
+    let mut tags = vec![];
+    let mut communities = vec![];
+    for name in ["tagged_comments", "other_tags"] {
+      let form = CommunityInsertForm::new(
+        data.instance.id,
+        name.to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      );
+      let community = Community::create(pool, &form).await?;
+      let tag_form = TagInsertForm {
+        ap_id: Url::parse(&format!("{}/tags/opinion", community.ap_id))?.into(),
+        name: "Opinion".into(),
+        display_name: None,
+        description: None,
+        community_id: community.id,
+        deleted: Some(false),
+      };
+      tags.push(Tag::create(pool, &tag_form).await?);
+      communities.push(community);
+    }
+    let (Some(tag), Some(other_tag), Some(community)) =
+      (tags.first(), tags.get(1), communities.first())
+    else {
+      Err(LemmyErrorType::NotFound)?
+    };
+    let post_form = PostInsertForm::new("tags".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let create = |tag_id| CreateComment {
+      content: "in my opinion".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: Some(vec![tag_id]),
+      challenge_token: None,
+    };
+    let comment_view = create_comment(Json(create(tag.id)), context.clone(), user.clone())
+      .await?
+      .0
+      .comment_view;
+    assert_eq!(vec![tag.clone()], comment_view.comment_tags.0);
+
+    // Tags of other communities can't be used
+    let res = create_comment(Json(create(other_tag.id)), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::TagNotInCommunity),
+      res.err().map(|e| e.error_type)
+    );
+    // The rejected comment wasn't inserted
+    let comments = CommentQuery {
+      post_id: Some(post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(1, comments.len());
+
+    for community in communities {
+      Community::delete(pool, community.id).await?;
+    }
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_comment_challenge() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+    let form = LocalSiteUpdateForm {
+      captcha_enabled: Some(true),
+      ..Default::default()
+    };
+    LocalSite::update(pool, &form).await?;
+
+    let user = LocalUserView::create_test_user(pool, "challenged", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "comment_challenge".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("challenge".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let create = |challenge_token| CreateComment {
+      content: "first comment".to_string(),
+      post_id: post.id,
+      parent_id: None,
+      language_id: None,
+      publish_at: None,
+      tags: None,
+      challenge_token,
+    };
+
+    // A new account without karma needs a solved challenge
+    let res = create_comment(Json(create(None)), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentChallengeRequired),
+      res.err().map(|e| e.error_type)
+    );
+    let invalid = Some(uuid::Uuid::new_v4().to_string());
+    let res = create_comment(Json(create(invalid)), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentChallengeRequired),
+      res.err().map(|e| e.error_type)
+    );
+
+    // An unsolved captcha isn't a valid token
+    let captcha_form = CaptchaAnswerForm {
+      answer: "XYZ".to_string(),
+    };
+    let captcha = CaptchaAnswer::insert(pool, &captcha_form).await?;
+    let unsolved = Some(captcha.uuid.to_string());
+    let res = create_comment(Json(create(unsolved)), context.clone(), user.clone()).await;
+    assert!(res.is_err());
+
+    let captcha = CaptchaAnswer::insert(pool, &captcha_form).await?;
+    let token = CaptchaAnswer::solve_comment_challenge(
+      pool,
+      CheckCaptchaAnswer {
+        uuid: captcha.uuid,
+        answer: "xyz".to_string(),
+      },
+    )
+    .await?;
+    let token = Some(token.to_string());
+
+    // A comment which is rejected for another reason doesn't use up the token
+    let invalid_tags = CreateComment {
+      tags: Some(vec![TagId(0)]),
+      ..create(token.clone())
+    };
+    let res = create_comment(Json(invalid_tags), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::TagNotInCommunity),
+      res.err().map(|e| e.error_type)
+    );
+    create_comment(Json(create(token.clone())), context.clone(), user.clone()).await?;
+
+    // Tokens can only be used once
+    let res = create_comment(Json(create(token)), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentChallengeRequired),
+      res.err().map(|e| e.error_type)
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
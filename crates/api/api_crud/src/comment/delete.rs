@@ -1,5 +1,6 @@
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
+use chrono::{TimeDelta, Utc};
 use lemmy_api_utils::{
   build_response::build_comment_response,
   context::LemmyContext,
@@ -30,8 +31,10 @@ pub async fn delete_comment(
   )
   .await?;
 
-  // Dont delete it if its already been deleted.
-  if orig_comment.comment.deleted == data.deleted {
+  // Dont delete it if its already been deleted. A pending delete counts as deleted, so that it can
+  // be restored.
+  let pending_delete = orig_comment.comment.pending_delete_until.is_some();
+  if (orig_comment.comment.deleted || pending_delete) == data.deleted {
     Err(LemmyErrorType::CouldntUpdate)?
   }
 
@@ -47,33 +50,52 @@ pub async fn delete_comment(
     Err(LemmyErrorType::NoCommentEditAllowed)?
   }
 
-  // Do the delete
   let deleted = data.deleted;
-  let updated_comment = Comment::update(
-    &mut context.pool(),
-    comment_id,
-    &CommentUpdateForm {
+  let grace_seconds = context.settings().comment_delete_grace_seconds;
+  if pending_delete {
+    // Restored during the grace period, so nothing was federated yet
+    Comment::restore_pending_delete(&mut context.pool(), comment_id).await?;
+  } else if orig_comment.comment.pending_federation_until.is_some() {
+    // Not federated yet, so only delete it locally. If it is still deleted once the delay is over,
+    // it never leaves the instance.
+    let form = CommentUpdateForm {
       deleted: Some(deleted),
       ..Default::default()
-    },
-  )
-  .await?;
-
-  let updated_comment_id = updated_comment.id;
+    };
+    Comment::update(&mut context.pool(), comment_id, &form).await?;
+  } else if deleted && grace_seconds > 0 {
+    // The delete is finalized and federated by a scheduled task once the grace period is over
+    let form = CommentUpdateForm {
+      pending_delete_until: Some(Some(Utc::now() + TimeDelta::seconds(grace_seconds.into()))),
+      ..Default::default()
+    };
+    Comment::update(&mut context.pool(), comment_id, &form).await?;
+  } else {
+    // Do the delete
+    let updated_comment = Comment::update(
+      &mut context.pool(),
+      comment_id,
+      &CommentUpdateForm {
+        deleted: Some(deleted),
+        ..Default::default()
+      },
+    )
+    .await?;
 
-  ActivityChannel::submit_activity(
-    SendActivityData::DeleteComment(
-      updated_comment,
-      local_user_view.person.clone(),
-      orig_comment.community,
-    ),
-    &context,
-  )?;
+    ActivityChannel::submit_activity(
+      SendActivityData::DeleteComment(
+        updated_comment,
+        local_user_view.person.clone(),
+        orig_comment.community,
+      ),
+      &context,
+    )?;
+  }
 
   Ok(Json(
     build_comment_response(
       &context,
-      updated_comment_id,
+      comment_id,
       Some(local_user_view),
       local_instance_id,
     )
@@ -1,5 +1,40 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::comment::Comment;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod cancel_scheduled;
 pub mod create;
 pub mod delete;
 pub mod read;
 pub mod remove;
+pub mod restore;
+pub mod solve_challenge;
 pub mod update;
+
+/// How far ahead a comment can be scheduled.
+const MAX_COMMENT_SCHEDULE_DAYS: i64 = 30;
+
+async fn check_scheduled_publish_time(
+  publish_at: DateTime<Utc>,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  const MAX_SCHEDULED_COMMENTS: i64 = 10;
+  let now = Utc::now();
+  if publish_at < now {
+    Err(LemmyErrorType::CommentScheduleTimeMustBeInFuture)?
+  }
+  if publish_at > now + TimeDelta::days(MAX_COMMENT_SCHEDULE_DAYS) {
+    Err(LemmyErrorType::CommentScheduleTimeTooFarInFuture)?
+  }
+  if !local_user_view.local_user.admin {
+    let count =
+      Comment::user_scheduled_comment_count(&mut context.pool(), local_user_view.person.id).await?;
+    if count >= MAX_SCHEDULED_COMMENTS {
+      Err(LemmyErrorType::TooManyScheduledComments)?
+    }
+  }
+  Ok(())
+}
@@ -76,13 +76,16 @@ pub async fn remove_comment(
   CommentReport::resolve_all_for_object(&mut context.pool(), comment_id, local_user_view.person.id)
     .await?;
 
-  // Mod tables
-  let form = ModlogInsertForm::mod_remove_comment(
-    local_user_view.person.id,
-    &orig_comment.comment,
-    removed,
-    &data.reason,
-  );
+  // Mod tables. The community is included so that the notification for the author can show it.
+  let form = ModlogInsertForm {
+    target_community_id: Some(orig_comment.community.id),
+    ..ModlogInsertForm::mod_remove_comment(
+      local_user_view.person.id,
+      &orig_comment.comment,
+      removed,
+      &data.reason,
+    )
+  };
   let actions = Modlog::create(&mut context.pool(), &[form]).await?;
   notify_mod_action(actions, context.app_data());
 
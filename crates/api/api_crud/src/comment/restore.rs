@@ -0,0 +1,129 @@
+use crate::comment::delete::delete_comment;
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use chrono::Utc;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_views_comment::{
+  CommentView,
+  api::{CommentResponse, DeleteComment, RestoreComment},
+};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub async fn restore_comment(
+  Json(data): Json<RestoreComment>,
+  context: Data<LemmyContext>,
+  local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentResponse>> {
+  let comment_id = data.comment_id;
+  let local_instance_id = local_user_view.person.instance_id;
+  let orig_comment = CommentView::read(
+    &mut context.pool(),
+    comment_id,
+    Some(&local_user_view.local_user),
+    local_instance_id,
+  )
+  .await?;
+
+  // Once the grace period is over the delete is final, even if the scheduled task didn't run yet
+  let in_grace = orig_comment
+    .comment
+    .pending_delete_until
+    .is_some_and(|until| until > Utc::now());
+  if !in_grace {
+    if orig_comment.comment.deleted || orig_comment.comment.pending_delete_until.is_some() {
+      Err(LemmyErrorType::CommentDeleteFinalized)?
+    } else {
+      Err(LemmyErrorType::CouldntUpdate)?
+    }
+  }
+
+  // Permission checks and the actual restore are the same as for an undelete
+  let undelete = DeleteComment {
+    comment_id,
+    deleted: false,
+  };
+  delete_comment(Json(undelete), context, local_user_view).await
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::{TimeDelta, Utc};
+  use lemmy_db_schema::{
+    source::{
+      comment::{Comment, CommentInsertForm},
+      community::{Community, CommunityInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::traits::Crud;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_restore_comment() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "restorer", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "restore_comments".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("restore".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    // Within the grace period, the comment can be restored
+    let form = CommentInsertForm {
+      pending_delete_until: Some(Utc::now() + TimeDelta::hours(1)),
+      ..CommentInsertForm::new(user.person.id, post.id, "in grace".to_string())
+    };
+    let in_grace = Comment::create(pool, &form, None).await?;
+    let restore = RestoreComment {
+      comment_id: in_grace.id,
+    };
+    let res = restore_comment(Json(restore), context.clone(), user.clone()).await?;
+    assert!(!res.comment_view.comment.deleted);
+    assert_eq!(None, res.comment_view.comment.pending_delete_until);
+
+    // Once the delete is finalized, restoring is rejected
+    let form = CommentInsertForm {
+      deleted: Some(true),
+      ..CommentInsertForm::new(user.person.id, post.id, "finalized".to_string())
+    };
+    let finalized = Comment::create(pool, &form, None).await?;
+    let restore = RestoreComment {
+      comment_id: finalized.id,
+    };
+    let res = restore_comment(Json(restore), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentDeleteFinalized),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Same if the grace period is over, but the scheduled task didn't run yet
+    let form = CommentInsertForm {
+      pending_delete_until: Some(Utc::now() - TimeDelta::minutes(1)),
+      ..CommentInsertForm::new(user.person.id, post.id, "expired".to_string())
+    };
+    let expired = Comment::create(pool, &form, None).await?;
+    let restore = RestoreComment {
+      comment_id: expired.id,
+    };
+    let res = restore_comment(Json(restore), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentDeleteFinalized),
+      res.err().map(|e| e.error_type)
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,27 @@
+use activitypub_federation::config::Data;
+use actix_web::web::Json;
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_db_schema::source::captcha_answer::{CaptchaAnswer, CheckCaptchaAnswer};
+use lemmy_db_views_comment::api::{CommentChallengeResponse, SolveCommentChallenge};
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::error::LemmyResult;
+
+pub async fn solve_comment_challenge(
+  Json(data): Json<SolveCommentChallenge>,
+  context: Data<LemmyContext>,
+  _local_user_view: LocalUserView,
+) -> LemmyResult<Json<CommentChallengeResponse>> {
+  let uuid = uuid::Uuid::parse_str(&data.captcha_uuid)?;
+  let token = CaptchaAnswer::solve_comment_challenge(
+    &mut context.pool(),
+    CheckCaptchaAnswer {
+      uuid,
+      answer: data.captcha_answer,
+    },
+  )
+  .await?;
+
+  Ok(Json(CommentChallengeResponse {
+    challenge_token: token.to_string(),
+  }))
+}
@@ -2,16 +2,28 @@ use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
 use lemmy_api_utils::{
+  automod::{apply_automod_rule, automod_holds, automod_removes, matching_automod_rule},
   build_response::build_comment_response,
   context::LemmyContext,
   notify::NotifyData,
   plugins::{plugin_hook_after, plugin_hook_before},
   send_activity::{ActivityChannel, SendActivityData},
-  utils::{check_community_user_action, get_url_blocklist, process_markdown_opt, slur_regex},
+  utils::{
+    check_community_tags,
+    check_community_user_action,
+    get_url_blocklist,
+    is_mod_or_admin,
+    process_markdown_opt,
+    slur_regex,
+  },
 };
 use lemmy_db_schema::{
   impls::actor_language::validate_post_language,
-  source::comment::{Comment, CommentUpdateForm},
+  source::{
+    comment::{Comment, CommentUpdateForm},
+    community_allowed_domain::CommunityAllowedDomain,
+    tag::CommentTag,
+  },
 };
 use lemmy_db_views_comment::{
   CommentView,
@@ -21,7 +33,10 @@ use lemmy_db_views_local_user::LocalUserView;
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
-  utils::validation::is_valid_body_field,
+  utils::{
+    markdown::markdown_check_for_allowed_domains,
+    validation::{is_valid_body_field, is_valid_comment_min_length},
+  },
 };
 
 pub async fn update_comment(
@@ -57,12 +72,59 @@ pub async fn update_comment(
   if let Some(content) = &content {
     is_valid_body_field(content, false)?;
   }
+  let mut automod_rule = None;
+  if let Some(content) = &data.content {
+    // Mods are exempt from the community's content restrictions
+    let is_mod_or_admin = is_mod_or_admin(
+      &mut context.pool(),
+      &local_user_view,
+      orig_comment.community.id,
+    )
+    .await
+    .is_ok();
+    if !is_mod_or_admin {
+      is_valid_comment_min_length(content, orig_comment.community.min_comment_length)?;
+    }
+    let allowed_domains =
+      CommunityAllowedDomain::read(&mut context.pool(), orig_comment.community.id).await?;
+    if !allowed_domains.is_empty() && !is_mod_or_admin {
+      markdown_check_for_allowed_domains(content, &allowed_domains)?;
+    }
+    // Prevent changing the meaning of a comment after others replied to it
+    if orig_comment.community.lock_edits_after_reply
+      && !is_mod_or_admin
+      && Comment::has_replies(&mut context.pool(), &orig_comment.comment.path).await?
+    {
+      Err(LemmyErrorType::CommentEditLockedAfterReply)?
+    }
+    // Check the new content too, so that the rules can't be bypassed by editing a harmless comment
+    if !is_mod_or_admin {
+      automod_rule = matching_automod_rule(content, orig_comment.community.id, &context).await?;
+    }
+  }
+
+  if let Some(tags) = &data.tags {
+    check_community_tags(orig_comment.community.id, tags, &context).await?;
+  }
 
   let comment_id = data.comment_id;
+  let content_edited_at = content
+    .as_ref()
+    .filter(|c| **c != orig_comment.comment.content)
+    .map(|_| Some(Utc::now()));
   let mut form = CommentUpdateForm {
     content,
     language_id: data.language_id,
     updated_at: Some(Some(Utc::now())),
+    content_edited_at,
+    removed: automod_rule
+      .as_ref()
+      .is_some_and(automod_removes)
+      .then_some(true),
+    pending_approval: automod_rule
+      .as_ref()
+      .is_some_and(automod_holds)
+      .then_some(true),
     ..Default::default()
   };
   form = plugin_hook_before("local_comment_before_update", form).await?;
@@ -74,23 +136,42 @@ pub async fn update_comment(
   .await?;
 
   let updated_comment = Comment::update(&mut context.pool(), comment_id, &form).await?;
+  if let Some(source) = &data.content {
+    Comment::upsert_content_source(&mut context.pool(), comment_id, source).await?;
+  }
+  if let Some(tags) = &data.tags {
+    CommentTag::update(&mut context.pool(), comment_id, tags).await?;
+  }
+  if let Some(rule) = &automod_rule {
+    apply_automod_rule(&updated_comment, rule, &context).await?;
+  }
 
   plugin_hook_after("local_comment_after_update", &updated_comment);
 
-  // Do the mentions / recipients
-  NotifyData::new(
-    orig_comment.post,
-    Some(updated_comment.clone()),
-    local_user_view.person.clone(),
-    orig_comment.community,
-    false,
-  )
-  .send(&context);
+  // Comments which are removed, held for approval or still scheduled are neither announced nor
+  // federated, they are sent with their latest content once published.
+  let publish = !updated_comment.removed
+    && !updated_comment.pending_approval
+    && updated_comment.scheduled_publish_time_at.is_none();
+  if publish {
+    // Do the mentions / recipients
+    NotifyData::new(
+      orig_comment.post,
+      Some(updated_comment.clone()),
+      local_user_view.person.clone(),
+      orig_comment.community,
+      false,
+    )
+    .send(&context);
+  }
 
-  ActivityChannel::submit_activity(
-    SendActivityData::UpdateComment(updated_comment.clone()),
-    &context,
-  )?;
+  // Delayed comments are federated with their latest content once the delay is over
+  if publish && updated_comment.pending_federation_until.is_none() {
+    ActivityChannel::submit_activity(
+      SendActivityData::UpdateComment(updated_comment.clone()),
+      &context,
+    )?;
+  }
 
   Ok(Json(
     build_comment_response(
@@ -102,3 +183,185 @@ pub async fn update_comment(
     .await?,
   ))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      comment::CommentInsertForm,
+      community::{Community, CommunityInsertForm},
+      community_automod_rule::{CommunityAutomodRule, CommunityAutomodRuleInsertForm},
+      person::Person,
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_db_schema_file::enums::AutomodAction;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_lock_edits_after_reply() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "edit_after_reply", "", false).await?;
+    let replier = LocalUserView::create_test_user(pool, "edit_replier", "", false).await?;
+    let community_form = CommunityInsertForm {
+      lock_edits_after_reply: Some(true),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "lock_edits".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("edits".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "question".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let edit = |content: &str| EditComment {
+      comment_id: comment.id,
+      content: Some(content.to_string()),
+      ..Default::default()
+    };
+
+    // Editing is allowed while there are no replies
+    update_comment(
+      Json(edit("changed question")),
+      context.clone(),
+      user.clone(),
+    )
+    .await?;
+
+    let reply_form = CommentInsertForm::new(replier.person.id, post.id, "answer".to_string());
+    Comment::create(pool, &reply_form, Some(&comment.path)).await?;
+
+    let res = update_comment(
+      Json(edit("different question")),
+      context.clone(),
+      user.clone(),
+    )
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentEditLockedAfterReply),
+      res.err().map(|e| e.error_type)
+    );
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, user.person.id).await?;
+    Person::delete(pool, replier.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_automod_on_edit() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "automod_editor", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "automod_edits".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let rule_form =
+      CommunityAutomodRuleInsertForm::new(community.id, "badword".into(), AutomodAction::Remove);
+    CommunityAutomodRule::create(pool, &rule_form).await?;
+    let rule_form = CommunityAutomodRuleInsertForm::new(
+      community.id,
+      "holdme".into(),
+      AutomodAction::RequireApproval,
+    );
+    CommunityAutomodRule::create(pool, &rule_form).await?;
+    let post_form = PostInsertForm::new("automod".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let edit = |comment: &Comment, content: &str| EditComment {
+      comment_id: comment.id,
+      content: Some(content.to_string()),
+      ..Default::default()
+    };
+
+    // Harmless comments which are edited to match a rule are handled like new comments
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "harmless".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+    update_comment(
+      Json(edit(&comment, "now with a badword")),
+      context.clone(),
+      user.clone(),
+    )
+    .await?;
+    let comment = Comment::read(pool, comment.id).await?;
+    assert!(comment.removed);
+    assert!(!comment.pending_approval);
+
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "harmless".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+    update_comment(
+      Json(edit(&comment, "please holdme")),
+      context.clone(),
+      user.clone(),
+    )
+    .await?;
+    let comment = Comment::read(pool, comment.id).await?;
+    assert!(!comment.removed);
+    assert!(comment.pending_approval);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, user.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_min_comment_length_on_edit() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "shrinking_editor", "", false).await?;
+    let community_form = CommunityInsertForm {
+      min_comment_length: Some(5),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "serious_edits".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("serious".to_string(), user.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(user.person.id, post.id, "indeed".to_string());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let edit = |content: &str| EditComment {
+      comment_id: comment.id,
+      content: Some(content.to_string()),
+      ..Default::default()
+    };
+    let res = update_comment(Json(edit("lol")), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentTooShort),
+      res.err().map(|e| e.error_type)
+    );
+    assert_eq!("indeed", Comment::read(pool, comment.id).await?.content);
+    update_comment(Json(edit("hmmmm")), context.clone(), user.clone()).await?;
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, user.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
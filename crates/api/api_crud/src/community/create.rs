@@ -18,6 +18,7 @@ use lemmy_api_utils::{
   },
 };
 use lemmy_db_schema::{
+  impls::actor_language::validate_post_language,
   source::{
     actor_language::{CommunityLanguage, LocalUserLanguage, SiteLanguage},
     community::{
@@ -26,6 +27,7 @@ use lemmy_db_schema::{
       CommunityFollowerForm,
       CommunityInsertForm,
       CommunityModeratorForm,
+      CommunityUpdateForm,
     },
   },
   traits::{ApubActor, Followable},
@@ -40,6 +42,7 @@ use lemmy_utils::{
   utils::{
     slurs::check_slurs,
     validation::{
+      check_not_negative,
       description_length_check,
       is_valid_actor_name,
       is_valid_body_field,
@@ -75,6 +78,12 @@ pub async fn create_community(
     is_valid_body_field(sidebar, false)?;
   }
 
+  let welcome_message =
+    process_markdown_opt(&data.welcome_message, &slur_regex, &url_blocklist, &context).await?;
+  if let Some(welcome_message) = &welcome_message {
+    is_valid_body_field(welcome_message, false)?;
+  }
+
   let description = data.description.clone();
   if let Some(desc) = &description {
     description_length_check(desc)?;
@@ -88,6 +97,9 @@ pub async fn create_community(
   }
 
   check_community_visibility_allowed(data.visibility, &local_user_view)?;
+  check_not_negative(data.new_member_comment_threshold)?;
+  check_not_negative(data.new_member_account_age_days)?;
+  check_not_negative(data.auto_lock_posts_after_seconds)?;
 
   // Double check for duplicate community actor_ids
   let community_ap_id = Community::generate_local_actor_url(&data.name, context.settings())?;
@@ -109,6 +121,19 @@ pub async fn create_community(
     featured_url: Some(generate_featured_url(&community_ap_id)?),
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    require_comment_approval_for_new_members: data.require_comment_approval_for_new_members,
+    new_member_comment_threshold: data.new_member_comment_threshold,
+    new_member_account_age_days: data.new_member_account_age_days,
+    auto_lock_posts_after_seconds: data.auto_lock_posts_after_seconds,
+    read_only: data.read_only,
+    welcome_message,
+    hide_scores_until_voted: data.hide_scores_until_voted,
+    comments_require_membership: data.comments_require_membership,
+    auto_undistinguish_below_score: data.auto_undistinguish_below_score,
+    min_comment_length: data.min_comment_length,
+    downvotes_enabled: data.downvotes_enabled,
+    lock_edits_after_reply: data.lock_edits_after_reply,
+    show_creator_instance: data.show_creator_instance,
     ..CommunityInsertForm::new(
       site_view.site.instance_id,
       data.name.clone(),
@@ -155,5 +180,15 @@ pub async fn create_community(
   };
   CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
 
+  // The primary language can only be checked once the discussion languages are stored
+  if let Some(primary_language_id) = data.primary_language_id {
+    validate_post_language(&mut context.pool(), Some(primary_language_id), community_id).await?;
+    let form = CommunityUpdateForm {
+      primary_language_id: Some(Some(primary_language_id)),
+      ..Default::default()
+    };
+    Community::update(&mut context.pool(), community_id, &form).await?;
+  }
+
   build_community_response(&context, local_user_view, community_id).await
 }
@@ -30,6 +30,7 @@ pub async fn list_communities(
     local_user: local_user.as_ref(),
     page_cursor: data.page_cursor,
     limit: data.limit,
+    include_comment_counts: data.include_comment_counts,
     ..Default::default()
   }
   .list(&local_site.site, &mut context.pool())
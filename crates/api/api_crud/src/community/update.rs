@@ -15,20 +15,32 @@ use lemmy_api_utils::{
     slur_regex,
   },
 };
-use lemmy_db_schema::source::{
-  actor_language::{CommunityLanguage, SiteLanguage},
-  community::{Community, CommunityUpdateForm},
-  modlog::{Modlog, ModlogInsertForm},
+use lemmy_db_schema::{
+  impls::actor_language::{UNDETERMINED_ID, validate_post_language},
+  source::{
+    actor_language::{CommunityLanguage, SiteLanguage},
+    community::{Community, CommunityUpdateForm},
+    community_allowed_domain::CommunityAllowedDomain,
+    modlog::{Modlog, ModlogInsertForm},
+  },
 };
 use lemmy_db_views_community::api::{CommunityResponse, EditCommunity};
 use lemmy_db_views_local_user::LocalUserView;
 use lemmy_db_views_site::SiteView;
-use lemmy_diesel_utils::{traits::Crud, utils::diesel_string_update};
+use lemmy_diesel_utils::{
+  traits::Crud,
+  utils::{diesel_opt_number_update, diesel_string_update},
+};
 use lemmy_utils::{
   error::{LemmyErrorType, LemmyResult},
   utils::{
     slurs::check_slurs_opt,
-    validation::{is_valid_body_field, is_valid_display_name},
+    validation::{
+      check_allowed_domains_are_valid,
+      check_not_negative,
+      is_valid_body_field,
+      is_valid_display_name,
+    },
   },
 };
 
@@ -60,7 +72,20 @@ pub async fn update_community(
     is_valid_body_field(sidebar, false)?;
   }
 
+  let welcome_message = diesel_string_update(
+    process_markdown_opt(&data.welcome_message, &slur_regex, &url_blocklist, &context)
+      .await?
+      .as_deref(),
+  );
+
+  if let Some(Some(welcome_message)) = &welcome_message {
+    is_valid_body_field(welcome_message, false)?;
+  }
+
   check_community_visibility_allowed(data.visibility, &local_user_view)?;
+  check_not_negative(data.new_member_comment_threshold)?;
+  check_not_negative(data.new_member_account_age_days)?;
+  check_not_negative(data.auto_lock_posts_after_seconds)?;
   let description = diesel_string_update(data.description.as_deref());
 
   let old_community = Community::read(&mut context.pool(), data.community_id).await?;
@@ -79,6 +104,17 @@ pub async fn update_community(
     }
     CommunityLanguage::update(&mut context.pool(), languages, community_id).await?;
   }
+  if let Some(domains) = &data.allowed_link_domains {
+    let domains = check_allowed_domains_are_valid(domains)?;
+    CommunityAllowedDomain::replace(&mut context.pool(), community_id, domains).await?;
+  }
+
+  // The primary language needs to remain one of the discussion languages
+  let primary_language_id = data
+    .primary_language_id
+    .map(|l| (l != UNDETERMINED_ID).then_some(l));
+  let new_primary_language_id = primary_language_id.unwrap_or(old_community.primary_language_id);
+  validate_post_language(&mut context.pool(), new_primary_language_id, community_id).await?;
 
   let community_form = CommunityUpdateForm {
     title: data.title.clone(),
@@ -87,6 +123,20 @@ pub async fn update_community(
     nsfw: data.nsfw,
     posting_restricted_to_mods: data.posting_restricted_to_mods,
     visibility: data.visibility,
+    require_comment_approval_for_new_members: data.require_comment_approval_for_new_members,
+    new_member_comment_threshold: diesel_opt_number_update(data.new_member_comment_threshold),
+    new_member_account_age_days: diesel_opt_number_update(data.new_member_account_age_days),
+    primary_language_id,
+    auto_lock_posts_after_seconds: diesel_opt_number_update(data.auto_lock_posts_after_seconds),
+    read_only: data.read_only,
+    welcome_message,
+    hide_scores_until_voted: data.hide_scores_until_voted,
+    comments_require_membership: data.comments_require_membership,
+    auto_undistinguish_below_score: diesel_opt_number_update(data.auto_undistinguish_below_score),
+    min_comment_length: diesel_opt_number_update(data.min_comment_length),
+    downvotes_enabled: data.downvotes_enabled,
+    lock_edits_after_reply: data.lock_edits_after_reply,
+    show_creator_instance: data.show_creator_instance,
     updated_at: Some(Some(Utc::now())),
     ..Default::default()
   };
@@ -10,6 +10,7 @@ use lemmy_api_utils::{
   request::generate_post_link_metadata,
   send_activity::SendActivityData,
   utils::{
+    check_community_not_read_only,
     check_community_user_action,
     check_nsfw_allowed,
     get_url_blocklist,
@@ -90,6 +91,7 @@ pub async fn create_post(
   .await?;
   let community = &community_view.community;
   check_community_user_action(&local_user_view, community, &mut context.pool()).await?;
+  check_community_not_read_only(&local_user_view, community, &mut context.pool()).await?;
 
   // Ensure that all posts in NSFW communities are marked as NSFW
   let nsfw = if community.nsfw {
@@ -176,3 +178,61 @@ pub async fn create_post(
 
   build_post_response(&context, community_id, local_user_view, post_id).await
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    source::{
+      community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+      person::Person,
+    },
+    test_data::TestData,
+  };
+  use lemmy_utils::error::LemmyErrorType;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_post_in_read_only_community() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let user = LocalUserView::create_test_user(pool, "read_only_poster", "", false).await?;
+    let moderator = LocalUserView::create_test_user(pool, "read_only_mod", "", false).await?;
+    let community_form = CommunityInsertForm {
+      read_only: Some(true),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "read_only_posts".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm::new(community.id, moderator.person.id);
+    CommunityActions::join(pool, &form).await?;
+
+    let create = CreatePost {
+      name: "announcement".to_string(),
+      community_id: community.id,
+      ..Default::default()
+    };
+    let res = create_post(Json(create.clone()), context.clone(), user.clone()).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommunityIsReadOnly),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Mods can still post
+    let res = create_post(Json(create), context.clone(), moderator.clone()).await?;
+    assert_eq!("announcement", res.post_view.post.name);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, user.person.id).await?;
+    Person::delete(pool, moderator.person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
@@ -1,5 +1,9 @@
 use super::not_zero;
-use crate::site::{application_question_check, site_default_post_listing_type_check};
+use crate::site::{
+  application_question_check,
+  max_comment_depth_check,
+  site_default_post_listing_type_check,
+};
 use activitypub_federation::{config::Data, http_signatures::generate_actor_keypair};
 use actix_web::web::Json;
 use chrono::Utc;
@@ -87,6 +91,7 @@ pub async fn create_site(
     default_theme: data.default_theme.clone(),
     default_post_listing_type: data.default_post_listing_type,
     default_post_sort_type: data.default_post_sort_type,
+    max_comment_depth: data.max_comment_depth,
     default_comment_sort_type: data.default_comment_sort_type,
     legal_information: diesel_string_update(data.legal_information.as_deref()),
     application_email_admins: data.application_email_admins,
@@ -163,6 +168,7 @@ fn validate_create_payload(local_site: &LocalSite, create_site: &CreateSite) ->
   }
 
   site_default_post_listing_type_check(&create_site.default_post_listing_type)?;
+  max_comment_depth_check(create_site.max_comment_depth)?;
 
   // Ensure that the sidebar has fewer than the max num characters...
   if let Some(body) = &create_site.sidebar {
@@ -1,5 +1,8 @@
 use lemmy_db_schema_file::enums::{ListingType, RegistrationMode};
-use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  MAX_COMMENT_DEPTH_LIMIT,
+  error::{LemmyErrorType, LemmyResult},
+};
 
 pub mod create;
 pub mod read;
@@ -21,6 +24,16 @@ pub fn site_default_post_listing_type_check(
   }
 }
 
+/// Checks that the maximum comment depth is positive and within the hardcoded limit.
+pub fn max_comment_depth_check(max_comment_depth: Option<i32>) -> LemmyResult<()> {
+  if let Some(depth) = max_comment_depth
+    && !(1..=MAX_COMMENT_DEPTH_LIMIT.try_into()?).contains(&depth)
+  {
+    Err(LemmyErrorType::InvalidMaxCommentDepth)?
+  }
+  Ok(())
+}
+
 /// Checks whether the application question and registration mode align.
 pub fn application_question_check(
   current_application_question: &Option<String>,
@@ -50,7 +63,12 @@ fn not_zero(val: Option<i32>) -> Option<i32> {
 #[cfg(test)]
 mod tests {
 
-  use crate::site::{application_question_check, not_zero, site_default_post_listing_type_check};
+  use crate::site::{
+    application_question_check,
+    max_comment_depth_check,
+    not_zero,
+    site_default_post_listing_type_check,
+  };
   use lemmy_db_schema_file::enums::{ListingType, RegistrationMode};
 
   #[test]
@@ -61,6 +79,15 @@ mod tests {
     assert!(site_default_post_listing_type_check(&Some(ListingType::Subscribed)).is_err());
   }
 
+  #[test]
+  fn test_max_comment_depth_check() {
+    assert!(max_comment_depth_check(None).is_ok());
+    assert!(max_comment_depth_check(Some(1)).is_ok());
+    assert!(max_comment_depth_check(Some(50)).is_ok());
+    assert!(max_comment_depth_check(Some(0)).is_err());
+    assert!(max_comment_depth_check(Some(51)).is_err());
+  }
+
   #[test]
   fn test_application_question_check() {
     assert!(
@@ -1,5 +1,9 @@
 use super::not_zero;
-use crate::site::{application_question_check, site_default_post_listing_type_check};
+use crate::site::{
+  application_question_check,
+  max_comment_depth_check,
+  site_default_post_listing_type_check,
+};
 use activitypub_federation::config::Data;
 use actix_web::web::Json;
 use chrono::Utc;
@@ -100,6 +104,7 @@ pub async fn update_site(
     default_post_sort_type: data.default_post_sort_type,
     default_post_time_range_seconds,
     default_items_per_page,
+    max_comment_depth: data.max_comment_depth,
     default_comment_sort_type: data.default_comment_sort_type,
     legal_information: diesel_string_update(data.legal_information.as_deref()),
     application_email_admins: data.application_email_admins,
@@ -211,6 +216,7 @@ fn validate_update_payload(local_site: &LocalSite, edit_site: &EditSite) -> Lemm
   }
 
   site_default_post_listing_type_check(&edit_site.default_post_listing_type)?;
+  max_comment_depth_check(edit_site.max_comment_depth)?;
 
   // Ensure that the sidebar has fewer than the max num characters...
   if let Some(body) = &edit_site.sidebar {
@@ -0,0 +1,238 @@
+use crate::{context::LemmyContext, notify::notify_mod_action};
+use lemmy_db_schema::{
+  newtypes::CommunityId,
+  source::{
+    comment::Comment,
+    comment_report::{CommentReport, CommentReportForm},
+    community_automod_rule::CommunityAutomodRule,
+    modlog::{Modlog, ModlogInsertForm},
+  },
+};
+use lemmy_db_schema_file::enums::AutomodAction;
+use lemmy_db_views_site::SiteView;
+use lemmy_utils::error::LemmyResult;
+use moka::future::Cache;
+use regex::{Regex, RegexBuilder};
+use std::{sync::LazyLock, time::Duration};
+
+/// Compiled rule patterns, so that they aren't compiled again for every new comment. Keyed by the
+/// pattern itself, so edited rules don't need to be invalidated.
+static RULE_REGEXES: LazyLock<Cache<String, Option<Regex>>> = LazyLock::new(|| {
+  Cache::builder()
+    .max_capacity(10_000)
+    .time_to_idle(Duration::from_secs(60 * 60))
+    .build()
+});
+
+/// Returns the first rule whose pattern matches the given text. Patterns which fail to compile are
+/// skipped, they are validated when the rule is saved.
+pub async fn first_matching_rule<'a>(
+  rules: &'a [CommunityAutomodRule],
+  text: &str,
+) -> Option<&'a CommunityAutomodRule> {
+  for rule in rules {
+    let regex = RULE_REGEXES
+      .get_with_by_ref(&rule.pattern, async {
+        RegexBuilder::new(&rule.pattern)
+          .case_insensitive(true)
+          .build()
+          .ok()
+      })
+      .await;
+    if regex.is_some_and(|regex| regex.is_match(text)) {
+      return Some(rule);
+    }
+  }
+  None
+}
+
+/// Checks the content of a new or edited local comment against the automod rules of its
+/// community. Returns the first matching rule, if any.
+pub async fn matching_automod_rule(
+  content: &str,
+  community_id: CommunityId,
+  context: &LemmyContext,
+) -> LemmyResult<Option<CommunityAutomodRule>> {
+  let rules = CommunityAutomodRule::list_for_community(&mut context.pool(), community_id).await?;
+  Ok(first_matching_rule(&rules, content).await.cloned())
+}
+
+/// Whether the comment has to be saved as removed. This way it is never visible before the rule is
+/// applied.
+pub fn automod_removes(rule: &CommunityAutomodRule) -> bool {
+  rule.action == AutomodAction::Remove
+}
+
+/// Whether the comment has to be saved as pending approval, so that only mods and its creator can
+/// see it until a mod approves it.
+pub fn automod_holds(rule: &CommunityAutomodRule) -> bool {
+  rule.action == AutomodAction::RequireApproval
+}
+
+/// Records the action of a matching rule for a new or edited comment, which was already saved as
+/// removed or pending approval if [automod_removes] or [automod_holds] says so. Actions are
+/// attributed to the site's system account. Held comments only show up in the approval queue.
+///
+/// Returns the modlog entries which were written.
+pub async fn apply_automod_rule(
+  comment: &Comment,
+  rule: &CommunityAutomodRule,
+  context: &LemmyContext,
+) -> LemmyResult<Vec<Modlog>> {
+  let system_account = SiteView::read_system_account(&mut context.pool()).await?;
+  let reason = format!("automod: matched rule {}", rule.id.0);
+
+  let mut actions = vec![];
+  if automod_removes(rule) {
+    let form = ModlogInsertForm::mod_remove_comment(system_account.id, comment, true, &reason);
+    actions = Modlog::create(&mut context.pool(), &[form]).await?;
+    notify_mod_action(actions.clone(), context);
+  }
+
+  // Flagged comments show up in the report queue, so that mods can review them. They are only
+  // reported once, even if they match again after an edit.
+  if rule.action == AutomodAction::Flag {
+    let report_form = CommentReportForm {
+      creator_id: system_account.id,
+      comment_id: comment.id,
+      original_comment_text: comment.content.clone(),
+      reason,
+      violates_instance_rules: false,
+    };
+    CommentReport::report_once(&mut context.pool(), &report_form).await?;
+  }
+
+  Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lemmy_db_schema::{
+    newtypes::CommunityAutomodRuleId,
+    source::{
+      comment::CommentInsertForm,
+      community::{Community, CommunityInsertForm},
+      community_automod_rule::CommunityAutomodRuleInsertForm,
+      person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
+    },
+    test_data::TestData,
+  };
+  use lemmy_db_schema_file::enums::ModlogKind;
+  use lemmy_diesel_utils::traits::Crud;
+  use lemmy_utils::error::LemmyErrorType;
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  fn rule(id: i32, pattern: &str, action: AutomodAction) -> CommunityAutomodRule {
+    CommunityAutomodRule {
+      id: CommunityAutomodRuleId(id),
+      community_id: CommunityId(1),
+      pattern: pattern.to_string(),
+      action,
+      published_at: Default::default(),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_first_matching_rule() {
+    let rules = vec![
+      rule(1, "spam(my)?link", AutomodAction::Flag),
+      rule(2, "[invalid", AutomodAction::Remove),
+      rule(3, "badword", AutomodAction::Remove),
+    ];
+    assert_eq!(
+      Some(3),
+      first_matching_rule(&rules, "this has a BadWord")
+        .await
+        .map(|r| r.id.0)
+    );
+    assert_eq!(
+      Some(1),
+      first_matching_rule(&rules, "spamlink and badword")
+        .await
+        .map(|r| r.id.0)
+    );
+    assert!(
+      first_matching_rule(&rules, "a friendly comment")
+        .await
+        .is_none()
+    );
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_apply_community_automod() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = TestData::create(pool).await?;
+
+    let person_form = PersonInsertForm::test_form(data.instance.id, "automod_commenter");
+    let person = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      data.instance.id,
+      "automod_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("automod post".into(), person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    let rule_form =
+      CommunityAutomodRuleInsertForm::new(community.id, "badword".into(), AutomodAction::Remove);
+    CommunityAutomodRule::create(pool, &rule_form).await?;
+
+    // A matching comment is inserted as removed, with a modlog entry
+    let content = "This has a BADWORD";
+    let rule = matching_automod_rule(content, community.id, &context)
+      .await?
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(automod_removes(&rule));
+    let form = CommentInsertForm {
+      removed: Some(automod_removes(&rule)),
+      ..CommentInsertForm::new(person.id, post.id, content.into())
+    };
+    let comment = Comment::create(pool, &form, None).await?;
+    let actions = apply_automod_rule(&comment, &rule, &context).await?;
+    assert_eq!(1, actions.len());
+    assert_eq!(ModlogKind::ModRemoveComment, actions[0].kind);
+    assert_eq!(Some(comment.id), actions[0].target_comment_id);
+    assert!(Comment::read(pool, comment.id).await?.removed);
+
+    // A held comment only waits for approval, without modlog entry
+    let rule_form = CommunityAutomodRuleInsertForm::new(
+      community.id,
+      "holdme".into(),
+      AutomodAction::RequireApproval,
+    );
+    CommunityAutomodRule::create(pool, &rule_form).await?;
+    let content = "Please holdme";
+    let rule = matching_automod_rule(content, community.id, &context)
+      .await?
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(!automod_removes(&rule));
+    assert!(automod_holds(&rule));
+    let form = CommentInsertForm {
+      pending_approval: Some(automod_holds(&rule)),
+      ..CommentInsertForm::new(person.id, post.id, content.into())
+    };
+    let comment = Comment::create(pool, &form, None).await?;
+    let actions = apply_automod_rule(&comment, &rule, &context).await?;
+    assert!(actions.is_empty());
+    let comment = Comment::read(pool, comment.id).await?;
+    assert!(!comment.removed);
+    assert!(comment.pending_approval);
+
+    // Other comments are posted normally
+    let rule = matching_automod_rule("A friendly comment", community.id, &context).await?;
+    assert!(rule.is_none());
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+
+    Ok(())
+  }
+}
@@ -1,3 +1,4 @@
+pub mod automod;
 pub mod build_response;
 pub mod claims;
 pub mod context;
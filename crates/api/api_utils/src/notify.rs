@@ -134,11 +134,16 @@ impl NotifyData {
     InstanceActions::read_persons_block(pool, potential_blocker_id, self.creator.instance_id)
       .await?;
     CommunityActions::read_block(pool, potential_blocker_id, self.post.community_id).await?;
-    let post_notifications = PostActions::read(pool, self.post.id, potential_blocker_id)
+    let post_actions = PostActions::read(pool, self.post.id, potential_blocker_id)
       .await
-      .ok()
+      .ok();
+    let post_notifications = post_actions
+      .as_ref()
       .and_then(|a| a.notifications)
       .unwrap_or_default();
+    let post_muted = post_actions
+      .and_then(|a| a.notifications_muted)
+      .unwrap_or_default();
     let community_notifications =
       CommunityActions::read(pool, self.community.id, potential_blocker_id)
         .await
@@ -146,6 +151,7 @@ impl NotifyData {
         .and_then(|a| a.notifications)
         .unwrap_or_default();
     if post_notifications == PostNotificationsMode::Mute
+      || post_muted
       || community_notifications == CommunityNotificationsMode::Mute
     {
       // The specific error type is irrelevant
@@ -235,8 +241,26 @@ impl NotifyData {
     context: &LemmyContext,
   ) -> LemmyResult<Vec<CollectedNotifyData<'a>>> {
     let is_post = self.comment_opt.is_none();
+    let (coalesced, others): (Vec<_>, Vec<_>) =
+      PostActions::list_subscribers(self.post.id, &mut context.pool())
+        .await?
+        .into_iter()
+        .partition(|(_, coalesce)| *coalesce && !is_post);
+    let mut post_subscribers: Vec<_> = others.into_iter().map(|s| s.0).collect();
+    if let Some(comment) = &self.comment_opt {
+      let coalesced: Vec<_> = coalesced.into_iter().map(|s| s.0).collect();
+      post_subscribers.extend(
+        PostActions::mark_coalesced_subscribers_notified(
+          self.post.id,
+          comment.creator_id,
+          &coalesced,
+          &mut context.pool(),
+        )
+        .await?,
+      );
+    }
     let subscribers = vec![
-      PostActions::list_subscribers(self.post.id, &mut context.pool()).await?,
+      post_subscribers,
       CommunityActions::list_subscribers(self.post.community_id, is_post, &mut context.pool())
         .await?,
     ]
@@ -320,43 +344,52 @@ pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
   }
 
   let context = context.clone();
-  spawn_try_task(async move {
-    for action in actions {
-      let Some(target_id) = action.target_person_id else {
-        continue;
-      };
-      let Ok(local_recipient) = LocalUserView::read_person(&mut context.pool(), target_id).await
-      else {
-        continue;
-      };
+  spawn_try_task(async move { notify_mod_action_internal(actions, &context).await })
+}
+async fn notify_mod_action_internal(
+  actions: Vec<Modlog>,
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  let site_view = SiteView::read_local(&mut context.pool()).await?;
+  for action in actions {
+    // Mods acting on their own content don't need to be told about it
+    let Some(target_id) = action.target_person_id.filter(|t| *t != action.mod_id) else {
+      continue;
+    };
+    let Ok(local_recipient) = LocalUserView::read_person(&mut context.pool(), target_id).await
+    else {
+      continue;
+    };
 
-      let form = NotificationInsertForm {
-        modlog_id: Some(action.id),
-        ..NotificationInsertForm::new(local_recipient.person.id, NotificationType::ModAction)
-      };
-      let notifications = Notification::create(&mut context.pool(), &[form]).await?;
-      plugin_hook_notification(notifications, &context).await?;
+    let form = NotificationInsertForm {
+      modlog_id: Some(action.id),
+      ..NotificationInsertForm::new(local_recipient.person.id, NotificationType::ModAction)
+    };
+    let notifications = Notification::create(&mut context.pool(), &[form]).await?;
+    plugin_hook_notification(notifications, context).await?;
 
-      let modlog_url = format!(
-        "{}/modlog?userId={}&actionType={}",
-        context.settings().get_protocol_and_hostname(),
-        local_recipient.person.id.0,
-        action.kind
-      );
-      let d = NotificationEmailData::ModAction {
-        kind: action.kind,
-        reason: action.reason.as_deref(),
-        is_revert: action.is_revert,
-      };
-      send_notification_email(
-        local_recipient,
-        Url::parse(&modlog_url)?.into(),
-        d,
-        context.settings(),
-      );
+    if site_view.local_site.disable_email_notifications {
+      continue;
     }
-    Ok(())
-  })
+    let modlog_url = format!(
+      "{}/modlog?userId={}&actionType={}",
+      context.settings().get_protocol_and_hostname(),
+      local_recipient.person.id.0,
+      action.kind
+    );
+    let d = NotificationEmailData::ModAction {
+      kind: action.kind,
+      reason: action.reason.as_deref(),
+      is_revert: action.is_revert,
+    };
+    send_notification_email(
+      local_recipient,
+      Url::parse(&modlog_url)?.into(),
+      d,
+      context.settings(),
+    );
+  }
+  Ok(())
 }
 
 #[cfg(test)]
@@ -364,7 +397,7 @@ pub fn notify_mod_action(actions: Vec<Modlog>, context: &LemmyContext) {
 mod tests {
   use crate::{
     context::LemmyContext,
-    notify::{NotifyData, notify_private_message_internal},
+    notify::{NotifyData, notify_mod_action_internal, notify_private_message_internal},
   };
   use lemmy_db_schema::{
     NotificationDataType,
@@ -373,14 +406,16 @@ mod tests {
       comment::{Comment, CommentInsertForm},
       community::{Community, CommunityInsertForm},
       instance::{Instance, InstanceActions, InstancePersonsBlockForm},
+      modlog::{Modlog, ModlogInsertForm},
       notification::{Notification, NotificationInsertForm},
       person::{Person, PersonActions, PersonBlockForm, PersonInsertForm, PersonUpdateForm},
-      post::{Post, PostInsertForm},
+      post::{Post, PostActions, PostInsertForm},
       private_message::{PrivateMessage, PrivateMessageInsertForm},
     },
+    test_data::TestData,
     traits::Blockable,
   };
-  use lemmy_db_schema_file::enums::NotificationType;
+  use lemmy_db_schema_file::enums::{NotificationType, PostNotificationsMode};
   use lemmy_db_views_local_user::LocalUserView;
   use lemmy_db_views_notification::{NotificationData, NotificationView, impls::NotificationQuery};
   use lemmy_db_views_private_message::PrivateMessageView;
@@ -589,6 +624,133 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn coalesce_subscriber_notifications() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = init_data(pool).await?;
+
+    // Timmy subscribes to jessicas post, with coalescing
+    PostActions::update_notification_state(
+      data.jessica_post.id,
+      data.timmy.person.id,
+      PostNotificationsMode::AllComments,
+      Some(true),
+      pool,
+    )
+    .await?;
+
+    // Sara comments twice in a row, and jessica once
+    let comments = [
+      (&data.sara.person, "sara first"),
+      (&data.sara.person, "sara second"),
+      (&data.jessica, "jessica comment"),
+    ];
+    for (creator, content) in comments {
+      let form = CommentInsertForm::new(creator.id, data.jessica_post.id, content.into());
+      let comment = Comment::create(pool, &form, None).await?;
+      NotifyData {
+        post: data.jessica_post.clone(),
+        comment_opt: Some(comment),
+        creator: creator.clone(),
+        community: data.community.clone(),
+        do_send_email: false,
+      }
+      .send_internal(context.app_data().clone())
+      .await?;
+    }
+
+    let timmy_inbox = NotificationQuery::default()
+      .list(pool, &data.timmy.person)
+      .await?;
+    assert_length!(2, timmy_inbox);
+    assert!(
+      timmy_inbox
+        .iter()
+        .all(|n| n.notification.kind == NotificationType::Subscribed)
+    );
+    let creators = timmy_inbox
+      .iter()
+      .filter_map(|n| match &n.data {
+        NotificationData::Comment(c) => Some(c.creator.id),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    assert_length!(2, creators);
+    assert!(creators.contains(&data.sara.person.id));
+    assert!(creators.contains(&data.jessica.id));
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn muted_subscriber_notifications() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let data = init_data(pool).await?;
+
+    // Timmy subscribes to jessicas post, then mutes it
+    PostActions::update_notification_state(
+      data.jessica_post.id,
+      data.timmy.person.id,
+      PostNotificationsMode::AllComments,
+      None,
+      pool,
+    )
+    .await?;
+    PostActions::update_notifications_muted(data.jessica_post.id, data.timmy.person.id, true, pool)
+      .await?;
+
+    let send_comment = async |content: &str| -> LemmyResult<()> {
+      let form = CommentInsertForm::new(data.sara.person.id, data.jessica_post.id, content.into());
+      let comment = Comment::create(&mut context.pool(), &form, None).await?;
+      NotifyData {
+        post: data.jessica_post.clone(),
+        comment_opt: Some(comment),
+        creator: data.sara.person.clone(),
+        community: data.community.clone(),
+        do_send_email: false,
+      }
+      .send_internal(context.app_data().clone())
+      .await
+    };
+    send_comment("while muted").await?;
+
+    // No notification, but still subscribed
+    let timmy_inbox = NotificationQuery::default()
+      .list(pool, &data.timmy.person)
+      .await?;
+    assert_length!(0, timmy_inbox);
+    let actions = PostActions::read(pool, data.jessica_post.id, data.timmy.person.id).await?;
+    assert_eq!(
+      Some(PostNotificationsMode::AllComments),
+      actions.notifications
+    );
+    assert_eq!(Some(true), actions.notifications_muted);
+
+    // After unmuting, notifications arrive again
+    PostActions::update_notifications_muted(
+      data.jessica_post.id,
+      data.timmy.person.id,
+      false,
+      pool,
+    )
+    .await?;
+    send_comment("after unmuting").await?;
+    let timmy_inbox = NotificationQuery::default()
+      .list(pool, &data.timmy.person)
+      .await?;
+    assert_length!(1, timmy_inbox);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn mentions() -> LemmyResult<()> {
@@ -866,4 +1028,52 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn mod_removal_notifies_author() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+    let test_data = TestData::create(pool).await?;
+    let data = init_data(pool).await?;
+
+    // Sara removes timmys comment as a mod
+    let form =
+      ModlogInsertForm::mod_remove_comment(data.sara.person.id, &data.timmy_comment, true, "spam");
+    let actions = Modlog::create(pool, &[form]).await?;
+    notify_mod_action_internal(actions, &context).await?;
+
+    let timmy_inbox = NotificationQuery::default()
+      .list(pool, &data.timmy.person)
+      .await?;
+    assert_length!(1, timmy_inbox);
+    assert_eq!(
+      NotificationType::ModAction,
+      timmy_inbox[0].notification.kind
+    );
+    let NotificationData::ModAction(m) = &timmy_inbox[0].data else {
+      panic!("wrong type")
+    };
+    assert_eq!(Some("spam"), m.modlog.reason.as_deref());
+    assert_eq!(
+      Some(data.timmy_comment.id),
+      m.target_comment.as_ref().map(|c| c.id)
+    );
+
+    // Removing her own comment doesn't notify sara
+    let sara_comment_form =
+      CommentInsertForm::new(data.sara.person.id, data.timmy_post.id, "sara".into());
+    let sara_comment = Comment::create(pool, &sara_comment_form, None).await?;
+    let form = ModlogInsertForm::mod_remove_comment(data.sara.person.id, &sara_comment, true, "");
+    let actions = Modlog::create(pool, &[form]).await?;
+    notify_mod_action_internal(actions, &context).await?;
+
+    let sara_unread = NotificationView::get_unread_count(pool, &data.sara.person, true).await?;
+    assert_eq!(0, sara_unread);
+
+    cleanup(data, pool).await?;
+    test_data.delete(pool).await?;
+
+    Ok(())
+  }
 }
@@ -6,9 +6,10 @@ use crate::{
 use activitypub_federation::config::Data;
 use chrono::{DateTime, Utc};
 use encoding_rs::{Encoding, UTF_8};
-use futures::StreamExt;
+use futures::{StreamExt, future::join_all};
 use lemmy_db_schema::source::{
   images::{ImageDetailsInsertForm, LocalImage, LocalImageForm},
+  person::{Person, PersonUpdateForm},
   post::{Post, PostUpdateForm},
   site::Site,
 };
@@ -19,8 +20,10 @@ use lemmy_utils::{
   VERSION,
   error::{LemmyError, LemmyErrorExt, LemmyErrorType, LemmyResult, UntranslatedError},
   settings::structs::{PictrsImageMode, Settings},
+  utils::markdown::image_links::{markdown_find_links, markdown_handle_title},
 };
 use mime::{Mime, TEXT_HTML};
+use regex::Regex;
 use reqwest::{
   Client,
   ClientBuilder,
@@ -30,8 +33,8 @@ use reqwest::{
 };
 use reqwest_middleware::ClientWithMiddleware;
 use serde::{Deserialize, Serialize};
-use std::net::IpAddr;
-use tokio::net::lookup_host;
+use std::{net::IpAddr, sync::LazyLock};
+use tokio::{net::lookup_host, time::timeout};
 use tracing::{info, warn};
 use url::Url;
 use urlencoding::encode;
@@ -48,12 +51,8 @@ pub fn client_builder(settings: &Settings) -> ClientBuilder {
     .use_rustls_tls()
 }
 
-/// Fetches metadata for the given link and optionally generates thumbnail.
-pub async fn fetch_link_metadata(
-  url: &Url,
-  context: &LemmyContext,
-  recursion: bool,
-) -> LemmyResult<LinkMetadata> {
+/// Only allow http(s) urls, and throw an error if the domain points to any internal IP.
+async fn check_url_is_external(url: &Url) -> LemmyResult<()> {
   if url.scheme() != "http" && url.scheme() != "https" {
     return Err(LemmyErrorType::InvalidUrl.into());
   }
@@ -81,6 +80,16 @@ pub async fn fetch_link_metadata(
       return Err(LemmyErrorType::InvalidUrl.into());
     }
   }
+  Ok(())
+}
+
+/// Fetches metadata for the given link and optionally generates thumbnail.
+pub async fn fetch_link_metadata(
+  url: &Url,
+  context: &LemmyContext,
+  recursion: bool,
+) -> LemmyResult<LinkMetadata> {
+  check_url_is_external(url).await?;
 
   info!("Fetching site metadata for url: {}", url);
   // We only fetch the first MB of data in order to not waste bandwidth especially for large
@@ -181,6 +190,113 @@ async fn collect_bytes_until_limit(
   Ok(bytes)
 }
 
+/// Only this many links of a bio are checked, the rest are never verified.
+const MAX_PROFILE_LINKS: usize = 10;
+
+/// Checks the links in the person's bio, and stores those whose page links back to the profile
+/// with `rel="me"` as verified. The pages are fetched concurrently.
+pub async fn verify_profile_links(person: &Person, context: &LemmyContext) -> LemmyResult<()> {
+  let bio = person.bio.clone().unwrap_or_default();
+  let mut urls: Vec<Url> = vec![];
+  for (start, end) in markdown_find_links(&bio) {
+    let (link, _) = markdown_handle_title(&bio, start, end);
+    let Ok(url) = Url::parse(link) else {
+      continue;
+    };
+    if urls.iter().any(|u| normalize_url(u) == normalize_url(&url)) {
+      continue;
+    }
+    urls.push(url);
+    if urls.len() >= MAX_PROFILE_LINKS {
+      break;
+    }
+  }
+
+  let checks = urls.into_iter().map(|url| async move {
+    let links_back = timeout(
+      REQWEST_TIMEOUT,
+      fetch_page_links_back(&url, &person.ap_id, context),
+    )
+    .await;
+    match links_back {
+      Ok(Ok(true)) => Some(url.to_string()),
+      Ok(Ok(false)) => None,
+      Ok(Err(e)) => {
+        info!("Failed to verify profile link {url}: {e}");
+        None
+      }
+      Err(_) => {
+        info!("Timed out verifying profile link {url}");
+        None
+      }
+    }
+  });
+  let verified_links = join_all(checks).await.into_iter().flatten().collect();
+
+  let form = PersonUpdateForm {
+    verified_links: Some(verified_links),
+    ..Default::default()
+  };
+  Person::update(&mut context.pool(), person.id, &form).await?;
+  Ok(())
+}
+
+async fn fetch_page_links_back(
+  url: &Url,
+  profile_url: &Url,
+  context: &LemmyContext,
+) -> LemmyResult<bool> {
+  check_url_is_external(url).await?;
+  let response = context
+    .client()
+    .get(url.as_str())
+    .send()
+    .await?
+    .error_for_status()?;
+  let html_bytes = collect_bytes_until_limit(response, 1024 * 1024).await?;
+  Ok(html_links_back(
+    &String::from_utf8_lossy(&html_bytes),
+    profile_url,
+  ))
+}
+
+/// Returns true if the html contains an `<a>` or `<link>` tag with `rel="me"` pointing to the
+/// given profile url.
+fn html_links_back(html: &str, profile_url: &Url) -> bool {
+  #[allow(clippy::expect_used)]
+  static TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<(?:a|link)\s[^>]*>").expect("compile regex"));
+  #[allow(clippy::expect_used)]
+  static REL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\srel\s*=\s*["']([^"']*)["']"#).expect("compile regex"));
+  #[allow(clippy::expect_used)]
+  static HREF_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\shref\s*=\s*["']([^"']*)["']"#).expect("compile regex"));
+
+  TAG_REGEX.find_iter(html).any(|tag| {
+    let tag = tag.as_str();
+    let is_rel_me = REL_REGEX
+      .captures(tag)
+      .and_then(|c| c.get(1))
+      .is_some_and(|rel| rel.as_str().split_whitespace().any(|r| r == "me"));
+    let href = HREF_REGEX
+      .captures(tag)
+      .and_then(|c| c.get(1))
+      .and_then(|h| Url::parse(h.as_str()).ok());
+    is_rel_me && href.is_some_and(|h| normalize_url(&h) == normalize_url(profile_url))
+  })
+}
+
+/// Drops the fragment and a trailing slash, so that different spellings of the same page compare
+/// equal. Scheme and host are already lowercased by the parser.
+fn normalize_url(url: &Url) -> String {
+  let mut url = url.clone();
+  url.set_fragment(None);
+  let path = url.path().trim_end_matches('/').to_string();
+  url.set_path(&path);
+  url.to_string().trim_end_matches('/').to_string()
+}
+
 /// Generates and saves a post thumbnail and metadata.
 ///
 /// Takes a callback to generate a send activity task, so that post can be federated with metadata.
@@ -591,7 +707,7 @@ mod tests {
 
   use crate::{
     context::LemmyContext,
-    request::{extract_opengraph_data, fetch_link_metadata},
+    request::{extract_opengraph_data, fetch_link_metadata, html_links_back},
   };
   use lemmy_utils::error::LemmyResult;
   use pretty_assertions::assert_eq;
@@ -681,4 +797,29 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_html_links_back() -> LemmyResult<()> {
+    let profile = Url::parse("https://lemmy.example/u/alice")?;
+
+    let html = r#"<html><head><link rel="me" href="https://lemmy.example/u/alice"></head></html>"#;
+    assert!(html_links_back(html, &profile));
+
+    let html = r#"<a class="profile" rel="nofollow me" href='https://lemmy.example/u/alice'>a</a>"#;
+    assert!(html_links_back(html, &profile));
+
+    // Missing rel="me"
+    let html = r#"<a href="https://lemmy.example/u/alice">alice</a>"#;
+    assert!(!html_links_back(html, &profile));
+
+    // Links to a different profile
+    let html = r#"<a rel="me" href="https://lemmy.example/u/mallory">mallory</a>"#;
+    assert!(!html_links_back(html, &profile));
+
+    // A trailing slash or different case in the domain still counts
+    let html = r#"<a rel="me" href="https://Lemmy.Example/u/alice/">alice</a>"#;
+    assert!(html_links_back(html, &profile));
+
+    Ok(())
+  }
 }
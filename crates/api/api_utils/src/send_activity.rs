@@ -60,6 +60,13 @@ pub enum SendActivityData {
     previous_is_upvote: Option<bool>,
     new_is_upvote: Option<bool>,
   },
+  ReactToComment {
+    object_id: DbUrl,
+    actor: Person,
+    community: Community,
+    emoji: String,
+    add: bool,
+  },
   FollowCommunity(Community, Person, bool),
   FollowMultiCommunity(MultiCommunity, Person, bool),
   AcceptFollower(CommunityId, PersonId),
@@ -5,7 +5,7 @@ use crate::{
 };
 use actix_web::{HttpRequest, http::header::Header};
 use actix_web_httpauth::headers::authorization::{Authorization, Bearer};
-use chrono::{DateTime, Days, Local, TimeZone, Utc};
+use chrono::{DateTime, Days, Local, TimeDelta, TimeZone, Utc};
 use enum_map::{EnumMap, enum_map};
 use lemmy_db_schema::{
   newtypes::{CommunityId, PostId, PostOrCommentId, TagId},
@@ -267,6 +267,40 @@ pub fn check_community_deleted_removed(community: &Community) -> LemmyResult<()>
   Ok(())
 }
 
+/// Read-only communities don't accept new posts, comments or votes, except from mods and admins.
+pub async fn check_community_not_read_only(
+  local_user_view: &LocalUserView,
+  community: &Community,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if community.read_only
+    && is_mod_or_admin(pool, local_user_view, community.id)
+      .await
+      .is_err()
+  {
+    Err(LemmyErrorType::CommunityIsReadOnly)?
+  }
+  Ok(())
+}
+
+/// Some communities only allow members approved by a mod to comment. Mods and admins can always
+/// comment.
+pub async fn check_community_comment_membership(
+  local_user_view: &LocalUserView,
+  community: &Community,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<()> {
+  if community.comments_require_membership
+    && !CommunityActions::is_member(pool, local_user_view.person.id, community.id).await?
+    && is_mod_or_admin(pool, local_user_view, community.id)
+      .await
+      .is_err()
+  {
+    Err(LemmyErrorType::NotACommunityMember)?
+  }
+  Ok(())
+}
+
 /// Check that the given user can perform a mod action in the community.
 ///
 /// In particular it checks that they're an admin or mod, wasn't banned and the community isn't
@@ -332,6 +366,19 @@ pub async fn check_local_vote_mode(
   Ok(())
 }
 
+/// Comments can only be downvoted if neither the site nor the community disabled it.
+pub fn comment_downvotes_enabled(community: &Community, local_site: &LocalSite) -> bool {
+  community.downvotes_enabled && local_site.comment_downvotes != FederationMode::Disable
+}
+
+/// New accounts without any karma need to solve a challenge before each comment, to slow down
+/// spam bots. Only applies if captchas are enabled for the site.
+pub fn comment_challenge_required(person: &Person, local_site: &LocalSite) -> bool {
+  let new_account = person.published_at > Utc::now() - TimeDelta::days(1);
+  let no_karma = person.post_score + person.comment_score <= 0;
+  local_site.captcha_enabled && new_account && no_karma
+}
+
 /// Dont allow bots to do certain actions, like voting
 pub fn check_bot_account(person: &Person) -> LemmyResult<()> {
   if person.bot_account {
@@ -390,6 +437,8 @@ pub fn local_site_rate_limit_to_rate_limit_config(
     ActionType::Comment => (l.comment_max_requests, l.comment_interval_seconds),
     ActionType::Search => (l.search_max_requests, l.search_interval_seconds),
     ActionType::ImportUserSettings => (l.import_user_settings_max_requests, l.import_user_settings_interval_seconds),
+    // Same limits as the user settings backup, but counted separately
+    ActionType::CommunitySettings => (l.import_user_settings_max_requests, l.import_user_settings_interval_seconds),
   }
   .map(|_key, (max_requests, interval)| BucketConfig {
     max_requests: u32::try_from(max_requests).unwrap_or(0),
@@ -937,28 +986,61 @@ pub fn send_webmention(post: Post, community: &Community) {
   };
 }
 
-/// Returns error if new comment exceeds maximum depth.
+/// Returns error if a reply to the given comment would exceed the maximum depth.
 ///
 /// Top-level comments have a path like `0.123` where 123 is the comment id. At the second level
 /// it is `0.123.456`, containing the parent id and current comment id.
-pub fn check_comment_depth(comment: &Comment) -> LemmyResult<()> {
+pub fn check_comment_depth(comment: &Comment, max_depth: usize) -> LemmyResult<()> {
   let path = &comment.path.0;
+  // The path always starts with 0, so its length is the depth of a reply
   let length = path.split('.').count();
-  // Need to increment by one because the path always starts with 0
-  if length > MAX_COMMENT_DEPTH_LIMIT + 1 {
+  if length > max_depth {
     Err(LemmyErrorType::MaxCommentDepthReached)?
   } else {
     Ok(())
   }
 }
 
-pub async fn update_post_tags(
-  post: &Post,
+/// Whether a post is past the community's age limit for new comments. The post's lock flag isn't
+/// changed for this.
+pub fn is_auto_locked(
+  published_at: DateTime<Utc>,
+  auto_lock_after_seconds: Option<i32>,
+  now: DateTime<Utc>,
+) -> bool {
+  auto_lock_after_seconds
+    .is_some_and(|seconds| published_at + TimeDelta::seconds(seconds.into()) < now)
+}
+
+/// Whether the person counts as a new member of a community which holds comments from new members
+/// for approval. Without any thresholds only the first comment is held.
+pub fn is_new_community_member(
+  community: &Community,
+  person: &Person,
+  approved_comments: i64,
+) -> bool {
+  let comment_threshold = match (
+    community.new_member_comment_threshold,
+    community.new_member_account_age_days,
+  ) {
+    (None, None) => Some(1),
+    (threshold, _) => threshold,
+  };
+  let new_account = community
+    .new_member_account_age_days
+    .is_some_and(|days| person.published_at > Utc::now() - TimeDelta::days(days.into()));
+  let few_comments =
+    comment_threshold.is_some_and(|threshold| approved_comments < i64::from(threshold));
+  new_account || few_comments
+}
+
+/// Checks that all the tags belong to the community, before they are applied to its content.
+pub async fn check_community_tags(
+  community_id: CommunityId,
   tag_ids: &[TagId],
   context: &LemmyContext,
 ) -> LemmyResult<()> {
-  // validate tags
-  let community_tags = Tag::read_for_community(&mut context.pool(), post.community_id)
+  let community_tags = Tag::read_for_community(&mut context.pool(), community_id)
     .await?
     .into_iter()
     .map(|t| t.id)
@@ -966,6 +1048,15 @@ pub async fn update_post_tags(
   if !community_tags.is_superset(&tag_ids.iter().copied().collect()) {
     return Err(LemmyErrorType::TagNotInCommunity.into());
   }
+  Ok(())
+}
+
+pub async fn update_post_tags(
+  post: &Post,
+  tag_ids: &[TagId],
+  context: &LemmyContext,
+) -> LemmyResult<()> {
+  check_community_tags(post.community_id, tag_ids, context).await?;
   PostTag::update(&mut context.pool(), post, tag_ids).await?;
   Ok(())
 }
@@ -1053,6 +1144,47 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_process_markdown_url_blocklist() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    LocalSiteUrlBlocklist::replace(&mut context.pool(), vec!["spam.example.com".to_string()])
+      .await?;
+    let slur_regex = slur_regex(&context).await?;
+    let url_blocklist = get_url_blocklist(&context).await?;
+
+    // Comment content with a blocked link is rejected
+    let blocked = "Check out [this](https://spam.example.com/offer)";
+    let res = process_markdown(blocked, &slur_regex, &url_blocklist, &context).await;
+    assert!(res.is_err_and(|e| e.error_type == LemmyErrorType::BlockedUrl));
+
+    // Other links are fine
+    let allowed = "Check out [this](https://lemmy.example.com/post/1)";
+    let res = process_markdown(allowed, &slur_regex, &url_blocklist, &context).await?;
+    assert_eq!(allowed, res);
+
+    LocalSiteUrlBlocklist::replace(&mut context.pool(), vec![]).await?;
+    Ok(())
+  }
+
+  #[test]
+  fn test_auto_lock() {
+    let published_at = Utc::now() - TimeDelta::days(30);
+    let day = 24 * 60 * 60;
+    let at_threshold = published_at + TimeDelta::days(1);
+
+    // Without the community setting posts never auto-lock
+    assert!(!is_auto_locked(published_at, None, Utc::now()));
+
+    // Comments are allowed up to the threshold, and rejected after it
+    assert!(!is_auto_locked(published_at, Some(day), at_threshold));
+    assert!(is_auto_locked(
+      published_at,
+      Some(day),
+      at_threshold + TimeDelta::seconds(1)
+    ));
+  }
+
   #[test]
   fn test_comment_depth() -> LemmyResult<()> {
     let mut comment = Comment {
@@ -1079,20 +1211,35 @@ mod tests {
       unresolved_report_count: 0,
       federation_pending: false,
       locked: false,
+      pending_approval: false,
+      pending_delete_until: None,
+      is_answer: false,
+      scheduled_publish_time_at: None,
+      content_edited_at: None,
+      pending_federation_until: None,
     };
-    assert!(check_comment_depth(&comment).is_ok());
+    assert!(check_comment_depth(&comment, MAX_COMMENT_DEPTH_LIMIT).is_ok());
     comment.path = Ltree("0.123.456".to_string());
-    assert!(check_comment_depth(&comment).is_ok());
+    assert!(check_comment_depth(&comment, MAX_COMMENT_DEPTH_LIMIT).is_ok());
 
-    // build path with items 1 to 50 which is still acceptable
-    let mut path = "0.1.2.3.4.5.6.7.8.9.10.11.12.13.14.15.16.17.18.19.20.21.22.23.24.25.26.27.28.29.30.31.32.33.34.35.36.37.38.39.40.41.42.43.44.45.46.47.48.49.50".to_string();
+    // build path with items 1 to 49, a reply to it is at the maximum depth of 50
+    let mut path = "0.1.2.3.4.5.6.7.8.9.10.11.12.13.14.15.16.17.18.19.20.21.22.23.24.25.26.27.28.29.30.31.32.33.34.35.36.37.38.39.40.41.42.43.44.45.46.47.48.49".to_string();
     comment.path = Ltree(path.clone());
-    assert!(check_comment_depth(&comment).is_ok());
+    assert!(check_comment_depth(&comment, MAX_COMMENT_DEPTH_LIMIT).is_ok());
 
     // add one more item and we exceed the max depth
-    path.push_str(".51");
+    path.push_str(".50");
     comment.path = Ltree(path);
-    assert!(check_comment_depth(&comment).is_err());
+    assert!(check_comment_depth(&comment, MAX_COMMENT_DEPTH_LIMIT).is_err());
+
+    // a lower limit configured by the admin rejects replies earlier
+    comment.path = Ltree("0.123".to_string());
+    assert!(check_comment_depth(&comment, 2).is_ok());
+    comment.path = Ltree("0.123.456".to_string());
+    assert_eq!(
+      Some(LemmyErrorType::MaxCommentDepthReached),
+      check_comment_depth(&comment, 2).err().map(|e| e.error_type)
+    );
     Ok(())
   }
 }
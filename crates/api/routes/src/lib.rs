@@ -1,14 +1,31 @@
 use actix_web::{guard, web::*};
 use lemmy_api::{
   comment::{
+    approve::approve_comment,
     distinguish::distinguish_comment,
+    draft::{delete_comment_draft, get_comment_draft, save_comment_draft},
+    federation_status::get_comment_federation_status,
     like::like_comment,
     list_comment_likes::list_comment_likes,
+    list_pending::list_pending_comments,
     lock::lock_comment,
+    mark_answer::mark_comment_as_answer,
+    move_subtree::move_comment_subtree,
+    pin_in_community::pin_comment_in_community,
+    preview::preview_comment,
+    reaction::{add_comment_reaction, remove_comment_reaction},
     save::save_comment,
   },
   community::{
+    active_users::get_community_active_users,
+    add_member::add_community_member,
     add_mod::add_mod_to_community,
+    automod::{
+      create_community_automod_rule,
+      delete_community_automod_rule,
+      edit_community_automod_rule,
+      list_community_automod_rules,
+    },
     ban::ban_from_community,
     block::user_block_community,
     follow::follow_community,
@@ -19,20 +36,37 @@ use lemmy_api::{
       list::get_pending_follows_list,
     },
     random::get_random_community,
+    settings_backup::{export_community_settings, import_community_settings},
     tag::{create_community_tag, delete_community_tag, update_community_tag},
     transfer::transfer_community,
+    update_comment_sort::update_community_comment_sort,
     update_notifications::update_community_notifications,
   },
   federation::{
-    list_comments::{list_comments, list_comments_slim},
+    list_comments::{
+      get_comment_siblings,
+      get_top_comments_per_post,
+      list_comments,
+      list_comments_slim,
+      list_person_comments_in_community,
+    },
     list_person_content::list_person_content,
     list_posts::list_posts,
     read_community::get_community,
     read_multi_community::read_multi_community,
     read_person::read_person,
+    read_person_karma::read_person_karma_breakdown,
+    resolve_comment::resolve_comment,
     resolve_object::resolve_object,
     search::search,
-    user_settings_backup::{export_settings, import_settings},
+    user_settings_backup::{
+      export_communities_opml,
+      export_settings,
+      export_settings_streaming,
+      get_settings_import_status,
+      import_settings,
+      retry_settings_import,
+    },
   },
   local_user::{
     add_admin::add_admin,
@@ -57,6 +91,7 @@ use lemmy_api::{
       list::list_notifications,
       mark_all_read::mark_all_notifications_read,
       mark_notification_read::mark_notification_as_read,
+      mark_notifications_read::mark_notifications_as_read,
       unread_count::unread_count,
     },
     report_count::report_count,
@@ -69,6 +104,7 @@ use lemmy_api::{
     verify_email::verify_email,
   },
   post::{
+    comment_activity::get_post_comment_activity,
     feature::feature_post,
     get_link_metadata::get_link_metadata,
     hide::hide_post,
@@ -79,26 +115,37 @@ use lemmy_api::{
     mark_read::mark_post_as_read,
     mod_update::mod_update_post,
     save::save_post,
-    update_notifications::update_post_notifications,
+    update_notifications::{mute_post_notifications, update_post_notifications},
   },
   reports::{
-    comment_report::{create::create_comment_report, resolve::resolve_comment_report},
+    comment_report::{
+      create::create_comment_report,
+      resolve::resolve_comment_report,
+      snooze::snooze_comment_report,
+      subtree::report_comment_subtree,
+    },
     community_report::{create::create_community_report, resolve::resolve_community_report},
     post_report::{create::create_post_report, resolve::resolve_post_report},
     private_message_report::{create::create_pm_report, resolve::resolve_pm_report},
-    report_combined::list::list_reports,
+    report_combined::{
+      list::list_reports,
+      mod_queue::get_mod_queue,
+      resolve_all::resolve_all_community_reports,
+    },
   },
   site::{
     admin_allow_instance::admin_allow_instance,
     admin_block_instance::admin_block_instance,
     admin_list_users::admin_list_users,
+    comment_stats::get_comment_stats,
     federated_instances::get_federated_instances,
     list_all_media::list_all_media,
     mod_log::get_mod_log,
     purge::{
       comment::purge_comment,
       community::purge_community,
-      person::purge_person,
+      orphaned_comments::{list_orphaned_comments, purge_orphaned_comments},
+      person::{preview_purge_person, purge_person},
       post::purge_post,
     },
     registration_applications::{
@@ -111,10 +158,13 @@ use lemmy_api::{
 };
 use lemmy_api_crud::{
   comment::{
+    cancel_scheduled::cancel_scheduled_comment,
     create::create_comment,
     delete::delete_comment,
     read::get_comment,
     remove::remove_comment,
+    restore::restore_comment,
+    solve_challenge::solve_comment_challenge,
     update::update_comment,
   },
   community::{
@@ -231,6 +281,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", put().to(update_community))
           .route("", delete().to(delete_community))
           .route("/random", get().to(get_random_community))
+          .route("/active_users", get().to(get_community_active_users))
           .route("/list", get().to(list_communities))
           .route("/follow", post().to(follow_community))
           .route("/report", post().to(create_community_report))
@@ -240,6 +291,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/transfer", post().to(transfer_community))
           .route("/ban_user", post().to(ban_from_community))
           .route("/mod", post().to(add_mod_to_community))
+          .route("/member", post().to(add_community_member))
           .route("/icon", post().to(upload_community_icon))
           .route("/icon", delete().to(delete_community_icon))
           .route("/banner", post().to(upload_community_banner))
@@ -247,7 +299,21 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/tag", post().to(create_community_tag))
           .route("/tag", put().to(update_community_tag))
           .route("/tag", delete().to(delete_community_tag))
+          .service(
+            scope("/settings")
+              .wrap(rate_limit.community_settings())
+              .route("/export", get().to(export_community_settings))
+              .route("/import", post().to(import_community_settings)),
+          )
+          .service(
+            scope("/automod")
+              .route("", post().to(create_community_automod_rule))
+              .route("", put().to(edit_community_automod_rule))
+              .route("", delete().to(delete_community_automod_rule))
+              .route("/list", get().to(list_community_automod_rules)),
+          )
           .route("/notifications", post().to(update_community_notifications))
+          .route("/comment_sort", post().to(update_community_comment_sort))
           .service(
             scope("/pending_follows")
               .route("/count", get().to(get_pending_follows_count))
@@ -293,10 +359,12 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/list", get().to(list_posts))
           .route("/like", post().to(like_post))
           .route("/like/list", get().to(list_post_likes))
+          .route("/comment_activity", get().to(get_post_comment_activity))
           .route("/save", put().to(save_post))
           .route("/report", post().to(create_post_report))
           .route("/report/resolve", put().to(resolve_post_report))
           .route("/notifications", post().to(update_post_notifications))
+          .route("/notifications/mute", post().to(mute_post_notifications))
           .route("/mod_update", put().to(mod_update_post)),
       )
       // Comment
@@ -313,15 +381,39 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("", put().to(update_comment))
           .route("", delete().to(delete_comment))
           .route("/remove", post().to(remove_comment))
+          .route("/restore", post().to(restore_comment))
+          .route("/scheduled/cancel", post().to(cancel_scheduled_comment))
           .route("/distinguish", post().to(distinguish_comment))
+          .route(
+            "/federation_status",
+            get().to(get_comment_federation_status),
+          )
           .route("/like", post().to(like_comment))
           .route("/like/list", get().to(list_comment_likes))
           .route("/save", put().to(save_comment))
+          .route("/draft", get().to(get_comment_draft))
+          .route("/draft", put().to(save_comment_draft))
+          .route("/draft", delete().to(delete_comment_draft))
+          .route("/reaction", post().to(add_comment_reaction))
+          .route("/reaction", delete().to(remove_comment_reaction))
           .route("/lock", post().to(lock_comment))
+          .route("/pin_in_community", post().to(pin_comment_in_community))
+          .route("/mark_as_answer", post().to(mark_comment_as_answer))
+          .route("/move", post().to(move_comment_subtree))
+          .route("/approve", post().to(approve_comment))
+          .route("/preview", post().to(preview_comment))
+          .route("/challenge", post().to(solve_comment_challenge))
+          .route("/pending/list", get().to(list_pending_comments))
           .route("/list", get().to(list_comments))
           .route("/list/slim", get().to(list_comments_slim))
+          .route("/list/person", get().to(list_person_comments_in_community))
+          .route("/list/top_per_post", get().to(get_top_comments_per_post))
+          .route("/siblings", get().to(get_comment_siblings))
+          .route("/resolve", get().to(resolve_comment))
           .route("/report", post().to(create_comment_report))
-          .route("/report/resolve", put().to(resolve_comment_report)),
+          .route("/report/subtree", post().to(report_comment_subtree))
+          .route("/report/resolve", put().to(resolve_comment_report))
+          .route("/report/snooze", put().to(snooze_comment_report)),
       )
       // Private Message
       .service(
@@ -337,7 +429,9 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         scope("/report")
           .wrap(rate_limit.message())
           .route("/count", get().to(report_count))
-          .route("/list", get().to(list_reports)),
+          .route("/list", get().to(list_reports))
+          .route("/mod_queue", get().to(get_mod_queue))
+          .route("/resolve_all", put().to(resolve_all_community_reports)),
       )
       // User
       .service(
@@ -372,6 +466,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
               .route("/list", get().to(list_notifications))
               .route("/mark_as_read/all", post().to(mark_all_notifications_read))
               .route("/mark_as_read", post().to(mark_notification_as_read))
+              .route("/mark_as_read/many", post().to(mark_notifications_as_read))
               .route("/count", get().to(unread_count)),
           )
           .route("", delete().to(delete_account))
@@ -397,12 +492,19 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           .route("/hidden", get().to(list_person_hidden))
           .route("/liked", get().to(list_person_liked))
           .route("/settings/save", put().to(save_user_settings))
+          .route(
+            "/settings/import/status",
+            get().to(get_settings_import_status),
+          )
           // Account settings import / export have a strict rate limit
           .service(
             scope("/settings")
               .wrap(rate_limit.import_user_settings())
               .route("/export", get().to(export_settings))
-              .route("/import", post().to(import_settings)),
+              .route("/export/opml", get().to(export_communities_opml))
+              .route("/export/streaming", get().to(export_settings_streaming))
+              .route("/import", post().to(import_settings))
+              .route("/import/retry", post().to(retry_settings_import)),
           )
           .service(
             resource("/data/export")
@@ -415,6 +517,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
         scope("/person")
           .route("", get().to(read_person))
           .route("/content", get().to(list_person_content))
+          .route("/karma", get().to(read_person_karma_breakdown))
           .route("/note", post().to(user_note_person)),
       )
       // Admin Actions
@@ -433,10 +536,13 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           )
           .service(
             scope("/purge")
+              .route("/person", get().to(preview_purge_person))
               .route("/person", post().to(purge_person))
               .route("/community", post().to(purge_community))
               .route("/post", post().to(purge_post))
-              .route("/comment", post().to(purge_comment)),
+              .route("/comment", post().to(purge_comment))
+              .route("/orphaned_comments", get().to(list_orphaned_comments))
+              .route("/orphaned_comments", post().to(purge_orphaned_comments)),
           )
           .service(
             scope("/tagline")
@@ -447,6 +553,7 @@ pub fn config(cfg: &mut ServiceConfig, rate_limit: &RateLimit) {
           )
           .route("/ban", post().to(ban_from_site))
           .route("/users", get().to(admin_list_users))
+          .route("/comment_stats", get().to(get_comment_stats))
           .service(
             scope("/instance")
               .route("/block", post().to(admin_block_instance))
@@ -27,7 +27,7 @@ use lemmy_api::{
   comment::{like::like_comment, save::save_comment},
   community::{block::user_block_community, follow::follow_community},
   federation::{
-    list_comments::list_comments,
+    list_comments::list_comments_common,
     list_posts::list_posts,
     read_community::get_community,
     resolve_object::resolve_object,
@@ -215,6 +215,7 @@ pub(crate) async fn list_comments_v3(
     type_: type_.map(convert_listing_type),
     sort,
     max_depth,
+    branch_sort: None,
     page_cursor: None,
     limit,
     community_id: community_id.map(|c| CommunityId(c.0)),
@@ -222,10 +223,24 @@ pub(crate) async fn list_comments_v3(
     post_id: post_id.map(|p| PostId(p.0)),
     parent_id: parent_id.map(|p| CommentId(p.0)),
     time_range_seconds: None,
+    created_after: None,
+    include_child_previews: None,
+    leaf_only: None,
+    edited_only: None,
+    edited_after: None,
+    saved_only: None,
+    saved_sort: None,
+    language_mismatch_only: None,
+    needs_translation_only: None,
+    min_community_activity_seconds: None,
+    creator_software: None,
+    show_blocked_instances: None,
+    moddable_only: None,
+    slim: None,
+    tag_id: None,
+    search_term: None,
   };
-  let comments = list_comments(Query(data), context, local_user_view)
-    .await?
-    .0;
+  let comments = list_comments_common(data, &context, local_user_view).await?;
   Ok(Json(GetCommentsResponseV3 {
     comments: comments.into_iter().map(convert_comment_view).collect(),
   }))
@@ -639,6 +654,7 @@ pub(crate) async fn list_communities_v3(
     sort,
     time_range_seconds,
     show_nsfw,
+    include_comment_counts: None,
     page_cursor: None,
     limit,
   };
@@ -17,6 +17,7 @@ use crate::protocol::{
     reject::RejectFollow,
     undo_follow::UndoFollow,
   },
+  reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
   voting::{undo_vote::UndoVote, vote::Vote},
 };
 use activitypub_federation::{config::Data, traits::Activity};
@@ -58,6 +59,8 @@ pub enum AnnouncableActivities {
   CreateOrUpdatePost(CreateOrUpdatePage),
   Vote(Vote),
   UndoVote(UndoVote),
+  EmojiReact(EmojiReact),
+  UndoEmojiReact(UndoEmojiReact),
   Delete(Delete),
   UndoDelete(UndoDelete),
   UpdateCommunity(Box<Update>),
@@ -81,6 +84,8 @@ impl InCommunity for AnnouncableActivities {
       CreateOrUpdatePost(a) => a.community(context).await,
       Vote(a) => a.community(context).await,
       UndoVote(a) => a.object.community(context).await,
+      EmojiReact(a) => a.community(context).await,
+      UndoEmojiReact(a) => a.object.community(context).await,
       Delete(a) => a.community(context).await,
       UndoDelete(a) => a.object.community(context).await,
       UpdateCommunity(a) => a.community(context).await,
@@ -25,7 +25,11 @@ use lemmy_db_schema::{
 };
 use lemmy_db_schema_file::enums::{CommunityFollowerState, CommunityVisibility};
 use lemmy_db_views_community_moderator::CommunityPersonBanView;
-use lemmy_utils::error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError};
+use lemmy_email::community::send_new_pending_follower_email_to_mods;
+use lemmy_utils::{
+  error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError},
+  spawn_try_task,
+};
 use url::Url;
 
 impl Follow {
@@ -127,6 +131,19 @@ impl Activity for Follow {
         };
         let form = CommunityFollowerForm::new(c.id, person.id, follow_state);
         CommunityActions::follow(&mut context.pool(), &form).await?;
+        if follow_state == CommunityFollowerState::ApprovalRequired {
+          let (person_name, community, context) = (person.name.clone(), c.clone(), context.clone());
+          spawn_try_task(async move {
+            send_new_pending_follower_email_to_mods(
+              &person_name,
+              &community,
+              &mut context.pool(),
+              context.settings(),
+            )
+            .await?;
+            Ok(())
+          });
+        }
         if c.visibility == CommunityVisibility::Public {
           AcceptFollow::send(self, context).await?;
         }
@@ -18,6 +18,7 @@ use crate::{
     community::{report::Report, resolve_report::ResolveReport},
     create_or_update::{note::CreateOrUpdateNote, page::CreateOrUpdatePage},
   },
+  reaction::send_reaction_activity,
   voting::send_like_activity,
 };
 use activitypub_federation::{
@@ -57,6 +58,7 @@ pub mod create_or_update;
 pub mod deletion;
 pub mod following;
 pub mod protocol;
+pub mod reaction;
 pub mod voting;
 
 const MOD_ACTION_DEFAULT_REASON: &str = "No reason provided";
@@ -264,6 +266,13 @@ pub async fn match_outgoing_activities(
         )
         .await
       }
+      ReactToComment {
+        object_id,
+        actor,
+        community,
+        emoji,
+        add,
+      } => send_reaction_activity(object_id, actor, community, emoji, add, context).await,
       FollowCommunity(community, person, follow) => {
         send_follow(Either::Left(community.into()), person, follow, &context).await
       }
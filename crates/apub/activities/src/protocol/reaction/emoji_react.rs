@@ -0,0 +1,41 @@
+use crate::post_or_comment_community;
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::{
+  objects::{PostOrComment, comment::ApubComment, community::ApubCommunity, person::ApubPerson},
+  utils::protocol::InCommunity,
+};
+use lemmy_utils::error::LemmyResult;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use url::Url;
+
+/// Emoji reaction to a comment, compatible with Pleroma and Misskey.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiReact {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  pub(crate) object: ObjectId<ApubComment>,
+  #[serde(rename = "type")]
+  pub(crate) kind: EmojiReactType,
+  /// The emoji itself
+  pub(crate) content: String,
+  pub(crate) id: Url,
+  pub(crate) audience: Option<ObjectId<ApubCommunity>>,
+}
+
+#[derive(Clone, Debug, Display, Deserialize, Serialize, PartialEq, Eq)]
+pub enum EmojiReactType {
+  EmojiReact,
+}
+
+impl InCommunity for EmojiReact {
+  async fn community(&self, context: &Data<LemmyContext>) -> LemmyResult<ApubCommunity> {
+    if let Some(audience) = &self.audience {
+      return audience.dereference(context).await;
+    }
+    let comment = self.object.dereference(context).await?;
+    let community = post_or_comment_community(&PostOrComment::Right(comment), context).await?;
+    Ok(community.into())
+  }
+}
@@ -0,0 +1,20 @@
+pub mod emoji_react;
+pub mod undo_emoji_react;
+
+#[cfg(test)]
+mod tests {
+  use crate::protocol::reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact};
+  use lemmy_apub_objects::utils::test::test_parse_lemmy_item;
+  use lemmy_utils::error::LemmyResult;
+
+  #[test]
+  fn test_parse_lemmy_reaction() -> LemmyResult<()> {
+    test_parse_lemmy_item::<EmojiReact>(
+      "../apub/assets/lemmy/activities/reaction/emoji_react.json",
+    )?;
+    test_parse_lemmy_item::<UndoEmojiReact>(
+      "../apub/assets/lemmy/activities/reaction/undo_emoji_react.json",
+    )?;
+    Ok(())
+  }
+}
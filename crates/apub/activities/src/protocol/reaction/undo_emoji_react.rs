@@ -0,0 +1,16 @@
+use super::emoji_react::EmojiReact;
+use activitypub_federation::{fetch::object_id::ObjectId, kinds::activity::UndoType};
+use lemmy_apub_objects::objects::{community::ApubCommunity, person::ApubPerson};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoEmojiReact {
+  pub(crate) actor: ObjectId<ApubPerson>,
+  pub(crate) object: EmojiReact,
+  #[serde(rename = "type")]
+  pub(crate) kind: UndoType,
+  pub(crate) id: Url,
+  pub(crate) audience: Option<ObjectId<ApubCommunity>>,
+}
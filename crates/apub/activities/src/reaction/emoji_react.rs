@@ -0,0 +1,68 @@
+use crate::{
+  generate_activity_id,
+  protocol::reaction::emoji_react::{EmojiReact, EmojiReactType},
+  reaction::check_reaction_allowed,
+};
+use activitypub_federation::{
+  config::Data,
+  fetch::object_id::ObjectId,
+  traits::{Activity, Object},
+};
+use lemmy_api_utils::{context::LemmyContext, utils::check_bot_account};
+use lemmy_apub_objects::{
+  objects::{comment::ApubComment, community::ApubCommunity, person::ApubPerson},
+  utils::{functions::verify_person_in_community, protocol::InCommunity},
+};
+use lemmy_db_schema::source::comment_reaction::{CommentReaction, CommentReactionForm};
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use url::Url;
+
+impl EmojiReact {
+  pub(in crate::reaction) fn new(
+    object_id: ObjectId<ApubComment>,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    emoji: String,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<EmojiReact> {
+    Ok(EmojiReact {
+      actor: actor.id().clone().into(),
+      object: object_id,
+      kind: EmojiReactType::EmojiReact,
+      content: emoji,
+      id: generate_activity_id(EmojiReactType::EmojiReact, context)?,
+      audience: Some(community.ap_id.clone().into()),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl Activity for EmojiReact {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let community = self.community(context).await?;
+    verify_person_in_community(&self.actor, &community, context).await?;
+    check_reaction_allowed(&self.content, context)?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let actor = self.actor.dereference(context).await?;
+    let comment = self.object.dereference(context).await?;
+    check_bot_account(&actor.0)?;
+
+    let form = CommentReactionForm::new(comment.id, actor.id, self.content);
+    CommentReaction::add(&mut context.pool(), &form).await?;
+    Ok(())
+  }
+}
@@ -0,0 +1,58 @@
+use crate::{
+  activity_lists::AnnouncableActivities,
+  community::send_activity_in_community,
+  protocol::reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+};
+use activitypub_federation::{config::Data, fetch::object_id::ObjectId};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::objects::{
+  comment::ApubComment,
+  community::ApubCommunity,
+  person::ApubPerson,
+};
+use lemmy_db_schema::source::{
+  activity::ActivitySendTargets,
+  community::Community,
+  person::Person,
+};
+use lemmy_diesel_utils::dburl::DbUrl;
+use lemmy_utils::error::{LemmyErrorType, LemmyResult};
+
+pub mod emoji_react;
+pub mod undo_emoji_react;
+
+pub(crate) async fn send_reaction_activity(
+  object_id: DbUrl,
+  actor: Person,
+  community: Community,
+  emoji: String,
+  add: bool,
+  context: Data<LemmyContext>,
+) -> LemmyResult<()> {
+  let object_id: ObjectId<ApubComment> = object_id.into();
+  let actor: ApubPerson = actor.into();
+  let community: ApubCommunity = community.into();
+
+  let empty = ActivitySendTargets::empty();
+  let react = EmojiReact::new(object_id, &actor, &community, emoji, &context)?;
+  let activity = if add {
+    AnnouncableActivities::EmojiReact(react)
+  } else {
+    let undo = UndoEmojiReact::new(react, &actor, &community, &context)?;
+    AnnouncableActivities::UndoEmojiReact(undo)
+  };
+  send_activity_in_community(activity, &actor, &community, empty, false, &context).await
+}
+
+/// Only accept reactions which are also allowed for local users.
+fn check_reaction_allowed(emoji: &str, context: &Data<LemmyContext>) -> LemmyResult<()> {
+  if !context
+    .settings()
+    .allowed_comment_reactions
+    .iter()
+    .any(|e| e == emoji)
+  {
+    Err(LemmyErrorType::ReactionNotAllowed)?
+  }
+  Ok(())
+}
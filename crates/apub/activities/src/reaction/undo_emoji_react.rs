@@ -0,0 +1,64 @@
+use crate::{
+  generate_activity_id,
+  protocol::reaction::{emoji_react::EmojiReact, undo_emoji_react::UndoEmojiReact},
+};
+use activitypub_federation::{
+  config::Data,
+  kinds::activity::UndoType,
+  protocol::verification::verify_urls_match,
+  traits::{Activity, Object},
+};
+use lemmy_api_utils::context::LemmyContext;
+use lemmy_apub_objects::{
+  objects::{community::ApubCommunity, person::ApubPerson},
+  utils::{functions::verify_person_in_community, protocol::InCommunity},
+};
+use lemmy_db_schema::source::comment_reaction::{CommentReaction, CommentReactionForm};
+use lemmy_utils::error::{LemmyError, LemmyResult};
+use url::Url;
+
+impl UndoEmojiReact {
+  pub(in crate::reaction) fn new(
+    react: EmojiReact,
+    actor: &ApubPerson,
+    community: &ApubCommunity,
+    context: &Data<LemmyContext>,
+  ) -> LemmyResult<Self> {
+    Ok(UndoEmojiReact {
+      actor: actor.id().clone().into(),
+      object: react,
+      kind: UndoType::Undo,
+      id: generate_activity_id(UndoType::Undo, context)?,
+      audience: Some(community.ap_id.clone().into()),
+    })
+  }
+}
+
+#[async_trait::async_trait]
+impl Activity for UndoEmojiReact {
+  type DataType = LemmyContext;
+  type Error = LemmyError;
+
+  fn id(&self) -> &Url {
+    &self.id
+  }
+
+  fn actor(&self) -> &Url {
+    self.actor.inner()
+  }
+
+  async fn verify(&self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let community = self.object.community(context).await?;
+    verify_person_in_community(&self.actor, &community, context).await?;
+    verify_urls_match(self.actor.inner(), self.object.actor.inner())?;
+    Ok(())
+  }
+
+  async fn receive(self, context: &Data<LemmyContext>) -> LemmyResult<()> {
+    let actor = self.actor.dereference(context).await?;
+    let comment = self.object.object.dereference(context).await?;
+    let form = CommentReactionForm::new(comment.id, actor.id, self.object.content);
+    CommentReaction::remove(&mut context.pool(), &form).await?;
+    Ok(())
+  }
+}
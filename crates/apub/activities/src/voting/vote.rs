@@ -11,7 +11,10 @@ use activitypub_federation::{
 use lemmy_api_utils::{context::LemmyContext, utils::check_bot_account};
 use lemmy_apub_objects::{
   objects::{PostOrComment, community::ApubCommunity, person::ApubPerson},
-  utils::{functions::verify_person_in_community, protocol::InCommunity},
+  utils::{
+    functions::{verify_community_not_read_only, verify_person_in_community},
+    protocol::InCommunity,
+  },
 };
 use lemmy_db_schema_file::enums::FederationMode;
 use lemmy_db_views_site::SiteView;
@@ -52,6 +55,10 @@ impl Activity for Vote {
   async fn verify(&self, context: &Data<LemmyContext>) -> LemmyResult<()> {
     let community = self.community(context).await?;
     verify_person_in_community(&self.actor, &community, context).await?;
+    if community.read_only {
+      let actor = self.actor.dereference(context).await?;
+      verify_community_not_read_only(actor.id, &community, context).await?;
+    }
     Ok(())
   }
 
@@ -75,8 +82,12 @@ impl Activity for Vote {
     // Don't allow dislikes for either disabled, or local only votes
     let downvote_fail = self.kind == VoteType::Dislike && downvote_setting != FederationMode::All;
     let upvote_fail = self.kind == VoteType::Like && upvote_setting != FederationMode::All;
+    // Communities can also disable comment downvotes
+    let community_downvote_fail = self.kind == VoteType::Dislike
+      && matches!(object, PostOrComment::Right(_))
+      && !self.community(context).await?.downvotes_enabled;
 
-    if downvote_fail || upvote_fail {
+    if downvote_fail || upvote_fail || community_downvote_fail {
       // If this is a rejection, undo the vote
       match object {
         PostOrComment::Left(p) => undo_vote_post(actor, &p, context).await,
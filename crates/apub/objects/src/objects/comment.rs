@@ -7,6 +7,7 @@ use crate::{
       context_url,
       generate_to,
       read_from_string_or_source,
+      verify_community_not_read_only,
       verify_person_in_community,
       verify_visibility,
     },
@@ -32,6 +33,7 @@ use lemmy_api_utils::{
     check_comment_depth,
     check_is_mod_or_admin,
     get_url_blocklist,
+    is_auto_locked,
     process_markdown,
     slur_regex,
   },
@@ -44,6 +46,7 @@ use lemmy_db_schema::source::{
 };
 use lemmy_diesel_utils::traits::Crud;
 use lemmy_utils::{
+  MAX_COMMENT_DEPTH_LIMIT,
   error::{LemmyError, LemmyResult, UntranslatedError},
   utils::markdown::markdown_to_html,
 };
@@ -177,11 +180,23 @@ impl Object for ApubComment {
     let (post, parent_comment) = Box::pin(note.get_parents(context)).await?;
     let creator = Box::pin(note.attributed_to.dereference(context)).await?;
 
+    // Edits of existing comments are still accepted
+    let is_new = note.id.dereference_local(context).await.is_err();
+    if community.read_only && is_new {
+      verify_community_not_read_only(creator.id, &community, context).await?;
+    }
+
     let is_mod_or_admin = check_is_mod_or_admin(&mut context.pool(), creator.id, community.id)
       .await
       .is_ok();
     let locked = post.locked || parent_comment.is_some_and(|c| c.locked);
-    if locked && !is_mod_or_admin {
+    let auto_locked = is_new
+      && is_auto_locked(
+        post.published_at,
+        community.auto_lock_posts_after_seconds,
+        Utc::now(),
+      );
+    if (locked || auto_locked) && !is_mod_or_admin {
       Err(UntranslatedError::PostIsLocked)?
     } else {
       Ok(())
@@ -195,7 +210,7 @@ impl Object for ApubComment {
     let creator = note.attributed_to.dereference(context).await?;
     let (post, parent_comment) = note.get_parents(context).await?;
     if let Some(c) = &parent_comment {
-      check_comment_depth(c)?;
+      check_comment_depth(c, MAX_COMMENT_DEPTH_LIMIT)?;
     }
 
     let content = read_from_string_or_source(&note.content, &note.media_type, &note.source);
@@ -224,6 +239,11 @@ impl Object for ApubComment {
       language_id,
       federation_pending: Some(false),
       locked: None,
+      pending_approval: None,
+      pending_delete_until: None,
+      scheduled_publish_time_at: None,
+      content_edited_at: note.updated,
+      pending_federation_until: None,
     };
     form = plugin_hook_before("federated_comment_before_receive", form).await?;
     let parent_comment_path = parent_comment.map(|t| t.0.path);
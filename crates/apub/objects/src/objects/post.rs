@@ -16,6 +16,7 @@ use crate::{
       context_url,
       generate_to,
       read_from_string_or_source_opt,
+      verify_community_not_read_only,
       verify_person_in_community,
       verify_visibility,
     },
@@ -222,6 +223,10 @@ impl Object for ApubPost {
       )
       .await?;
     }
+    // Edits of existing posts are still accepted
+    if community.read_only && page.id.dereference_local(context).await.is_err() {
+      verify_community_not_read_only(creator.id, &community, context).await?;
+    }
     let mut name = page
       .name
       .clone()
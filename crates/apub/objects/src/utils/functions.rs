@@ -17,14 +17,17 @@ use lemmy_db_schema::source::{
   instance::{Instance, InstanceActions},
   local_site::LocalSite,
 };
-use lemmy_db_schema_file::enums::{ActorType, CommunityVisibility};
+use lemmy_db_schema_file::{
+  PersonId,
+  enums::{ActorType, CommunityVisibility},
+};
 use lemmy_db_views_community_moderator::CommunityPersonBanView;
 use lemmy_db_views_site::SiteView;
 use lemmy_diesel_utils::connection::DbPool;
 use lemmy_utils::{
   CACHE_DURATION_FEDERATION,
   CacheLock,
-  error::{LemmyError, LemmyResult, UntranslatedError},
+  error::{LemmyError, LemmyErrorType, LemmyResult, UntranslatedError},
 };
 use moka::future::Cache;
 use std::sync::{Arc, LazyLock};
@@ -221,6 +224,24 @@ pub async fn verify_person_in_community(
   CommunityPersonBanView::check(&mut context.pool(), person_id, community_id).await
 }
 
+/// Local read-only communities don't accept new content or votes from remote users, except from
+/// mods and admins.
+pub async fn verify_community_not_read_only(
+  person_id: PersonId,
+  community: &ApubCommunity,
+  context: &Data<LemmyContext>,
+) -> LemmyResult<()> {
+  if community.local
+    && community.read_only
+    && check_is_mod_or_admin(&mut context.pool(), person_id, community.id)
+      .await
+      .is_err()
+  {
+    Err(LemmyErrorType::CommunityIsReadOnly)?
+  }
+  Ok(())
+}
+
 /// Fetches the person and community or site to verify their type, then checks if person is banned
 /// from local site or community.
 pub async fn verify_person_in_site_or_community(
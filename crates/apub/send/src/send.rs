@@ -65,7 +65,7 @@ impl Ord for SendSuccessInfo {
 /// 5. It simplifies concurrency management and makes the flow of data more predictable.
 pub(crate) enum SendActivityResult {
   Success(SendSuccessInfo),
-  Failure { fail_count: i32 },
+  Failure { fail_count: i32, error: String },
 }
 /// Represents a task for retrying to send an activity.
 ///
@@ -128,6 +128,7 @@ impl SendRetryTask<'_> {
         fail_count += 1;
         report.send(SendActivityResult::Failure {
           fail_count,
+          error: e.to_string(),
           // activity_id: activity.id,
         })?;
         let retry_delay = federate_retry_sleep_duration(fail_count);
@@ -274,7 +274,7 @@ impl InstanceWorker {
           }
           self.successfuls.push(s);
         }
-        SendActivityResult::Failure { fail_count, .. } => {
+        SendActivityResult::Failure { fail_count, error } => {
           if fail_count > self.state.fail_count {
             // override fail count - if multiple activities are currently sending this value may get
             // conflicting info but that's fine.
@@ -285,6 +285,7 @@ impl InstanceWorker {
 
             self.state.fail_count = fail_count;
             self.state.last_retry_at = Some(Utc::now());
+            self.state.last_error = Some(error);
             force_write = true;
           }
         }
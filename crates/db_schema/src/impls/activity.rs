@@ -3,7 +3,13 @@ use crate::{
   newtypes::ActivityId,
   source::activity::{ReceivedActivity, SentActivity, SentActivityForm},
 };
-use diesel::{ExpressionMethods, QueryDsl, dsl::insert_into};
+use chrono::{DateTime, Utc};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  dsl::{insert_into, sql},
+  sql_types::{Bool, Text},
+};
 use diesel_async::RunQueryDsl;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -40,6 +46,32 @@ impl SentActivity {
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
+
+  /// All activities sent since the given time which have the given object, either directly or
+  /// wrapped in an undo or announce. Oldest first. The object ids are matched with the same
+  /// expression as in the index `idx_sent_activity_object_ids`, so that it can be used.
+  pub async fn list_for_object(
+    pool: &mut DbPool<'_>,
+    object_id: &DbUrl,
+    since: DateTime<Utc>,
+  ) -> LemmyResult<Vec<Self>> {
+    use lemmy_db_schema_file::schema::sent_activity::dsl::{id, published_at, sent_activity};
+    let conn = &mut get_conn(pool).await?;
+    sent_activity
+      .filter(published_at.ge(since))
+      .filter(
+        sql::<Bool>(
+          "ARRAY[data ->> 'object', (data -> 'object') ->> 'id', (data -> 'object') ->> 'object', \
+           ((data -> 'object') -> 'object') ->> 'id'] @> ARRAY[",
+        )
+        .bind::<Text, _>(object_id)
+        .sql("]"),
+      )
+      .order_by(id.asc())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 impl ReceivedActivity {
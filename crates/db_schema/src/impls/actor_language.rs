@@ -28,7 +28,10 @@ use lemmy_db_schema_file::{
   schema::{community_language, local_site, local_user_language, site, site_language},
 };
 use lemmy_diesel_utils::connection::{DbPool, get_conn};
-use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  COMMUNITY_INLINE_LANGUAGES_LIMIT,
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+};
 use tokio::sync::OnceCell;
 
 pub const UNDETERMINED_ID: LanguageId = LanguageId(0);
@@ -254,6 +257,8 @@ impl CommunityLanguage {
     convert_read_languages(conn, langs).await
   }
 
+  /// Fails if there are more languages than can be returned inline with the community, unless
+  /// all languages are allowed.
   pub async fn update(
     pool: &mut DbPool<'_>,
     mut language_ids: Vec<LanguageId>,
@@ -264,6 +269,11 @@ impl CommunityLanguage {
     }
     let conn = &mut get_conn(pool).await?;
     let lang_ids = convert_update_languages(conn, language_ids).await?;
+    if convert_read_languages(conn, lang_ids.clone()).await?.len()
+      > COMMUNITY_INLINE_LANGUAGES_LIMIT
+    {
+      Err(LemmyErrorType::TooManyItems)?
+    }
 
     // No need to update if languages are unchanged
     let current = CommunityLanguage::read(&mut conn.into(), for_community_id).await?;
@@ -538,6 +548,25 @@ mod tests {
     let community_langs3 = CommunityLanguage::read(pool, community.id).await?;
     assert_eq!(test_langs2, community_langs3);
 
+    // too many languages are rejected, unless all of them are allowed
+    let mut all_langs = Language::read_all(pool)
+      .await?
+      .into_iter()
+      .map(|l| l.id)
+      .collect::<Vec<_>>();
+    let update = CommunityLanguage::update(pool, all_langs.clone(), community.id).await;
+    assert!(update.is_ok());
+    all_langs.pop();
+    let update = CommunityLanguage::update(pool, all_langs, community.id).await;
+    assert_eq!(
+      Some(LemmyErrorType::TooManyItems),
+      update.err().map(|e| e.error_type)
+    );
+    assert_eq!(
+      Vec::<LanguageId>::new(),
+      CommunityLanguage::read(pool, community.id).await?
+    );
+
     Community::delete(pool, community.id).await?;
     LocalSite::delete(pool).await?;
     data.delete(pool).await?;
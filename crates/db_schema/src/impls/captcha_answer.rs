@@ -7,6 +7,10 @@ use lemmy_diesel_utils::{
   utils::functions::lower,
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use uuid::Uuid;
+
+/// Stored as answer for solved comment challenges. Generated captchas never have this answer.
+const SOLVED_COMMENT_CHALLENGE: &str = "solved_comment_challenge";
 
 impl CaptchaAnswer {
   pub async fn insert(pool: &mut DbPool<'_>, captcha: &CaptchaAnswerForm) -> LemmyResult<Self> {
@@ -42,6 +46,33 @@ impl CaptchaAnswer {
       .then_some(())
       .ok_or(LemmyErrorType::CaptchaIncorrect.into())
   }
+
+  /// Checks the captcha answer and returns a single-use token for creating a comment. The token
+  /// expires together with other captchas.
+  pub async fn solve_comment_challenge(
+    pool: &mut DbPool<'_>,
+    to_check: CheckCaptchaAnswer,
+  ) -> LemmyResult<Uuid> {
+    Self::check_captcha(pool, to_check).await?;
+    let form = CaptchaAnswerForm {
+      answer: SOLVED_COMMENT_CHALLENGE.to_string(),
+    };
+    Ok(Self::insert(pool, &form).await?.uuid)
+  }
+
+  /// Consumes a token from `solve_comment_challenge`.
+  pub async fn check_comment_challenge_token(
+    pool: &mut DbPool<'_>,
+    token: Uuid,
+  ) -> LemmyResult<()> {
+    let to_check = CheckCaptchaAnswer {
+      uuid: token,
+      answer: SOLVED_COMMENT_CHALLENGE.to_string(),
+    };
+    Self::check_captcha(pool, to_check)
+      .await
+      .with_lemmy_type(LemmyErrorType::CommentChallengeRequired)
+  }
 }
 
 #[cfg(test)]
@@ -1,39 +1,49 @@
 use crate::{
   diesel::{DecoratableTarget, OptionalExtension},
   newtypes::{CommentId, CommunityId, PostId},
-  source::comment::{
-    Comment,
-    CommentActions,
-    CommentInsertForm,
-    CommentLikeForm,
-    CommentSavedForm,
-    CommentUpdateForm,
+  source::{
+    comment::{
+      Comment,
+      CommentActions,
+      CommentInsertForm,
+      CommentLikeForm,
+      CommentSavedForm,
+      CommentUpdateForm,
+    },
+    modlog::{Modlog, ModlogInsertForm},
   },
   traits::{Likeable, Saveable},
   utils::DELETED_REPLACEMENT_TEXT,
 };
 use chrono::{DateTime, Utc};
 use diesel::{
+  BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
+  NullableExpressionMethods,
   QueryDsl,
-  dsl::{insert_into, not},
+  dsl::{count_star, exists, insert_into, max, not, select},
   expression::SelectableHelper,
+  sql_query,
+  sql_types::{Integer, Text},
   update,
 };
-use diesel_async::RunQueryDsl;
-use diesel_ltree::{Ltree, dsl::LtreeExtensions};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use diesel_ltree::{Ltree, dsl::LtreeExtensions, nlevel, subpath};
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  schema::{comment, comment_actions, community, post},
+  schema::{comment, comment_actions, comment_source, community, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
   traits::Crud,
-  utils::functions::{coalesce, hot_rank},
+  utils::{
+    functions::{coalesce, hot_rank},
+    now,
+  },
 };
 use lemmy_utils::{
   error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
@@ -48,6 +58,16 @@ impl Comment {
   ) -> LemmyResult<Vec<Self>> {
     let conn = &mut get_conn(pool).await?;
 
+    let creator_comments = comment::table
+      .filter(comment::creator_id.eq(creator_id))
+      .select(comment::id);
+    diesel::delete(
+      comment_source::table.filter(comment_source::comment_id.eq_any(creator_comments)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
     diesel::update(comment::table.filter(comment::creator_id.eq(creator_id)))
       .set((
         comment::content.eq(DELETED_REPLACEMENT_TEXT),
@@ -59,6 +79,45 @@ impl Comment {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 
+  /// Number of comments which are purged together with the person. Besides their own comments,
+  /// this includes the comments of others on their posts.
+  pub async fn count_for_creator_purge(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    comment::table
+      .inner_join(post::table)
+      .filter(
+        comment::creator_id
+          .eq(creator_id)
+          .or(post::creator_id.eq(creator_id)),
+      )
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Purges up to `limit` comments of the creator, and returns how many were purged. Call
+  /// repeatedly until it returns zero to purge all of them.
+  pub async fn purge_batch_for_creator(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+    limit: i64,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let batch = comment::table
+      .filter(comment::creator_id.eq(creator_id))
+      .select(comment::id)
+      .order_by(comment::id)
+      .limit(limit);
+    diesel::delete(comment::table.filter(comment::id.eq_any(batch)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
   pub async fn update_removed_for_creator(
     pool: &mut DbPool<'_>,
     creator_id: PersonId,
@@ -254,6 +313,317 @@ impl Comment {
     Self::update_comment_and_children(pool, comment_path, &form).await
   }
 
+  /// Marks the comment as the accepted answer of its post, replacing any previous answer. With
+  /// `answer` false the mark is only removed from this comment.
+  pub async fn mark_as_answer(
+    pool: &mut DbPool<'_>,
+    comment: &Comment,
+    answer: bool,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          if answer {
+            update(
+              comment::table
+                .filter(comment::post_id.eq(comment.post_id))
+                .filter(comment::is_answer),
+            )
+            .set(comment::is_answer.eq(false))
+            .execute(conn)
+            .await?;
+          }
+          update(comment::table.find(comment.id))
+            .set(comment::is_answer.eq(answer))
+            .get_result::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Moves the comment with all its replies to another post, below `new_parent` or at the top
+  /// level. The paths of the whole subtree are rewritten in a single transaction. Returns the moved
+  /// comments, starting with the given one.
+  pub async fn move_subtree(
+    pool: &mut DbPool<'_>,
+    comment: &Comment,
+    target_post_id: PostId,
+    new_parent: Option<&Comment>,
+    max_depth: usize,
+  ) -> LemmyResult<Vec<Self>> {
+    let new_base = match new_parent {
+      Some(parent) => {
+        if parent.post_id != target_post_id {
+          Err(LemmyErrorType::CommentParentNotOnPost)?
+        }
+        if parent.path.0 == comment.path.0
+          || parent.path.0.starts_with(&format!("{}.", comment.path.0))
+        {
+          Err(LemmyErrorType::CannotMoveCommentBelowItself)?
+        }
+        parent.path.0.clone()
+      }
+      None => "0".to_string(),
+    };
+    let levels = |path: &str| path.split('.').count();
+    let root_offset = levels(&comment.path.0) - 1;
+
+    let conn = &mut get_conn(pool).await?;
+    conn
+      .run_transaction(|conn| {
+        async move {
+          // The deepest reply decides if the subtree still fits below the new parent
+          let deepest = comment::table
+            .filter(comment::path.contained_by(&comment.path))
+            .select(max(nlevel(comment::path)))
+            .first::<Option<i32>>(conn)
+            .await?
+            .unwrap_or_default();
+          let new_levels = levels(&new_base) + usize::try_from(deepest)? - root_offset;
+          // The path always starts with 0
+          if new_levels > max_depth + 1 {
+            Err(LemmyErrorType::MaxCommentDepthReached)?
+          }
+
+          // Diesel doesn't support concatenating ltrees, so use raw sql here.
+          sql_query(
+            "UPDATE comment
+            SET path = text2ltree($1) || subpath(path, $2), post_id = $3
+            WHERE path <@ text2ltree($4)",
+          )
+          .bind::<Text, _>(&new_base)
+          .bind::<Integer, _>(i32::try_from(root_offset)?)
+          .bind::<Integer, _>(target_post_id.0)
+          .bind::<Text, _>(&comment.path.0)
+          .execute(conn)
+          .await
+          .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+
+          let new_path = Ltree(format!("{new_base}.{}", comment.id.0));
+          comment::table
+            .filter(comment::path.contained_by(new_path))
+            .order_by((nlevel(comment::path), comment::id))
+            .get_results::<Self>(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::NotFound)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+
+  /// Checks that neither the comment at the given path, nor any of its ancestors is locked. Used
+  /// before creating a reply, so that replies deep inside a locked subtree are rejected too.
+  pub async fn check_not_in_locked_subtree(
+    pool: &mut DbPool<'_>,
+    comment_path: &Ltree,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let locked = select(exists(
+      comment::table
+        .filter(comment::path.contains(comment_path))
+        .filter(comment::locked),
+    ))
+    .get_result::<bool>(conn)
+    .await?;
+    if locked {
+      Err(LemmyErrorType::CommentLocked)?
+    }
+    Ok(())
+  }
+
+  /// Whether any comment was posted below the comment at the given path.
+  pub async fn has_replies(pool: &mut DbPool<'_>, comment_path: &Ltree) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      comment::table
+        .filter(comment::path.contained_by(comment_path))
+        .filter(comment::path.ne(comment_path)),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// The number of comments by the creator in the community which are visible to everyone, ie
+  /// approved and not removed.
+  pub async fn count_approved_in_community(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+    community_id: CommunityId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    comment::table
+      .inner_join(post::table)
+      .filter(comment::creator_id.eq(creator_id))
+      .filter(post::community_id.eq(community_id))
+      .filter(comment::pending_approval.eq(false))
+      .filter(comment::removed.eq(false))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Cancels a deferred delete by the creator. Fails once the grace period is over.
+  pub async fn restore_pending_delete(
+    pool: &mut DbPool<'_>,
+    comment_id: CommentId,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      comment::table
+        .find(comment_id)
+        .filter(comment::pending_delete_until.gt(now().nullable())),
+    )
+    .set(comment::pending_delete_until.eq(None::<DateTime<Utc>>))
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// The number of comments which the person scheduled and which aren't published yet.
+  pub async fn user_scheduled_comment_count(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    comment::table
+      .filter(comment::creator_id.eq(person_id))
+      .filter(comment::scheduled_publish_time_at.gt(now().nullable()))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false))
+      .select(count_star())
+      .first(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Deletes a scheduled comment. Fails once it is published.
+  pub async fn delete_scheduled(pool: &mut DbPool<'_>, comment_id: CommentId) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let deleted = diesel::delete(
+      comment::table
+        .find(comment_id)
+        .filter(comment::scheduled_publish_time_at.gt(now().nullable())),
+    )
+    .execute(conn)
+    .await?;
+    if deleted == 0 {
+      Err(LemmyErrorType::CommentAlreadyPublished)?
+    }
+    Ok(())
+  }
+
+  /// Stores the markdown which the creator submitted for a comment, before it was processed.
+  pub async fn upsert_content_source(
+    pool: &mut DbPool<'_>,
+    comment_id: CommentId,
+    content: &str,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_source::table)
+      .values((
+        comment_source::comment_id.eq(comment_id),
+        comment_source::content.eq(content),
+      ))
+      .on_conflict(comment_source::comment_id)
+      .do_update()
+      .set(comment_source::content.eq(content))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(())
+  }
+
+  /// Lists comments whose post doesn't exist anymore, or whose parent comment was purged. Only the
+  /// topmost orphan of each comment tree is returned, as its replies still have a parent.
+  pub async fn list_orphaned(pool: &mut DbPool<'_>, limit: i64) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let parent = diesel::alias!(comment as parent);
+    let parent_exists = parent.filter(parent.field(comment::path).eq(subpath(
+      comment::path,
+      0,
+      -1,
+    )));
+    comment::table
+      .left_join(post::table)
+      .filter(
+        post::id
+          .nullable()
+          .is_null()
+          .or(nlevel(comment::path).gt(2).and(not(exists(parent_exists)))),
+      )
+      .select(Self::as_select())
+      .order_by(comment::id)
+      .limit(limit)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Purges all orphaned comments together with their replies, in batches which each run in a
+  /// single transaction. Each purged orphan whose post still exists gets a modlog entry. Returns
+  /// the number of purged comments.
+  pub async fn purge_orphaned(
+    pool: &mut DbPool<'_>,
+    admin_person_id: PersonId,
+    reason: &str,
+  ) -> LemmyResult<usize> {
+    const BATCH_SIZE: i64 = 100;
+    let conn = &mut get_conn(pool).await?;
+    let mut purged = 0;
+    loop {
+      let batch_purged = conn
+        .run_transaction(|conn| {
+          async move {
+            let orphans = Self::list_orphaned(&mut conn.into(), BATCH_SIZE).await?;
+            let post_communities = post::table
+              .filter(post::id.eq_any(orphans.iter().map(|c| c.post_id)))
+              .select((post::id, post::community_id))
+              .load::<(PostId, CommunityId)>(conn)
+              .await?;
+
+            let mut batch_purged = 0;
+            let mut forms = vec![];
+            for orphan in &orphans {
+              batch_purged +=
+                diesel::delete(comment::table.filter(comment::path.contained_by(&orphan.path)))
+                  .execute(conn)
+                  .await
+                  .with_lemmy_type(LemmyErrorType::Deleted)?;
+              if let Some((_, community_id)) = post_communities
+                .iter()
+                .find(|(post_id, _)| *post_id == orphan.post_id)
+              {
+                forms.push(ModlogInsertForm::admin_purge_comment(
+                  admin_person_id,
+                  orphan,
+                  *community_id,
+                  reason,
+                ));
+              }
+            }
+            if !forms.is_empty() {
+              Modlog::create(&mut conn.into(), &forms).await?;
+            }
+            Ok(batch_purged)
+          }
+          .scope_boxed()
+        })
+        .await?;
+      if batch_purged == 0 {
+        break;
+      }
+      purged += batch_purged;
+    }
+    Ok(purged)
+  }
+
   /// A helper function to update comment and all its children.
   ///
   /// Don't expose so as to make sure you aren't overwriting data.
@@ -403,6 +773,20 @@ impl Saveable for CommentActions {
 }
 
 impl CommentActions {
+  /// Like [Saveable::save], but keeps the time of an existing save.
+  pub async fn save_if_new(pool: &mut DbPool<'_>, form: &CommentSavedForm) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_actions::table)
+      .values(form)
+      .on_conflict((comment_actions::comment_id, comment_actions::person_id))
+      .do_update()
+      .set(form)
+      .filter(comment_actions::saved_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
   pub async fn read(
     pool: &mut DbPool<'_>,
     comment_id: CommentId,
@@ -502,6 +886,12 @@ mod tests {
       unresolved_report_count: 0,
       federation_pending: false,
       locked: false,
+      pending_approval: false,
+      pending_delete_until: None,
+      is_answer: false,
+      scheduled_publish_time_at: None,
+      content_edited_at: None,
+      pending_federation_until: None,
     };
 
     let child_comment_form = CommentInsertForm::new(
@@ -716,4 +1106,252 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_locked_subtree() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "mydomain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "lockie");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "test_lock".into(),
+      "test_lock".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+    let new_post = PostInsertForm::new(
+      "Post Title".to_string(),
+      inserted_person.id,
+      inserted_community.id,
+    );
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let form = CommentInsertForm::new(inserted_person.id, inserted_post.id, "Top".to_string());
+    let locked_top = Comment::create(pool, &form, None).await?;
+    let child = Comment::create(pool, &form, Some(&locked_top.path)).await?;
+    let sibling_top = Comment::create(pool, &form, None).await?;
+
+    // Only lock the top level comment itself, to make sure that ancestors are checked
+    let lock_form = CommentUpdateForm {
+      locked: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, locked_top.id, &lock_form).await?;
+
+    // Replies anywhere below the locked comment are rejected
+    let reply_to_locked = Comment::check_not_in_locked_subtree(pool, &locked_top.path).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentLocked),
+      reply_to_locked.err().map(|e| e.error_type)
+    );
+    let reply_to_child = Comment::check_not_in_locked_subtree(pool, &child.path).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentLocked),
+      reply_to_child.err().map(|e| e.error_type)
+    );
+
+    // Other threads are unaffected
+    Comment::check_not_in_locked_subtree(pool, &sibling_top.path).await?;
+
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_mark_as_answer() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "mydomain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "answerer");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "test_answer".into(),
+      "test_answer".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+    let new_post = PostInsertForm::new(
+      "Question".to_string(),
+      inserted_person.id,
+      inserted_community.id,
+    );
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let form = CommentInsertForm::new(inserted_person.id, inserted_post.id, "Reply".to_string());
+    let first = Comment::create(pool, &form, None).await?;
+    let second = Comment::create(pool, &form, None).await?;
+    assert!(!first.is_answer);
+
+    let marked = Comment::mark_as_answer(pool, &first, true).await?;
+    assert!(marked.is_answer);
+
+    // Marking another comment replaces the previous answer
+    let remarked = Comment::mark_as_answer(pool, &second, true).await?;
+    assert!(remarked.is_answer);
+    assert!(!Comment::read(pool, first.id).await?.is_answer);
+
+    // There is at most one answer per post, even when writing directly
+    let duplicate = update(comment::table.find(first.id))
+      .set(comment::is_answer.eq(true))
+      .execute(&mut get_conn(pool).await?)
+      .await;
+    assert!(duplicate.is_err());
+
+    // Unmarking only affects the given comment
+    let unmarked = Comment::mark_as_answer(pool, &second, false).await?;
+    assert!(!unmarked.is_answer);
+    assert!(!Comment::read(pool, first.id).await?.is_answer);
+
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_orphaned_comments() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "mydomain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "orphaner");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "test_orphans".into(),
+      "test_orphans".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+    let new_post = PostInsertForm::new(
+      "Orphans".to_string(),
+      inserted_person.id,
+      inserted_community.id,
+    );
+    let inserted_post = Post::create(pool, &new_post).await?;
+
+    let form = CommentInsertForm::new(inserted_person.id, inserted_post.id, "Text".to_string());
+    let parent = Comment::create(pool, &form, None).await?;
+    let child = Comment::create(pool, &form, Some(&parent.path)).await?;
+    let grandchild = Comment::create(pool, &form, Some(&child.path)).await?;
+    let other = Comment::create(pool, &form, None).await?;
+    assert!(Comment::list_orphaned(pool, 50).await?.is_empty());
+
+    // Deleting the parent directly leaves its replies without a parent. Only the topmost orphan
+    // is listed.
+    Comment::delete(pool, parent.id).await?;
+    let orphans = Comment::list_orphaned(pool, 50).await?;
+    assert_eq!(
+      vec![child.id],
+      orphans.iter().map(|c| c.id).collect::<Vec<_>>()
+    );
+
+    // Purging removes the whole orphaned tree, but nothing else
+    assert_eq!(
+      2,
+      Comment::purge_orphaned(pool, inserted_person.id, "orphaned").await?
+    );
+    assert!(Comment::read(pool, grandchild.id).await.is_err());
+    assert!(Comment::read(pool, other.id).await.is_ok());
+    assert!(Comment::list_orphaned(pool, 50).await?.is_empty());
+
+    Post::delete(pool, inserted_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_move_subtree() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let inserted_instance = Instance::read_or_create(pool, "mydomain.tld").await?;
+    let new_person = PersonInsertForm::test_form(inserted_instance.id, "mover");
+    let inserted_person = Person::create(pool, &new_person).await?;
+    let new_community = CommunityInsertForm::new(
+      inserted_instance.id,
+      "test_move".into(),
+      "test_move".to_owned(),
+      "pubkey".to_string(),
+    );
+    let inserted_community = Community::create(pool, &new_community).await?;
+    let post_form =
+      |name: &str| PostInsertForm::new(name.to_string(), inserted_person.id, inserted_community.id);
+    let post = Post::create(pool, &post_form("Duplicate")).await?;
+    let target_post = Post::create(pool, &post_form("Original")).await?;
+
+    let form = CommentInsertForm::new(inserted_person.id, post.id, "Text".to_string());
+    let parent = Comment::create(pool, &form, None).await?;
+    let child = Comment::create(pool, &form, Some(&parent.path)).await?;
+    let grandchild = Comment::create(pool, &form, Some(&child.path)).await?;
+    let form = CommentInsertForm::new(inserted_person.id, target_post.id, "Text".to_string());
+    let target_parent = Comment::create(pool, &form, None).await?;
+
+    // Move the child with its reply below the comment on the other post
+    let moved =
+      Comment::move_subtree(pool, &child, target_post.id, Some(&target_parent), 10).await?;
+    assert_eq!(
+      vec![
+        (child.id, format!("0.{}.{}", target_parent.id, child.id)),
+        (
+          grandchild.id,
+          format!("0.{}.{}.{}", target_parent.id, child.id, grandchild.id)
+        ),
+      ],
+      moved
+        .iter()
+        .map(|c| (c.id, c.path.0.clone()))
+        .collect::<Vec<_>>()
+    );
+    assert!(moved.iter().all(|c| c.post_id == target_post.id));
+    assert_eq!(1, Post::read(pool, post.id).await?.comments);
+    assert_eq!(3, Post::read(pool, target_post.id).await?.comments);
+    assert_eq!(0, Comment::read(pool, parent.id).await?.child_count);
+    assert_eq!(2, Comment::read(pool, target_parent.id).await?.child_count);
+
+    // A comment can't be moved below its own reply
+    let child = Comment::read(pool, child.id).await?;
+    let grandchild = Comment::read(pool, grandchild.id).await?;
+    let res = Comment::move_subtree(pool, &child, target_post.id, Some(&grandchild), 10).await;
+    assert_eq!(
+      Some(LemmyErrorType::CannotMoveCommentBelowItself),
+      res.err().map(|e| e.error_type)
+    );
+
+    // Three levels of comments don't fit below a top-level comment with a depth limit of three
+    let target_parent = Comment::read(pool, target_parent.id).await?;
+    let res = Comment::move_subtree(pool, &target_parent, post.id, Some(&parent), 3).await;
+    assert_eq!(
+      Some(LemmyErrorType::MaxCommentDepthReached),
+      res.err().map(|e| e.error_type)
+    );
+    // The new parent has to be on the target post
+    let res = Comment::move_subtree(pool, &target_parent, target_post.id, Some(&parent), 10).await;
+    assert_eq!(
+      Some(LemmyErrorType::CommentParentNotOnPost),
+      res.err().map(|e| e.error_type)
+    );
+
+    Post::delete(pool, post.id).await?;
+    Post::delete(pool, target_post.id).await?;
+    Community::delete(pool, inserted_community.id).await?;
+    Person::delete(pool, inserted_person.id).await?;
+
+    Ok(())
+  }
 }
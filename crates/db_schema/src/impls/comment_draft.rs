@@ -0,0 +1,153 @@
+use crate::{
+  newtypes::{CommentId, PostId},
+  source::comment_draft::{CommentDraft, CommentDraftForm},
+};
+use diesel::{
+  ExpressionMethods,
+  OptionalExtension,
+  PgExpressionMethods,
+  QueryDsl,
+  SelectableHelper,
+  delete,
+  insert_into,
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{PersonId, schema::comment_draft};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommentDraft {
+  /// Saves the draft, replacing any previous draft for the same post and parent comment.
+  pub async fn save(pool: &mut DbPool<'_>, form: &CommentDraftForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_draft::table)
+      .values(form)
+      .on_conflict((
+        comment_draft::person_id,
+        comment_draft::post_id,
+        comment_draft::parent_id,
+      ))
+      .do_update()
+      .set(form)
+      .returning(Self::as_select())
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn read(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_id: PostId,
+    parent_id: Option<CommentId>,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    comment_draft::table
+      .filter(comment_draft::person_id.eq(person_id))
+      .filter(comment_draft::post_id.eq(post_id))
+      .filter(comment_draft::parent_id.is_not_distinct_from(parent_id))
+      .select(Self::as_select())
+      .first(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn delete(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    post_id: PostId,
+    parent_id: Option<CommentId>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(
+      comment_draft::table
+        .filter(comment_draft::person_id.eq(person_id))
+        .filter(comment_draft::post_id.eq(post_id))
+        .filter(comment_draft::parent_id.is_not_distinct_from(parent_id)),
+    )
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::source::{
+    comment::{Comment, CommentInsertForm},
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_drafts() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "draft_sara");
+    let sara = Person::create(pool, &person_form).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "draft_tom");
+    let tom = Person::create(pool, &person_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "draft community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("A test post".into(), sara.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(tom.id, post.id, "A test comment".into());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    assert_eq!(
+      None,
+      CommentDraft::read(pool, sara.id, post.id, None).await?
+    );
+
+    let form = CommentDraftForm::new(sara.id, post.id, None, "first".to_string());
+    let first = CommentDraft::save(pool, &form).await?;
+    let form = CommentDraftForm::new(sara.id, post.id, Some(comment.id), "reply".to_string());
+    let reply = CommentDraft::save(pool, &form).await?;
+
+    // Saving again overwrites the draft for the same parent
+    let form = CommentDraftForm::new(sara.id, post.id, None, "second".to_string());
+    let second = CommentDraft::save(pool, &form).await?;
+    assert_eq!(first.id, second.id);
+    assert_eq!("second", second.content);
+    assert!(second.updated_at >= first.updated_at);
+
+    let read = CommentDraft::read(pool, sara.id, post.id, None).await?;
+    assert_eq!(Some(second), read);
+    let read = CommentDraft::read(pool, sara.id, post.id, Some(comment.id)).await?;
+    assert_eq!(Some(reply), read);
+    // Drafts are per person
+    assert_eq!(None, CommentDraft::read(pool, tom.id, post.id, None).await?);
+
+    assert_eq!(1, CommentDraft::delete(pool, sara.id, post.id, None).await?);
+    assert_eq!(
+      None,
+      CommentDraft::read(pool, sara.id, post.id, None).await?
+    );
+    assert!(
+      CommentDraft::read(pool, sara.id, post.id, Some(comment.id))
+        .await?
+        .is_some()
+    );
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,203 @@
+use crate::{
+  newtypes::CommentId,
+  source::comment_reaction::{
+    CommentReaction,
+    CommentReactionCount,
+    CommentReactionForm,
+    CommentReactionsView,
+  },
+};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  delete,
+  deserialize::FromSql,
+  dsl::count_star,
+  insert_into,
+  pg::{Pg, PgValue},
+  serialize::ToSql,
+  sql_types::{Json, Nullable},
+};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{PersonId, schema::comment_reaction};
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommentReaction {
+  /// Adds a reaction. Adding the same reaction again does nothing.
+  pub async fn add(pool: &mut DbPool<'_>, form: &CommentReactionForm) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_reaction::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn remove(pool: &mut DbPool<'_>, form: &CommentReactionForm) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(comment_reaction::table.find((form.comment_id, form.person_id, &form.emoji)))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// The reactions for each of the given comments, most frequent first.
+  pub async fn read_for_comments(
+    pool: &mut DbPool<'_>,
+    comment_ids: &[CommentId],
+    my_person_id: Option<PersonId>,
+  ) -> LemmyResult<Vec<(CommentId, CommentReactionCount)>> {
+    let conn = &mut get_conn(pool).await?;
+    let counts = comment_reaction::table
+      .filter(comment_reaction::comment_id.eq_any(comment_ids))
+      .group_by((comment_reaction::comment_id, comment_reaction::emoji))
+      .select((
+        comment_reaction::comment_id,
+        comment_reaction::emoji,
+        count_star(),
+      ))
+      .order_by((
+        comment_reaction::comment_id,
+        count_star().desc(),
+        comment_reaction::emoji,
+      ))
+      .load::<(CommentId, String, i64)>(conn)
+      .await?;
+
+    let mine = if let Some(my_person_id) = my_person_id {
+      comment_reaction::table
+        .filter(comment_reaction::comment_id.eq_any(comment_ids))
+        .filter(comment_reaction::person_id.eq(my_person_id))
+        .select((comment_reaction::comment_id, comment_reaction::emoji))
+        .load::<(CommentId, String)>(conn)
+        .await?
+    } else {
+      vec![]
+    };
+
+    Ok(
+      counts
+        .into_iter()
+        .map(|(comment_id, emoji, count)| {
+          let viewer_reacted = mine.contains(&(comment_id, emoji.clone()));
+          let reaction = CommentReactionCount {
+            emoji,
+            count,
+            viewer_reacted,
+          };
+          (comment_id, reaction)
+        })
+        .collect(),
+    )
+  }
+}
+
+impl FromSql<Nullable<Json>, Pg> for CommentReactionsView {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    let value = <serde_json::Value as FromSql<Json, Pg>>::from_sql(bytes)?;
+    Ok(serde_json::from_value::<CommentReactionsView>(value)?)
+  }
+  fn from_nullable_sql(
+    bytes: Option<<Pg as diesel::backend::Backend>::RawValue<'_>>,
+  ) -> diesel::deserialize::Result<Self> {
+    match bytes {
+      Some(bytes) => Self::from_sql(bytes),
+      None => Ok(Self(vec![])),
+    }
+  }
+}
+
+impl ToSql<Nullable<Json>, Pg> for CommentReactionsView {
+  fn to_sql(&self, out: &mut diesel::serialize::Output<Pg>) -> diesel::serialize::Result {
+    let value = serde_json::to_value(self)?;
+    <serde_json::Value as ToSql<Json, Pg>>::to_sql(&value, &mut out.reborrow())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::source::{
+    comment::{Comment, CommentInsertForm},
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_reactions() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "reaction_sara");
+    let sara = Person::create(pool, &person_form).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "reaction_tom");
+    let tom = Person::create(pool, &person_form).await?;
+
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "reaction community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("A test post".into(), sara.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm::new(sara.id, post.id, "A test comment".into());
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    let heart = CommentReactionForm::new(comment.id, sara.id, "❤️".to_string());
+    CommentReaction::add(pool, &heart).await?;
+    // Adding the same reaction twice is ignored
+    assert_eq!(0, CommentReaction::add(pool, &heart).await?);
+    let tom_heart = CommentReactionForm::new(comment.id, tom.id, "❤️".to_string());
+    CommentReaction::add(pool, &tom_heart).await?;
+    let tom_laugh = CommentReactionForm::new(comment.id, tom.id, "😂".to_string());
+    CommentReaction::add(pool, &tom_laugh).await?;
+
+    let reactions = CommentReaction::read_for_comments(pool, &[comment.id], Some(sara.id)).await?;
+    let expected = vec![
+      (
+        comment.id,
+        CommentReactionCount {
+          emoji: "❤️".to_string(),
+          count: 2,
+          viewer_reacted: true,
+        },
+      ),
+      (
+        comment.id,
+        CommentReactionCount {
+          emoji: "😂".to_string(),
+          count: 1,
+          viewer_reacted: false,
+        },
+      ),
+    ];
+    assert_eq!(expected, reactions);
+
+    CommentReaction::remove(pool, &tom_heart).await?;
+    let reactions = CommentReaction::read_for_comments(pool, &[comment.id], None).await?;
+    let counts = reactions
+      .into_iter()
+      .map(|(_, r)| (r.emoji, r.count, r.viewer_reacted))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      vec![("❤️".to_string(), 1, false), ("😂".to_string(), 1, false)],
+      counts
+    );
+
+    Instance::delete(pool, instance.id).await?;
+
+    Ok(())
+  }
+}
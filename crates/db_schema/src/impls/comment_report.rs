@@ -1,18 +1,27 @@
 use crate::{
-  newtypes::{CommentId, CommentReportId},
+  newtypes::{CommentId, CommentReportId, CommunityId},
   source::comment_report::{CommentReport, CommentReportForm},
   traits::Reportable,
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
+  OptionalExtension,
   QueryDsl,
-  dsl::{insert_into, update},
+  SelectableHelper,
+  dsl::{exists, insert_into, not, update},
 };
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::{PersonId, schema::comment_report};
-use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use diesel_ltree::{Ltree, dsl::LtreeExtensions, nlevel};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{comment, comment_report, post},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl Reportable for CommentReport {
@@ -96,3 +105,308 @@ impl Reportable for CommentReport {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 }
+
+impl CommentReport {
+  /// Like [Reportable::report], but does nothing if the person already reported the comment.
+  pub async fn report_once(
+    pool: &mut DbPool<'_>,
+    form: &CommentReportForm,
+  ) -> LemmyResult<Option<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(comment_report::table)
+      .values(form)
+      .on_conflict_do_nothing()
+      .get_result::<Self>(conn)
+      .await
+      .optional()
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Hides the report from the report queue until the given time, or shows it again if `None`.
+  pub async fn update_snoozed(
+    pool: &mut DbPool<'_>,
+    report_id: CommentReportId,
+    snoozed_until: Option<DateTime<Utc>>,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(comment_report::table.find(report_id))
+      .set((
+        comment_report::snoozed_until.eq(snoozed_until),
+        comment_report::updated_at.eq(Utc::now()),
+      ))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Reports the comment and its replies, up to `limit` comments. Removed and deleted comments are
+  /// skipped, as well as comments which the reporter already reported, because each person can
+  /// only report a comment once. Returns the created reports, the topmost comments first.
+  pub async fn report_subtree(
+    pool: &mut DbPool<'_>,
+    root_path: &Ltree,
+    creator_id: PersonId,
+    reason: &str,
+    violates_instance_rules: bool,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let already_reported = comment_report::table
+      .filter(comment_report::comment_id.eq(comment::id))
+      .filter(comment_report::creator_id.eq(creator_id));
+    let comments = comment::table
+      .filter(comment::path.contained_by(root_path))
+      .filter(not(comment::removed))
+      .filter(not(comment::deleted))
+      .filter(not(exists(already_reported)))
+      .select((comment::id, comment::content))
+      .order_by((nlevel(comment::path), comment::id))
+      .limit(limit)
+      .load::<(CommentId, String)>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+
+    let forms = comments
+      .into_iter()
+      .map(|(comment_id, content)| CommentReportForm {
+        creator_id,
+        comment_id,
+        original_comment_text: content,
+        reason: reason.to_string(),
+        violates_instance_rules,
+      })
+      .collect::<Vec<_>>();
+    if forms.is_empty() {
+      return Ok(vec![]);
+    }
+    // Comments may be reported concurrently by the same person
+    insert_into(comment_report::table)
+      .values(forms)
+      .on_conflict_do_nothing()
+      .get_results::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  /// Number of reports which are purged together with the person, on their own comments and on
+  /// the comments of others on their posts.
+  pub async fn count_for_creator_purge(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    comment_report::table
+      .inner_join(comment::table.inner_join(post::table))
+      .filter(
+        comment::creator_id
+          .eq(creator_id)
+          .or(post::creator_id.eq(creator_id)),
+      )
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// All comment reports filed by the person, together with the ap_id of the reported comment.
+  pub async fn list_for_creator(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+  ) -> LemmyResult<Vec<(Self, DbUrl)>> {
+    let conn = &mut get_conn(pool).await?;
+    comment_report::table
+      .inner_join(comment::table)
+      .filter(comment_report::creator_id.eq(creator_id))
+      .select((Self::as_select(), comment::ap_id))
+      .order_by(comment_report::published_at)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Resolves all open comment reports in the given community, and returns how many were resolved.
+  pub async fn resolve_all_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    by_resolver_id: PersonId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let comments_in_community = comment::table
+      .inner_join(post::table)
+      .filter(post::community_id.eq(community_id))
+      .select(comment::id);
+    update(
+      comment_report::table
+        .filter(comment_report::comment_id.eq_any(comments_in_community))
+        .filter(comment_report::resolved.eq(false)),
+    )
+    .set((
+      comment_report::resolved.eq(true),
+      comment_report::resolver_id.eq(by_resolver_id),
+      comment_report::updated_at.eq(Utc::now()),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::source::{
+    comment::{Comment, CommentInsertForm},
+    community::{Community, CommunityInsertForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+    post::{Post, PostInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_resolve_all_for_community() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "reporter");
+    let person = Person::create(pool, &person_form).await?;
+
+    let mut comments = vec![];
+    let mut communities = vec![];
+    for name in ["resolve_all_1", "resolve_all_2"] {
+      let community_form = CommunityInsertForm::new(
+        instance.id,
+        name.to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      );
+      let community = Community::create(pool, &community_form).await?;
+      let post_form = PostInsertForm::new("A test post".into(), person.id, community.id);
+      let post = Post::create(pool, &post_form).await?;
+      for content in ["first", "second"] {
+        let form = CommentInsertForm::new(person.id, post.id, content.into());
+        comments.push(Comment::create(pool, &form, None).await?);
+      }
+      communities.push(community);
+    }
+
+    // Two reports on the first comment, and one on each of the others
+    let person_form = PersonInsertForm::test_form(instance.id, "reporter_2");
+    let person_2 = Person::create(pool, &person_form).await?;
+    let reported = comments
+      .iter()
+      .map(|c| (person.id, c))
+      .chain(comments.first().map(|c| (person_2.id, c)));
+    for (creator_id, comment) in reported {
+      let form = CommentReportForm {
+        creator_id,
+        comment_id: comment.id,
+        original_comment_text: comment.content.clone(),
+        reason: "my reason".to_string(),
+        violates_instance_rules: false,
+      };
+      CommentReport::report(pool, &form).await?;
+    }
+
+    // Each reporter only sees their own reports
+    let reports = CommentReport::list_for_creator(pool, person_2.id).await?;
+    assert_eq!(1, reports.len());
+    assert_eq!(
+      comments.first().map(|c| c.ap_id.clone()),
+      reports.first().map(|r| r.1.clone())
+    );
+    assert_eq!(
+      4,
+      CommentReport::list_for_creator(pool, person.id)
+        .await?
+        .len()
+    );
+
+    // Only the open reports in the first community are resolved
+    let community_id = communities.first().map(|c| c.id).unwrap_or_default();
+    let resolved = CommentReport::resolve_all_for_community(pool, community_id, person.id).await?;
+    assert_eq!(3, resolved);
+    let resolved = CommentReport::resolve_all_for_community(pool, community_id, person.id).await?;
+    assert_eq!(0, resolved);
+
+    for community in communities {
+      Community::delete(pool, community.id).await?;
+    }
+    Person::delete(pool, person.id).await?;
+    Person::delete(pool, person_2.id).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_report_subtree() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "subtree_reporter");
+    let reporter = Person::create(pool, &person_form).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "subtree_reporter_2");
+    let reporter_2 = Person::create(pool, &person_form).await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "report_subtree".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("A test post".into(), reporter.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+
+    // root -> (a -> a1, b), and an unrelated comment
+    let form = CommentInsertForm::new(reporter.id, post.id, "root".into());
+    let root = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(reporter.id, post.id, "a".into());
+    let a = Comment::create(pool, &form, Some(&root.path)).await?;
+    let form = CommentInsertForm::new(reporter.id, post.id, "a1".into());
+    Comment::create(pool, &form, Some(&a.path)).await?;
+    let form = CommentInsertForm::new(reporter.id, post.id, "b".into());
+    let b = Comment::create(pool, &form, Some(&root.path)).await?;
+    let form = CommentInsertForm::new(reporter.id, post.id, "other".into());
+    Comment::create(pool, &form, None).await?;
+
+    // Existing reports aren't duplicated, even if they are resolved
+    let form = CommentReportForm {
+      creator_id: reporter.id,
+      comment_id: b.id,
+      original_comment_text: b.content.clone(),
+      reason: "earlier".to_string(),
+      violates_instance_rules: false,
+    };
+    let earlier = CommentReport::report(pool, &form).await?;
+    CommentReport::update_resolved(pool, earlier.id, reporter_2.id, true).await?;
+
+    let reports =
+      CommentReport::report_subtree(pool, &root.path, reporter.id, "brigade", false, 10).await?;
+    assert_eq!(3, reports.len());
+    assert_eq!(Some(root.id), reports.first().map(|r| r.comment_id));
+    assert!(reports.iter().all(|r| r.comment_id != b.id));
+    assert!(reports.iter().all(|r| r.reason == "brigade"));
+    let reports =
+      CommentReport::report_subtree(pool, &root.path, reporter.id, "brigade", false, 10).await?;
+    assert_eq!(0, reports.len());
+
+    // The number of reports is capped, topmost comments first
+    let reports =
+      CommentReport::report_subtree(pool, &root.path, reporter_2.id, "brigade", true, 3).await?;
+    let mut comment_ids = reports.iter().map(|r| r.comment_id.0).collect::<Vec<_>>();
+    comment_ids.sort_unstable();
+    assert_eq!(vec![root.id.0, a.id.0, b.id.0], comment_ids);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, reporter.id).await?;
+    Person::delete(pool, reporter_2.id).await?;
+
+    Ok(())
+  }
+}
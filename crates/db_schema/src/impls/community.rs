@@ -9,6 +9,7 @@ use crate::{
       CommunityBlockForm,
       CommunityFollowerForm,
       CommunityInsertForm,
+      CommunityMemberForm,
       CommunityModeratorForm,
       CommunityPersonBanForm,
       CommunityUpdateForm,
@@ -33,7 +34,13 @@ use diesel_async::RunQueryDsl;
 use diesel_uplete::{UpleteCount, uplete};
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityFollowerState, CommunityNotificationsMode, CommunityVisibility, ListingType},
+  enums::{
+    CommentSortType,
+    CommunityFollowerState,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+    ListingType,
+  },
   schema::{comment, community, community_actions, instance, local_user, post},
 };
 use lemmy_diesel_utils::{
@@ -113,6 +120,51 @@ impl CommunityActions {
       .await
       .with_lemmy_type(LemmyErrorType::AlreadyExists)
   }
+
+  pub async fn add_member(pool: &mut DbPool<'_>, form: &CommunityMemberForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_actions::table)
+      .values(form)
+      .on_conflict((
+        community_actions::person_id,
+        community_actions::community_id,
+      ))
+      .do_update()
+      .set(form)
+      .returning(Self::as_select())
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::AlreadyExists)
+  }
+
+  pub async fn remove_member(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+  ) -> LemmyResult<UpleteCount> {
+    let conn = &mut get_conn(pool).await?;
+    uplete(community_actions::table.find((person_id, community_id)))
+      .set_null(community_actions::became_member_at)
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  pub async fn is_member(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    community_id: CommunityId,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    select(exists(
+      community_actions::table
+        .find((person_id, community_id))
+        .filter(community_actions::became_member_at.is_not_null()),
+    ))
+    .get_result(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 }
 
 #[derive(Debug)]
@@ -284,6 +336,75 @@ impl Community {
 }
 
 impl CommunityActions {
+  /// Like [Followable::follow], but leaves an existing follow and its state untouched. Used for
+  /// settings import, so that importing the same backup twice doesn't reset accepted follows.
+  pub async fn follow_if_new(
+    pool: &mut DbPool<'_>,
+    form: &CommunityFollowerForm,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_actions::table)
+      .values(form)
+      .on_conflict((
+        community_actions::community_id,
+        community_actions::person_id,
+      ))
+      .do_update()
+      .set(form)
+      .filter(community_actions::followed_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Like [Blockable::block], but keeps the time of an existing block.
+  pub async fn block_if_new(
+    pool: &mut DbPool<'_>,
+    form: &CommunityBlockForm,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_actions::table)
+      .values(form)
+      .on_conflict((
+        community_actions::person_id,
+        community_actions::community_id,
+      ))
+      .do_update()
+      .set(form)
+      .filter(community_actions::blocked_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Records that the community welcome message was sent to the person. Returns false if it was
+  /// already sent before, so that it goes out only once.
+  pub async fn mark_welcomed(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    person_id: PersonId,
+  ) -> LemmyResult<bool> {
+    let conn = &mut get_conn(pool).await?;
+    let welcomed_at = community_actions::welcomed_at.eq(Utc::now());
+    let inserted = insert_into(community_actions::table)
+      .values((
+        community_actions::community_id.eq(community_id),
+        community_actions::person_id.eq(person_id),
+        welcomed_at,
+      ))
+      .on_conflict((
+        community_actions::community_id,
+        community_actions::person_id,
+      ))
+      .do_update()
+      .set(welcomed_at)
+      .filter(community_actions::welcomed_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+    Ok(inserted > 0)
+  }
+
   pub async fn read(
     pool: &mut DbPool<'_>,
     community_id: CommunityId,
@@ -440,6 +561,40 @@ impl CommunityActions {
     Ok(())
   }
 
+  /// Stores the default comment sort of the person for this community. None removes it, so that
+  /// their global default is used again.
+  pub async fn update_comment_sort_type(
+    community_id: CommunityId,
+    person_id: PersonId,
+    sort: Option<CommentSortType>,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    if let Some(sort) = sort {
+      let form = (
+        community_actions::person_id.eq(person_id),
+        community_actions::community_id.eq(community_id),
+        community_actions::comment_sort_type.eq(sort),
+      );
+      insert_into(community_actions::table)
+        .values(form.clone())
+        .on_conflict((
+          community_actions::person_id,
+          community_actions::community_id,
+        ))
+        .do_update()
+        .set(form)
+        .execute(conn)
+        .await?;
+    } else {
+      uplete(community_actions::table.find((person_id, community_id)))
+        .set_null(community_actions::comment_sort_type)
+        .get_result::<UpleteCount>(conn)
+        .await?;
+    }
+    Ok(())
+  }
+
   pub async fn list_subscribers(
     community_id: CommunityId,
     is_post: bool,
@@ -767,6 +922,22 @@ mod tests {
       unresolved_report_count: 0,
       interactions_month: 0,
       local_removed: false,
+      require_comment_approval_for_new_members: false,
+      new_member_comment_threshold: None,
+      new_member_account_age_days: None,
+      primary_language_id: None,
+      auto_lock_posts_after_seconds: None,
+      read_only: false,
+      welcome_message: None,
+      hide_scores_until_voted: false,
+      comments_require_membership: false,
+      auto_undistinguish_below_score: None,
+      min_comment_length: None,
+      comments_day: 0,
+      downvotes_enabled: true,
+      lock_edits_after_reply: false,
+      show_creator_instance: true,
+      pinned_comment_id: None,
     };
 
     let community_follower_form = CommunityFollowerForm::new(
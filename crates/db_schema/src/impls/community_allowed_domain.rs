@@ -0,0 +1,108 @@
+use crate::{newtypes::CommunityId, source::community_allowed_domain::CommunityAllowedDomain};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into};
+use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
+use lemmy_db_schema_file::schema::community_allowed_domain;
+use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl CommunityAllowedDomain {
+  /// The domains which comments in the community may link to. Empty if all domains are allowed.
+  pub async fn read(pool: &mut DbPool<'_>, community_id: CommunityId) -> LemmyResult<Vec<String>> {
+    let conn = &mut get_conn(pool).await?;
+    community_allowed_domain::table
+      .filter(community_allowed_domain::community_id.eq(community_id))
+      .order_by(community_allowed_domain::domain)
+      .select(community_allowed_domain::domain)
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Replaces the allowed domains of the community. An empty list allows all domains.
+  pub async fn replace(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    domains: Vec<String>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(
+            community_allowed_domain::table
+              .filter(community_allowed_domain::community_id.eq(community_id)),
+          )
+          .execute(conn)
+          .await?;
+
+          let forms = domains
+            .into_iter()
+            .map(|domain| CommunityAllowedDomain {
+              community_id,
+              domain,
+            })
+            .collect::<Vec<_>>();
+          insert_into(community_allowed_domain::table)
+            .values(forms)
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntUpdate)?;
+          Ok(())
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    source::community::{Community, CommunityInsertForm},
+    test_data::TestData,
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_replace_allowed_domains() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = TestData::create(pool).await?;
+
+    let form = CommunityInsertForm::new(
+      data.instance.id,
+      "allowed_domains".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &form).await?;
+    assert!(
+      CommunityAllowedDomain::read(pool, community.id)
+        .await?
+        .is_empty()
+    );
+
+    let domains = vec!["nature.com".to_string(), "arxiv.org".to_string()];
+    CommunityAllowedDomain::replace(pool, community.id, domains).await?;
+    assert_eq!(
+      vec!["arxiv.org".to_string(), "nature.com".to_string()],
+      CommunityAllowedDomain::read(pool, community.id).await?
+    );
+
+    CommunityAllowedDomain::replace(pool, community.id, vec![]).await?;
+    assert!(
+      CommunityAllowedDomain::read(pool, community.id)
+        .await?
+        .is_empty()
+    );
+
+    Community::delete(pool, community.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+}
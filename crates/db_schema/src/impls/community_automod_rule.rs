@@ -0,0 +1,59 @@
+use crate::{
+  newtypes::{CommunityAutomodRuleId, CommunityId},
+  source::community_automod_rule::{
+    CommunityAutomodRule,
+    CommunityAutomodRuleInsertForm,
+    CommunityAutomodRuleUpdateForm,
+  },
+};
+use diesel::{ExpressionMethods, QueryDsl, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::schema::community_automod_rule;
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  traits::Crud,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl Crud for CommunityAutomodRule {
+  type InsertForm = CommunityAutomodRuleInsertForm;
+  type UpdateForm = CommunityAutomodRuleUpdateForm;
+  type IdType = CommunityAutomodRuleId;
+
+  async fn create(pool: &mut DbPool<'_>, form: &Self::InsertForm) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(community_automod_rule::table)
+      .values(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  async fn update(
+    pool: &mut DbPool<'_>,
+    rule_id: CommunityAutomodRuleId,
+    form: &Self::UpdateForm,
+  ) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(community_automod_rule::table.find(rule_id))
+      .set(form)
+      .get_result::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
+impl CommunityAutomodRule {
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    community_automod_rule::table
+      .filter(community_automod_rule::community_id.eq(community_id))
+      .order_by(community_automod_rule::id)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
@@ -22,6 +22,7 @@ impl FederationQueueState {
           last_retry_at: None,
           last_successful_id: None, // this value is set to the most current id for new instances
           last_successful_published_time_at: None,
+          last_error: None,
         }),
     )
   }
@@ -18,7 +18,7 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use lemmy_db_schema_file::{
   PersonId,
-  enums::CommunityVisibility,
+  enums::{CommunityVisibility, SettingsImportKind},
   schema::{community, community_actions, local_user, person, registration_application},
 };
 use lemmy_diesel_utils::{
@@ -166,8 +166,6 @@ impl LocalUser {
       comment_actions,
       community,
       community_actions,
-      instance,
-      instance_actions,
       person_actions,
       post,
       post_actions,
@@ -214,6 +212,101 @@ impl LocalUser {
       .get_results(conn)
       .await?;
 
+    // TODO: use join for parallel queries?
+
+    Ok(UserBackupLists {
+      followed_communities,
+      saved_posts,
+      saved_comments,
+      blocked_communities,
+      blocked_users,
+    })
+  }
+
+  /// Returns a chunk of one of the backup lists, with up to `limit` items whose id is greater
+  /// than `after_id`. The items are ordered by id, so that the last id can be used for the next
+  /// chunk.
+  pub async fn export_backup_chunk(
+    pool: &mut DbPool<'_>,
+    person_id_: PersonId,
+    kind: SettingsImportKind,
+    after_id: i32,
+    limit: i64,
+  ) -> LemmyResult<Vec<(i32, DbUrl)>> {
+    use lemmy_db_schema_file::schema::{
+      comment,
+      comment_actions,
+      person_actions,
+      post,
+      post_actions,
+    };
+    let conn = &mut get_conn(pool).await?;
+
+    let chunk = match kind {
+      SettingsImportKind::FollowedCommunity | SettingsImportKind::BlockedCommunity => {
+        let query = community_actions::table
+          .filter(community_actions::person_id.eq(person_id_))
+          .inner_join(community::table)
+          .filter(community::id.gt(after_id))
+          .select((community::id, community::ap_id))
+          .order_by(community::id)
+          .limit(limit)
+          .into_boxed();
+        let query = if kind == SettingsImportKind::FollowedCommunity {
+          query.filter(community_actions::followed_at.is_not_null())
+        } else {
+          query.filter(community_actions::blocked_at.is_not_null())
+        };
+        query.load(conn).await?
+      }
+      SettingsImportKind::SavedPost => {
+        post_actions::table
+          .filter(post_actions::saved_at.is_not_null())
+          .filter(post_actions::person_id.eq(person_id_))
+          .inner_join(post::table)
+          .filter(post::id.gt(after_id))
+          .select((post::id, post::ap_id))
+          .order_by(post::id)
+          .limit(limit)
+          .load(conn)
+          .await?
+      }
+      SettingsImportKind::SavedComment => {
+        comment_actions::table
+          .filter(comment_actions::saved_at.is_not_null())
+          .filter(comment_actions::person_id.eq(person_id_))
+          .inner_join(comment::table)
+          .filter(comment::id.gt(after_id))
+          .select((comment::id, comment::ap_id))
+          .order_by(comment::id)
+          .limit(limit)
+          .load(conn)
+          .await?
+      }
+      SettingsImportKind::BlockedPerson => {
+        person_actions::table
+          .filter(person_actions::blocked_at.is_not_null())
+          .filter(person_actions::person_id.eq(person_id_))
+          .inner_join(person::table.on(person_actions::target_id.eq(person::id)))
+          .filter(person::id.gt(after_id))
+          .select((person::id, person::ap_id))
+          .order_by(person::id)
+          .limit(limit)
+          .load(conn)
+          .await?
+      }
+    };
+    Ok(chunk)
+  }
+
+  /// Domains of the instances whose communities and persons the user blocked, in this order.
+  pub async fn export_blocked_instances(
+    pool: &mut DbPool<'_>,
+    person_id_: PersonId,
+  ) -> LemmyResult<(Vec<String>, Vec<String>)> {
+    use lemmy_db_schema_file::schema::{instance, instance_actions};
+    let conn = &mut get_conn(pool).await?;
+
     let blocked_instances_communities = instance_actions::table
       .filter(instance_actions::blocked_communities_at.is_not_null())
       .filter(instance_actions::person_id.eq(person_id_))
@@ -230,17 +323,7 @@ impl LocalUser {
       .get_results(conn)
       .await?;
 
-    // TODO: use join for parallel queries?
-
-    Ok(UserBackupLists {
-      followed_communities,
-      saved_posts,
-      saved_comments,
-      blocked_communities,
-      blocked_users,
-      blocked_instances_communities,
-      blocked_instances_persons,
-    })
+    Ok((blocked_instances_communities, blocked_instances_persons))
   }
 
   /// Checks to make sure the acting admin is higher than the target admin
@@ -317,6 +400,8 @@ pub trait LocalUserOptionHelper {
   fn local_user_id(&self) -> Option<LocalUserId>;
   fn show_bot_accounts(&self) -> bool;
   fn show_read_posts(&self) -> bool;
+  fn show_read_comments(&self) -> bool;
+  fn show_avatars(&self) -> bool;
   fn is_admin(&self) -> bool;
   fn show_nsfw(&self, site: &Site) -> bool;
   fn hide_media(&self) -> bool;
@@ -345,6 +430,14 @@ impl LocalUserOptionHelper for Option<&LocalUser> {
     self.map(|l| l.show_read_posts).unwrap_or(true)
   }
 
+  fn show_read_comments(&self) -> bool {
+    self.map(|l| l.show_read_comments).unwrap_or(true)
+  }
+
+  fn show_avatars(&self) -> bool {
+    self.map(|l| l.show_avatars).unwrap_or(true)
+  }
+
   fn is_admin(&self) -> bool {
     self.map(|l| l.admin).unwrap_or(false)
   }
@@ -394,8 +487,6 @@ pub struct UserBackupLists {
   pub saved_comments: Vec<DbUrl>,
   pub blocked_communities: Vec<DbUrl>,
   pub blocked_users: Vec<DbUrl>,
-  pub blocked_instances_communities: Vec<String>,
-  pub blocked_instances_persons: Vec<String>,
 }
 
 #[cfg(test)]
@@ -2,8 +2,12 @@ pub mod activity;
 pub mod actor_language;
 pub mod captcha_answer;
 pub mod comment;
+pub mod comment_draft;
+pub mod comment_reaction;
 pub mod comment_report;
 pub mod community;
+pub mod community_allowed_domain;
+pub mod community_automod_rule;
 pub mod community_community_follow;
 pub mod community_report;
 pub mod custom_emoji;
@@ -33,6 +37,7 @@ pub mod private_message;
 pub mod private_message_report;
 pub mod registration_application;
 pub mod secret;
+pub mod settings_import_failure;
 pub mod site;
 pub mod tag;
 pub mod tagline;
@@ -92,6 +92,43 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::ModLockComment, !removed, mod_person_id)
     }
   }
+  pub fn mod_mark_comment_as_answer(
+    mod_person_id: PersonId,
+    comment: &Comment,
+    answer: bool,
+  ) -> Self {
+    Self {
+      target_comment_id: Some(comment.id),
+      target_post_id: Some(comment.post_id),
+      target_person_id: Some(comment.creator_id),
+      ..ModlogInsertForm::new(ModlogKind::ModMarkCommentAsAnswer, !answer, mod_person_id)
+    }
+  }
+  pub fn mod_distinguish_comment(
+    mod_person_id: PersonId,
+    comment: &Comment,
+    distinguished: bool,
+  ) -> Self {
+    Self {
+      target_comment_id: Some(comment.id),
+      target_post_id: Some(comment.post_id),
+      target_person_id: Some(comment.creator_id),
+      ..ModlogInsertForm::new(
+        ModlogKind::ModDistinguishComment,
+        !distinguished,
+        mod_person_id,
+      )
+    }
+  }
+  pub fn mod_move_comment(mod_person_id: PersonId, comment: &Comment, reason: &'a str) -> Self {
+    Self {
+      reason: Some(reason),
+      target_comment_id: Some(comment.id),
+      target_post_id: Some(comment.post_id),
+      target_person_id: Some(comment.creator_id),
+      ..ModlogInsertForm::new(ModlogKind::ModMoveComment, false, mod_person_id)
+    }
+  }
   pub fn mod_lock_post(
     mod_person_id: PersonId,
     post: &Post,
@@ -251,4 +288,15 @@ impl<'a> ModlogInsertForm<'a> {
       ..ModlogInsertForm::new(ModlogKind::AdminFeaturePostSite, !featured, mod_person_id)
     }
   }
+  pub fn mod_resolve_community_reports(
+    mod_person_id: PersonId,
+    community_id: CommunityId,
+    reason: &'a str,
+  ) -> Self {
+    Self {
+      reason: Some(reason),
+      target_community_id: Some(community_id),
+      ..ModlogInsertForm::new(ModlogKind::ModResolveCommunityReports, false, mod_person_id)
+    }
+  }
 }
@@ -97,6 +97,26 @@ impl Notification {
     .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Marks the given notifications as read or unread with a single update. Notifications of other
+  /// recipients are ignored.
+  pub async fn mark_read_by_ids_and_person(
+    pool: &mut DbPool<'_>,
+    notification_ids: &[NotificationId],
+    recipient_id: PersonId,
+    read: bool,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    update(
+      notification::table
+        .filter(notification::id.eq_any(notification_ids))
+        .filter(notification::recipient_id.eq(recipient_id)),
+    )
+    .set(notification::read.eq(read))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   /// Only for tests
   pub async fn delete(pool: &mut DbPool<'_>, id: NotificationId) -> LemmyResult<()> {
     let conn = &mut get_conn(pool).await?;
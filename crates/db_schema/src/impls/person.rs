@@ -321,6 +321,20 @@ impl Blockable for PersonActions {
 }
 
 impl PersonActions {
+  /// Like [Blockable::block], but keeps the time of an existing block.
+  pub async fn block_if_new(pool: &mut DbPool<'_>, form: &PersonBlockForm) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(person_actions::table)
+      .values(form)
+      .on_conflict((person_actions::person_id, person_actions::target_id))
+      .do_update()
+      .set(form)
+      .filter(person_actions::blocked_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
   pub async fn follower_inboxes(
     pool: &mut DbPool<'_>,
     for_person_id: PersonId,
@@ -489,6 +503,7 @@ mod tests {
       post_score: 0,
       comment_count: 0,
       comment_score: 0,
+      verified_links: vec![],
     };
 
     let read_person = Person::read(pool, inserted_person.id).await?;
@@ -12,7 +12,13 @@ use crate::{
     PostUpdateForm,
   },
   traits::{Likeable, Saveable},
-  utils::{DELETED_REPLACEMENT_TEXT, FETCH_LIMIT_MAX, SITEMAP_DAYS, SITEMAP_LIMIT},
+  utils::{
+    DELETED_REPLACEMENT_TEXT,
+    FETCH_LIMIT_MAX,
+    SITEMAP_DAYS,
+    SITEMAP_LIMIT,
+    SUBSCRIBER_NOTIFY_COALESCE_WINDOW,
+  },
 };
 use chrono::{DateTime, Utc};
 use diesel::{
@@ -22,6 +28,7 @@ use diesel::{
   JoinOnDsl,
   NullableExpressionMethods,
   OptionalExtension,
+  PgExpressionMethods,
   QueryDsl,
   dsl::{count, insert_into, not, update},
   expression::SelectableHelper,
@@ -32,7 +39,7 @@ use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
   enums::PostNotificationsMode,
-  schema::{community, local_user, person, post, post_actions},
+  schema::{community, local_user, person, post, post_actions, post_subscriber_notified},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -157,6 +164,36 @@ impl Post {
       .with_lemmy_type(LemmyErrorType::CouldntUpdate)
   }
 
+  pub async fn count_for_creator(pool: &mut DbPool<'_>, creator_id: PersonId) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    post::table
+      .filter(post::creator_id.eq(creator_id))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Purges up to `limit` posts of the creator, and returns the purged posts. Call repeatedly
+  /// until it returns an empty list to purge all of them.
+  pub async fn purge_batch_for_creator(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+    limit: i64,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let batch = post::table
+      .filter(post::creator_id.eq(creator_id))
+      .select(post::id)
+      .order_by(post::id)
+      .limit(limit);
+    diesel::delete(post::table.filter(post::id.eq_any(batch)))
+      .returning(Self::as_select())
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
   async fn creator_post_ids_in_community(
     pool: &mut DbPool<'_>,
     creator_id: PersonId,
@@ -435,6 +472,20 @@ impl Saveable for PostActions {
 }
 
 impl PostActions {
+  /// Like [Saveable::save], but keeps the time of an existing save.
+  pub async fn save_if_new(pool: &mut DbPool<'_>, form: &PostSavedForm) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(post_actions::table)
+      .values(form)
+      .on_conflict((post_actions::post_id, post_actions::person_id))
+      .do_update()
+      .set(form)
+      .filter(post_actions::saved_at.is_null())
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
   pub async fn mark_as_unread(
     pool: &mut DbPool<'_>,
     person_id: PersonId,
@@ -543,6 +594,7 @@ impl PostActions {
     post_id: PostId,
     person_id: PersonId,
     new_state: PostNotificationsMode,
+    coalesce: Option<bool>,
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<()> {
     let conn = &mut get_conn(pool).await?;
@@ -550,6 +602,7 @@ impl PostActions {
       post_actions::person_id.eq(person_id),
       post_actions::post_id.eq(post_id),
       post_actions::notifications.eq(new_state),
+      coalesce.map(|c| post_actions::coalesce_notifications.eq(c)),
     );
 
     insert_into(post_actions::table)
@@ -562,21 +615,110 @@ impl PostActions {
     Ok(())
   }
 
+  /// Mutes or unmutes notifications for the post. Unlike changing the notification mode, this
+  /// keeps the subscription and read state intact.
+  pub async fn update_notifications_muted(
+    post_id: PostId,
+    person_id: PersonId,
+    muted: bool,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    let form = (
+      post_actions::person_id.eq(person_id),
+      post_actions::post_id.eq(post_id),
+      post_actions::notifications_muted.eq(muted),
+    );
+
+    insert_into(post_actions::table)
+      .values(form)
+      .on_conflict((post_actions::person_id, post_actions::post_id))
+      .do_update()
+      .set(post_actions::notifications_muted.eq(muted))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
+  /// Lists the local users subscribed to all comments on the post, and whether they enabled
+  /// coalescing. Muted subscriptions are left out.
   pub async fn list_subscribers(
     post_id: PostId,
     pool: &mut DbPool<'_>,
-  ) -> LemmyResult<Vec<PersonId>> {
+  ) -> LemmyResult<Vec<(PersonId, bool)>> {
     let conn = &mut get_conn(pool).await?;
 
     post_actions::table
       .inner_join(local_user::table.on(post_actions::person_id.eq(local_user::person_id)))
       .filter(post_actions::post_id.eq(post_id))
       .filter(post_actions::notifications.eq(PostNotificationsMode::AllComments))
-      .select(local_user::person_id)
+      .filter(post_actions::notifications_muted.is_distinct_from(true))
+      .select((
+        local_user::person_id,
+        post_actions::coalesce_notifications.is_not_distinct_from(true),
+      ))
       .get_results(conn)
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
+
+  /// Marks the given coalescing subscribers as notified about a comment by `creator_id` now.
+  /// Returns the subscribers which weren't already notified about a comment by the same creator
+  /// within [SUBSCRIBER_NOTIFY_COALESCE_WINDOW], only those should get a notification.
+  pub async fn mark_coalesced_subscribers_notified(
+    post_id: PostId,
+    creator_id: PersonId,
+    subscribers: &[PersonId],
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Vec<PersonId>> {
+    if subscribers.is_empty() {
+      return Ok(vec![]);
+    }
+    let conn = &mut get_conn(pool).await?;
+
+    let notified_at = post_subscriber_notified::notified_at.eq(Utc::now());
+    let forms = subscribers
+      .iter()
+      .map(|person_id| {
+        (
+          post_subscriber_notified::post_id.eq(post_id),
+          post_subscriber_notified::person_id.eq(*person_id),
+          post_subscriber_notified::creator_id.eq(creator_id),
+          notified_at,
+        )
+      })
+      .collect::<Vec<_>>();
+    // Only rows which were inserted or updated are returned, so subscribers which were notified
+    // recently are skipped.
+    insert_into(post_subscriber_notified::table)
+      .values(forms)
+      .on_conflict((
+        post_subscriber_notified::post_id,
+        post_subscriber_notified::person_id,
+        post_subscriber_notified::creator_id,
+      ))
+      .do_update()
+      .set(notified_at)
+      .filter(
+        post_subscriber_notified::notified_at.lt(Utc::now() - SUBSCRIBER_NOTIFY_COALESCE_WINDOW),
+      )
+      .returning(post_subscriber_notified::person_id)
+      .get_results(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+
+  /// Deletes records of coalesced notifications which are older than
+  /// [SUBSCRIBER_NOTIFY_COALESCE_WINDOW], as they don't hold back any notifications anymore.
+  pub async fn delete_expired_subscriber_notified(pool: &mut DbPool<'_>) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::delete(post_subscriber_notified::table.filter(
+      post_subscriber_notified::notified_at.lt(Utc::now() - SUBSCRIBER_NOTIFY_COALESCE_WINDOW),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::Deleted)
+  }
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 use crate::{
-  newtypes::{PostId, PostReportId},
+  newtypes::{CommunityId, PostId, PostReportId},
   source::post_report::{PostReport, PostReportForm},
   traits::Reportable,
 };
@@ -8,11 +8,18 @@ use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   QueryDsl,
+  SelectableHelper,
   dsl::{insert_into, update},
 };
 use diesel_async::RunQueryDsl;
-use lemmy_db_schema_file::{PersonId, schema::post_report};
-use lemmy_diesel_utils::connection::{DbPool, get_conn};
+use lemmy_db_schema_file::{
+  PersonId,
+  schema::{post, post_report},
+};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+};
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
 impl Reportable for PostReport {
@@ -89,6 +96,64 @@ impl Reportable for PostReport {
   }
 }
 
+impl PostReport {
+  /// Number of reports on posts written by the person.
+  pub async fn count_for_post_creator(
+    pool: &mut DbPool<'_>,
+    post_creator_id: PersonId,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    post_report::table
+      .inner_join(post::table)
+      .filter(post::creator_id.eq(post_creator_id))
+      .count()
+      .get_result(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// All post reports filed by the person, together with the ap_id of the reported post.
+  pub async fn list_for_creator(
+    pool: &mut DbPool<'_>,
+    creator_id: PersonId,
+  ) -> LemmyResult<Vec<(Self, DbUrl)>> {
+    let conn = &mut get_conn(pool).await?;
+    post_report::table
+      .inner_join(post::table)
+      .filter(post_report::creator_id.eq(creator_id))
+      .select((Self::as_select(), post::ap_id))
+      .order_by(post_report::published_at)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Resolves all open post reports in the given community, and returns how many were resolved.
+  pub async fn resolve_all_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    by_resolver_id: PersonId,
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    let posts_in_community = post::table
+      .filter(post::community_id.eq(community_id))
+      .select(post::id);
+    update(
+      post_report::table
+        .filter(post_report::post_id.eq_any(posts_in_community))
+        .filter(post_report::resolved.eq(false)),
+    )
+    .set((
+      post_report::resolved.eq(true),
+      post_report::resolver_id.eq(by_resolver_id),
+      post_report::updated_at.eq(Utc::now()),
+    ))
+    .execute(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::CouldntUpdate)
+  }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -166,4 +231,24 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_list_post_reports_for_creator() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let (person, report) = init(pool).await?;
+    let post = Post::read(pool, report.post_id).await?;
+
+    let reports = PostReport::list_for_creator(pool, person.id).await?;
+    assert_eq!(1, reports.len());
+    assert_eq!(report.id, reports[0].0.id);
+    assert_eq!(post.ap_id, reports[0].1);
+
+    Person::delete(pool, person.id).await?;
+    Post::delete(pool, report.post_id).await?;
+
+    Ok(())
+  }
 }
@@ -0,0 +1,170 @@
+use crate::{
+  newtypes::LocalUserId,
+  source::settings_import_failure::{SettingsImportFailure, SettingsImportFailureForm},
+};
+use diesel::{ExpressionMethods, QueryDsl, delete, insert_into};
+use diesel_async::RunQueryDsl;
+use lemmy_db_schema_file::{enums::SettingsImportKind, schema::settings_import_failure};
+use lemmy_diesel_utils::{
+  connection::{DbPool, get_conn},
+  dburl::DbUrl,
+};
+use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+
+impl SettingsImportFailure {
+  pub async fn create_many(
+    pool: &mut DbPool<'_>,
+    forms: &[SettingsImportFailureForm],
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    insert_into(settings_import_failure::table)
+      .values(forms)
+      .on_conflict_do_nothing()
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::CouldntCreate)
+  }
+
+  pub async fn list(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    settings_import_failure::table
+      .filter(settings_import_failure::local_user_id.eq(for_local_user_id))
+      .order_by(settings_import_failure::published_at)
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
+  /// Removes all stored failures for the user, eg when a new import is started.
+  pub async fn clear(pool: &mut DbPool<'_>, for_local_user_id: LocalUserId) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(settings_import_failure::table)
+      .filter(settings_import_failure::local_user_id.eq(for_local_user_id))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+
+  /// Removes the stored failures of the given kind, eg once a retry imported them successfully.
+  pub async fn remove(
+    pool: &mut DbPool<'_>,
+    for_local_user_id: LocalUserId,
+    for_kind: SettingsImportKind,
+    ap_ids: &[DbUrl],
+  ) -> LemmyResult<usize> {
+    let conn = &mut get_conn(pool).await?;
+    delete(settings_import_failure::table)
+      .filter(settings_import_failure::local_user_id.eq(for_local_user_id))
+      .filter(settings_import_failure::kind.eq(for_kind))
+      .filter(settings_import_failure::ap_id.eq_any(ap_ids))
+      .execute(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::Deleted)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+
+  use super::*;
+  use crate::source::{
+    instance::Instance,
+    local_user::{LocalUser, LocalUserInsertForm},
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use pretty_assertions::assert_eq;
+  use serial_test::serial;
+  use url::Url;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_settings_import_failures() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "import_failure_sara");
+    let sara = Person::create(pool, &person_form).await?;
+    let sara_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(sara.id), vec![]).await?;
+    let person_form = PersonInsertForm::test_form(instance.id, "import_failure_tom");
+    let tom = Person::create(pool, &person_form).await?;
+    let tom_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(tom.id), vec![]).await?;
+
+    let community: Url = "https://example.com/c/failed".parse()?;
+    let post: Url = "https://example.com/post/1".parse()?;
+    let forms = [
+      SettingsImportFailureForm::new(
+        sara_local_user.id,
+        SettingsImportKind::FollowedCommunity,
+        community.clone().into(),
+      ),
+      SettingsImportFailureForm::new(
+        sara_local_user.id,
+        SettingsImportKind::SavedPost,
+        post.clone().into(),
+      ),
+      // Duplicates are ignored
+      SettingsImportFailureForm::new(
+        sara_local_user.id,
+        SettingsImportKind::SavedPost,
+        post.clone().into(),
+      ),
+      SettingsImportFailureForm::new(
+        tom_local_user.id,
+        SettingsImportKind::BlockedCommunity,
+        community.clone().into(),
+      ),
+    ];
+    assert_eq!(3, SettingsImportFailure::create_many(pool, &forms).await?);
+
+    let failed = SettingsImportFailure::list(pool, sara_local_user.id).await?;
+    let failed = failed
+      .into_iter()
+      .map(|f| (f.kind, f.ap_id.inner().clone()))
+      .collect::<Vec<_>>();
+    assert_eq!(2, failed.len());
+    assert!(failed.contains(&(SettingsImportKind::FollowedCommunity, community.clone())));
+    assert!(failed.contains(&(SettingsImportKind::SavedPost, post.clone())));
+
+    // Removing only affects the given kind
+    assert_eq!(
+      1,
+      SettingsImportFailure::remove(
+        pool,
+        sara_local_user.id,
+        SettingsImportKind::SavedPost,
+        &[post.clone().into(), community.clone().into()],
+      )
+      .await?
+    );
+    SettingsImportFailure::create_many(pool, &forms).await?;
+
+    // Clearing only affects the given user
+    assert_eq!(
+      2,
+      SettingsImportFailure::clear(pool, sara_local_user.id).await?
+    );
+    assert!(
+      SettingsImportFailure::list(pool, sara_local_user.id)
+        .await?
+        .is_empty()
+    );
+    assert_eq!(
+      1,
+      SettingsImportFailure::list(pool, tom_local_user.id)
+        .await?
+        .len()
+    );
+
+    Person::delete(pool, sara.id).await?;
+    Person::delete(pool, tom.id).await?;
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
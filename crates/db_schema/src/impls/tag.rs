@@ -1,9 +1,18 @@
 use crate::{
   diesel::SelectableHelper,
-  newtypes::{CommunityId, PostId, TagId},
+  newtypes::{CommentId, CommunityId, PostId, TagId},
   source::{
     post::Post,
-    tag::{PostTag, PostTagForm, Tag, TagInsertForm, TagUpdateForm, TagsView},
+    tag::{
+      CommentTag,
+      CommentTagForm,
+      PostTag,
+      PostTagForm,
+      Tag,
+      TagInsertForm,
+      TagUpdateForm,
+      TagsView,
+    },
   },
 };
 use diesel::{
@@ -18,7 +27,7 @@ use diesel::{
   upsert::excluded,
 };
 use diesel_async::{RunQueryDsl, scoped_futures::ScopedFutureExt};
-use lemmy_db_schema_file::schema::{post_tag, tag};
+use lemmy_db_schema_file::schema::{comment_tag, post_tag, tag};
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
   dburl::DbUrl,
@@ -193,3 +202,39 @@ impl PostTag {
       .await
   }
 }
+
+impl CommentTag {
+  pub async fn update(
+    pool: &mut DbPool<'_>,
+    comment_id: CommentId,
+    tag_ids: &[TagId],
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+
+    conn
+      .run_transaction(|conn| {
+        async move {
+          delete(comment_tag::table.filter(comment_tag::comment_id.eq(comment_id)))
+            .execute(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::Deleted)?;
+
+          let forms = tag_ids
+            .iter()
+            .map(|tag_id| CommentTagForm {
+              comment_id,
+              tag_id: *tag_id,
+            })
+            .collect::<Vec<_>>();
+          insert_into(comment_tag::table)
+            .values(forms)
+            .returning(Self::as_select())
+            .get_results(conn)
+            .await
+            .with_lemmy_type(LemmyErrorType::CouldntCreate)
+        }
+        .scope_boxed()
+      })
+      .await
+  }
+}
@@ -60,6 +60,8 @@ pub enum CommunitySortType {
   Posts,
   Subscribers,
   SubscribersLocal,
+  /// Most comments in the last day.
+  TrendingComments,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -147,6 +149,18 @@ pub enum ReportType {
   Communities,
 }
 
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// A list of possible types for the mod queue.
+pub enum ModQueueType {
+  All,
+  Posts,
+  Comments,
+  RegistrationApplications,
+}
+
 #[derive(
   EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash,
 )]
@@ -67,6 +67,22 @@ pub struct Comment {
   pub federation_pending: bool,
   /// Whether the comment is locked.
   pub locked: bool,
+  /// Comments by new members of some communities are held until a mod approves them. Until then
+  /// they are only visible to the creator and to mods.
+  pub pending_approval: bool,
+  /// When the creator deletes a comment, it is hidden and can be restored until this time. Then
+  /// the delete is finalized.
+  pub pending_delete_until: Option<DateTime<Utc>>,
+  /// Whether the post creator or a mod marked this comment as the accepted answer to the post.
+  pub is_answer: bool,
+  /// If set, the comment is only visible to its creator until this time, when it gets published.
+  pub scheduled_publish_time_at: Option<DateTime<Utc>>,
+  /// The last time the content was edited. Unlike `updated_at`, this isn't changed by removals or
+  /// other moderation actions.
+  pub content_edited_at: Option<DateTime<Utc>>,
+  /// New local comments are only visible to their creator and aren't federated until this time.
+  /// If the comment is deleted before, it is never federated.
+  pub pending_federation_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, derive_new::new, Serialize, Deserialize)]
@@ -96,6 +112,16 @@ pub struct CommentInsertForm {
   pub federation_pending: Option<bool>,
   #[new(default)]
   pub locked: Option<bool>,
+  #[new(default)]
+  pub pending_approval: Option<bool>,
+  #[new(default)]
+  pub pending_delete_until: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub scheduled_publish_time_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub content_edited_at: Option<DateTime<Utc>>,
+  #[new(default)]
+  pub pending_federation_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -113,6 +139,12 @@ pub struct CommentUpdateForm {
   pub language_id: Option<LanguageId>,
   pub federation_pending: Option<bool>,
   pub locked: Option<bool>,
+  pub pending_approval: Option<bool>,
+  pub pending_delete_until: Option<Option<DateTime<Utc>>>,
+  pub scheduled_publish_time_at: Option<Option<DateTime<Utc>>>,
+  pub published_at: Option<DateTime<Utc>>,
+  pub content_edited_at: Option<Option<DateTime<Utc>>>,
+  pub pending_federation_until: Option<Option<DateTime<Utc>>>,
 }
 
 #[skip_serializing_none]
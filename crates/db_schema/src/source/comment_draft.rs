@@ -0,0 +1,38 @@
+use crate::newtypes::{CommentDraftId, CommentId, PostId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::comment_draft;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+#[skip_serializing_none]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_draft))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An unpublished comment, saved so that it can be continued later. Only visible to its author,
+/// and never federated. There is at most one draft per post and parent comment.
+pub struct CommentDraft {
+  pub id: CommentDraftId,
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  /// The comment which is being replied to, or none for a top-level comment.
+  pub parent_id: Option<CommentId>,
+  pub content: String,
+  pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_draft))]
+pub struct CommentDraftForm {
+  pub person_id: PersonId,
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+  pub content: String,
+  #[new(value = "Utc::now()")]
+  pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,50 @@
+use crate::newtypes::CommentId;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "full")]
+use diesel::{AsExpression, FromSqlRow, sql_types::Nullable};
+use lemmy_db_schema_file::PersonId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::comment_reaction;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_reaction))]
+#[cfg_attr(feature = "full", diesel(primary_key(comment_id, person_id, emoji)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// An emoji reaction to a comment. This is separate from votes, and doesn't affect the score.
+pub struct CommentReaction {
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_reaction))]
+pub struct CommentReactionForm {
+  pub comment_id: CommentId,
+  pub person_id: PersonId,
+  pub emoji: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// How often a comment received a given emoji reaction.
+pub struct CommentReactionCount {
+  pub emoji: String,
+  pub count: i64,
+  /// Whether the current user added this reaction.
+  pub viewer_reacted: bool,
+}
+
+/// We wrap this in a struct so we can implement FromSqlRow<Json> for it
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+#[serde(transparent)]
+#[cfg_attr(feature = "full", derive(FromSqlRow, AsExpression))]
+#[cfg_attr(feature = "full", diesel(sql_type = Nullable<diesel::sql_types::Json>))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommentReactionsView(pub Vec<CommentReactionCount>);
@@ -29,6 +29,8 @@ pub struct CommentReport {
   pub published_at: DateTime<Utc>,
   pub updated_at: Option<DateTime<Utc>>,
   pub violates_instance_rules: bool,
+  /// A mod deferred the report, it is hidden from the report queue until this time.
+  pub snoozed_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone)]
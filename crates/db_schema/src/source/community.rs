@@ -1,9 +1,17 @@
-use crate::{newtypes::CommunityId, source::placeholder_apub_url};
+use crate::{
+  newtypes::{CommentId, CommunityId, LanguageId},
+  source::placeholder_apub_url,
+};
 use chrono::{DateTime, Utc};
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
-  enums::{CommunityFollowerState, CommunityNotificationsMode, CommunityVisibility},
+  enums::{
+    CommentSortType,
+    CommunityFollowerState,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+  },
 };
 use lemmy_diesel_utils::{dburl::DbUrl, sensitive::SensitiveString};
 use serde::{Deserialize, Serialize};
@@ -95,6 +103,38 @@ pub struct Community {
   pub report_count: i16,
   pub unresolved_report_count: i16,
   pub local_removed: bool,
+  /// Whether comments from new members are held until a mod approves them.
+  pub require_comment_approval_for_new_members: bool,
+  /// Members with fewer approved comments in the community than this count as new.
+  pub new_member_comment_threshold: Option<i32>,
+  /// Accounts younger than this many days count as new members.
+  pub new_member_account_age_days: Option<i32>,
+  /// The default language of new comments, if the client doesn't specify one.
+  pub primary_language_id: Option<LanguageId>,
+  /// Posts older than this many seconds don't accept new comments, except from mods.
+  pub auto_lock_posts_after_seconds: Option<i32>,
+  /// Existing content stays visible, but only mods can add posts, comments or votes.
+  pub read_only: bool,
+  /// Posted as a reply to the first comment of each user in the community.
+  pub welcome_message: Option<String>,
+  /// Comment scores are only shown to users who voted on the comment, and to mods.
+  pub hide_scores_until_voted: bool,
+  /// Only members approved by a mod can comment. Followers can still read.
+  pub comments_require_membership: bool,
+  /// Distinguished comments whose score drops below this are undistinguished automatically.
+  pub auto_undistinguish_below_score: Option<i32>,
+  /// Comments from non-mods need at least this many characters.
+  pub min_comment_length: Option<i32>,
+  /// The number of comments in the last day.
+  pub comments_day: i32,
+  /// Downvotes on comments are rejected if false.
+  pub downvotes_enabled: bool,
+  /// Comments which have replies can't be edited anymore by non-mods.
+  pub lock_edits_after_reply: bool,
+  /// Whether to show the instance of comment creators, as in `@user@example.com`.
+  pub show_creator_instance: bool,
+  /// A comment by the mods which is shown above the comments of every post in the community.
+  pub pinned_comment_id: Option<CommentId>,
 }
 
 #[derive(Debug, Clone, derive_new::new)]
@@ -145,6 +185,34 @@ pub struct CommunityInsertForm {
   pub description: Option<String>,
   #[new(default)]
   pub local_removed: Option<bool>,
+  #[new(default)]
+  pub require_comment_approval_for_new_members: Option<bool>,
+  #[new(default)]
+  pub new_member_comment_threshold: Option<i32>,
+  #[new(default)]
+  pub new_member_account_age_days: Option<i32>,
+  #[new(default)]
+  pub primary_language_id: Option<LanguageId>,
+  #[new(default)]
+  pub auto_lock_posts_after_seconds: Option<i32>,
+  #[new(default)]
+  pub read_only: Option<bool>,
+  #[new(default)]
+  pub welcome_message: Option<String>,
+  #[new(default)]
+  pub hide_scores_until_voted: Option<bool>,
+  #[new(default)]
+  pub comments_require_membership: Option<bool>,
+  #[new(default)]
+  pub auto_undistinguish_below_score: Option<i32>,
+  #[new(default)]
+  pub min_comment_length: Option<i32>,
+  #[new(default)]
+  pub downvotes_enabled: Option<bool>,
+  #[new(default)]
+  pub lock_edits_after_reply: Option<bool>,
+  #[new(default)]
+  pub show_creator_instance: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -173,6 +241,21 @@ pub struct CommunityUpdateForm {
   pub visibility: Option<CommunityVisibility>,
   pub description: Option<Option<String>>,
   pub local_removed: Option<bool>,
+  pub require_comment_approval_for_new_members: Option<bool>,
+  pub new_member_comment_threshold: Option<Option<i32>>,
+  pub new_member_account_age_days: Option<Option<i32>>,
+  pub primary_language_id: Option<Option<LanguageId>>,
+  pub auto_lock_posts_after_seconds: Option<Option<i32>>,
+  pub read_only: Option<bool>,
+  pub welcome_message: Option<Option<String>>,
+  pub hide_scores_until_voted: Option<bool>,
+  pub comments_require_membership: Option<bool>,
+  pub auto_undistinguish_below_score: Option<Option<i32>>,
+  pub min_comment_length: Option<Option<i32>>,
+  pub downvotes_enabled: Option<bool>,
+  pub lock_edits_after_reply: Option<bool>,
+  pub show_creator_instance: Option<bool>,
+  pub pinned_comment_id: Option<Option<CommentId>>,
 }
 
 #[skip_serializing_none]
@@ -212,6 +295,14 @@ pub struct CommunityActions {
   #[serde(skip)]
   pub follow_approver_id: Option<PersonId>,
   pub notifications: Option<CommunityNotificationsMode>,
+  /// When the community welcome message was sent to this user.
+  #[serde(skip)]
+  pub welcomed_at: Option<DateTime<Utc>>,
+  /// The default sort for comments in this community, instead of the user's global default.
+  pub comment_sort_type: Option<CommentSortType>,
+  /// When a mod approved this user as a member, which is required to comment in some
+  /// communities.
+  pub became_member_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -224,6 +315,16 @@ pub struct CommunityModeratorForm {
   pub became_moderator_at: DateTime<Utc>,
 }
 
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_actions))]
+pub struct CommunityMemberForm {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  #[new(value = "Utc::now()")]
+  pub became_member_at: DateTime<Utc>,
+}
+
 #[derive(Clone, derive_new::new)]
 #[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
 #[cfg_attr(feature = "full", diesel(table_name = community_actions))]
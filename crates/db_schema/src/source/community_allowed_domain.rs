@@ -0,0 +1,15 @@
+use crate::newtypes::CommunityId;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_allowed_domain;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Insertable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_allowed_domain))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// A domain which comments in the community may link to. If a community has no allowed domains,
+/// links to all domains are allowed.
+pub struct CommunityAllowedDomain {
+  pub community_id: CommunityId,
+  pub domain: String,
+}
@@ -0,0 +1,39 @@
+use crate::newtypes::{CommunityAutomodRuleId, CommunityId};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::AutomodAction;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::community_automod_rule;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = community_automod_rule))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A keyword rule set by community mods, which is checked against new comments.
+pub struct CommunityAutomodRule {
+  pub id: CommunityAutomodRuleId,
+  pub community_id: CommunityId,
+  /// A case insensitive regex.
+  pub pattern: String,
+  pub action: AutomodAction,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_automod_rule))]
+pub struct CommunityAutomodRuleInsertForm {
+  pub community_id: CommunityId,
+  pub pattern: String,
+  pub action: AutomodAction,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "full", derive(AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = community_automod_rule))]
+pub struct CommunityAutomodRuleUpdateForm {
+  pub pattern: Option<String>,
+  pub action: Option<AutomodAction>,
+}
@@ -25,4 +25,6 @@ pub struct FederationQueueState {
   pub fail_count: i32,
   /// timestamp of the last retry attempt (when the last failing activity was resent)
   pub last_retry_at: Option<DateTime<Utc>>,
+  /// error message of the last failed attempt
+  pub last_error: Option<String>,
 }
@@ -99,6 +99,8 @@ pub struct LocalSite {
   #[serde(skip)]
   pub system_account: PersonId,
   pub default_items_per_page: i32,
+  /// The maximum nesting depth of comment replies.
+  pub max_comment_depth: i32,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -200,4 +202,5 @@ pub struct LocalSiteUpdateForm {
   pub disable_email_notifications: Option<bool>,
   pub suggested_communities: Option<MultiCommunityId>,
   pub default_items_per_page: Option<i32>,
+  pub max_comment_depth: Option<i32>,
 }
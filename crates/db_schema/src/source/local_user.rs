@@ -78,6 +78,17 @@ pub struct LocalUser {
   pub show_upvote_percentage: bool,
   pub show_person_votes: bool,
   pub default_items_per_page: i32,
+  /// Whether to show comments on posts which were already read.
+  pub show_read_comments: bool,
+  /// Whether to receive an email when a new follower is pending approval in a community they
+  /// moderate.
+  pub email_pending_community_followers: bool,
+  /// Whether communities can reply to the first comment there with their welcome message.
+  pub receive_community_welcome_messages: bool,
+  /// Suggest to collapse comments from accounts younger than this many days.
+  pub collapse_comments_account_age_days: Option<i32>,
+  /// Suggest to collapse comments from accounts with a lower comment score than this.
+  pub collapse_comments_below_score: Option<i32>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -150,6 +161,16 @@ pub struct LocalUserInsertForm {
   pub show_upvote_percentage: Option<bool>,
   #[new(default)]
   pub show_person_votes: Option<bool>,
+  #[new(default)]
+  pub show_read_comments: Option<bool>,
+  #[new(default)]
+  pub email_pending_community_followers: Option<bool>,
+  #[new(default)]
+  pub receive_community_welcome_messages: Option<bool>,
+  #[new(default)]
+  pub collapse_comments_account_age_days: Option<i32>,
+  #[new(default)]
+  pub collapse_comments_below_score: Option<i32>,
 }
 
 #[derive(Clone, Default)]
@@ -190,4 +211,9 @@ pub struct LocalUserUpdateForm {
   pub show_upvote_percentage: Option<bool>,
   pub show_person_votes: Option<bool>,
   pub default_items_per_page: Option<i32>,
+  pub show_read_comments: Option<bool>,
+  pub email_pending_community_followers: Option<bool>,
+  pub receive_community_welcome_messages: Option<bool>,
+  pub collapse_comments_account_age_days: Option<Option<i32>>,
+  pub collapse_comments_below_score: Option<Option<i32>>,
 }
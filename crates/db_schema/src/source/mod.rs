@@ -7,8 +7,12 @@ pub mod actor_language;
 pub mod captcha_answer;
 pub mod combined;
 pub mod comment;
+pub mod comment_draft;
+pub mod comment_reaction;
 pub mod comment_report;
 pub mod community;
+pub mod community_allowed_domain;
+pub mod community_automod_rule;
 #[cfg(feature = "full")]
 pub mod community_community_follow;
 pub mod community_report;
@@ -40,6 +44,7 @@ pub mod private_message;
 pub mod private_message_report;
 pub mod registration_application;
 pub mod secret;
+pub mod settings_import_failure;
 pub mod site;
 pub mod tag;
 pub mod tagline;
@@ -60,6 +60,9 @@ pub struct Person {
   pub comment_count: i32,
   #[serde(skip)]
   pub comment_score: i32,
+  /// Links from the bio whose page links back to this profile with `rel="me"`. These are set by
+  /// the server and can't be edited directly.
+  pub verified_links: Vec<String>,
 }
 
 #[derive(Clone, derive_new::new)]
@@ -117,6 +120,7 @@ pub struct PersonUpdateForm {
   pub inbox_url: Option<DbUrl>,
   pub matrix_user_id: Option<Option<String>>,
   pub bot_account: Option<bool>,
+  pub verified_links: Option<Vec<String>>,
 }
 
 #[skip_serializing_none]
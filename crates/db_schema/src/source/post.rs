@@ -209,6 +209,10 @@ pub struct PostActions {
   /// True if upvoted, false if downvoted. Upvote is greater than downvote.
   pub vote_is_upvote: Option<bool>,
   pub notifications: Option<PostNotificationsMode>,
+  /// Only notify once for multiple comments by the same person in a short time.
+  pub coalesce_notifications: Option<bool>,
+  /// Temporarily silences all notifications for the post, while keeping the notification mode.
+  pub notifications_muted: Option<bool>,
 }
 
 #[derive(Clone, derive_new::new, Serialize, Deserialize)]
@@ -0,0 +1,31 @@
+use crate::newtypes::LocalUserId;
+use chrono::{DateTime, Utc};
+use lemmy_db_schema_file::enums::SettingsImportKind;
+#[cfg(feature = "full")]
+use lemmy_db_schema_file::schema::settings_import_failure;
+use lemmy_diesel_utils::dburl::DbUrl;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "full", derive(Queryable, Selectable, Identifiable))]
+#[cfg_attr(feature = "full", diesel(table_name = settings_import_failure))]
+#[cfg_attr(feature = "full", diesel(primary_key(local_user_id, kind, ap_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An item from a user settings import which couldn't be fetched, and can be retried later.
+pub struct SettingsImportFailure {
+  pub local_user_id: LocalUserId,
+  pub kind: SettingsImportKind,
+  pub ap_id: DbUrl,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, derive_new::new)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = settings_import_failure))]
+pub struct SettingsImportFailureForm {
+  pub local_user_id: LocalUserId,
+  pub kind: SettingsImportKind,
+  pub ap_id: DbUrl,
+}
@@ -1,9 +1,9 @@
-use crate::newtypes::{CommunityId, PostId, TagId};
+use crate::newtypes::{CommentId, CommunityId, PostId, TagId};
 use chrono::{DateTime, Utc};
 #[cfg(feature = "full")]
 use diesel::{AsExpression, FromSqlRow, sql_types::Nullable};
 #[cfg(feature = "full")]
-use lemmy_db_schema_file::schema::{post_tag, tag};
+use lemmy_db_schema_file::schema::{comment_tag, post_tag, tag};
 use lemmy_diesel_utils::dburl::DbUrl;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -88,3 +88,28 @@ pub struct PostTagForm {
   pub post_id: PostId,
   pub tag_id: TagId,
 }
+
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[cfg_attr(
+  feature = "full",
+  derive(Queryable, Selectable, Associations, Identifiable)
+)]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::comment::Comment)))]
+#[cfg_attr(feature = "full", diesel(belongs_to(crate::source::tag::Tag)))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_tag))]
+#[cfg_attr(feature = "full", diesel(primary_key(comment_id, tag_id)))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+/// An association between a comment and a tag of its community. Set by the comment author.
+pub struct CommentTag {
+  pub comment_id: CommentId,
+  pub tag_id: TagId,
+  pub published_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "full", derive(Insertable, AsChangeset))]
+#[cfg_attr(feature = "full", diesel(table_name = comment_tag))]
+pub struct CommentTagForm {
+  pub comment_id: CommentId,
+  pub tag_id: TagId,
+}
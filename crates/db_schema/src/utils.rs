@@ -11,6 +11,8 @@ const FETCH_LIMIT_DEFAULT: i64 = 20;
 pub const FETCH_LIMIT_MAX: usize = 50;
 pub const SITEMAP_LIMIT: i64 = 50000;
 pub const SITEMAP_DAYS: TimeDelta = TimeDelta::days(31);
+/// Comments by the same person within this window only notify coalescing post subscribers once.
+pub const SUBSCRIBER_NOTIFY_COALESCE_WINDOW: TimeDelta = TimeDelta::minutes(10);
 pub const RANK_DEFAULT: f32 = 0.0001;
 pub const DELETED_REPLACEMENT_TEXT: &str = "*Permanently Deleted*";
 
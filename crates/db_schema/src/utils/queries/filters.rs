@@ -35,6 +35,16 @@ pub fn filter_blocked() -> _ {
     )
 }
 
+/// Like [filter_blocked], but keeps content from persons of blocked instances.
+#[diesel::dsl::auto_type]
+pub fn filter_blocked_except_instance_persons() -> _ {
+  instance_actions::blocked_communities_at
+    .is_null()
+    .or(community_actions::followed_at.is_not_null())
+    .and(community_actions::blocked_at.is_null())
+    .and(person_actions::blocked_at.is_null())
+}
+
 type IsSubscribedType =
   Eq<lemmy_db_schema_file::schema::community_actions::follow_state, Option<CommunityFollowerState>>;
 
@@ -9,7 +9,7 @@ use diesel::{
   expression::SqlLiteral,
   helper_types::Nullable,
   query_source::AliasedField,
-  sql_types::{Json, Timestamptz},
+  sql_types::{self, Json, Text, Timestamptz},
 };
 use lemmy_db_schema_file::{
   aliases::{
@@ -26,6 +26,7 @@ use lemmy_db_schema_file::{
   },
   schema::{
     comment,
+    comment_tag,
     community,
     community_actions,
     instance_actions,
@@ -342,6 +343,18 @@ pub fn post_tags_fragment() -> _ {
     .single_value()
 }
 
+#[diesel::dsl::auto_type]
+/// Gets the tags set on a specific comment
+pub fn comment_tags_fragment() -> _ {
+  let sel: SqlLiteral<Json> = diesel::dsl::sql::<diesel::sql_types::Json>("json_agg(tag.*)");
+  comment_tag::table
+    .inner_join(tag::table)
+    .select(sel)
+    .filter(comment_tag::comment_id.eq(comment::id))
+    .filter(tag::deleted.eq(false))
+    .single_value()
+}
+
 #[diesel::dsl::auto_type]
 /// Gets the post tags available within a specific community
 pub fn community_post_tags_fragment() -> _ {
@@ -353,6 +366,81 @@ pub fn community_post_tags_fragment() -> _ {
     .single_value()
 }
 
+pub type CommentFederationStateType = SqlLiteral<sql_types::Nullable<Text>>;
+
+/// The federation state of a local comment in a remote community, derived from the send queue for
+/// the community's instance. It is sent once any activity published after the comment was
+/// delivered, failed while delivery is being retried, and pending otherwise. Null for all other
+/// comments.
+pub fn comment_federation_state() -> CommentFederationStateType {
+  diesel::dsl::sql::<sql_types::Nullable<Text>>(
+    "CASE WHEN comment.local AND NOT community.local THEN coalesce((
+      SELECT CASE
+        WHEN q.last_successful_published_time_at >= comment.published_at THEN 'Sent'
+        WHEN q.fail_count > 0 THEN 'Failed'
+      END
+      FROM federation_queue_state q
+      WHERE q.instance_id = community.instance_id), 'Pending')
+    END",
+  )
+}
+
+pub type PostCrosspostOfType = SqlLiteral<sql_types::Nullable<sql_types::Integer>>;
+
+/// Posts with the same url are crossposts of each other, and the oldest one which is still visible
+/// counts as the original. Null for posts without url, and for the original itself.
+pub fn post_crosspost_of() -> PostCrosspostOfType {
+  diesel::dsl::sql::<sql_types::Nullable<sql_types::Integer>>(
+    "CASE WHEN post.url IS NOT NULL THEN nullif((
+      SELECT p.id FROM post p
+      WHERE p.url = post.url
+        AND NOT p.deleted
+        AND NOT p.removed
+      ORDER BY p.published_at, p.id
+      LIMIT 1), post.id)
+    END",
+  )
+}
+
+pub type CommentCreatorInstanceDomainType = SqlLiteral<sql_types::Nullable<Text>>;
+
+/// The domain of the comment creator's instance. Null if the community hides creator instances.
+pub fn comment_creator_instance_domain() -> CommentCreatorInstanceDomainType {
+  diesel::dsl::sql::<sql_types::Nullable<Text>>(
+    "(SELECT i.domain FROM instance i
+      WHERE i.id = person.instance_id AND community.show_creator_instance)",
+  )
+}
+
+pub type NullCountType = SqlLiteral<sql_types::Nullable<sql_types::BigInt>>;
+
+/// A null placeholder for optional counts, which are too expensive to compute for every query and
+/// are filled in afterwards when requested.
+pub fn null_count() -> NullCountType {
+  diesel::dsl::sql::<sql_types::Nullable<sql_types::BigInt>>("NULL")
+}
+
+pub type FalseLiteralType = SqlLiteral<sql_types::Bool>;
+
+/// A false placeholder for flags which depend on the viewer, and are filled in after the query.
+pub fn false_literal() -> FalseLiteralType {
+  diesel::dsl::sql::<sql_types::Bool>("FALSE")
+}
+
+pub type NullJsonLiteralType = SqlLiteral<sql_types::Nullable<Json>>;
+
+/// An empty placeholder for lists which depend on the viewer, and are filled in after the query.
+pub fn null_json_literal() -> NullJsonLiteralType {
+  diesel::dsl::sql::<sql_types::Nullable<Json>>("NULL")
+}
+
+pub type NullTextLiteralType = SqlLiteral<sql_types::Nullable<Text>>;
+
+/// A null placeholder for text which depends on the viewer, and is filled in after the query.
+pub fn null_text_literal() -> NullTextLiteralType {
+  diesel::dsl::sql::<sql_types::Nullable<Text>>("NULL")
+}
+
 /// The select for the person1 alias.
 pub fn person1_select() -> Person1AliasAllColumnsTuple {
   person1.fields(person::all_columns)
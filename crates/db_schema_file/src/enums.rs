@@ -50,6 +50,9 @@ pub enum CommentSortType {
   New,
   Old,
   Controversial,
+  /// In the order the comments were stored on this instance, newest first. Unlike `New`, this
+  /// doesn't depend on the publish time claimed by remote instances.
+  Received,
 }
 
 #[derive(
@@ -328,4 +331,47 @@ pub enum ModlogKind {
   ModRemovePost,
   ModTransferCommunity,
   ModLockComment,
+  ModResolveCommunityReports,
+  ModMarkCommentAsAnswer,
+  ModMoveComment,
+  ModDistinguishComment,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::SettingsImportKindEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The kind of item in a user settings import which needs to be fetched from a remote instance.
+pub enum SettingsImportKind {
+  FollowedCommunity,
+  SavedPost,
+  SavedComment,
+  BlockedCommunity,
+  BlockedPerson,
+}
+
+#[derive(EnumString, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "full", derive(DbEnum))]
+#[cfg_attr(
+  feature = "full",
+  ExistingTypePath = "crate::schema::sql_types::AutomodActionEnum"
+)]
+#[cfg_attr(feature = "full", DbValueStyle = "verbatim")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// What happens to a new or edited comment which matches a community automod rule.
+pub enum AutomodAction {
+  /// Remove the comment, with a modlog entry.
+  Remove,
+  /// Keep the comment, but file a report so that mods can review it.
+  Flag,
+  /// Hold the comment in the approval queue until a mod approves it.
+  RequireApproval,
 }
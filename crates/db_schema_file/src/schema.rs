@@ -5,6 +5,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "actor_type_enum"))]
   pub struct ActorTypeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "automod_action_enum"))]
+  pub struct AutomodActionEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "comment_sort_type_enum"))]
   pub struct CommentSortTypeEnum;
@@ -57,6 +61,10 @@ pub mod sql_types {
   #[diesel(postgres_type(name = "registration_mode_enum"))]
   pub struct RegistrationModeEnum;
 
+  #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+  #[diesel(postgres_type(name = "settings_import_kind_enum"))]
+  pub struct SettingsImportKindEnum;
+
   #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
   #[diesel(postgres_type(name = "vote_show_enum"))]
   pub struct VoteShowEnum;
@@ -99,6 +107,12 @@ diesel::table! {
         unresolved_report_count -> Int2,
         federation_pending -> Bool,
         locked -> Bool,
+        pending_approval -> Bool,
+        pending_delete_until -> Nullable<Timestamptz>,
+        is_answer -> Bool,
+        scheduled_publish_time_at -> Nullable<Timestamptz>,
+        content_edited_at -> Nullable<Timestamptz>,
+        pending_federation_until -> Nullable<Timestamptz>,
     }
 }
 
@@ -112,6 +126,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    comment_draft (id) {
+        id -> Int4,
+        person_id -> Int4,
+        post_id -> Int4,
+        parent_id -> Nullable<Int4>,
+        content -> Text,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    comment_reaction (comment_id, person_id, emoji) {
+        comment_id -> Int4,
+        person_id -> Int4,
+        emoji -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     comment_report (id) {
         id -> Int4,
@@ -124,6 +158,22 @@ diesel::table! {
         published_at -> Timestamptz,
         updated_at -> Nullable<Timestamptz>,
         violates_instance_rules -> Bool,
+        snoozed_until -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    comment_source (comment_id) {
+        comment_id -> Int4,
+        content -> Text,
+    }
+}
+
+diesel::table! {
+    comment_tag (comment_id, tag_id) {
+        comment_id -> Int4,
+        tag_id -> Int4,
+        published_at -> Timestamptz,
     }
 }
 
@@ -178,6 +228,22 @@ diesel::table! {
         report_count -> Int2,
         unresolved_report_count -> Int2,
         local_removed -> Bool,
+        require_comment_approval_for_new_members -> Bool,
+        new_member_comment_threshold -> Nullable<Int4>,
+        new_member_account_age_days -> Nullable<Int4>,
+        primary_language_id -> Nullable<Int4>,
+        auto_lock_posts_after_seconds -> Nullable<Int4>,
+        read_only -> Bool,
+        welcome_message -> Nullable<Text>,
+        hide_scores_until_voted -> Bool,
+        comments_require_membership -> Bool,
+        auto_undistinguish_below_score -> Nullable<Int4>,
+        min_comment_length -> Nullable<Int4>,
+        comments_day -> Int4,
+        downvotes_enabled -> Bool,
+        lock_edits_after_reply -> Bool,
+        show_creator_instance -> Bool,
+        pinned_comment_id -> Nullable<Int4>,
     }
 }
 
@@ -185,6 +251,7 @@ diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::CommunityFollowerState;
     use super::sql_types::CommunityNotificationsModeEnum;
+    use super::sql_types::CommentSortTypeEnum;
 
     community_actions (person_id, community_id) {
         followed_at -> Nullable<Timestamptz>,
@@ -197,6 +264,29 @@ diesel::table! {
         follow_state -> Nullable<CommunityFollowerState>,
         follow_approver_id -> Nullable<Int4>,
         notifications -> Nullable<CommunityNotificationsModeEnum>,
+        welcomed_at -> Nullable<Timestamptz>,
+        comment_sort_type -> Nullable<CommentSortTypeEnum>,
+        became_member_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    community_allowed_domain (community_id, domain) {
+        community_id -> Int4,
+        domain -> Text,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AutomodActionEnum;
+
+    community_automod_rule (id) {
+        id -> Int4,
+        community_id -> Int4,
+        pattern -> Text,
+        action -> AutomodActionEnum,
+        published_at -> Timestamptz,
     }
 }
 
@@ -289,6 +379,7 @@ diesel::table! {
         fail_count -> Int4,
         last_retry_at -> Nullable<Timestamptz>,
         last_successful_published_time_at -> Nullable<Timestamptz>,
+        last_error -> Nullable<Text>,
     }
 }
 
@@ -399,6 +490,7 @@ diesel::table! {
         suggested_communities -> Nullable<Int4>,
         system_account -> Int4,
         default_items_per_page -> Int4,
+        max_comment_depth -> Int4,
     }
 }
 
@@ -479,6 +571,11 @@ diesel::table! {
         show_upvote_percentage -> Bool,
         show_person_votes -> Bool,
         default_items_per_page -> Int4,
+        show_read_comments -> Bool,
+        email_pending_community_followers -> Bool,
+        receive_community_welcome_messages -> Bool,
+        collapse_comments_account_age_days -> Nullable<Int4>,
+        collapse_comments_below_score -> Nullable<Int4>,
     }
 }
 
@@ -657,6 +754,7 @@ diesel::table! {
         post_score -> Int4,
         comment_count -> Int4,
         comment_score -> Int4,
+        verified_links -> Array<Text>,
     }
 }
 
@@ -695,6 +793,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    person_report_stats (person_id) {
+        person_id -> Int4,
+        resolved_count -> Int4,
+        valid_count -> Int4,
+        trust_score -> Nullable<Float4>,
+    }
+}
+
 diesel::table! {
     person_saved_combined (id) {
         saved_at -> Timestamptz,
@@ -767,6 +874,8 @@ diesel::table! {
         read_comments_amount -> Nullable<Int4>,
         vote_is_upvote -> Nullable<Bool>,
         notifications -> Nullable<PostNotificationsModeEnum>,
+        coalesce_notifications -> Nullable<Bool>,
+        notifications_muted -> Nullable<Bool>,
     }
 }
 
@@ -788,6 +897,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    post_subscriber_notified (post_id, person_id, creator_id) {
+        post_id -> Int4,
+        person_id -> Int4,
+        creator_id -> Int4,
+        notified_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     post_tag (post_id, tag_id) {
         post_id -> Int4,
@@ -901,6 +1019,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::SettingsImportKindEnum;
+
+    settings_import_failure (local_user_id, kind, ap_id) {
+        local_user_id -> Int4,
+        kind -> SettingsImportKindEnum,
+        ap_id -> Text,
+        published_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     site (id) {
         id -> Int4,
@@ -962,9 +1092,19 @@ diesel::joinable!(comment -> person (creator_id));
 diesel::joinable!(comment -> post (post_id));
 diesel::joinable!(comment_actions -> comment (comment_id));
 diesel::joinable!(comment_actions -> person (person_id));
+diesel::joinable!(comment_draft -> comment (parent_id));
+diesel::joinable!(comment_draft -> person (person_id));
+diesel::joinable!(comment_draft -> post (post_id));
+diesel::joinable!(comment_reaction -> comment (comment_id));
+diesel::joinable!(comment_reaction -> person (person_id));
 diesel::joinable!(comment_report -> comment (comment_id));
+diesel::joinable!(comment_source -> comment (comment_id));
+diesel::joinable!(comment_tag -> comment (comment_id));
+diesel::joinable!(comment_tag -> tag (tag_id));
 diesel::joinable!(community -> instance (instance_id));
 diesel::joinable!(community_actions -> community (community_id));
+diesel::joinable!(community_allowed_domain -> community (community_id));
+diesel::joinable!(community_automod_rule -> community (community_id));
 diesel::joinable!(community_language -> community (community_id));
 diesel::joinable!(community_language -> language (language_id));
 diesel::joinable!(community_report -> community (community_id));
@@ -1006,6 +1146,7 @@ diesel::joinable!(person_content_combined -> post (post_id));
 diesel::joinable!(person_liked_combined -> comment (comment_id));
 diesel::joinable!(person_liked_combined -> person (person_id));
 diesel::joinable!(person_liked_combined -> post (post_id));
+diesel::joinable!(person_report_stats -> person (person_id));
 diesel::joinable!(person_saved_combined -> comment (comment_id));
 diesel::joinable!(person_saved_combined -> person (person_id));
 diesel::joinable!(person_saved_combined -> post (post_id));
@@ -1015,6 +1156,7 @@ diesel::joinable!(post -> person (creator_id));
 diesel::joinable!(post_actions -> person (person_id));
 diesel::joinable!(post_actions -> post (post_id));
 diesel::joinable!(post_report -> post (post_id));
+diesel::joinable!(post_subscriber_notified -> post (post_id));
 diesel::joinable!(post_tag -> post (post_id));
 diesel::joinable!(post_tag -> tag (tag_id));
 diesel::joinable!(private_message_report -> private_message (private_message_id));
@@ -1029,6 +1171,7 @@ diesel::joinable!(search_combined -> community (community_id));
 diesel::joinable!(search_combined -> multi_community (multi_community_id));
 diesel::joinable!(search_combined -> person (person_id));
 diesel::joinable!(search_combined -> post (post_id));
+diesel::joinable!(settings_import_failure -> local_user (local_user_id));
 diesel::joinable!(site -> instance (instance_id));
 diesel::joinable!(site_language -> language (language_id));
 diesel::joinable!(site_language -> site (site_id));
@@ -1037,9 +1180,15 @@ diesel::joinable!(tag -> community (community_id));
 diesel::allow_tables_to_appear_in_same_query!(
   comment,
   comment_actions,
+  comment_draft,
+  comment_reaction,
   comment_report,
+  comment_source,
+  comment_tag,
   community,
   community_actions,
+  community_allowed_domain,
+  community_automod_rule,
   community_language,
   community_report,
   email_verification,
@@ -1067,16 +1216,19 @@ diesel::allow_tables_to_appear_in_same_query!(
   person,
   person_content_combined,
   person_liked_combined,
+  person_report_stats,
   person_saved_combined,
   post,
   post_actions,
   post_report,
+  post_subscriber_notified,
   post_tag,
   private_message,
   private_message_report,
   registration_application,
   report_combined,
   search_combined,
+  settings_import_failure,
   site,
   site_language,
   tag,
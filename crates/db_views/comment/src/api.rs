@@ -1,7 +1,11 @@
-use crate::CommentView;
-use lemmy_db_schema::newtypes::{CommentId, CommunityId, LanguageId, PostId};
+use crate::{CommentFederationTarget, CommentSlimView, CommentStats, CommentView};
+use chrono::{DateTime, Utc};
+use lemmy_db_schema::{
+  newtypes::{CommentId, CommunityId, LanguageId, PersonId, PostId, TagId},
+  source::{comment::Comment, comment_draft::CommentDraft, community::Community},
+};
 use lemmy_db_schema_file::enums::{CommentSortType, ListingType};
-use lemmy_diesel_utils::pagination::PaginationCursor;
+use lemmy_diesel_utils::pagination::{PagedResponse, PaginationCursor};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
@@ -24,6 +28,49 @@ pub struct CreateComment {
   pub post_id: PostId,
   pub parent_id: Option<CommentId>,
   pub language_id: Option<LanguageId>,
+  /// Publish the comment at this time instead of right away. Until then it is only visible to
+  /// you.
+  pub publish_at: Option<DateTime<Utc>>,
+  /// Tags of the community, eg `Source` or `Opinion`.
+  pub tags: Option<Vec<TagId>>,
+  /// Required for new accounts without karma if captchas are enabled. Get it from
+  /// `SolveCommentChallenge`.
+  pub challenge_token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Solve a captcha from `GetCaptcha`, to get a token for creating a comment.
+pub struct SolveCommentChallenge {
+  pub captcha_uuid: String,
+  pub captcha_answer: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommentChallengeResponse {
+  /// Can be used once, within ten minutes.
+  pub challenge_token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Preview how the server would store and render comment content, without creating a comment.
+pub struct PreviewComment {
+  pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PreviewCommentResponse {
+  /// The markdown content as it would be stored.
+  pub content: String,
+  /// The content rendered to html, as sent to other instances.
+  pub html: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -46,6 +93,24 @@ pub struct DeleteComment {
   pub deleted: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Restore your own deleted comment, while its grace period isn't over yet.
+pub struct RestoreComment {
+  pub comment_id: CommentId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Cancel your own scheduled comment before it is published. This deletes the comment.
+pub struct CancelScheduledComment {
+  pub comment_id: CommentId,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -65,6 +130,15 @@ pub struct GetComment {
   pub id: CommentId,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Fetch a comment by its ActivityPub id. Remote comments which aren't known yet are fetched from
+/// their instance.
+pub struct ResolveComment {
+  pub ap_id: String,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -76,13 +150,224 @@ pub struct GetComments {
   /// Filter to within a given time range, in seconds.
   /// IE 60 would give results for the past minute.
   pub time_range_seconds: Option<i32>,
+  /// Only return comments published after this time. Unlike `time_range_seconds` this is an
+  /// absolute time, so clients can poll for new comments since their last fetch.
+  pub created_after: Option<DateTime<Utc>>,
   pub max_depth: Option<i32>,
+  /// The order of replies within each branch, when fetching a comment tree with `max_depth`.
+  /// Defaults to `sort`.
+  pub branch_sort: Option<CommentSortType>,
+  /// Only valid for the sort it was returned with. Start from the first page after changing the
+  /// sort.
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
   pub community_id: Option<CommunityId>,
   pub community_name: Option<String>,
   pub post_id: Option<PostId>,
   pub parent_id: Option<CommentId>,
+  /// Include a preview of the top reply for each comment. Only used for the slim comment list.
+  pub include_child_previews: Option<bool>,
+  /// Only return comments without any replies, eg for finding unanswered questions.
+  pub leaf_only: Option<bool>,
+  /// Only return comments whose content was edited after publishing.
+  pub edited_only: Option<bool>,
+  /// Only return comments whose content was edited after this time. Implies `edited_only`.
+  pub edited_after: Option<DateTime<Utc>>,
+  /// Only return comments which you saved.
+  pub saved_only: Option<bool>,
+  /// Order saved comments by when you saved them, instead of `sort`. Requires `saved_only`.
+  pub saved_sort: Option<SavedCommentSort>,
+  /// Only return comments whose language isn't allowed in their community. Mods only.
+  pub language_mismatch_only: Option<bool>,
+  /// Only return comments in languages which you haven't enabled, eg to find comments which need
+  /// translation. Requires a community.
+  pub needs_translation_only: Option<bool>,
+  /// For the subscribed listing, hide comments from communities without any new posts or comments
+  /// within this many seconds.
+  pub min_community_activity_seconds: Option<i32>,
+  /// Only return comments whose creator's instance runs this software, eg `lemmy` or `mastodon`.
+  /// Admins only.
+  pub creator_software: Option<String>,
+  /// Include comments from instances which you blocked, with `creator_instance_blocked` set.
+  pub show_blocked_instances: Option<bool>,
+  /// Only return comments in communities which you moderate. Admins see all comments.
+  pub moddable_only: Option<bool>,
+  /// Return slim comments without the post and community, for clients which already have them.
+  pub slim: Option<bool>,
+  /// Only return comments with this community tag.
+  pub tag_id: Option<TagId>,
+  /// Only return comments whose content contains this text, eg to find comments within a
+  /// community.
+  pub search_term: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[serde(untagged)]
+/// A list of comments, with slim comments if `GetComments::slim` was given.
+pub enum GetCommentsResponse {
+  Full(PagedResponse<CommentView>),
+  Slim(PagedResponse<CommentSlimView>),
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the comments of a person within a single community, eg for mods reviewing their activity.
+pub struct GetPersonCommentsInCommunity {
+  pub person_id: PersonId,
+  pub community_id: CommunityId,
+  pub sort: Option<CommentSortType>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the highest-scoring comment of each post in a community, eg for "best of" digests.
+pub struct GetTopCommentsPerPost {
+  pub community_id: CommunityId,
+  /// Only consider comments published within this many seconds.
+  pub window_seconds: Option<i32>,
+  /// The maximum number of posts, the newest are used.
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetTopCommentsPerPostResponse {
+  /// One comment per post, highest score first.
+  pub comments: Vec<CommentView>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get comment statistics for the whole instance. Admins only.
+pub struct GetCommentStats {
+  /// The number of communities with the most comments to return.
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetCommentStatsResponse {
+  pub stats: CommentStats,
+  /// The share of removed comments, between 0 and 1.
+  pub removed_ratio: f64,
+  /// The communities with the most comments, most first.
+  pub top_communities: Vec<Community>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the comments with the same parent as the given comment, for next/previous navigation.
+pub struct GetCommentSiblings {
+  pub comment_id: CommentId,
+  pub sort: Option<CommentSortType>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetCommentSiblingsResponse {
+  /// The siblings in the requested order, including the comment itself. At most 50 are returned.
+  pub comments: Vec<CommentView>,
+  /// The index of the comment in `comments`, or none if it isn't included.
+  pub position: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// Sorts for saved comments, by the time they were saved.
+pub enum SavedCommentSort {
+  SavedNew,
+  SavedOld,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Add an emoji reaction to a comment. Only emojis allowed by the instance can be used.
+pub struct AddCommentReaction {
+  pub comment_id: CommentId,
+  pub emoji: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Remove your emoji reaction from a comment.
+pub struct RemoveCommentReaction {
+  pub comment_id: CommentId,
+  pub emoji: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Save a draft for a new comment, replacing any previous draft for the same post and parent.
+pub struct SaveCommentDraft {
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+  pub content: String,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get your comment draft for a post, or for a reply to `parent_id`.
+pub struct GetCommentDraft {
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete your comment draft for a post, or for a reply to `parent_id`.
+pub struct DeleteCommentDraft {
+  pub post_id: PostId,
+  pub parent_id: Option<CommentId>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct CommentDraftResponse {
+  pub draft: Option<CommentDraft>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the delivery state of your own comment to remote instances. Admins can check any comment.
+pub struct GetCommentFederationStatus {
+  pub comment_id: CommentId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetCommentFederationStatusResponse {
+  pub targets: Vec<CommentFederationTarget>,
 }
 
 #[skip_serializing_none]
@@ -107,6 +392,61 @@ pub struct LockComment {
   pub reason: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Pins a comment above the comments of every post in its community, eg for a note about the
+/// rules. Only one comment can be pinned per community, and it must be written by a mod or admin.
+/// Mods only.
+pub struct PinCommentInCommunity {
+  pub comment_id: CommentId,
+  pub pinned: bool,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Moves a comment and its replies to another post, for example to merge duplicate threads. Only
+/// for mods of both communities, or admins.
+pub struct MoveCommentSubtree {
+  pub comment_id: CommentId,
+  pub target_post_id: PostId,
+  /// The comment on the target post to reply to. Without it the comment becomes top-level.
+  pub new_parent_id: Option<CommentId>,
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Mark a comment as the accepted answer to its post. Only the post creator or a mod can do this.
+///
+/// A post has at most one answer, so this replaces any previously marked answer.
+pub struct MarkCommentAsAnswer {
+  pub comment_id: CommentId,
+  pub answer: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Approves a comment which was held because its creator is a new member of the community.
+pub struct ApproveComment {
+  pub comment_id: CommentId,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List comments waiting for approval in the communities you moderate, oldest first.
+pub struct ListPendingComments {
+  pub community_id: Option<CommunityId>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -117,6 +457,40 @@ pub struct PurgeComment {
   pub reason: String,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List comments whose post doesn't exist anymore, or whose parent comment was purged. Admins
+/// only.
+pub struct ListOrphanedComments {
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListOrphanedCommentsResponse {
+  /// Only the topmost orphan of each comment tree.
+  pub comments: Vec<Comment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Purges all orphaned comments together with their replies. Admins only.
+pub struct PurgeOrphanedComments {
+  pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PurgeOrphanedCommentsResponse {
+  /// The number of purged comments.
+  pub purged: i64,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -146,4 +520,6 @@ pub struct EditComment {
   pub comment_id: CommentId,
   pub content: Option<String>,
   pub language_id: Option<LanguageId>,
+  /// Replaces the tags of the comment. Tags need to belong to the community of the comment.
+  pub tags: Option<Vec<TagId>>,
 }
@@ -1,37 +1,65 @@
-use crate::{CommentSlimView, CommentView};
+use crate::{
+  CommentFederationState,
+  CommentFederationTarget,
+  CommentPreview,
+  CommentSlimView,
+  CommentStats,
+  CommentView,
+  api::SavedCommentSort,
+};
+use chrono::{DateTime, TimeDelta, Utc};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   JoinOnDsl,
   NullableExpressionMethods,
+  OptionalExtension,
   QueryDsl,
   SelectableHelper,
-  dsl::exists,
+  deserialize::FromSql,
+  dsl::{count_star, exists, not},
+  pg::{Pg, PgValue},
+  sql_query,
+  sql_types::{Array, Integer, Nullable, Text},
 };
-use diesel_async::RunQueryDsl;
-use diesel_ltree::{Ltree, LtreeExtensions, nlevel};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_ltree::{Ltree, LtreeExtensions, nlevel, subpath};
 use i_love_jesus::asc_if;
 use lemmy_db_schema::{
   impls::local_user::LocalUserOptionHelper,
-  newtypes::{CommentId, CommunityId, PostId},
+  newtypes::{CommentId, CommunityId, PostId, TagId},
   source::{
+    activity::SentActivity,
     comment::{Comment, comment_keys as key},
+    comment_reaction::{CommentReaction, CommentReactionsView},
+    community::Community,
+    federation_queue_state::FederationQueueState,
     local_user::LocalUser,
+    person::Person,
     site::Site,
   },
   utils::{
     limit_fetch,
-    queries::filters::{filter_blocked, filter_suggested_communities},
+    queries::{
+      filters::{
+        filter_blocked,
+        filter_blocked_except_instance_persons,
+        filter_suggested_communities,
+      },
+      selects::{comment_federation_state, false_literal},
+    },
   },
 };
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
+  aliases::person1,
   enums::{
     CommentSortType::{self, *},
     CommunityFollowerState,
     CommunityVisibility,
     ListingType,
+    ModlogKind,
   },
   joins::{
     creator_community_actions_join,
@@ -45,7 +73,27 @@ use lemmy_db_schema_file::{
     my_local_user_admin_join,
     my_person_actions_join,
   },
-  schema::{comment, community, community_actions, local_user_language, person, post},
+  schema::{
+    comment,
+    comment_actions,
+    comment_source,
+    comment_tag,
+    community,
+    community_actions,
+    community_language,
+    federation_allowlist,
+    federation_blocklist,
+    federation_queue_state,
+    instance,
+    instance_actions,
+    local_site,
+    local_user_language,
+    modlog,
+    person,
+    post,
+    post_actions,
+    site,
+  },
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -54,24 +102,44 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    ReverseCursor,
     paginate_response,
   },
   traits::Crud,
-  utils::{Subpath, now, seconds_to_pg_interval},
+  utils::{Subpath, functions::coalesce, now, seconds_to_pg_interval, substring_search},
 };
-use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+  utils::validation::check_api_elements_count,
+};
+use std::collections::HashMap;
+
+diesel::alias!(comment as reply: Reply);
+diesel::alias!(post as active_post: ActivePost);
 
+/// The cursor stores a snapshot of the hot rank next to the comment id. Hot ranks are updated
+/// periodically, and reading the current value would shift the page boundary, leading to
+/// duplicated or skipped comments while scrolling.
+///
+/// The cursor is only valid for the sort it was created with, switching the sort requires starting
+/// from the first page again.
 impl PaginationCursorConversion for CommentView {
   type PaginatedType = Comment;
   fn to_cursor(&self) -> CursorData {
-    CursorData::new_id(self.comment.id.0)
+    CursorData::new_plain(format!("{},{}", self.comment.id.0, self.comment.hot_rank))
   }
 
   async fn from_cursor(
     data: CursorData,
     pool: &mut DbPool<'_>,
   ) -> LemmyResult<Self::PaginatedType> {
-    Comment::read(pool, CommentId(data.id()?)).await
+    let data = data.plain();
+    let (id, hot_rank) = data
+      .split_once(',')
+      .ok_or(LemmyErrorType::CouldntParsePaginationToken)?;
+    let mut comment = Comment::read(pool, CommentId(id.parse()?)).await?;
+    comment.hot_rank = hot_rank.parse()?;
+    Ok(comment)
   }
 }
 
@@ -91,6 +159,8 @@ impl CommentView {
     let my_person_actions_join: my_person_actions_join = my_person_actions_join(my_person_id);
     let creator_local_instance_actions_join: creator_local_instance_actions_join =
       creator_local_instance_actions_join(local_instance_id);
+    // The remover is filled in after the query, only for comments which the viewer can moderate
+    let comment_remover_join = person1.on(false_literal());
 
     comment::table
       .inner_join(person::table)
@@ -106,6 +176,7 @@ impl CommentView {
       .left_join(my_local_user_admin_join)
       .left_join(my_instance_communities_actions_join)
       .left_join(my_instance_persons_actions_join_1)
+      .left_join(comment_remover_join)
   }
 
   pub async fn read(
@@ -114,10 +185,29 @@ impl CommentView {
     my_local_user: Option<&'_ LocalUser>,
     local_instance_id: InstanceId,
   ) -> LemmyResult<Self> {
+    Self::read_many(pool, &[comment_id], my_local_user, local_instance_id)
+      .await?
+      .into_iter()
+      .next()
+      .ok_or(LemmyErrorType::NotFound.into())
+  }
+
+  /// Reads multiple comments in a single query, with the same permission checks as
+  /// [CommentView::read]. Comments which don't exist or which the user can't see are left out.
+  /// The order isn't guaranteed. Fails with [LemmyErrorType::TooManyItems] if too many ids are
+  /// given.
+  pub async fn read_many(
+    pool: &mut DbPool<'_>,
+    comment_ids: &[CommentId],
+    my_local_user: Option<&'_ LocalUser>,
+    local_instance_id: InstanceId,
+  ) -> LemmyResult<Vec<Self>> {
+    check_api_elements_count(comment_ids.len())?;
     let conn = &mut get_conn(pool).await?;
+    let my_person_id = my_local_user.person_id();
 
-    let mut query = Self::joins(my_local_user.person_id(), local_instance_id)
-      .filter(comment::id.eq(comment_id))
+    let mut query = Self::joins(my_person_id, local_instance_id)
+      .filter(comment::id.eq_any(comment_ids))
       .select(Self::as_select())
       .into_boxed();
 
@@ -135,10 +225,455 @@ impl CommentView {
       );
     }
 
-    query
-      .first::<Self>(conn)
+    let comment_views = query
+      .load::<Self>(conn)
       .await
-      .with_lemmy_type(LemmyErrorType::NotFound)
+      .with_lemmy_type(LemmyErrorType::NotFound)?
+      .into_iter()
+      // Comments waiting for approval are only visible to their creator and to mods.
+      .filter(|c| !c.comment.pending_approval || c.can_mod || Some(c.creator.id) == my_person_id)
+      // Scheduled comments are only visible to their creator.
+      .filter(|c| {
+        c.comment.scheduled_publish_time_at.is_none() || Some(c.creator.id) == my_person_id
+      })
+      // Comments with a pending delete are only visible to their creator, who can restore them.
+      .filter(|c| c.comment.pending_delete_until.is_none() || Some(c.creator.id) == my_person_id)
+      .collect::<Vec<_>>();
+    Self::prepare_for_viewer(comment_views, my_local_user, conn).await
+  }
+
+  /// Counts the comments of a person which the viewer can see, e.g. for profile pages. Deleted,
+  /// removed and unpublished comments are excluded, as well as comments in private communities
+  /// which the viewer can't access.
+  pub async fn count_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    my_local_user: Option<&'_ LocalUser>,
+  ) -> LemmyResult<i64> {
+    let conn = &mut get_conn(pool).await?;
+    let my_community_actions_join: my_community_actions_join =
+      my_community_actions_join(my_local_user.person_id());
+
+    let mut query = comment::table
+      .inner_join(post::table)
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .left_join(my_community_actions_join)
+      .filter(comment::creator_id.eq(person_id))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false))
+      .filter(comment::federation_pending.eq(false))
+      .filter(comment::pending_approval.eq(false))
+      .filter(comment::pending_delete_until.is_null())
+      .filter(comment::scheduled_publish_time_at.is_null())
+      .select(count_star())
+      .into_boxed();
+
+    query = my_local_user.visible_communities_only(query);
+    if !my_local_user.is_admin() {
+      query = query.filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+      );
+    }
+
+    Ok(query.first::<i64>(conn).await?)
+  }
+
+  /// Lists all comments of a community in ascending id order, for archiving or exporting the
+  /// community. The order is stable, so callers page through the whole history by passing the
+  /// id of the last comment in the previous batch as `after_id`, until an empty batch is
+  /// returned. Admins also receive removed and deleted comments, but not those which are about to
+  /// be deleted.
+  pub async fn stream_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    after_id: Option<CommentId>,
+    limit: Option<i64>,
+    my_local_user: Option<&'_ LocalUser>,
+    local_instance_id: InstanceId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let limit = limit_fetch(limit, None)?;
+
+    let mut query = Self::joins(my_local_user.person_id(), local_instance_id)
+      .filter(post::community_id.eq(community_id))
+      .filter(comment::federation_pending.eq(false))
+      .filter(comment::pending_approval.eq(false))
+      .filter(comment::scheduled_publish_time_at.is_null())
+      .filter(comment::pending_delete_until.is_null())
+      .select(Self::as_select())
+      .order_by(comment::id.asc())
+      .limit(limit)
+      .into_boxed();
+
+    if let Some(after_id) = after_id {
+      query = query.filter(comment::id.gt(after_id));
+    }
+
+    query = my_local_user.visible_communities_only(query);
+    if !my_local_user.is_admin() {
+      query = query
+        .filter(comment::deleted.eq(false))
+        .filter(comment::removed.eq(false))
+        .filter(
+          community::visibility
+            .ne(CommunityVisibility::Private)
+            .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+        );
+    }
+
+    let res = query.load::<Self>(conn).await?;
+    Self::prepare_for_viewer(res, my_local_user, conn).await
+  }
+
+  /// Returns the highest-scoring comment of each post in a community, highest score first. Only
+  /// comments published within the last `window_seconds` count. `limit` is the number of posts,
+  /// the newest of them are used.
+  pub async fn top_per_post(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    window_seconds: Option<i32>,
+    limit: Option<i64>,
+    my_local_user: Option<&'_ LocalUser>,
+    local_instance_id: InstanceId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let limit = limit_fetch(limit, None)?;
+    let since = window_seconds
+      .map(|s| Utc::now() - TimeDelta::seconds(s.into()))
+      .unwrap_or(DateTime::UNIX_EPOCH);
+
+    let mut res = Self::joins(my_local_user.person_id(), local_instance_id)
+      .filter(post::community_id.eq(community_id))
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false))
+      .filter(comment::published_at.gt(since))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false))
+      .filter(comment::pending_delete_until.is_null())
+      .filter(comment::federation_pending.eq(false))
+      .filter(comment::pending_approval.eq(false))
+      .filter(comment::scheduled_publish_time_at.is_null())
+      .filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+      )
+      .select(Self::as_select())
+      .distinct_on(comment::post_id)
+      .order_by((
+        comment::post_id.desc(),
+        comment::score.desc(),
+        comment::id.asc(),
+      ))
+      .limit(limit)
+      .load::<Self>(conn)
+      .await?;
+
+    res.sort_by_key(|c| std::cmp::Reverse(c.comment.score));
+    Self::prepare_for_viewer(res, my_local_user, conn).await
+  }
+
+  /// Hides and fills in the fields which depend on the viewer. All comment listings need this,
+  /// so that a comment looks the same wherever it is listed.
+  async fn prepare_for_viewer(
+    comment_views: Vec<Self>,
+    my_local_user: Option<&'_ LocalUser>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<Vec<Self>> {
+    let my_person_id = my_local_user.person_id();
+    let mut res = comment_views
+      .into_iter()
+      .map(|c| {
+        c.hide_creator_only_fields(my_person_id)
+          .hide_scores_until_voted()
+          .hide_creator_avatar(my_local_user.show_avatars())
+          .suggest_collapse(my_local_user)
+      })
+      .collect::<Vec<_>>();
+    Self::fill_viewer_has_replied(&mut res, my_person_id, conn).await?;
+    Self::fill_removal_details(&mut res, conn).await?;
+    Self::fill_federation_state(&mut res, my_person_id, conn).await?;
+    Self::fill_content_source(&mut res, my_person_id, conn).await?;
+    Self::fill_creator_instance_blocked(res.iter_mut(), my_person_id, conn).await?;
+    Self::fill_reactions(&mut res, my_person_id, conn).await?;
+    Ok(res)
+  }
+
+  /// The federation state and content source are only meant for the creator of the comment.
+  fn hide_creator_only_fields(mut self, my_person_id: Option<PersonId>) -> Self {
+    if Some(self.creator.id) != my_person_id {
+      self.federation_state = None;
+      self.content_source = None;
+    }
+    self
+  }
+
+  /// In communities which hide scores until voted, the score and vote counts are zero for users
+  /// who didn't vote on the comment yet. Mods always see them. Views which include comments need
+  /// to apply this as well.
+  pub fn hide_scores_until_voted(mut self) -> Self {
+    let voted = self
+      .comment_actions
+      .as_ref()
+      .is_some_and(|a| a.vote_is_upvote.is_some());
+    if self.community.hide_scores_until_voted && !self.can_mod && !voted {
+      self.comment.score = 0;
+      self.comment.upvotes = 0;
+      self.comment.downvotes = 0;
+    }
+    self
+  }
+
+  /// The comment which the community of the post pinned above the comments of all its posts.
+  async fn community_pinned_comment_id(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+  ) -> LemmyResult<Option<CommentId>> {
+    let conn = &mut get_conn(pool).await?;
+    let pinned_comment_id = post::table
+      .inner_join(community::table)
+      .filter(post::id.eq(post_id))
+      .select(community::pinned_comment_id)
+      .first::<Option<CommentId>>(conn)
+      .await
+      .optional()?;
+    Ok(pinned_comment_id.flatten())
+  }
+
+  /// Avatars aren't sent to users who disabled them, to save bandwidth.
+  fn hide_creator_avatar(mut self, show_avatars: bool) -> Self {
+    if !show_avatars {
+      self.creator.avatar = None;
+    }
+    self
+  }
+
+  /// Suggests collapsing comments from accounts which are newer, or have a lower comment score,
+  /// than the thresholds of the current user.
+  fn suggest_collapse(mut self, my_local_user: Option<&LocalUser>) -> Self {
+    let Some(local_user) = my_local_user else {
+      return self;
+    };
+    let new_account = local_user
+      .collapse_comments_account_age_days
+      .is_some_and(|days| self.creator.published_at > Utc::now() - TimeDelta::days(days.into()));
+    let low_score = local_user
+      .collapse_comments_below_score
+      .is_some_and(|score| self.creator.comment_score < score);
+    self.collapsed_suggested = new_account || low_score;
+    self
+  }
+
+  /// Sets `viewer_has_replied` for comments which have a direct reply by the given person.
+  async fn fill_viewer_has_replied(
+    items: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let Some(my_person_id) = my_person_id else {
+      return Ok(());
+    };
+    let ids = items
+      .iter()
+      .filter(|c| c.comment.child_count > 0)
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let my_replies = reply
+      .filter(reply.field(comment::creator_id).eq(my_person_id))
+      .filter(subpath(reply.field(comment::path), 0, -1).eq(comment::path));
+    let replied = comment::table
+      .filter(comment::id.eq_any(ids))
+      .filter(exists(my_replies))
+      .select(comment::id)
+      .load::<CommentId>(conn)
+      .await?;
+
+    for item in items.iter_mut() {
+      item.viewer_has_replied = replied.contains(&item.comment.id);
+    }
+    Ok(())
+  }
+
+  /// Sets who removed each comment and why, from the latest removal in the modlog. This is only
+  /// meant for those who can moderate the comment.
+  async fn fill_removal_details(
+    items: &mut [CommentView],
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let ids = items
+      .iter()
+      .filter(|c| c.can_mod && c.comment.removed)
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let removals = modlog::table
+      .inner_join(person::table.on(modlog::mod_id.eq(person::id)))
+      .filter(modlog::target_comment_id.eq_any(ids))
+      .filter(modlog::kind.eq(ModlogKind::ModRemoveComment))
+      .filter(modlog::is_revert.eq(false))
+      .distinct_on(modlog::target_comment_id)
+      .order_by((modlog::target_comment_id, modlog::published_at.desc()))
+      .select((
+        modlog::target_comment_id,
+        Person::as_select(),
+        modlog::reason,
+      ))
+      .load::<(Option<CommentId>, Person, Option<String>)>(conn)
+      .await?;
+
+    for item in items.iter_mut() {
+      if let Some((_, person, reason)) = removals
+        .iter()
+        .find(|(id, ..)| *id == Some(item.comment.id))
+      {
+        item.removed_by = Some(person.clone());
+        item.removed_reason = reason.clone();
+      }
+    }
+    Ok(())
+  }
+
+  /// Sets `federation_state` for local comments of the given person in remote communities. The
+  /// state is only shown to the creator, so it isn't computed for other comments.
+  async fn fill_federation_state(
+    items: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let ids = items
+      .iter()
+      .filter(|c| Some(c.creator.id) == my_person_id && c.comment.local && !c.community.local)
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let states = comment::table
+      .inner_join(post::table.inner_join(community::table))
+      .filter(comment::id.eq_any(ids))
+      .select((comment::id, comment_federation_state()))
+      .load::<(CommentId, Option<CommentFederationState>)>(conn)
+      .await?;
+
+    for item in items.iter_mut() {
+      item.federation_state = states
+        .iter()
+        .find(|(id, _)| *id == item.comment.id)
+        .and_then(|(_, state)| *state);
+    }
+    Ok(())
+  }
+
+  /// Sets `content_source` for the comments of the given person. The source is only shown to the
+  /// creator, so it isn't loaded for other comments.
+  async fn fill_content_source(
+    items: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let ids = items
+      .iter()
+      .filter(|c| Some(c.creator.id) == my_person_id && c.comment.local)
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let sources = comment_source::table
+      .filter(comment_source::comment_id.eq_any(ids))
+      .select((comment_source::comment_id, comment_source::content))
+      .load::<(CommentId, String)>(conn)
+      .await?;
+
+    for item in items.iter_mut() {
+      item.content_source = sources
+        .iter()
+        .find(|(id, _)| *id == item.comment.id)
+        .map(|(_, content)| content.clone());
+    }
+    Ok(())
+  }
+
+  /// Sets `creator_instance_blocked` for comments whose creator is from an instance which the
+  /// given person blocked, or which the admins blocked or left out of the allowlist. Views which
+  /// include comments need to call this as well.
+  pub async fn fill_creator_instance_blocked<'a>(
+    items: impl IntoIterator<Item = &'a mut CommentView>,
+    my_person_id: Option<PersonId>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let mut items = items.into_iter().collect::<Vec<_>>();
+    let instance_ids = items
+      .iter()
+      .map(|c| c.creator.instance_id)
+      .collect::<Vec<_>>();
+    if instance_ids.is_empty() {
+      return Ok(());
+    }
+
+    let mut blocked = federation_blocklist::table
+      .filter(federation_blocklist::instance_id.eq_any(&instance_ids))
+      .select(federation_blocklist::instance_id)
+      .load::<InstanceId>(conn)
+      .await?;
+    if let Some(my_person_id) = my_person_id {
+      let my_blocks = instance_actions::table
+        .filter(instance_actions::person_id.eq(my_person_id))
+        .filter(instance_actions::instance_id.eq_any(&instance_ids))
+        .filter(instance_actions::blocked_persons_at.is_not_null())
+        .select(instance_actions::instance_id)
+        .load::<InstanceId>(conn)
+        .await?;
+      blocked.extend(my_blocks);
+    }
+    // With an allowlist, remote instances which aren't on it are blocked as well
+    let allowlist = federation_allowlist::table
+      .select(federation_allowlist::instance_id)
+      .load::<InstanceId>(conn)
+      .await?;
+
+    for item in items.iter_mut() {
+      let instance_id = item.creator.instance_id;
+      item.creator_instance_blocked = blocked.contains(&instance_id)
+        || (!allowlist.is_empty() && !item.creator.local && !allowlist.contains(&instance_id));
+    }
+    Ok(())
+  }
+
+  /// Sets the emoji `reactions` of each comment, and whether the given person added them.
+  async fn fill_reactions(
+    items: &mut [CommentView],
+    my_person_id: Option<PersonId>,
+    conn: &mut AsyncPgConnection,
+  ) -> LemmyResult<()> {
+    let ids = items.iter().map(|c| c.comment.id).collect::<Vec<_>>();
+    if ids.is_empty() {
+      return Ok(());
+    }
+
+    let reactions =
+      CommentReaction::read_for_comments(&mut conn.into(), &ids, my_person_id).await?;
+    for item in items.iter_mut() {
+      item.reactions = CommentReactionsView(
+        reactions
+          .iter()
+          .filter(|(comment_id, _)| *comment_id == item.comment.id)
+          .map(|(_, reaction)| reaction.clone())
+          .collect(),
+      );
+    }
+    Ok(())
   }
 
   pub fn map_to_slim(self) -> CommentSlimView {
@@ -152,7 +687,77 @@ impl CommentView {
       creator_banned: self.creator_banned,
       creator_banned_from_community: self.creator_banned_from_community,
       creator_is_moderator: self.creator_is_moderator,
+      top_child_preview: None,
+    }
+  }
+}
+
+impl CommentSlimView {
+  /// Fills in the `top_child_preview` for each comment, which is its direct reply with the highest
+  /// hot rank. Replies which aren't listed for everyone, like deleted, removed, unpublished or
+  /// pending replies, are never shown as a preview, as well as replies by persons who the viewer
+  /// blocked directly or by instance.
+  pub async fn fill_top_child_previews(
+    items: &mut [CommentSlimView],
+    my_person_id: Option<PersonId>,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<()> {
+    let parent_ids = items
+      .iter()
+      .filter(|c| c.comment.child_count > 0)
+      .map(|c| c.comment.id.0)
+      .collect::<Vec<i32>>();
+    if parent_ids.is_empty() {
+      return Ok(());
+    }
+
+    let conn = &mut get_conn(pool).await?;
+
+    // Diesel doesn't support lateral joins, so use raw sql here.
+    let previews = sql_query(
+      "SELECT parent.id AS parent_id, child.id, child.creator_id, child.content,
+        child.published_at, child.score, child.child_count
+      FROM comment parent
+      CROSS JOIN LATERAL (
+        SELECT c.* FROM comment c
+        WHERE c.path <@ parent.path
+          AND nlevel(c.path) = nlevel(parent.path) + 1
+          AND NOT c.deleted
+          AND NOT c.removed
+          AND NOT c.federation_pending
+          AND NOT c.pending_approval
+          AND c.scheduled_publish_time_at IS NULL
+          AND c.pending_delete_until IS NULL
+          AND NOT EXISTS (
+            SELECT 1 FROM person_actions pa
+            WHERE pa.person_id = $2
+              AND pa.target_id = c.creator_id
+              AND pa.blocked_at IS NOT NULL
+          )
+          AND NOT EXISTS (
+            SELECT 1 FROM person p
+            JOIN instance_actions ia ON ia.instance_id = p.instance_id
+            WHERE p.id = c.creator_id
+              AND ia.person_id = $2
+              AND ia.blocked_persons_at IS NOT NULL
+          )
+        ORDER BY c.hot_rank DESC, c.id DESC
+        LIMIT 1
+      ) child
+      WHERE parent.id = ANY($1)",
+    )
+    .bind::<Array<Integer>, _>(parent_ids)
+    .bind::<Nullable<Integer>, _>(my_person_id.map(|id| id.0))
+    .load::<CommentPreview>(conn)
+    .await?;
+
+    for item in items.iter_mut() {
+      item.top_child_preview = previews
+        .iter()
+        .find(|p| p.parent_id == item.comment.id)
+        .cloned();
     }
+    Ok(())
   }
 }
 
@@ -161,11 +766,57 @@ pub struct CommentQuery<'a> {
   pub listing_type: Option<ListingType>,
   pub sort: Option<CommentSortType>,
   pub time_range_seconds: Option<i32>,
+  /// Only return comments published after this time. Useful for polling for new comments.
+  pub created_after: Option<DateTime<Utc>>,
   pub community_id: Option<CommunityId>,
   pub post_id: Option<PostId>,
   pub parent_path: Option<Ltree>,
+  /// Only return comments with the same parent as the comment with this path, including itself.
+  /// Requires `post_id`, because all top-level comments have the same parent.
+  pub sibling_path: Option<Ltree>,
+  pub creator_id: Option<PersonId>,
+  /// Comments from any of these creators. Can't be combined with `creator_id`.
+  pub creator_ids: Option<Vec<PersonId>>,
+  /// Only return comments which are waiting for mod approval, for the mod queue.
+  pub pending_approval_only: Option<bool>,
+  /// Only return comments which have no replies.
+  pub leaf_only: Option<bool>,
+  /// Only return comments whose content was edited after publishing.
+  pub edited_only: Option<bool>,
+  /// Only return comments whose content was edited after this time. Implies `edited_only`.
+  pub edited_after: Option<DateTime<Utc>>,
   pub local_user: Option<&'a LocalUser>,
   pub max_depth: Option<i32>,
+  /// The order of replies within each branch for tree fetches, instead of `sort`. Distinguished
+  /// comments still go first within their branch, unless the branch sort is `New` or `Old`.
+  pub branch_sort: Option<CommentSortType>,
+  /// Only return comments saved by `local_user`.
+  pub saved_only: Option<bool>,
+  /// Order by the time `local_user` saved the comments, instead of `sort`. Only used together with
+  /// `saved_only`.
+  pub saved_sort: Option<SavedCommentSort>,
+  /// Only return comments whose language isn't allowed in their community. Limited to communities
+  /// which `local_user` moderates, unless they are an admin.
+  pub language_mismatch_only: Option<bool>,
+  /// Only return comments in a language which `local_user` hasn't enabled, to find comments which
+  /// need translation. Requires `community_id`.
+  pub needs_translation_only: Option<bool>,
+  /// For the subscribed listing, hide comments from communities without any new posts or comments
+  /// within this many seconds.
+  pub min_community_activity_seconds: Option<i32>,
+  /// Only return comments whose creator's instance runs this software, as reported by nodeinfo.
+  /// Admins only.
+  pub creator_software: Option<String>,
+  /// Include comments from persons of instances which `local_user` blocked. They have
+  /// `creator_instance_blocked` set, so clients can collapse them.
+  pub show_blocked_instances: Option<bool>,
+  /// Only return comments in communities which `local_user` moderates, or all comments if they
+  /// are an admin.
+  pub moddable_only: Option<bool>,
+  /// Only return comments with this community tag.
+  pub tag_id: Option<TagId>,
+  /// Only return comments whose content contains this text, case-insensitively.
+  pub search_term: Option<String>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -190,14 +841,48 @@ impl CommentQuery<'_> {
       query = query.filter(comment::post_id.eq(post_id));
     };
 
+    // The pinned comment of the community is added on top of the first page instead, so it must
+    // not show up a second time if it belongs to this post.
+    let community_pinned_comment_id = match o.post_id {
+      Some(post_id) if o.is_post_listing() => {
+        CommentView::community_pinned_comment_id(pool, post_id).await?
+      }
+      _ => None,
+    };
+    if let Some(pinned_id) = community_pinned_comment_id {
+      query = query.filter(comment::id.ne(pinned_id));
+    }
+
     if let Some(parent_path) = o.parent_path.as_ref() {
       query = query.filter(comment::path.contained_by(parent_path));
     };
 
+    if let Some(sibling_path) = o.sibling_path.as_ref() {
+      if o.post_id.is_none() {
+        Err(LemmyErrorType::ContradictingFilters)?
+      }
+      let parent_path = sibling_path
+        .0
+        .rsplit_once('.')
+        .map(|(parent, _)| parent.to_string())
+        .unwrap_or_default();
+      query = query.filter(subpath(comment::path, 0, -1).eq(Ltree(parent_path)));
+    }
+
     if let Some(community_id) = o.community_id {
       query = query.filter(post::community_id.eq(community_id));
     }
 
+    match (o.creator_id, o.creator_ids) {
+      (Some(_), Some(_)) => Err(LemmyErrorType::ContradictingFilters)?,
+      (Some(creator_id), None) => query = query.filter(comment::creator_id.eq(creator_id)),
+      (None, Some(creator_ids)) => {
+        check_api_elements_count(creator_ids.len())?;
+        query = query.filter(comment::creator_id.eq_any(creator_ids));
+      }
+      (None, None) => {}
+    }
+
     let is_subscribed = community_actions::followed_at.is_not_null();
 
     // For posts, we only show hidden if its subscribed, but for comments,
@@ -212,25 +897,69 @@ impl CommentQuery<'_> {
       ListingType::Suggested => query.filter(filter_suggested_communities()),
     };
 
+    if let Some(min_activity_seconds) = o.min_community_activity_seconds
+      && o.listing_type.unwrap_or_default() == ListingType::Subscribed
+    {
+      let last_activity = coalesce(
+        active_post.field(post::newest_comment_time_at),
+        active_post.field(post::published_at),
+      );
+      query = query.filter(exists(
+        active_post
+          .filter(active_post.field(post::community_id).eq(community::id))
+          .filter(last_activity.gt(now() - seconds_to_pg_interval(min_activity_seconds))),
+      ));
+    }
+
     if !o.local_user.show_bot_accounts() {
       query = query.filter(person::bot_account.eq(false));
     };
 
-    if o.local_user.is_some() && o.listing_type.unwrap_or_default() != ListingType::ModeratorView {
+    // Hide comments on posts which were already read. Not for the comments of a single post,
+    // because opening the post marks it as read.
+    if !o.local_user.show_read_comments()
+      && o.post_id.is_none()
+      && let Some(my_person_id) = my_person_id
+    {
+      query = query.filter(not(exists(
+        post_actions::table
+          .filter(post_actions::post_id.eq(comment::post_id))
+          .filter(post_actions::person_id.eq(my_person_id))
+          .filter(post_actions::read_at.is_not_null()),
+      )));
+    }
+
+    let in_my_languages = exists(
+      local_user_language::table.filter(
+        comment::language_id
+          .eq(local_user_language::language_id)
+          .and(
+            local_user_language::local_user_id
+              .nullable()
+              .eq(local_user_id),
+          ),
+      ),
+    );
+    let not_mod_view = o.listing_type.unwrap_or_default() != ListingType::ModeratorView;
+    if o.needs_translation_only.unwrap_or_default() {
+      if o.local_user.is_none() {
+        Err(LemmyErrorType::NotLoggedIn)?
+      }
+      if o.community_id.is_none() {
+        Err(LemmyErrorType::NoIdGiven)?
+      }
+      query = query.filter(not(in_my_languages));
+    } else if o.local_user.is_some() && not_mod_view {
       // Filter out the rows with missing languages
-      query = query.filter(exists(
-        local_user_language::table.filter(
-          comment::language_id
-            .eq(local_user_language::language_id)
-            .and(
-              local_user_language::local_user_id
-                .nullable()
-                .eq(local_user_id),
-            ),
-        ),
-      ));
+      query = query.filter(in_my_languages);
+    }
 
-      query = query.filter(filter_blocked());
+    if o.local_user.is_some() && not_mod_view {
+      if o.show_blocked_instances.unwrap_or_default() {
+        query = query.filter(filter_blocked_except_instance_persons());
+      } else {
+        query = query.filter(filter_blocked());
+      }
     };
 
     if !o.local_user.show_nsfw(site) {
@@ -246,6 +975,86 @@ impl CommentQuery<'_> {
         .or(comment::creator_id.nullable().eq(my_person_id)),
     );
 
+    // Comments with a pending delete are only shown to their creator, who can still restore them
+    query = query.filter(
+      comment::pending_delete_until
+        .is_null()
+        .or(comment::creator_id.nullable().eq(my_person_id)),
+    );
+
+    // Scheduled comments are only shown to their creator until they are published
+    query = query.filter(
+      comment::scheduled_publish_time_at
+        .is_null()
+        .or(comment::creator_id.nullable().eq(my_person_id)),
+    );
+
+    // Comments waiting for approval are only shown to their creator and to mods
+    if o.pending_approval_only.unwrap_or_default() {
+      query = query.filter(comment::pending_approval);
+    }
+
+    if o.leaf_only.unwrap_or_default() {
+      query = query.filter(comment::child_count.eq(0));
+    }
+
+    if o.edited_only.unwrap_or_default() {
+      query = query.filter(comment::content_edited_at.is_not_null());
+    }
+
+    if let Some(edited_after) = o.edited_after {
+      query = query.filter(comment::content_edited_at.gt(edited_after));
+    }
+
+    if o.saved_only.unwrap_or_default() {
+      query = query.filter(comment_actions::saved_at.is_not_null());
+    }
+
+    if let Some(search_term) = &o.search_term {
+      query = query.filter(comment::content.ilike(substring_search(search_term)));
+    }
+
+    if let Some(tag_id) = o.tag_id {
+      query = query.filter(exists(
+        comment_tag::table
+          .filter(comment_tag::comment_id.eq(comment::id))
+          .filter(comment_tag::tag_id.eq(tag_id)),
+      ));
+    }
+
+    if let Some(software) = o.creator_software {
+      if !o.local_user.is_admin() {
+        Err(LemmyErrorType::NotAnAdmin)?
+      }
+      query = query.filter(exists(
+        instance::table
+          .filter(instance::id.eq(person::instance_id))
+          .filter(instance::software.eq(software.to_lowercase())),
+      ));
+    }
+
+    if o.language_mismatch_only.unwrap_or_default() {
+      query = query.filter(not(exists(
+        community_language::table
+          .filter(community_language::community_id.eq(community::id))
+          .filter(community_language::language_id.eq(comment::language_id)),
+      )));
+      if !o.local_user.is_admin() {
+        query = query.filter(community_actions::became_moderator_at.is_not_null());
+      }
+    }
+    if o.moddable_only.unwrap_or_default() && !o.local_user.is_admin() {
+      query = query.filter(community_actions::became_moderator_at.is_not_null());
+    }
+    if !o.local_user.is_admin() {
+      query = query.filter(
+        comment::pending_approval
+          .eq(false)
+          .or(comment::creator_id.nullable().eq(my_person_id))
+          .or(community_actions::became_moderator_at.is_not_null()),
+      );
+    }
+
     if !o.local_user.is_admin() {
       query = query.filter(
         community::visibility
@@ -260,6 +1069,10 @@ impl CommentQuery<'_> {
         query.filter(comment::published_at.gt(now() - seconds_to_pg_interval(time_range_seconds)));
     }
 
+    if let Some(created_after) = o.created_after {
+      query = query.filter(comment::published_at.gt(created_after));
+    }
+
     // A Max depth given means its a tree fetch
     let limit = if let Some(max_depth) = o.max_depth {
       let depth_limit = if let Some(parent_path) = o.parent_path.as_ref() {
@@ -286,81 +1099,379 @@ impl CommentQuery<'_> {
     } else {
       limit_fetch(o.limit, None)?
     };
+
+    // The pinned comment takes up one place on the first page
+    let pinned = match community_pinned_comment_id.filter(|_| o.page_cursor.is_none()) {
+      Some(pinned_id) => CommentView::read(pool, pinned_id, o.local_user, site.instance_id)
+        .await
+        .ok()
+        .filter(|p| !p.comment.deleted && !p.comment.removed),
+      None => None,
+    };
+    let limit = if pinned.is_some() {
+      (limit - 1).max(1)
+    } else {
+      limit
+    };
     query = query.limit(limit);
 
-    // Only sort by ascending for Old
-    let sort = o.sort.unwrap_or(Hot);
-    let sort_direction = asc_if(sort == Old);
+    // Only order by a subpath for max depth queries if filtering by a post id, or parent_path.
+    // DOS potential otherwise and max_depth + !post_id isn't used anyways (afaik)
+    let is_tree_fetch = o.max_depth.is_some() && (o.post_id.is_some() || o.parent_path.is_some());
+    // Trees are ordered by their path first, which doesn't match the cursor keys. They are always
+    // fetched at once instead.
+    let page_cursor = o.page_cursor.clone().filter(|_| !is_tree_fetch);
+
+    let saved_sort = o.saved_sort.filter(|_| o.saved_only.unwrap_or_default());
+    let res = if let (Some(saved_sort), Some(my_person_id)) = (saved_sort, my_person_id) {
+      // The save time isn't part of the comment, so it can't be used as a cursor key. Instead the
+      // cursor only points to the comment, and its save time is read here.
+      let (cursor_data, reverse_cursor) = ReverseCursor::new(o.page_cursor.clone())?;
+      let cursor = match cursor_data {
+        Some(data) => {
+          let comment_id = data
+            .plain()
+            .split(',')
+            .next()
+            .ok_or(LemmyErrorType::CouldntParsePaginationToken)?
+            .parse()?;
+          Some(CommentId(comment_id))
+        }
+        None => None,
+      };
+
+      let conn = &mut get_conn(pool).await?;
+      let newest_first = reverse_cursor.load_descending(saved_sort == SavedCommentSort::SavedNew);
+      if let Some(cursor) = cursor {
+        let cursor_saved_at = comment_actions::table
+          .find((my_person_id, cursor))
+          .select(comment_actions::saved_at)
+          .first::<Option<DateTime<Utc>>>(conn)
+          .await?
+          .ok_or(LemmyErrorType::CouldntParsePaginationToken)?;
+        let same_time = comment_actions::saved_at.eq(cursor_saved_at);
+        query = if newest_first {
+          query.filter(
+            comment_actions::saved_at
+              .lt(cursor_saved_at)
+              .or(same_time.and(comment::id.lt(cursor))),
+          )
+        } else {
+          query.filter(
+            comment_actions::saved_at
+              .gt(cursor_saved_at)
+              .or(same_time.and(comment::id.gt(cursor))),
+          )
+        };
+      }
+      query = if newest_first {
+        query.order_by((comment_actions::saved_at.desc(), comment::id.desc()))
+      } else {
+        query.order_by((comment_actions::saved_at.asc(), comment::id.asc()))
+      };
+
+      let res = query.load::<CommentView>(conn).await?;
+      reverse_cursor.restore_order(res)
+    } else {
+      // Tree fetches are ordered by branch first, so the branch sort replaces the overall sort
+      let sort = o.sort.unwrap_or(Hot);
+      let sort = match o.branch_sort {
+        Some(branch_sort) if is_tree_fetch => branch_sort,
+        _ => sort,
+      };
+
+      // Only sort by ascending for Old
+      let sort_direction = asc_if(sort == Old);
 
-    let mut pq = CommentView::paginate(query, &o.page_cursor, sort_direction, pool, None).await?;
+      let mut pq = CommentView::paginate(query, &page_cursor, sort_direction, pool, None).await?;
 
-    // Order by a subpath for max depth queries
-    // Only order if filtering by a post id, or parent_path. DOS potential otherwise and max_depth
-    // + !post_id isn't used anyways (afaik)
-    if o.max_depth.is_some() && (o.post_id.is_some() || o.parent_path.is_some()) {
-      // Always order by the parent path first
-      pq = pq.then_order_by(Subpath(key::path));
-    }
+      if is_tree_fetch {
+        // Always order by the parent path first
+        pq = pq.then_order_by(Subpath(key::path));
+      }
 
-    // Distinguished comments should go first when viewing post
-    // Don't do for new / old sorts
-    if sort != New && sort != Old && (o.post_id.is_some() || o.parent_path.is_some()) {
-      pq = pq.then_order_by(key::distinguished);
-    }
+      // The accepted answer and distinguished comments should go first when viewing post
+      // Don't do for chronological sorts
+      let chronological = matches!(sort, New | Old | Received);
+      if !chronological && (o.post_id.is_some() || o.parent_path.is_some()) {
+        pq = pq.then_order_by(key::is_answer);
+        pq = pq.then_order_by(key::distinguished);
+      }
+
+      pq = match sort {
+        // Only order by the hot rank snapshot from the cursor and the id, so that the page boundary
+        // stays stable while scrolling.
+        Hot => pq.then_order_by(key::hot_rank),
+        Controversial => pq.then_order_by(key::controversy_rank),
+        Old | New => pq.then_order_by(key::published_at),
+        Top => pq.then_order_by(key::score),
+        // Ids are assigned in insert order, so the tie breaker alone gives the received order
+        Received => pq,
+      };
+      // Tie breaker, to get a deterministic order
+      pq = pq.then_order_by(key::id);
 
-    pq = match sort {
-      Hot => pq.then_order_by(key::hot_rank).then_order_by(key::score),
-      Controversial => pq.then_order_by(key::controversy_rank),
-      Old | New => pq.then_order_by(key::published_at),
-      Top => pq.then_order_by(key::score),
+      let conn = &mut get_conn(pool).await?;
+      pq.load::<CommentView>(conn).await?
     };
 
     let conn = &mut get_conn(pool).await?;
-    let res = pq.load::<CommentView>(conn).await?;
+    let res = CommentView::prepare_for_viewer(res, o.local_user, conn).await?;
+
+    let mut res = paginate_response(res, limit, page_cursor)?;
+    if is_tree_fetch {
+      res.next_page = None;
+      res.prev_page = None;
+    }
+    if let Some(mut pinned) = pinned {
+      pinned.pinned_in_community = true;
+      res.items.insert(0, pinned);
+    }
+    Ok(res)
+  }
 
-    paginate_response(res, limit, o.page_cursor)
+  /// Whether this lists the comments of a post without filtering them further, so that the pinned
+  /// comment of the community should be included. Tree fetches only return the post's own
+  /// comments, as the pinned comment doesn't belong into their tree.
+  fn is_post_listing(&self) -> bool {
+    self.post_id.is_some()
+      && self.max_depth.is_none()
+      && self.parent_path.is_none()
+      && self.sibling_path.is_none()
+      && self.creator_id.is_none()
+      && self.creator_ids.is_none()
+      && self.created_after.is_none()
+      && self.search_term.is_none()
+      && !self.saved_only.unwrap_or_default()
+      && !self.pending_approval_only.unwrap_or_default()
   }
 }
 
-#[cfg(test)]
-#[expect(clippy::indexing_slicing)]
-mod tests {
+impl CommentStats {
+  pub async fn read(pool: &mut DbPool<'_>) -> LemmyResult<Self> {
+    let conn = &mut get_conn(pool).await?;
+    // Diesel doesn't support aggregate filters, so use raw sql here.
+    sql_query(
+      "SELECT count(*) AS total,
+        count(*) FILTER (WHERE published_at > now() - interval '1 day') AS last_day,
+        count(*) FILTER (WHERE published_at > now() - interval '7 days') AS last_week,
+        count(*) FILTER (WHERE removed) AS removed
+      FROM comment",
+    )
+    .get_result::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
 
-  use super::*;
-  use crate::{CommentView, impls::CommentQuery};
-  use lemmy_db_schema::{
-    assert_length,
-    impls::actor_language::UNDETERMINED_ID,
-    newtypes::CommentId,
-    source::{
-      actor_language::LocalUserLanguage,
-      comment::{Comment, CommentActions, CommentInsertForm, CommentLikeForm, CommentUpdateForm},
-      community::{
-        Community,
-        CommunityActions,
-        CommunityFollowerForm,
-        CommunityInsertForm,
-        CommunityModeratorForm,
+  /// The share of removed comments, between 0 and 1.
+  pub fn removed_ratio(&self) -> f64 {
+    if self.total == 0 {
+      0.0
+    } else {
+      self.removed as f64 / self.total as f64
+    }
+  }
+
+  /// The communities with the most comments, using the stored comment counts.
+  pub async fn top_communities(pool: &mut DbPool<'_>, limit: i64) -> LemmyResult<Vec<Community>> {
+    let conn = &mut get_conn(pool).await?;
+    community::table
+      .filter(community::deleted.eq(false))
+      .filter(community::removed.eq(false))
+      .order_by((community::comments.desc(), community::id.desc()))
+      .limit(limit)
+      .select(Community::as_select())
+      .load(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
+impl FromSql<Text, Pg> for CommentFederationState {
+  fn from_sql(bytes: PgValue) -> diesel::deserialize::Result<Self> {
+    match <String as FromSql<Text, Pg>>::from_sql(bytes)?.as_str() {
+      "Pending" => Ok(Self::Pending),
+      "Sent" => Ok(Self::Sent),
+      "Failed" => Ok(Self::Failed),
+      other => Err(format!("Unknown comment federation state: {other}").into()),
+    }
+  }
+}
+
+impl CommentFederationTarget {
+  /// The delivery state of all activities about the comment, for each instance they were sent to.
+  /// Uses the newest activity for each instance.
+  pub async fn list_for_comment(
+    pool: &mut DbPool<'_>,
+    comment: &Comment,
+  ) -> LemmyResult<Vec<Self>> {
+    let activities =
+      SentActivity::list_for_object(pool, &comment.ap_id, comment.published_at).await?;
+    let conn = &mut get_conn(pool).await?;
+
+    // Load the instances of all activities at once
+    let domains: Vec<String> = activities
+      .iter()
+      .flat_map(|a| a.send_inboxes.iter().flatten())
+      .filter_map(|inbox| inbox.domain().map(ToString::to_string))
+      .collect();
+    let domain_instances: HashMap<String, InstanceId> = instance::table
+      .filter(instance::domain.eq_any(domains))
+      .select((instance::domain, instance::id))
+      .load(conn)
+      .await?
+      .into_iter()
+      .collect();
+    let community_ids: Vec<CommunityId> = activities
+      .iter()
+      .filter_map(|a| a.send_community_followers_of)
+      .collect();
+    let follower_instances: Vec<(CommunityId, InstanceId)> = community_actions::table
+      .inner_join(person::table.on(community_actions::person_id.eq(person::id)))
+      .filter(community_actions::community_id.eq_any(community_ids))
+      .filter(community_actions::followed_at.is_not_null())
+      .filter(person::local.eq(false))
+      .select((community_actions::community_id, person::instance_id))
+      .distinct()
+      .load(conn)
+      .await?;
+    // Activities for all instances are sent to every remote instance with a site
+    let all_instances: Vec<InstanceId> = if activities.iter().any(|a| a.send_all_instances) {
+      site::table
+        .filter(not(
+          site::id.eq_any(local_site::table.select(local_site::site_id)),
+        ))
+        .select(site::instance_id)
+        .load(conn)
+        .await?
+    } else {
+      vec![]
+    };
+
+    // Activities are sorted oldest first, so newer ones overwrite the entry
+    let mut newest_activity = HashMap::new();
+    for activity in activities {
+      let mut instance_ids: Vec<InstanceId> = activity
+        .send_inboxes
+        .iter()
+        .flatten()
+        .filter_map(|inbox| inbox.domain())
+        .filter_map(|domain| domain_instances.get(domain).copied())
+        .collect();
+      if let Some(community_id) = activity.send_community_followers_of {
+        instance_ids.extend(
+          follower_instances
+            .iter()
+            .filter(|(c, _)| *c == community_id)
+            .map(|(_, i)| *i),
+        );
+      }
+      if activity.send_all_instances {
+        instance_ids.extend(&all_instances);
+      }
+      for instance_id in instance_ids {
+        newest_activity.insert(instance_id, activity.id);
+      }
+    }
+
+    let instance_ids: Vec<InstanceId> = newest_activity.keys().copied().collect();
+    let instances: Vec<(InstanceId, String)> = instance::table
+      .filter(instance::id.eq_any(&instance_ids))
+      .select((instance::id, instance::domain))
+      .order_by(instance::domain)
+      .load(conn)
+      .await?;
+    let queue_states: HashMap<InstanceId, FederationQueueState> = federation_queue_state::table
+      .filter(federation_queue_state::instance_id.eq_any(&instance_ids))
+      .select(FederationQueueState::as_select())
+      .load(conn)
+      .await?
+      .into_iter()
+      .map(|s| (s.instance_id, s))
+      .collect();
+
+    Ok(
+      instances
+        .into_iter()
+        .map(|(instance_id, domain)| {
+          let activity_id = newest_activity.get(&instance_id).copied();
+          let queue_state = queue_states.get(&instance_id);
+          let state = match queue_state {
+            Some(q) if q.last_successful_id >= activity_id => CommentFederationState::Sent,
+            Some(q) if q.fail_count > 0 => CommentFederationState::Failed,
+            _ => CommentFederationState::Pending,
+          };
+          let failed = state == CommentFederationState::Failed;
+          CommentFederationTarget {
+            instance_id,
+            domain,
+            state,
+            last_attempt_at: queue_state.and_then(|q| q.last_retry_at),
+            error: queue_state
+              .and_then(|q| q.last_error.clone())
+              .filter(|_| failed),
+          }
+        })
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+#[expect(clippy::indexing_slicing)]
+mod tests {
+
+  use super::*;
+  use crate::{CommentSlimView, CommentView, impls::CommentQuery};
+  use lemmy_db_schema::{
+    assert_length,
+    impls::actor_language::UNDETERMINED_ID,
+    newtypes::{ActivityId, CommentId},
+    source::{
+      activity::SentActivityForm,
+      actor_language::{CommunityLanguage, LocalUserLanguage},
+      comment::{
+        Comment,
+        CommentActions,
+        CommentInsertForm,
+        CommentLikeForm,
+        CommentSavedForm,
+        CommentUpdateForm,
+      },
+      community::{
+        Community,
+        CommunityActions,
+        CommunityFollowerForm,
+        CommunityInsertForm,
+        CommunityModeratorForm,
         CommunityPersonBanForm,
         CommunityUpdateForm,
       },
-      instance::Instance,
+      federation_blocklist::{FederationBlockList, FederationBlockListForm},
+      federation_queue_state::FederationQueueState,
+      instance::{Instance, InstanceActions, InstanceForm, InstancePersonsBlockForm},
       language::Language,
       local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
-      person::{Person, PersonActions, PersonBlockForm, PersonInsertForm},
-      post::{Post, PostInsertForm, PostUpdateForm},
+      modlog::{Modlog, ModlogInsertForm},
+      person::{Person, PersonActions, PersonBlockForm, PersonInsertForm, PersonUpdateForm},
+      post::{Post, PostActions, PostInsertForm, PostUpdateForm},
       site::{Site, SiteInsertForm},
+      tag::{CommentTag, Tag, TagInsertForm},
     },
-    traits::{Bannable, Blockable, Followable, Likeable},
+    traits::{Bannable, Blockable, Followable, Likeable, Saveable},
   };
+  use lemmy_db_schema_file::{enums::ActorType, schema::sent_activity};
   use lemmy_db_views_local_user::LocalUserView;
   use lemmy_diesel_utils::{
     connection::{DbPool, build_db_pool_for_tests},
+    dburl::DbUrl,
     traits::Crud,
   };
-  use lemmy_utils::error::LemmyResult;
+  use lemmy_utils::error::{LemmyResult, MAX_API_PARAM_ELEMENTS};
   use pretty_assertions::assert_eq;
+  use serde_json::json;
   use serial_test::serial;
+  use url::Url;
 
   // TODO rename these
   struct Data {
@@ -684,6 +1795,61 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_show_read_comments() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await?;
+
+    PostActions::mark_as_read(pool, data.timmy_local_user_view.person.id, &[data.post.id]).await?;
+
+    // Shown by default, even though the post is read
+    let read_shown = CommentQuery {
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(5, read_shown);
+
+    let local_user_form = LocalUserUpdateForm {
+      show_read_comments: Some(false),
+      ..Default::default()
+    };
+    LocalUser::update(
+      pool,
+      data.timmy_local_user_view.local_user.id,
+      &local_user_form,
+    )
+    .await?;
+    data.timmy_local_user_view.local_user.show_read_comments = false;
+
+    let read_hidden = CommentQuery {
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(0, read_hidden);
+
+    // The comments of the read post itself are still listed
+    let read_post = CommentQuery {
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      post_id: Some(data.post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(5, read_post);
+
+    // Other users still see the comments
+    let anonymous = CommentQuery::default().list(&data.site, pool).await?;
+    assert_length!(6, anonymous);
+
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_distinguished_first() -> LemmyResult<()> {
@@ -763,6 +1929,1958 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn test_top_child_preview() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let mut comments = CommentQuery {
+      post_id: Some(data.post.id),
+      sort: Some(CommentSortType::Old),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?
+    .into_iter()
+    .map(CommentView::map_to_slim)
+    .collect::<Vec<_>>();
+    CommentSlimView::fill_top_child_previews(&mut comments, None, pool).await?;
+
+    // Comment 0 has two direct children, with equal rank the newest one wins
+    let preview_0 = comments[0].top_child_preview.as_ref().map(|p| p.id);
+    assert_eq!(Some(data.comment_2.id), preview_0);
+
+    // Comment 2 has no children
+    let comment_2 = comments.iter().find(|c| c.comment.id == data.comment_2.id);
+    assert!(comment_2.is_some_and(|c| c.top_child_preview.is_none()));
+
+    // Removed children are never used as preview
+    Comment::update(
+      pool,
+      data.comment_2.id,
+      &CommentUpdateForm {
+        removed: Some(true),
+        ..Default::default()
+      },
+    )
+    .await?;
+    CommentSlimView::fill_top_child_previews(&mut comments, None, pool).await?;
+    let preview_0 = comments[0].top_child_preview.as_ref().map(|p| p.id);
+    assert_eq!(Some(data.comment_1.id), preview_0);
+
+    // Timmy blocked Sara, who wrote the remaining child
+    let timmy = Some(data.timmy_local_user_view.person.id);
+    CommentSlimView::fill_top_child_previews(&mut comments, timmy, pool).await?;
+    assert!(comments[0].top_child_preview.is_none());
+
+    // Scheduled children aren't published yet
+    Comment::update(
+      pool,
+      data.comment_1.id,
+      &CommentUpdateForm {
+        scheduled_publish_time_at: Some(Some(Utc::now() + TimeDelta::days(1))),
+        ..Default::default()
+      },
+    )
+    .await?;
+    CommentSlimView::fill_top_child_previews(&mut comments, None, pool).await?;
+    assert!(comments[0].top_child_preview.is_none());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_state() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    let remote_instance = Instance::read_or_create(pool, "remote.tld").await?;
+    let community_form = CommunityInsertForm {
+      local: Some(false),
+      ..CommunityInsertForm::new(
+        remote_instance.id,
+        "remote_community".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let post_form = PostInsertForm::new("Remote post".into(), timmy.person.id, community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let comment_form = CommentInsertForm {
+      local: Some(true),
+      ..CommentInsertForm::new(timmy.person.id, post.id, "Federated".into())
+    };
+    let comment = Comment::create(pool, &comment_form, None).await?;
+
+    // Nothing was delivered yet
+    let creator_view =
+      CommentView::read(pool, comment.id, Some(&timmy.local_user), data.instance.id).await?;
+    assert_eq!(
+      Some(CommentFederationState::Pending),
+      creator_view.federation_state
+    );
+
+    // Delivery to the instance is being retried
+    FederationQueueState::upsert(
+      pool,
+      &FederationQueueState {
+        instance_id: remote_instance.id,
+        last_successful_id: None,
+        last_successful_published_time_at: None,
+        fail_count: 3,
+        last_retry_at: Some(Utc::now()),
+        last_error: None,
+      },
+    )
+    .await?;
+    let creator_view =
+      CommentView::read(pool, comment.id, Some(&timmy.local_user), data.instance.id).await?;
+    assert_eq!(
+      Some(CommentFederationState::Failed),
+      creator_view.federation_state
+    );
+
+    FederationQueueState::upsert(
+      pool,
+      &FederationQueueState {
+        instance_id: remote_instance.id,
+        last_successful_id: None,
+        last_successful_published_time_at: Some(Utc::now()),
+        fail_count: 0,
+        last_retry_at: None,
+        last_error: None,
+      },
+    )
+    .await?;
+    let creator_view =
+      CommentView::read(pool, comment.id, Some(&timmy.local_user), data.instance.id).await?;
+    assert_eq!(
+      Some(CommentFederationState::Sent),
+      creator_view.federation_state
+    );
+
+    // Others don't see the state, and comments in local communities have none
+    let anonymous_view = CommentView::read(pool, comment.id, None, data.instance.id).await?;
+    assert_eq!(None, anonymous_view.federation_state);
+    let local_view = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!(None, local_view.federation_state);
+
+    Community::delete(pool, community.id).await?;
+    Instance::delete(pool, remote_instance.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federation_targets() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let remote_instance = Instance::read_or_create(pool, "remote.tld").await?;
+
+    // Nothing was sent yet
+    let targets = CommentFederationTarget::list_for_comment(pool, &data.comment_0).await?;
+    assert!(targets.is_empty());
+
+    let form = SentActivityForm {
+      ap_id: Url::parse("http://my_domain.tld/activities/create/1")?.into(),
+      data: json!({
+        "type": "Create",
+        "object": { "id": data.comment_0.ap_id.to_string() }
+      }),
+      sensitive: false,
+      send_inboxes: vec![Some(Url::parse("http://remote.tld/inbox")?.into())],
+      send_community_followers_of: None,
+      send_all_instances: false,
+      actor_type: ActorType::Person,
+      actor_apub_id: data.timmy_local_user_view.person.ap_id.clone(),
+    };
+    let activity = SentActivity::create(pool, form).await?;
+
+    // The send failed
+    let last_retry_at = Utc::now();
+    FederationQueueState::upsert(
+      pool,
+      &FederationQueueState {
+        instance_id: remote_instance.id,
+        last_successful_id: Some(ActivityId(activity.id.0 - 1)),
+        last_successful_published_time_at: None,
+        fail_count: 3,
+        last_retry_at: Some(last_retry_at),
+        last_error: Some("connection refused".to_string()),
+      },
+    )
+    .await?;
+    let targets = CommentFederationTarget::list_for_comment(pool, &data.comment_0).await?;
+    assert_length!(1, targets);
+    assert_eq!(remote_instance.id, targets[0].instance_id);
+    assert_eq!("remote.tld", targets[0].domain);
+    assert_eq!(CommentFederationState::Failed, targets[0].state);
+    assert_eq!(Some("connection refused"), targets[0].error.as_deref());
+    assert!(targets[0].last_attempt_at.is_some());
+
+    // Once it goes through, the error isn't shown anymore
+    FederationQueueState::upsert(
+      pool,
+      &FederationQueueState {
+        instance_id: remote_instance.id,
+        last_successful_id: Some(activity.id),
+        last_successful_published_time_at: Some(activity.published_at),
+        fail_count: 0,
+        last_retry_at: Some(last_retry_at),
+        last_error: Some("connection refused".to_string()),
+      },
+    )
+    .await?;
+    let targets = CommentFederationTarget::list_for_comment(pool, &data.comment_0).await?;
+    assert_length!(1, targets);
+    assert_eq!(CommentFederationState::Sent, targets[0].state);
+    assert_eq!(None, targets[0].error);
+
+    diesel::delete(sent_activity::table.find(activity.id))
+      .execute(&mut get_conn(pool).await?)
+      .await?;
+    Instance::delete(pool, remote_instance.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pending_delete_hidden() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    let form = CommentUpdateForm {
+      pending_delete_until: Some(Some(Utc::now() + TimeDelta::hours(1))),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+
+    // Only the creator can still read the comment, to restore it
+    let creator_view = CommentView::read(
+      pool,
+      data.comment_2.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(creator_view.comment.pending_delete_until.is_some());
+    let anonymous_view = CommentView::read(pool, data.comment_2.id, None, data.instance.id).await;
+    assert!(anonymous_view.is_err());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_branch_sort() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let tree_ids = |comments: &[CommentView]| {
+      comments
+        .iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<CommentId>>()
+    };
+    let position = |ids: &[CommentId], id| ids.iter().position(|i| *i == id);
+
+    // Comments 1 and 2 are both direct replies to comment 0, and 2 was created later
+    let old_branches = CommentQuery {
+      post_id: Some(data.post.id),
+      max_depth: Some(5),
+      sort: Some(CommentSortType::New),
+      branch_sort: Some(CommentSortType::Old),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let ids = tree_ids(&old_branches);
+    assert_eq!(Some(0), position(&ids, data.comment_0.id));
+    assert!(position(&ids, data.comment_1.id) < position(&ids, data.comment_2.id));
+
+    let new_branches = CommentQuery {
+      post_id: Some(data.post.id),
+      max_depth: Some(5),
+      sort: Some(CommentSortType::Old),
+      branch_sort: Some(CommentSortType::New),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let ids = tree_ids(&new_branches);
+    assert!(position(&ids, data.comment_2.id) < position(&ids, data.comment_1.id));
+
+    // Comment 2 gets mixed votes, so it's the more controversial branch
+    let form = CommentLikeForm::new(
+      data.timmy_local_user_view.person.id,
+      data.comment_2.id,
+      true,
+    );
+    CommentActions::like(pool, &form).await?;
+    let form = CommentLikeForm::new(data.sara_person.id, data.comment_2.id, false);
+    CommentActions::like(pool, &form).await?;
+    let controversial_branches = CommentQuery {
+      post_id: Some(data.post.id),
+      max_depth: Some(5),
+      sort: Some(CommentSortType::Old),
+      branch_sort: Some(CommentSortType::Controversial),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let ids = tree_ids(&controversial_branches);
+    assert!(position(&ids, data.comment_2.id) < position(&ids, data.comment_1.id));
+
+    // The branch sort is ignored outside of tree fetches
+    let flat = CommentQuery {
+      post_id: Some(data.post.id),
+      sort: Some(CommentSortType::Old),
+      branch_sort: Some(CommentSortType::New),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let ids = tree_ids(&flat);
+    assert_eq!(Some(0), position(&ids, data.comment_0.id));
+    assert!(position(&ids, data.comment_1.id) < position(&ids, data.comment_2.id));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_viewer_has_replied() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    // Timmy replied to comment 0 with comment 2, but nobody replied to comment 2
+    let comment_0 = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(comment_0.viewer_has_replied);
+    let comment_2 = CommentView::read(
+      pool,
+      data.comment_2.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(!comment_2.viewer_has_replied);
+
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      local_user: Some(&timmy.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let replied = comments
+      .iter()
+      .filter(|c| c.viewer_has_replied)
+      .map(|c| c.comment.id)
+      .collect::<Vec<_>>();
+    assert!(replied.contains(&data.comment_0.id));
+    assert!(!replied.contains(&data.comment_2.id));
+
+    // Always false without a user
+    let anonymous = CommentView::read(pool, data.comment_0.id, None, data.instance.id).await?;
+    assert!(!anonymous.viewer_has_replied);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_removed_by() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Sara moderates the community, and removes one of timmy's comments
+    let form = CommunityModeratorForm::new(data.community.id, data.sara_person.id);
+    CommunityActions::join(pool, &form).await?;
+    let form = CommentUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    let removed = Comment::update(pool, data.comment_2.id, &form).await?;
+    let form = ModlogInsertForm::mod_remove_comment(data.sara_person.id, &removed, true, "spam");
+    Modlog::create(pool, &[form]).await?;
+
+    // Another mod of the community sees who removed it
+    let other_mod_form = PersonInsertForm::test_form(data.instance.id, "other_mod");
+    let other_mod = Person::create(pool, &other_mod_form).await?;
+    let other_mod_local_user =
+      LocalUser::create(pool, &LocalUserInsertForm::test_form(other_mod.id), vec![]).await?;
+    let form = CommunityModeratorForm::new(data.community.id, other_mod.id);
+    CommunityActions::join(pool, &form).await?;
+    let mod_view = CommentView::read(
+      pool,
+      removed.id,
+      Some(&other_mod_local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(mod_view.can_mod);
+    assert_eq!(Some(data.sara_person.id), mod_view.removed_by.map(|p| p.id));
+    assert_eq!(Some("spam".to_string()), mod_view.removed_reason);
+
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      local_user: Some(&other_mod_local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let listed = comments
+      .iter()
+      .find(|c| c.comment.id == removed.id)
+      .and_then(|c| c.removed_by.as_ref())
+      .map(|p| p.id);
+    assert_eq!(Some(data.sara_person.id), listed);
+
+    // Comments which aren't removed have no remover
+    let other = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&other_mod_local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(other.removed_by.is_none());
+
+    // Others don't see it
+    let anonymous = CommentView::read(pool, removed.id, None, data.instance.id).await?;
+    assert!(anonymous.removed_by.is_none());
+    assert!(anonymous.removed_reason.is_none());
+
+    Person::delete(pool, other_mod.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_leaf_only() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Comments 2, 3 and 5 have no replies
+    let leaves = CommentQuery {
+      post_id: Some(data.post.id),
+      leaf_only: Some(true),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(3, leaves);
+    let ids = leaves.iter().map(|c| c.comment.id).collect::<Vec<_>>();
+    assert!(ids.contains(&data.comment_2.id));
+    assert!(ids.contains(&data._comment_5.id));
+    assert!(!ids.contains(&data.comment_0.id));
+    assert!(!ids.contains(&data.comment_1.id));
+    assert!(leaves.iter().all(|c| c.comment.child_count == 0));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_edited_only() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let edited_at = Utc::now() - chrono::Duration::minutes(5);
+    let form = CommentUpdateForm {
+      content: Some("edited comment".into()),
+      content_edited_at: Some(Some(edited_at)),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_1.id, &form).await?;
+    // Moderation updates don't count as edits
+    let form = CommentUpdateForm {
+      distinguished: Some(true),
+      updated_at: Some(Some(Utc::now())),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+
+    let query = |edited_after| CommentQuery {
+      post_id: Some(data.post.id),
+      edited_only: Some(true),
+      edited_after,
+      ..Default::default()
+    };
+    let edited = query(None).list(&data.site, pool).await?;
+    assert_length!(1, edited);
+    assert_eq!(data.comment_1.id, edited[0].comment.id);
+    assert!(edited[0].comment.content_edited_at.is_some());
+
+    let before = Some(edited_at - chrono::Duration::minutes(1));
+    assert_length!(1, query(before).list(&data.site, pool).await?);
+    let after = Some(edited_at + chrono::Duration::minutes(1));
+    assert_length!(0, query(after).list(&data.site, pool).await?);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_read_many() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Waiting for approval, so only visible to its creator
+    let form = CommentInsertForm {
+      pending_approval: Some(true),
+      ..CommentInsertForm::new(data.sara_person.id, data.post.id, "pending".into())
+    };
+    let pending = Comment::create(pool, &form, None).await?;
+
+    let ids = [
+      data.comment_0.id,
+      data.comment_1.id,
+      data.comment_2.id,
+      pending.id,
+      CommentId(-1),
+    ];
+    let read_ids = |comments: Vec<CommentView>| {
+      let mut ids = comments.iter().map(|c| c.comment.id).collect::<Vec<_>>();
+      ids.sort_by_key(|id| id.0);
+      ids
+    };
+    let timmy = &data.timmy_local_user_view.local_user;
+    let comments = CommentView::read_many(pool, &ids, Some(timmy), data.instance.id).await?;
+    assert_eq!(
+      vec![data.comment_0.id, data.comment_1.id, data.comment_2.id],
+      read_ids(comments)
+    );
+
+    let sara_local_user_form = LocalUserInsertForm::test_form(data.sara_person.id);
+    let sara_local_user = LocalUser::create(pool, &sara_local_user_form, vec![]).await?;
+    let comments =
+      CommentView::read_many(pool, &ids, Some(&sara_local_user), data.instance.id).await?;
+    assert_eq!(
+      vec![
+        data.comment_0.id,
+        data.comment_1.id,
+        data.comment_2.id,
+        pending.id
+      ],
+      read_ids(comments)
+    );
+
+    let too_many = vec![data.comment_0.id; MAX_API_PARAM_ELEMENTS];
+    let res = CommentView::read_many(pool, &too_many, Some(timmy), data.instance.id).await;
+    assert_eq!(
+      Some(LemmyErrorType::TooManyItems),
+      res.err().map(|e| e.error_type)
+    );
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_search_term() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let form = CommentInsertForm::new(data.sara_person.id, data.post.id, "50% off".into());
+    let percent = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(data.sara_person.id, data.post.id, "500 off".into());
+    Comment::create(pool, &form, None).await?;
+
+    let search = |search_term: &str, creator_id| CommentQuery {
+      community_id: Some(data.community.id),
+      search_term: Some(search_term.to_string()),
+      creator_id,
+      ..Default::default()
+    };
+
+    // Wildcards in the search term are matched literally
+    let comments = search("50%", None).list(&data.site, pool).await?;
+    assert_length!(1, comments);
+    assert_eq!(percent.id, comments[0].comment.id);
+    assert_length!(0, search("comment_", None).list(&data.site, pool).await?);
+
+    // Words have to be next to each other, "50 off" isn't in "50% off" or "500 off"
+    assert_length!(1, search("50% OFF", None).list(&data.site, pool).await?);
+    assert_length!(0, search("50 off", None).list(&data.site, pool).await?);
+
+    // Case-insensitive, and combined with other filters
+    assert_length!(2, search("OFF", None).list(&data.site, pool).await?);
+    assert_length!(6, search("comment", None).list(&data.site, pool).await?);
+    let comments = search("comment", Some(data.sara_person.id))
+      .list(&data.site, pool)
+      .await?;
+    assert_length!(1, comments);
+    assert_eq!(data.comment_1.id, comments[0].comment.id);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_saved_sort() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    // Save comments in the order 1, 0, 2
+    let now = Utc::now();
+    for (comment, minutes_ago) in [
+      (&data.comment_1, 3),
+      (&data.comment_0, 2),
+      (&data.comment_2, 1),
+    ] {
+      let form = CommentSavedForm {
+        saved_at: now - chrono::Duration::minutes(minutes_ago),
+        ..CommentSavedForm::new(timmy.person.id, comment.id)
+      };
+      CommentActions::save(pool, &form).await?;
+    }
+
+    let list = |saved_sort, page_cursor| CommentQuery {
+      saved_only: Some(true),
+      saved_sort: Some(saved_sort),
+      local_user: Some(&timmy.local_user),
+      page_cursor,
+      limit: Some(2),
+      ..Default::default()
+    };
+    let ids =
+      |page: &PagedResponse<CommentView>| page.iter().map(|c| c.comment.id).collect::<Vec<_>>();
+
+    let page_1 = list(SavedCommentSort::SavedNew, None)
+      .list(&data.site, pool)
+      .await?;
+    assert_eq!(vec![data.comment_2.id, data.comment_0.id], ids(&page_1));
+    let page_2 = list(SavedCommentSort::SavedNew, page_1.next_page.clone())
+      .list(&data.site, pool)
+      .await?;
+    assert_eq!(vec![data.comment_1.id], ids(&page_2));
+
+    // Going back returns the first page again
+    let page_back = list(SavedCommentSort::SavedNew, page_2.prev_page.clone())
+      .list(&data.site, pool)
+      .await?;
+    assert_eq!(ids(&page_1), ids(&page_back));
+
+    let oldest = list(SavedCommentSort::SavedOld, None)
+      .list(&data.site, pool)
+      .await?;
+    assert_eq!(vec![data.comment_1.id, data.comment_0.id], ids(&oldest));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_language_mismatch_only() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Allow the languages of all existing comments, then add a german one
+    let mut language_ids = vec![UNDETERMINED_ID];
+    for code in ["en", "fi", "pl"] {
+      language_ids.push(Language::read_id_from_code(pool, code).await?);
+    }
+    CommunityLanguage::update(pool, language_ids, data.community.id).await?;
+    let german_id = Language::read_id_from_code(pool, "de").await?;
+    let german_form = CommentInsertForm {
+      language_id: Some(german_id),
+      ..CommentInsertForm::new(
+        data.timmy_local_user_view.person.id,
+        data.post.id,
+        "Kommentar".into(),
+      )
+    };
+    let german_comment = Comment::create(pool, &german_form, None).await?;
+
+    let query = |local_user| CommentQuery {
+      language_mismatch_only: Some(true),
+      local_user,
+      ..Default::default()
+    };
+
+    // Timmy is an admin, so sees all mismatched comments
+    let comments = query(Some(&data.timmy_local_user_view.local_user))
+      .list(&data.site, pool)
+      .await?;
+    assert_length!(1, comments);
+    assert_eq!(german_comment.id, comments[0].comment.id);
+
+    // Without being a mod, nothing is returned
+    let comments = query(None).list(&data.site, pool).await?;
+    assert_length!(0, comments);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_comment_tags() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let tag_form = TagInsertForm {
+      ap_id: Url::parse(&format!("{}/tags/source", data.community.ap_id))?.into(),
+      name: "Source".into(),
+      display_name: None,
+      description: None,
+      community_id: data.community.id,
+      deleted: Some(false),
+    };
+    let tag = Tag::create(pool, &tag_form).await?;
+    CommentTag::update(pool, data.comment_1.id, &[tag.id]).await?;
+
+    // The tag is shown on the tagged comment only
+    let tagged = CommentView::read(pool, data.comment_1.id, None, data.instance.id).await?;
+    assert_eq!(vec![tag.clone()], tagged.comment_tags.0);
+    let untagged = CommentView::read(pool, data.comment_2.id, None, data.instance.id).await?;
+    assert!(untagged.comment_tags.0.is_empty());
+
+    let comments = CommentQuery {
+      tag_id: Some(tag.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(1, comments);
+    assert_eq!(data.comment_1.id, comments[0].comment.id);
+
+    // Removing all tags
+    CommentTag::update(pool, data.comment_1.id, &[]).await?;
+    let tagged = CommentView::read(pool, data.comment_1.id, None, data.instance.id).await?;
+    assert!(tagged.comment_tags.0.is_empty());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_needs_translation_only() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let finnish_id = Language::read_id_from_code(pool, "fi").await?;
+    LocalUserLanguage::update(
+      pool,
+      vec![finnish_id],
+      data.timmy_local_user_view.local_user.id,
+    )
+    .await?;
+
+    let query = |community_id| CommentQuery {
+      needs_translation_only: Some(true),
+      community_id,
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    };
+
+    // Everything except the finnish comment, and still without the blocked user's comment
+    let comments = query(Some(data.community.id))
+      .list(&data.site, pool)
+      .await?;
+    assert_length!(4, comments);
+    assert!(comments.iter().all(|c| c.comment.language_id != finnish_id));
+    assert!(comments.iter().all(|c| c.comment.id != data.comment_2.id));
+
+    // Needs a community to look in
+    assert!(query(None).list(&data.site, pool).await.is_err());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_stream_for_community() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let form = CommentUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_1.id, &form).await?;
+
+    let all_ids = CommentQuery {
+      community_id: Some(data.community.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?
+    .into_iter()
+    .map(|c| c.comment.id)
+    .collect::<Vec<_>>();
+
+    // Anonymous users don't get the removed comment
+    let mut expected = all_ids.clone();
+    expected.retain(|id| *id != data.comment_1.id);
+    expected.sort();
+    assert_eq!(expected, stream_ids(pool, &data, None).await?);
+
+    // Admins get everything, without gaps or duplicates
+    let admin = Some(&data.timmy_local_user_view.local_user);
+    let admin_ids = stream_ids(pool, &data, admin).await?;
+    let mut expected = all_ids;
+    expected.push(data.comment_1.id);
+    expected.sort();
+    expected.dedup();
+    assert_eq!(expected, admin_ids);
+
+    // Comments which are about to be deleted aren't streamed, not even to admins
+    let form = CommentUpdateForm {
+      pending_delete_until: Some(Some(Utc::now() + TimeDelta::days(1))),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+    let admin_ids = stream_ids(pool, &data, admin).await?;
+    assert!(!admin_ids.is_empty());
+    assert!(!admin_ids.contains(&data.comment_2.id));
+
+    // Anonymous users can't stream local-only communities
+    let form = CommunityUpdateForm {
+      visibility: Some(CommunityVisibility::LocalOnlyPublic),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+    assert!(stream_ids(pool, &data, None).await?.is_empty());
+
+    cleanup(data, pool).await
+  }
+
+  /// Pages through the community in small batches until an empty batch is returned
+  async fn stream_ids(
+    pool: &mut DbPool<'_>,
+    data: &Data,
+    local_user: Option<&LocalUser>,
+  ) -> LemmyResult<Vec<CommentId>> {
+    let mut ids = vec![];
+    loop {
+      let batch = CommentView::stream_for_community(
+        pool,
+        data.community.id,
+        ids.last().copied(),
+        Some(2),
+        local_user,
+        data.instance.id,
+      )
+      .await?;
+      if batch.is_empty() {
+        return Ok(ids);
+      }
+      ids.extend(batch.into_iter().map(|c| c.comment.id));
+    }
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_received_sort() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view.person;
+
+    let post_form = PostInsertForm::new("received".to_string(), timmy.id, data.community.id);
+    let post = Post::create(pool, &post_form).await?;
+    let form = CommentInsertForm::new(timmy.id, post.id, "on time".to_string());
+    let on_time = Comment::create(pool, &form, None).await?;
+
+    // Arrives later, but claims to be older, eg from a remote instance
+    let form = CommentInsertForm {
+      published_at: Some(Utc::now() - TimeDelta::days(1)),
+      ..CommentInsertForm::new(timmy.id, post.id, "backdated".to_string())
+    };
+    let backdated = Comment::create(pool, &form, None).await?;
+
+    let mut list = async |sort| -> LemmyResult<Vec<CommentId>> {
+      Ok(
+        CommentQuery {
+          post_id: Some(post.id),
+          sort: Some(sort),
+          ..Default::default()
+        }
+        .list(&data.site, pool)
+        .await?
+        .into_iter()
+        .map(|c| c.comment.id)
+        .collect(),
+      )
+    };
+    assert_eq!(vec![on_time.id, backdated.id], list(New).await?);
+    assert_eq!(vec![backdated.id, on_time.id], list(Received).await?);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_sibling_comments() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let siblings = async |pool: &mut DbPool<'_>, comment: &Comment, sort| -> LemmyResult<_> {
+      Ok(
+        CommentQuery {
+          sort: Some(sort),
+          post_id: Some(comment.post_id),
+          sibling_path: Some(comment.path.clone()),
+          ..Default::default()
+        }
+        .list(&data.site, pool)
+        .await?
+        .into_iter()
+        .map(|c| c.comment.id)
+        .collect::<Vec<_>>(),
+      )
+    };
+
+    // Comment 1 and 2 are replies to comment 0, but the replies to comment 1 aren't included
+    let expected = vec![data.comment_1.id, data.comment_2.id];
+    assert_eq!(expected, siblings(pool, &data.comment_2, Old).await?);
+    let expected = vec![data.comment_2.id, data.comment_1.id];
+    assert_eq!(expected, siblings(pool, &data.comment_1, New).await?);
+
+    // Comment 0 is the only top-level comment of the post
+    let expected = vec![data.comment_0.id];
+    assert_eq!(expected, siblings(pool, &data.comment_0, New).await?);
+
+    // Top-level comments of all posts have the same parent, so a post is required
+    let res = CommentQuery {
+      sibling_path: Some(data.comment_0.path.clone()),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await;
+    assert!(res.is_err());
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_comment_stats() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view.person;
+
+    let before = CommentStats::read(pool).await?;
+    assert!(before.total >= 6);
+
+    let form = CommentInsertForm {
+      removed: Some(true),
+      ..CommentInsertForm::new(timmy.id, data.post.id, "removed".to_string())
+    };
+    Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm {
+      published_at: Some(Utc::now() - TimeDelta::days(3)),
+      ..CommentInsertForm::new(timmy.id, data.post.id, "this week".to_string())
+    };
+    Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm {
+      published_at: Some(Utc::now() - TimeDelta::days(30)),
+      ..CommentInsertForm::new(timmy.id, data.post.id, "last month".to_string())
+    };
+    Comment::create(pool, &form, None).await?;
+
+    let after = CommentStats::read(pool).await?;
+    assert_eq!(before.total + 3, after.total);
+    assert_eq!(before.last_day + 1, after.last_day);
+    assert_eq!(before.last_week + 2, after.last_week);
+    assert_eq!(before.removed + 1, after.removed);
+    assert!(after.removed_ratio() > 0.0);
+
+    let top = CommentStats::top_communities(pool, 50).await?;
+    assert!(top.iter().any(|c| c.id == data.community.id));
+    assert!(top.is_sorted_by(|a, b| a.comments >= b.comments));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_collapse_suggested() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await?;
+
+    // Without thresholds nothing is suggested for collapsing
+    let comments = CommentQuery {
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(comments.iter().all(|c| !c.collapsed_suggested));
+
+    let local_user_form = LocalUserUpdateForm {
+      collapse_comments_account_age_days: Some(Some(7)),
+      ..Default::default()
+    };
+    let local_user = LocalUser::update(
+      pool,
+      data.timmy_local_user_view.local_user.id,
+      &local_user_form,
+    )
+    .await?;
+    assert_eq!(Some(7), local_user.collapse_comments_account_age_days);
+    data.timmy_local_user_view.local_user = local_user;
+
+    // All test accounts were just created, so their comments are flagged but still listed
+    let comments = CommentQuery {
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(!comments.is_empty());
+    assert!(comments.iter().all(|c| c.collapsed_suggested));
+
+    let comment = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&data.timmy_local_user_view.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(comment.collapsed_suggested);
+
+    // Other users aren't affected
+    let anonymous = CommentQuery::default().list(&data.site, pool).await?;
+    assert!(anonymous.iter().all(|c| !c.collapsed_suggested));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_min_community_activity() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    // A community where the last post and comment are from a month ago
+    let stale_community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "stale community".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let month_ago = Utc::now() - chrono::Duration::days(30);
+    let stale_post_form = PostInsertForm {
+      published_at: Some(month_ago),
+      ..PostInsertForm::new("stale".into(), timmy.person.id, stale_community.id)
+    };
+    let stale_post = Post::create(pool, &stale_post_form).await?;
+    let stale_comment_form = CommentInsertForm {
+      published_at: Some(month_ago),
+      ..CommentInsertForm::new(timmy.person.id, stale_post.id, "stale comment".into())
+    };
+    let stale_comment = Comment::create(pool, &stale_comment_form, None).await?;
+
+    for community_id in [data.community.id, stale_community.id] {
+      let form = CommunityFollowerForm::new(
+        community_id,
+        timmy.person.id,
+        CommunityFollowerState::Accepted,
+      );
+      CommunityActions::follow(pool, &form).await?;
+    }
+
+    let list = |min_community_activity_seconds| CommentQuery {
+      listing_type: Some(ListingType::Subscribed),
+      min_community_activity_seconds,
+      local_user: Some(&timmy.local_user),
+      ..Default::default()
+    };
+
+    // Without the option, comments from both communities are returned
+    let all = list(None).list(&data.site, pool).await?;
+    assert!(all.iter().any(|c| c.comment.id == stale_comment.id));
+    assert!(all.iter().any(|c| c.community.id == data.community.id));
+
+    // Only the active community is left when requiring activity within the last week
+    let active = list(Some(7 * 24 * 60 * 60)).list(&data.site, pool).await?;
+    assert!(!active.is_empty());
+    assert!(active.iter().all(|c| c.community.id == data.community.id));
+
+    Community::delete(pool, stale_community.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_creator_software() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let mastodon_instance = Instance::read_or_create(pool, "mastodon.tld").await?;
+    let form = InstanceForm {
+      software: Some("mastodon".to_string()),
+      ..InstanceForm::new(mastodon_instance.domain.clone())
+    };
+    Instance::update(pool, mastodon_instance.id, form).await?;
+    let mastodon_person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(mastodon_instance.id, "tooter"),
+    )
+    .await?;
+    let comment_form =
+      CommentInsertForm::new(mastodon_person.id, data.post.id, "from mastodon".into());
+    let mastodon_comment = Comment::create(pool, &comment_form, None).await?;
+
+    let query = |local_user, software: &str| CommentQuery {
+      creator_software: Some(software.to_string()),
+      local_user,
+      ..Default::default()
+    };
+
+    let timmy = Some(&data.timmy_local_user_view.local_user);
+    let comments = query(timmy, "Mastodon").list(&data.site, pool).await?;
+    assert_length!(1, comments);
+    assert_eq!(mastodon_comment.id, comments[0].comment.id);
+
+    let comments = query(timmy, "lemmy").list(&data.site, pool).await?;
+    assert_length!(0, comments);
+
+    // Only for admins
+    let res = query(None, "mastodon").list(&data.site, pool).await;
+    assert_eq!(
+      Some(LemmyErrorType::NotAnAdmin),
+      res.err().map(|e| e.error_type)
+    );
+
+    Instance::delete(pool, mastodon_instance.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_moddable_only() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Another community with a comment, which sara doesn't moderate
+    let other_community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "other community".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      ),
+    )
+    .await?;
+    let other_post = Post::create(
+      pool,
+      &PostInsertForm::new(
+        "other post".into(),
+        data.timmy_local_user_view.person.id,
+        other_community.id,
+      ),
+    )
+    .await?;
+    let other_comment_form = CommentInsertForm::new(
+      data.timmy_local_user_view.person.id,
+      other_post.id,
+      "other comment".into(),
+    );
+    let other_comment = Comment::create(pool, &other_comment_form, None).await?;
+
+    let sara_local_user_form = LocalUserInsertForm::test_form(data.sara_person.id);
+    let sara_local_user = LocalUser::create(pool, &sara_local_user_form, vec![]).await?;
+    let form = CommunityModeratorForm::new(data.community.id, data.sara_person.id);
+    CommunityActions::join(pool, &form).await?;
+
+    let query = |local_user| CommentQuery {
+      moddable_only: Some(true),
+      local_user,
+      ..Default::default()
+    };
+
+    // Sara only sees comments of the community they moderate
+    let comments = query(Some(&sara_local_user)).list(&data.site, pool).await?;
+    assert!(!comments.is_empty());
+    assert!(comments.iter().all(|c| c.community.id == data.community.id));
+    assert!(comments.iter().all(|c| c.comment.id != other_comment.id));
+
+    // Timmy is an admin, so sees comments from both communities
+    let comments = query(Some(&data.timmy_local_user_view.local_user))
+      .list(&data.site, pool)
+      .await?;
+    assert!(comments.iter().any(|c| c.comment.id == other_comment.id));
+    assert!(comments.iter().any(|c| c.community.id == data.community.id));
+
+    Community::delete(pool, other_community.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_creator_instance_blocked() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    let remote_instance = Instance::read_or_create(pool, "blocked.tld").await?;
+    let person_form = PersonInsertForm::test_form(remote_instance.id, "remote_commenter");
+    let remote_person = Person::create(pool, &person_form).await?;
+    let form = CommentInsertForm::new(remote_person.id, data.post.id, "remote comment".into());
+    let remote_comment = Comment::create(pool, &form, None).await?;
+
+    let read = CommentView::read(
+      pool,
+      remote_comment.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(!read.creator_instance_blocked);
+
+    // Instances which the admins blocked are flagged for everyone
+    let form = FederationBlockListForm::new(remote_instance.id, None);
+    FederationBlockList::block(pool, &form).await?;
+    let read = CommentView::read(pool, remote_comment.id, None, data.instance.id).await?;
+    assert!(read.creator_instance_blocked);
+    FederationBlockList::unblock(pool, remote_instance.id).await?;
+
+    let form = InstancePersonsBlockForm::new(timmy.person.id, remote_instance.id);
+    InstanceActions::block_persons(pool, &form).await?;
+
+    let read = CommentView::read(
+      pool,
+      remote_comment.id,
+      Some(&timmy.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert!(read.creator_instance_blocked);
+
+    // The comment is hidden from listings by default
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      local_user: Some(&timmy.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(comments.iter().all(|c| c.comment.id != remote_comment.id));
+    assert!(comments.iter().all(|c| !c.creator_instance_blocked));
+
+    // Unless comments from blocked instances are requested, then they are flagged
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      local_user: Some(&timmy.local_user),
+      show_blocked_instances: Some(true),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let remote = comments
+      .iter()
+      .find(|c| c.comment.id == remote_comment.id)
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(remote.creator_instance_blocked);
+    assert!(
+      comments
+        .iter()
+        .filter(|c| c.comment.id != remote_comment.id)
+        .all(|c| !c.creator_instance_blocked)
+    );
+
+    Instance::delete(pool, remote_instance.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_show_creator_instance() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let remote_instance = Instance::read_or_create(pool, "creator-instance.tld").await?;
+    let person_form = PersonInsertForm::test_form(remote_instance.id, "instance_shower");
+    let remote_person = Person::create(pool, &person_form).await?;
+    let form = CommentInsertForm::new(remote_person.id, data.post.id, "remote comment".into());
+    let remote_comment = Comment::create(pool, &form, None).await?;
+
+    // Shown by default
+    let read = CommentView::read(pool, remote_comment.id, None, data.instance.id).await?;
+    assert_eq!(
+      Some("creator-instance.tld".to_string()),
+      read.creator_instance_domain
+    );
+
+    let form = CommunityUpdateForm {
+      show_creator_instance: Some(false),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+    let read = CommentView::read(pool, remote_comment.id, None, data.instance.id).await?;
+    assert_eq!(None, read.creator_instance_domain);
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(comments.iter().all(|c| c.creator_instance_domain.is_none()));
+
+    Instance::delete(pool, remote_instance.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pinned_in_community() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let post_form =
+      PostInsertForm::new("other post".into(), data.sara_person.id, data.community.id);
+    let other_post = Post::create(pool, &post_form).await?;
+    let form = CommentInsertForm::new(data.sara_person.id, other_post.id, "other".into());
+    let other_comment = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(data.sara_person.id, other_post.id, "another".into());
+    Comment::create(pool, &form, None).await?;
+
+    let form = CommunityUpdateForm {
+      pinned_comment_id: Some(Some(data.comment_0.id)),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+
+    // Shown above the comments of the other post
+    let comments = CommentQuery {
+      post_id: Some(other_post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(3, comments.len());
+    assert_eq!(
+      Some((data.comment_0.id, true)),
+      comments
+        .first()
+        .map(|c| (c.comment.id, c.pinned_in_community))
+    );
+
+    // It takes up one place of the page
+    let comments = CommentQuery {
+      post_id: Some(other_post.id),
+      sort: Some(CommentSortType::Old),
+      limit: Some(2),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(
+      vec![(data.comment_0.id, true), (other_comment.id, false)],
+      comments
+        .iter()
+        .map(|c| (c.comment.id, c.pinned_in_community))
+        .collect::<Vec<_>>()
+    );
+
+    // Tree fetches only contain the comments of the post
+    let comments = CommentQuery {
+      post_id: Some(other_post.id),
+      max_depth: Some(8),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(2, comments.len());
+    assert!(comments.iter().all(|c| !c.pinned_in_community));
+
+    // Only listed once for its own post
+    let comments = CommentQuery {
+      post_id: Some(data.post.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(
+      Some((data.comment_0.id, true)),
+      comments
+        .first()
+        .map(|c| (c.comment.id, c.pinned_in_community))
+    );
+    assert_eq!(
+      1,
+      comments
+        .iter()
+        .filter(|c| c.comment.id == data.comment_0.id)
+        .count()
+    );
+
+    // Not pinned when listing by something other than the post
+    let comments = CommentQuery {
+      creator_id: Some(data.sara_person.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(comments.iter().all(|c| !c.pinned_in_community));
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_hide_scores_until_voted() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let form = CommunityUpdateForm {
+      hide_scores_until_voted: Some(true),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+
+    let sara_local_user_form = LocalUserInsertForm::test_form(data.sara_person.id);
+    let sara_local_user = LocalUser::create(pool, &sara_local_user_form, vec![]).await?;
+    // Timmy upvoted the comment, but sara doesn't see it before voting
+    let sara_view = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&sara_local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!(
+      (0, 0, 0),
+      (
+        sara_view.comment.score,
+        sara_view.comment.upvotes,
+        sara_view.comment.downvotes
+      )
+    );
+
+    let form = CommentLikeForm::new(data.sara_person.id, data.comment_0.id, false);
+    CommentActions::like(pool, &form).await?;
+    let sara_view = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&sara_local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!(
+      (0, 1, 1),
+      (
+        sara_view.comment.score,
+        sara_view.comment.upvotes,
+        sara_view.comment.downvotes
+      )
+    );
+
+    // Timmy is an admin, and always sees the score
+    let timmy_view = CommentView::read(
+      pool,
+      data.comment_0.id,
+      Some(&data.timmy_local_user_view.local_user),
+      data.instance.id,
+    )
+    .await?;
+    assert_eq!(1, timmy_view.comment.upvotes);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_hide_creator_avatar() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let mut data = init_data(pool).await?;
+
+    let avatar: DbUrl = Url::parse("https://example.com/avatar.png")?.into();
+    let form = PersonUpdateForm {
+      avatar: Some(Some(avatar.clone())),
+      ..Default::default()
+    };
+    Person::update(pool, data.timmy_local_user_view.person.id, &form).await?;
+
+    let comment_id = data.comment_0.id;
+    let timmy = &data.timmy_local_user_view.local_user;
+    let shown = CommentView::read(pool, comment_id, Some(timmy), data.instance.id).await?;
+    assert_eq!(Some(avatar.clone()), shown.creator.avatar);
+
+    let form = LocalUserUpdateForm {
+      show_avatars: Some(false),
+      ..Default::default()
+    };
+    LocalUser::update(pool, data.timmy_local_user_view.local_user.id, &form).await?;
+    data.timmy_local_user_view.local_user.show_avatars = false;
+    let timmy = &data.timmy_local_user_view.local_user;
+
+    let hidden = CommentView::read(pool, comment_id, Some(timmy), data.instance.id).await?;
+    assert_eq!(None, hidden.creator.avatar);
+    let listed = CommentQuery {
+      local_user: Some(timmy),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(listed.iter().all(|c| c.creator.avatar.is_none()));
+    let top = CommentView::top_per_post(
+      pool,
+      data.community.id,
+      None,
+      None,
+      Some(timmy),
+      data.instance.id,
+    )
+    .await?;
+    assert!(!top.is_empty());
+    assert!(top.iter().all(|c| c.creator.avatar.is_none()));
+
+    // Other users still get the avatar
+    let anonymous = CommentView::read(pool, comment_id, None, data.instance.id).await?;
+    assert_eq!(Some(avatar), anonymous.creator.avatar);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_count_for_person() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let timmy = &data.timmy_local_user_view;
+
+    // Timmy wrote all comments except comment 1
+    let count = CommentView::count_for_person(pool, timmy.person.id, None).await?;
+    assert_eq!(5, count);
+    let count = CommentView::count_for_person(pool, data.sara_person.id, None).await?;
+    assert_eq!(1, count);
+
+    let form = CommentUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, data.comment_2.id, &form).await?;
+    let count = CommentView::count_for_person(pool, timmy.person.id, None).await?;
+    assert_eq!(4, count);
+
+    // Comments in a private community are only counted for those who can see them
+    let form = CommunityUpdateForm {
+      visibility: Some(CommunityVisibility::Private),
+      ..Default::default()
+    };
+    Community::update(pool, data.community.id, &form).await?;
+    let count = CommentView::count_for_person(pool, timmy.person.id, None).await?;
+    assert_eq!(0, count);
+    let count =
+      CommentView::count_for_person(pool, timmy.person.id, Some(&timmy.local_user)).await?;
+    assert_eq!(4, count);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_crosspost_of() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let url: DbUrl = Url::parse("https://example.com/crossposted-article")?.into();
+    let timmy_id = data.timmy_local_user_view.person.id;
+    let original_form = PostInsertForm {
+      url: Some(url.clone()),
+      ..PostInsertForm::new("Original".into(), timmy_id, data.community.id)
+    };
+    let original = Post::create(pool, &original_form).await?;
+    let crosspost_form = PostInsertForm {
+      url: Some(url),
+      ..PostInsertForm::new("Crosspost".into(), timmy_id, data.community.id)
+    };
+    let crosspost = Post::create(pool, &crosspost_form).await?;
+
+    let form = CommentInsertForm::new(timmy_id, original.id, "On original".into());
+    let original_comment = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm::new(timmy_id, crosspost.id, "On crosspost".into());
+    let crosspost_comment = Comment::create(pool, &form, None).await?;
+
+    let view = CommentView::read(pool, crosspost_comment.id, None, data.instance.id).await?;
+    assert_eq!(Some(original.id), view.crosspost_of);
+    let view = CommentView::read(pool, original_comment.id, None, data.instance.id).await?;
+    assert_eq!(None, view.crosspost_of);
+
+    // Posts without url are never crossposts
+    let view = CommentView::read(pool, data.comment_0.id, None, data.instance.id).await?;
+    assert_eq!(None, view.crosspost_of);
+
+    Post::delete(pool, crosspost.id).await?;
+    Post::delete(pool, original.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pending_approval() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let sara_local_user_form = LocalUserInsertForm::test_form(data.sara_person.id);
+    let sara_local_user = LocalUser::create(pool, &sara_local_user_form, vec![]).await?;
+
+    let form = CommentInsertForm {
+      pending_approval: Some(true),
+      ..CommentInsertForm::new(data.sara_person.id, data.post.id, "Held".into())
+    };
+    let held = Comment::create(pool, &form, None).await?;
+
+    // Held comments are hidden from others
+    let anonymous = CommentQuery::default().list(&data.site, pool).await?;
+    assert_length!(6, anonymous);
+    let anonymous_read = CommentView::read(pool, held.id, None, data.instance.id).await;
+    assert!(anonymous_read.is_err());
+
+    // But visible to the creator
+    let creator_read =
+      CommentView::read(pool, held.id, Some(&sara_local_user), data.instance.id).await?;
+    assert!(creator_read.comment.pending_approval);
+
+    // And listed in the queue for mods and admins
+    let unblock_form =
+      PersonBlockForm::new(data.timmy_local_user_view.person.id, data.sara_person.id);
+    PersonActions::unblock(pool, &unblock_form).await?;
+    let queue = CommentQuery {
+      pending_approval_only: Some(true),
+      local_user: Some(&data.timmy_local_user_view.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(
+      vec![held.id],
+      queue.iter().map(|c| c.comment.id).collect::<Vec<_>>()
+    );
+
+    // Once approved it is shown to everyone
+    let approve_form = CommentUpdateForm {
+      pending_approval: Some(false),
+      ..Default::default()
+    };
+    Comment::update(pool, held.id, &approve_form).await?;
+    let anonymous = CommentQuery::default().list(&data.site, pool).await?;
+    assert_length!(7, anonymous);
+    CommentView::read(pool, held.id, None, data.instance.id).await?;
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_created_after() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let created_after = Utc::now();
+    let form = CommentInsertForm::new(
+      data.timmy_local_user_view.person.id,
+      data.post.id,
+      "Comment 6".into(),
+    );
+    let comment_6 = Comment::create(pool, &form, Some(&data.comment_2.path)).await?;
+
+    let new_comments = CommentQuery {
+      post_id: Some(data.post.id),
+      created_after: Some(created_after),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(1, new_comments);
+    assert_eq!(comment_6.id, new_comments[0].comment.id);
+
+    // Composes with the parent path filter
+    let new_comments_in_other_thread = CommentQuery {
+      post_id: Some(data.post.id),
+      parent_path: Some(data.comment_1.path.clone()),
+      created_after: Some(created_after),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(0, new_comments_in_other_thread);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_creator_ids() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let jane_form = PersonInsertForm::test_form(data.instance.id, "jane");
+    let jane = Person::create(pool, &jane_form).await?;
+    let form = CommentInsertForm::new(jane.id, data.post.id, "Jane's comment".into());
+    Comment::create(pool, &form, Some(&data.comment_2.path)).await?;
+
+    let sara_and_jane = CommentQuery {
+      creator_ids: Some(vec![data.sara_person.id, jane.id]),
+      sort: Some(CommentSortType::Old),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_eq!(
+      vec!["Comment 1", "Jane's comment"],
+      sara_and_jane
+        .iter()
+        .map(|c| c.comment.content.as_str())
+        .collect::<Vec<_>>()
+    );
+
+    let only_sara = CommentQuery {
+      creator_id: Some(data.sara_person.id),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert_length!(1, only_sara);
+
+    // Single and multiple creators can't be combined
+    let both = CommentQuery {
+      creator_id: Some(data.sara_person.id),
+      creator_ids: Some(vec![jane.id]),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::ContradictingFilters),
+      both.err().map(|e| e.error_type)
+    );
+
+    let too_many = CommentQuery {
+      creator_ids: Some(vec![jane.id; MAX_API_PARAM_ELEMENTS]),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await;
+    assert_eq!(
+      Some(LemmyErrorType::TooManyItems),
+      too_many.err().map(|e| e.error_type)
+    );
+
+    Person::delete(pool, jane.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_hot_rank_pagination() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let post_form = PostInsertForm::new(
+      "pagination post".into(),
+      data.timmy_local_user_view.person.id,
+      data.community.id,
+    );
+    let post = Post::create(pool, &post_form).await?;
+    let mut comment_ids = vec![];
+    for i in 0..25u8 {
+      let form = CommentInsertForm::new(
+        data.timmy_local_user_view.person.id,
+        post.id,
+        format!("Comment {i}"),
+      );
+      let comment = Comment::create(pool, &form, None).await?;
+      set_hot_rank(pool, comment.id, f32::from(i)).await?;
+      comment_ids.push(comment.id);
+    }
+
+    let mut seen = vec![];
+    let mut page_cursor = None;
+    loop {
+      let page = CommentQuery {
+        post_id: Some(post.id),
+        sort: Some(CommentSortType::Hot),
+        limit: Some(10),
+        page_cursor,
+        ..Default::default()
+      }
+      .list(&data.site, pool)
+      .await?;
+
+      // Move the comment which the cursor points to from the bottom of the page to the top of the
+      // listing.
+      if seen.is_empty()
+        && let Some(last) = page.last()
+      {
+        set_hot_rank(pool, last.comment.id, 1000.0).await?;
+      }
+      seen.extend(page.iter().map(|c| c.comment.id));
+      if page.next_page.is_none() {
+        break;
+      }
+      page_cursor = page.next_page.clone();
+    }
+
+    // Every comment is returned exactly once, in descending order of the original rank
+    comment_ids.reverse();
+    assert_eq!(comment_ids, seen);
+
+    Post::delete(pool, post.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_pagination_back() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let post_form = PostInsertForm::new(
+      "pagination back".to_string(),
+      data.timmy_local_user_view.person.id,
+      data.community.id,
+    );
+    let post = Post::create(pool, &post_form).await?;
+    for i in 0..25u8 {
+      let form = CommentInsertForm::new(
+        data.timmy_local_user_view.person.id,
+        post.id,
+        format!("Comment {i}"),
+      );
+      let comment = Comment::create(pool, &form, None).await?;
+      // Groups of equal rank, so that the id tie breaker is needed for a stable order
+      set_hot_rank(pool, comment.id, f32::from(i / 5)).await?;
+    }
+
+    let list = |page_cursor| CommentQuery {
+      post_id: Some(post.id),
+      sort: Some(CommentSortType::Hot),
+      limit: Some(10),
+      page_cursor,
+      ..Default::default()
+    };
+    let ids =
+      |page: &PagedResponse<CommentView>| page.iter().map(|c| c.comment.id).collect::<Vec<_>>();
+
+    // Forward through all pages
+    let mut pages = vec![list(None).list(&data.site, pool).await?];
+    while let Some(next_page) = pages.last().and_then(|p| p.next_page.clone()) {
+      pages.push(list(Some(next_page)).list(&data.site, pool).await?);
+    }
+    assert_length!(3, pages);
+    let forward = pages.iter().flat_map(ids).collect::<Vec<_>>();
+    let mut expected = forward.clone();
+    expected.sort_by_key(|id| std::cmp::Reverse(*id));
+    assert_eq!(expected, forward);
+
+    // Backward from the last page gives the same pages again
+    let mut page = pages.last().cloned();
+    for expected_page in pages.iter().rev().skip(1) {
+      let prev_page = page.and_then(|p| p.prev_page);
+      let back = list(prev_page).list(&data.site, pool).await?;
+      assert_eq!(ids(expected_page), ids(&back));
+      page = Some(back);
+    }
+
+    Post::delete(pool, post.id).await?;
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_tree_fetch_without_cursor() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let list = |page_cursor| CommentQuery {
+      post_id: Some(data.post.id),
+      max_depth: Some(10),
+      limit: Some(2),
+      page_cursor,
+      ..Default::default()
+    };
+    let tree = list(None).list(&data.site, pool).await?;
+    assert_length!(6, tree);
+    assert!(tree.next_page.is_none() && tree.prev_page.is_none());
+
+    // A cursor from a flat listing is ignored
+    let flat = CommentQuery {
+      post_id: Some(data.post.id),
+      limit: Some(2),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let tree_with_cursor = list(flat.next_page.clone()).list(&data.site, pool).await?;
+    assert_length!(6, tree_with_cursor);
+
+    cleanup(data, pool).await
+  }
+
+  async fn set_hot_rank(pool: &mut DbPool<'_>, id: CommentId, hot_rank: f32) -> LemmyResult<()> {
+    let conn = &mut get_conn(pool).await?;
+    diesel::update(comment::table.find(id))
+      .set(comment::hot_rank.eq(hot_rank))
+      .execute(conn)
+      .await?;
+    Ok(())
+  }
+
   async fn cleanup(data: Data, pool: &mut DbPool<'_>) -> LemmyResult<()> {
     CommentActions::remove_like(
       pool,
@@ -1,27 +1,54 @@
 use chrono::{DateTime, Utc};
-use lemmy_db_schema::source::{
-  comment::{Comment, CommentActions},
-  community::{Community, CommunityActions},
-  person::{Person, PersonActions},
-  post::Post,
-  tag::TagsView,
+use lemmy_db_schema::{
+  newtypes::{CommentId, PostId},
+  source::{
+    comment::{Comment, CommentActions},
+    comment_reaction::CommentReactionsView,
+    community::{Community, CommunityActions},
+    person::{Person, PersonActions},
+    post::Post,
+    tag::TagsView,
+  },
 };
+use lemmy_db_schema_file::{InstanceId, PersonId};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
 use {
-  diesel::{Queryable, Selectable},
-  lemmy_db_schema::utils::queries::selects::{
-    CreatorLocalHomeCommunityBanExpiresType,
-    comment_creator_is_admin,
-    comment_select_remove_deletes,
-    creator_ban_expires_from_community,
-    creator_banned_from_community,
-    creator_is_moderator,
-    creator_local_home_community_ban_expires,
-    creator_local_home_community_banned,
-    local_user_can_mod_comment,
-    post_tags_fragment,
+  diesel::{
+    NullableExpressionMethods,
+    Queryable,
+    QueryableByName,
+    Selectable,
+    deserialize::FromSqlRow,
+    dsl::Nullable,
+  },
+  lemmy_db_schema::{
+    Person1AliasAllColumnsTuple,
+    utils::queries::selects::{
+      CommentCreatorInstanceDomainType,
+      CreatorLocalHomeCommunityBanExpiresType,
+      FalseLiteralType,
+      NullJsonLiteralType,
+      NullTextLiteralType,
+      PostCrosspostOfType,
+      comment_creator_instance_domain,
+      comment_creator_is_admin,
+      comment_select_remove_deletes,
+      comment_tags_fragment,
+      creator_ban_expires_from_community,
+      creator_banned_from_community,
+      creator_is_moderator,
+      creator_local_home_community_ban_expires,
+      creator_local_home_community_banned,
+      false_literal,
+      local_user_can_mod_comment,
+      null_json_literal,
+      null_text_literal,
+      person1_select,
+      post_crosspost_of,
+      post_tags_fragment,
+    },
   },
 };
 
@@ -67,6 +94,13 @@ pub struct CommentView {
     )
   )]
   pub post_tags: TagsView,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression = comment_tags_fragment()
+    )
+  )]
+  /// Community tags which the author set on the comment.
+  pub comment_tags: TagsView,
   #[cfg_attr(feature = "full",
     diesel(
       select_expression = local_user_can_mod_comment()
@@ -104,6 +138,132 @@ pub struct CommentView {
     )
   )]
   pub creator_community_ban_expires_at: Option<DateTime<Utc>>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = FalseLiteralType,
+      select_expression = false_literal()
+    )
+  )]
+  /// Whether you blocked the instance of the creator, or the admins blocked it from federating.
+  /// Comments from instances you blocked are only listed with `show_blocked_instances`.
+  pub creator_instance_blocked: bool,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = CommentCreatorInstanceDomainType,
+      select_expression = comment_creator_instance_domain()
+    )
+  )]
+  /// The domain of the creator's instance, to show as `@user@domain`. Empty if the community hides
+  /// creator instances.
+  pub creator_instance_domain: Option<String>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = NullTextLiteralType,
+      select_expression = null_text_literal()
+    )
+  )]
+  /// Whether the comment was delivered to the instance of a remote community. Only shown to the
+  /// creator of the comment.
+  pub federation_state: Option<CommentFederationState>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = NullTextLiteralType,
+      select_expression = null_text_literal()
+    )
+  )]
+  /// The markdown as submitted by the creator, before slurs were removed and links rewritten. Use
+  /// this to prefill edits. Only shown to the creator of the comment.
+  pub content_source: Option<String>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = FalseLiteralType,
+      select_expression = false_literal()
+    )
+  )]
+  /// Whether the current user wrote a direct reply to this comment.
+  pub viewer_has_replied: bool,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = FalseLiteralType,
+      select_expression = false_literal()
+    )
+  )]
+  /// The creator's account is newer, or has a lower comment score, than the current user's
+  /// settings allow. Clients should collapse the comment by default, but still show it.
+  pub collapsed_suggested: bool,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = FalseLiteralType,
+      select_expression = false_literal()
+    )
+  )]
+  /// The comment is pinned by the mods above the comments of every post in the community. Clients
+  /// should style it as a mod note.
+  pub pinned_in_community: bool,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = Nullable<Person1AliasAllColumnsTuple>,
+      select_expression = person1_select().nullable()
+    )
+  )]
+  /// The mod who removed the comment. Only shown to users who can moderate it.
+  pub removed_by: Option<Person>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = NullTextLiteralType,
+      select_expression = null_text_literal()
+    )
+  )]
+  /// The reason given for removing the comment. Only shown to users who can moderate it.
+  pub removed_reason: Option<String>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = PostCrosspostOfType,
+      select_expression = post_crosspost_of()
+    )
+  )]
+  /// If the post is a crosspost, the original post with the same url.
+  pub crosspost_of: Option<PostId>,
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = NullJsonLiteralType,
+      select_expression = null_json_literal()
+    )
+  )]
+  /// Emoji reactions to the comment, most frequent first.
+  pub reactions: CommentReactionsView,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+#[cfg_attr(feature = "full", derive(FromSqlRow))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[serde(rename_all = "snake_case")]
+/// The delivery state of a local comment to the instance of a remote community.
+pub enum CommentFederationState {
+  /// Not yet delivered.
+  Pending,
+  /// Delivered successfully.
+  Sent,
+  /// Delivery failed, and is being retried.
+  Failed,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The delivery state of a local comment to a single remote instance.
+pub struct CommentFederationTarget {
+  pub instance_id: InstanceId,
+  pub domain: String,
+  /// State of the newest activity about the comment which was sent to this instance.
+  pub state: CommentFederationState,
+  /// When sending a failed activity to this instance was last retried. Successful sends don't
+  /// change this.
+  pub last_attempt_at: Option<DateTime<Utc>>,
+  /// The error of the last failed attempt, only while delivery is failing.
+  pub error: Option<String>,
 }
 
 #[skip_serializing_none]
@@ -123,4 +283,50 @@ pub struct CommentSlimView {
   pub creator_banned: bool,
   pub creator_is_moderator: bool,
   pub creator_banned_from_community: bool,
+  /// The highest ranked direct reply, only filled when requested.
+  pub top_child_preview: Option<CommentPreview>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(QueryableByName))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A short preview of a comment reply, used for collapsed thread views.
+pub struct CommentPreview {
+  #[serde(skip)]
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Integer))]
+  pub parent_id: CommentId,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Integer))]
+  pub id: CommentId,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Integer))]
+  pub creator_id: PersonId,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Text))]
+  pub content: String,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Timestamptz))]
+  pub published_at: DateTime<Utc>,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Integer))]
+  pub score: i32,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Integer))]
+  pub child_count: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "full", derive(QueryableByName))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Comment counts across all communities known to the instance, for the admin dashboard.
+pub struct CommentStats {
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub total: i64,
+  /// Comments published within the last 24 hours.
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub last_day: i64,
+  /// Comments published within the last 7 days.
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub last_week: i64,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub removed: i64,
 }
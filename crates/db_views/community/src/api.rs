@@ -3,12 +3,18 @@ use lemmy_db_schema::{
   CommunitySortType,
   MultiCommunityListingType,
   MultiCommunitySortType,
-  newtypes::{CommunityId, LanguageId, MultiCommunityId, TagId},
-  source::site::Site,
+  newtypes::{CommunityAutomodRuleId, CommunityId, LanguageId, MultiCommunityId, TagId},
+  source::{community_automod_rule::CommunityAutomodRule, site::Site},
 };
 use lemmy_db_schema_file::{
   PersonId,
-  enums::{CommunityNotificationsMode, CommunityVisibility, ListingType},
+  enums::{
+    AutomodAction,
+    CommentSortType,
+    CommunityNotificationsMode,
+    CommunityVisibility,
+    ListingType,
+  },
 };
 use lemmy_db_views_community_moderator::CommunityModeratorView;
 use lemmy_diesel_utils::pagination::PaginationCursor;
@@ -25,11 +31,23 @@ pub struct AddModToCommunity {
   pub added: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Approve a member of a community, or remove them. Only members can comment in communities with
+/// `comments_require_membership`.
+pub struct AddCommunityMember {
+  pub community_id: CommunityId,
+  pub person_id: PersonId,
+  pub added: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
 /// The response of adding a moderator to a community.
 pub struct AddModToCommunityResponse {
+  /// The longest serving moderators first, up to a fixed limit.
   pub moderators: Vec<CommunityModeratorView>,
 }
 
@@ -85,6 +103,7 @@ pub struct CommunityIdQuery {
 /// A simple community response.
 pub struct CommunityResponse {
   pub community_view: CommunityView,
+  /// Empty if all languages are allowed.
   pub discussion_languages: Vec<LanguageId>,
 }
 
@@ -112,6 +131,34 @@ pub struct CreateCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether to hold comments from new members until a mod approves them.
+  pub require_comment_approval_for_new_members: Option<bool>,
+  /// Members with fewer approved comments in the community than this count as new.
+  pub new_member_comment_threshold: Option<i32>,
+  /// Accounts younger than this many days count as new members.
+  pub new_member_account_age_days: Option<i32>,
+  /// The default language of new comments. Must be one of the discussion languages.
+  pub primary_language_id: Option<LanguageId>,
+  /// Posts older than this many seconds don't accept new comments, except from mods.
+  pub auto_lock_posts_after_seconds: Option<i32>,
+  /// Keep existing content visible, but only allow mods to add posts, comments or votes.
+  pub read_only: Option<bool>,
+  /// Posted as a reply to the first comment of each user in the community.
+  pub welcome_message: Option<String>,
+  /// Only show comment scores to users who voted on the comment, and to mods.
+  pub hide_scores_until_voted: Option<bool>,
+  /// Only allow members approved by a mod to comment. Followers can still read.
+  pub comments_require_membership: Option<bool>,
+  /// Undistinguish distinguished comments once their score drops below this.
+  pub auto_undistinguish_below_score: Option<i32>,
+  /// Reject comments from non-mods with fewer characters than this.
+  pub min_comment_length: Option<i32>,
+  /// Reject downvotes on comments if false.
+  pub downvotes_enabled: Option<bool>,
+  /// Reject edits by non-mods to comments which already have replies.
+  pub lock_edits_after_reply: Option<bool>,
+  /// Whether to show the instance of comment creators. Enabled by default.
+  pub show_creator_instance: Option<bool>,
 }
 
 #[skip_serializing_none]
@@ -143,6 +190,42 @@ pub struct EditCommunity {
   pub posting_restricted_to_mods: Option<bool>,
   pub discussion_languages: Option<Vec<LanguageId>>,
   pub visibility: Option<CommunityVisibility>,
+  /// Whether to hold comments from new members until a mod approves them.
+  pub require_comment_approval_for_new_members: Option<bool>,
+  /// Members with fewer approved comments in the community than this count as new. Zero removes
+  /// the threshold.
+  pub new_member_comment_threshold: Option<i32>,
+  /// Accounts younger than this many days count as new members. Zero removes the threshold.
+  pub new_member_account_age_days: Option<i32>,
+  /// The default language of new comments. Must be one of the discussion languages. Undetermined
+  /// removes the default.
+  pub primary_language_id: Option<LanguageId>,
+  /// Posts older than this many seconds don't accept new comments, except from mods. Zero removes
+  /// the limit.
+  pub auto_lock_posts_after_seconds: Option<i32>,
+  /// Keep existing content visible, but only allow mods to add posts, comments or votes.
+  pub read_only: Option<bool>,
+  /// Posted as a reply to the first comment of each user in the community. An empty string
+  /// removes it.
+  pub welcome_message: Option<String>,
+  /// Only show comment scores to users who voted on the comment, and to mods.
+  pub hide_scores_until_voted: Option<bool>,
+  /// Only allow members approved by a mod to comment. Followers can still read.
+  pub comments_require_membership: Option<bool>,
+  /// Undistinguish distinguished comments once their score drops below this. Zero removes the
+  /// setting.
+  pub auto_undistinguish_below_score: Option<i32>,
+  /// Reject comments from non-mods with fewer characters than this. Zero removes the setting.
+  pub min_comment_length: Option<i32>,
+  /// Only allow comments from non-mods to link to these domains and their subdomains. An empty
+  /// list allows all domains.
+  pub allowed_link_domains: Option<Vec<String>>,
+  /// Reject downvotes on comments if false.
+  pub downvotes_enabled: Option<bool>,
+  /// Reject edits by non-mods to comments which already have replies.
+  pub lock_edits_after_reply: Option<bool>,
+  /// Whether to show the instance of comment creators. Enabled by default.
+  pub show_creator_instance: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
@@ -154,16 +237,35 @@ pub struct FollowCommunity {
   pub follow: bool,
 }
 
-#[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case", try_from = "GetCommunityFields")]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
-#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
-// TODO make this into a tagged enum
-/// Get a community. Must provide either an id, or a name.
-pub struct GetCommunity {
-  pub id: Option<CommunityId>,
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// Get a community, either by its id or by its name.
+pub enum GetCommunity {
+  Id(CommunityId),
   /// Example: star_trek , or star_trek@xyz.tld
-  pub name: Option<String>,
+  Name(String),
+}
+
+/// The parameters of [GetCommunity] as clients send them. Exactly one of them must be given.
+#[derive(Deserialize)]
+struct GetCommunityFields {
+  id: Option<CommunityId>,
+  name: Option<String>,
+}
+
+impl TryFrom<GetCommunityFields> for GetCommunity {
+  type Error = &'static str;
+
+  fn try_from(fields: GetCommunityFields) -> Result<Self, Self::Error> {
+    match (fields.id, fields.name) {
+      (Some(id), None) => Ok(Self::Id(id)),
+      (None, Some(name)) => Ok(Self::Name(name)),
+      (None, None) => Err("either id or name must be given"),
+      (Some(_), Some(_)) => Err("id and name can't be given together"),
+    }
+  }
 }
 
 #[skip_serializing_none]
@@ -174,8 +276,16 @@ pub struct GetCommunity {
 pub struct GetCommunityResponse {
   pub community_view: CommunityView,
   pub site: Option<Site>,
+  /// The longest serving moderators first, up to a fixed limit.
   pub moderators: Vec<CommunityModeratorView>,
+  /// Empty if all languages are allowed.
   pub discussion_languages: Vec<LanguageId>,
+  /// Comments from non-mods may only link to these domains and their subdomains. Empty if all
+  /// domains are allowed.
+  pub allowed_link_domains: Vec<String>,
+  /// Whether comments in this community can be downvoted. False if either the community or the
+  /// site disabled comment downvotes.
+  pub downvotes_enabled: bool,
 }
 
 #[skip_serializing_none]
@@ -211,6 +321,8 @@ pub struct ListCommunities {
   /// IE 60 would give results for the past minute.
   pub time_range_seconds: Option<i32>,
   pub show_nsfw: Option<bool>,
+  /// Include the number of comments made in each community during the last week.
+  pub include_comment_counts: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
 }
@@ -326,6 +438,18 @@ pub struct UpdateCommunityNotifications {
   pub mode: CommunityNotificationsMode,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Remember a comment sort for a community, which is used instead of your default comment sort
+/// when listing its comments.
+pub struct UpdateCommunityCommentSort {
+  pub community_id: CommunityId,
+  /// None goes back to your default comment sort.
+  pub sort: Option<CommentSortType>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -357,3 +481,103 @@ pub struct UpdateCommunityTag {
 pub struct DeleteCommunityTag {
   pub tag_id: TagId,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Create an automod rule for a community. The pattern is a case insensitive regex.
+pub struct CreateCommunityAutomodRule {
+  pub community_id: CommunityId,
+  pub pattern: String,
+  pub action: AutomodAction,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Make changes to a community automod rule.
+pub struct EditCommunityAutomodRule {
+  pub rule_id: CommunityAutomodRuleId,
+  pub pattern: Option<String>,
+  pub action: Option<AutomodAction>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Delete a community automod rule.
+pub struct DeleteCommunityAutomodRule {
+  pub rule_id: CommunityAutomodRuleId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// List the automod rules of a community. Only available to mods.
+pub struct ListCommunityAutomodRules {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ListCommunityAutomodRulesResponse {
+  pub rules: Vec<CommunityAutomodRule>,
+}
+
+/// The current format version of [CommunitySettingsBackup]. Only needs to be increased for
+/// incompatible changes, new optional fields are ignored by older versions.
+pub const COMMUNITY_SETTINGS_BACKUP_VERSION: i32 = 1;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The settings of a community, for backups or for moving it to another instance. Languages are
+/// stored by their code, so that the backup can be imported on other instances.
+pub struct CommunitySettingsBackup {
+  pub version: i32,
+  pub title: Option<String>,
+  pub sidebar: Option<String>,
+  pub description: Option<String>,
+  pub nsfw: Option<bool>,
+  pub posting_restricted_to_mods: Option<bool>,
+  pub visibility: Option<CommunityVisibility>,
+  #[serde(default)]
+  pub discussion_languages: Vec<String>,
+  pub primary_language: Option<String>,
+  #[serde(default)]
+  pub tags: Vec<CommunityTagBackup>,
+  #[serde(default)]
+  pub automod_rules: Vec<CommunityAutomodRuleBackup>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A post tag in a community settings backup.
+pub struct CommunityTagBackup {
+  pub name: String,
+  pub display_name: Option<String>,
+  pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// An automod rule in a community settings backup.
+pub struct CommunityAutomodRuleBackup {
+  pub pattern: String,
+  pub action: AutomodAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Apply a settings backup to a community without any posts. Only available to mods.
+pub struct ImportCommunitySettings {
+  pub community_id: CommunityId,
+  pub backup: CommunitySettingsBackup,
+}
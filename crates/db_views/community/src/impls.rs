@@ -1,5 +1,10 @@
 use crate::{CommunityView, MultiCommunityView};
-use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use diesel::{
+  ExpressionMethods,
+  QueryDsl,
+  SelectableHelper,
+  dsl::{IntervalDsl, count_star},
+};
 use diesel_async::RunQueryDsl;
 use i_love_jesus::asc_if;
 use lemmy_db_schema::{
@@ -33,6 +38,7 @@ use lemmy_db_schema_file::{
     my_multi_community_follower_join,
   },
   schema::{
+    comment,
     community,
     community_actions,
     instance_actions,
@@ -40,6 +46,7 @@ use lemmy_db_schema_file::{
     multi_community_entry,
     multi_community_follow,
     person,
+    post,
   },
 };
 use lemmy_diesel_utils::{
@@ -55,6 +62,7 @@ use lemmy_diesel_utils::{
   utils::{LowerKey, now, seconds_to_pg_interval},
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use std::collections::HashMap;
 
 impl CommunityView {
   #[diesel::dsl::auto_type(no_type_alias)]
@@ -122,6 +130,7 @@ pub struct CommunityQuery<'a> {
   pub multi_community_id: Option<MultiCommunityId>,
   pub page_cursor: Option<PaginationCursor>,
   pub limit: Option<i64>,
+  pub include_comment_counts: Option<bool>,
 }
 
 impl CommunityQuery<'_> {
@@ -198,6 +207,7 @@ impl CommunityQuery<'_> {
       Old => pq.then_order_by(key::published_at),
       Subscribers => pq.then_order_by(key::subscribers),
       SubscribersLocal => pq.then_order_by(key::subscribers_local),
+      TrendingComments => pq.then_order_by(key::comments_day),
       ActiveSixMonths => pq.then_order_by(key::users_active_half_year),
       ActiveMonthly => pq.then_order_by(key::users_active_month),
       ActiveWeekly => pq.then_order_by(key::users_active_week),
@@ -209,15 +219,47 @@ impl CommunityQuery<'_> {
     // finally use unique id as tie breaker
     pq = pq.then_order_by(key::id);
 
-    let conn = &mut get_conn(pool).await?;
-    let res = pq
-      .load::<CommunityView>(conn)
+    let mut res = pq
+      .load::<CommunityView>(&mut get_conn(pool).await?)
       .await
       .with_lemmy_type(LemmyErrorType::NotFound)?;
+
+    if o.include_comment_counts.unwrap_or_default() {
+      CommunityView::fill_comments_last_week(&mut res, pool).await?;
+    }
     paginate_response(res, limit, o.page_cursor)
   }
 }
 
+impl CommunityView {
+  /// Fills in the number of comments made in each community during the last week. This needs a
+  /// count over the comment table, so it is only done when requested.
+  async fn fill_comments_last_week(
+    communities: &mut [CommunityView],
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<()> {
+    let ids: Vec<CommunityId> = communities.iter().map(|c| c.community.id).collect();
+    let conn = &mut get_conn(pool).await?;
+    let counts: HashMap<CommunityId, i64> = comment::table
+      .inner_join(post::table)
+      .filter(post::community_id.eq_any(ids))
+      .filter(comment::published_at.gt(now() - 1.week()))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false))
+      .group_by(post::community_id)
+      .select((post::community_id, count_star()))
+      .load::<(CommunityId, i64)>(conn)
+      .await?
+      .into_iter()
+      .collect();
+
+    for c in communities {
+      c.comments_last_week = Some(counts.get(&c.community.id).copied().unwrap_or_default());
+    }
+    Ok(())
+  }
+}
+
 impl MultiCommunityView {
   #[diesel::dsl::auto_type(no_type_alias)]
   fn joins(person_id: Option<PersonId>) -> _ {
@@ -349,6 +391,7 @@ mod tests {
   use lemmy_db_schema::{
     CommunitySortType,
     source::{
+      comment::{Comment, CommentInsertForm},
       community::{
         Community,
         CommunityActions,
@@ -361,6 +404,7 @@ mod tests {
       local_user::{LocalUser, LocalUserInsertForm},
       multi_community::{MultiCommunity, MultiCommunityFollowForm, MultiCommunityInsertForm},
       person::{Person, PersonInsertForm},
+      post::{Post, PostInsertForm},
       site::Site,
     },
     traits::Followable,
@@ -658,6 +702,53 @@ mod tests {
     cleanup(data, pool).await
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn comment_counts() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let community = &data.communities[0];
+
+    let post_form = PostInsertForm::new(
+      "comment counts".into(),
+      data.local_user.person_id,
+      community.id,
+    );
+    let post = Post::create(pool, &post_form).await?;
+    for content in ["first", "second"] {
+      let form = CommentInsertForm::new(data.local_user.person_id, post.id, content.into());
+      Comment::create(pool, &form, None).await?;
+    }
+
+    // Counts are only filled in when requested
+    let communities = CommunityQuery {
+      local_user: Some(&data.local_user),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    assert!(communities.iter().all(|c| c.comments_last_week.is_none()));
+
+    let communities = CommunityQuery {
+      local_user: Some(&data.local_user),
+      include_comment_counts: Some(true),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?;
+    let count = |id| {
+      communities
+        .iter()
+        .find(|c| c.community.id == id)
+        .and_then(|c| c.comments_last_week)
+    };
+    assert_eq!(Some(2), count(community.id));
+    assert_eq!(Some(0), count(data.communities[1].id));
+
+    cleanup(data, pool).await
+  }
+
   #[tokio::test]
   #[serial]
   async fn test_multi_community_list() -> LemmyResult<()> {
@@ -11,8 +11,10 @@ use serde_with::skip_serializing_none;
 use {
   diesel::{NullableExpressionMethods, Queryable, Selectable},
   lemmy_db_schema::utils::queries::selects::{
+    NullCountType,
     community_post_tags_fragment,
     local_user_community_can_mod,
+    null_count,
   },
   lemmy_db_schema_file::schema::multi_community_follow,
 };
@@ -45,6 +47,14 @@ pub struct CommunityView {
     )
   )]
   pub post_tags: TagsView,
+  /// The number of comments in the community during the last week. Only filled in when requested.
+  #[cfg_attr(feature = "full",
+    diesel(
+      select_expression_type = NullCountType,
+      select_expression = null_count()
+    )
+  )]
+  pub comments_last_week: Option<i64>,
 }
 
 #[skip_serializing_none]
@@ -19,7 +19,10 @@ use lemmy_db_schema_file::{
   schema::{community, community_actions, person},
 };
 use lemmy_diesel_utils::connection::{DbPool, get_conn};
-use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
+use lemmy_utils::{
+  COMMUNITY_INLINE_MODERATORS_LIMIT,
+  error::{LemmyErrorExt, LemmyErrorType, LemmyResult},
+};
 
 impl CommunityModeratorView {
   #[diesel::dsl::auto_type(no_type_alias)]
@@ -75,6 +78,23 @@ impl CommunityModeratorView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Like [[CommunityModeratorView::for_community]], but capped to a size which is reasonable for
+  /// returning inline in API responses.
+  pub async fn for_community_inline(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .filter(community_actions::community_id.eq(community_id))
+      .select(Self::as_select())
+      .order_by(community_actions::became_moderator_at)
+      .limit(COMMUNITY_INLINE_MODERATORS_LIMIT)
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn top_mod_for_community(
     pool: &mut DbPool<'_>,
     community_id: CommunityId,
@@ -157,3 +177,56 @@ impl CommunityPersonBanView {
       .ok_or(LemmyErrorType::PersonIsBannedFromCommunity.into())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::CommunityModeratorView;
+  use lemmy_db_schema::source::{
+    community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
+    instance::Instance,
+    person::{Person, PersonInsertForm},
+  };
+  use lemmy_diesel_utils::{connection::build_db_pool_for_tests, traits::Crud};
+  use lemmy_utils::{COMMUNITY_INLINE_MODERATORS_LIMIT, error::LemmyResult};
+  use serial_test::serial;
+
+  #[tokio::test]
+  #[serial]
+  async fn test_for_community_inline() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+
+    let instance = Instance::read_or_create(pool, "my_domain.tld").await?;
+    let community_form = CommunityInsertForm::new(
+      instance.id,
+      "many_mods".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    let mod_count = COMMUNITY_INLINE_MODERATORS_LIMIT + 1;
+    let mut first_mod = None;
+    for i in 0..mod_count {
+      let person_form = PersonInsertForm::test_form(instance.id, &format!("mod_{i}"));
+      let person = Person::create(pool, &person_form).await?;
+      let mod_form = CommunityModeratorForm::new(community.id, person.id);
+      CommunityActions::join(pool, &mod_form).await?;
+      first_mod.get_or_insert(person.id);
+    }
+
+    let all = CommunityModeratorView::for_community(pool, community.id).await?;
+    assert_eq!(mod_count, i64::try_from(all.len())?);
+
+    // Only the longest serving moderators are returned inline
+    let inline = CommunityModeratorView::for_community_inline(pool, community.id).await?;
+    assert_eq!(
+      COMMUNITY_INLINE_MODERATORS_LIMIT,
+      i64::try_from(inline.len())?
+    );
+    assert_eq!(first_mod, inline.first().map(|m| m.moderator.id));
+
+    Instance::delete(pool, instance.id).await?;
+    Ok(())
+  }
+}
@@ -3,6 +3,7 @@ use actix_web::{FromRequest, HttpMessage, HttpRequest, dev::Payload};
 use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
+  JoinOnDsl,
   NullableExpressionMethods,
   QueryDsl,
   SelectableHelper,
@@ -10,7 +11,7 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use i_love_jesus::SortDirection;
 use lemmy_db_schema::{
-  newtypes::{LocalUserId, OAuthProviderId},
+  newtypes::{CommunityId, LocalUserId, OAuthProviderId},
   source::{
     instance::Instance,
     local_user::{LocalUser, LocalUserInsertForm},
@@ -21,7 +22,7 @@ use lemmy_db_schema_file::{
   PersonId,
   aliases::creator_home_instance_actions,
   joins::creator_home_instance_actions_join,
-  schema::{instance_actions, local_user, oauth_account, person},
+  schema::{community_actions, instance_actions, local_user, oauth_account, person},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -133,6 +134,31 @@ impl LocalUserView {
       .with_lemmy_type(LemmyErrorType::NotFound)
   }
 
+  /// Local moderators of the given community who want to be emailed about new pending
+  /// followers.
+  pub async fn list_mods_for_pending_follower_email(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    Self::joins()
+      .inner_join(
+        community_actions::table.on(
+          community_actions::person_id
+            .eq(person::id)
+            .and(community_actions::community_id.eq(community_id)),
+        ),
+      )
+      .filter(community_actions::became_moderator_at.is_not_null())
+      .filter(local_user::email.is_not_null())
+      .filter(local_user::send_notifications_to_email.eq(true))
+      .filter(local_user::email_pending_community_followers.eq(true))
+      .select(Self::as_select())
+      .load::<Self>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+
   pub async fn create_test_user(
     pool: &mut DbPool<'_>,
     name: &str,
@@ -242,6 +268,7 @@ mod tests {
   use lemmy_db_schema::{
     assert_length,
     source::{
+      community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
       instance::{Instance, InstanceActions, InstanceBanForm},
       local_user::{LocalUser, LocalUserInsertForm},
       person::{Person, PersonInsertForm},
@@ -303,4 +330,45 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn list_mods_for_pending_follower_email() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let community_form = CommunityInsertForm::new(
+      data.alice.instance_id,
+      "pending_follower_email".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+
+    // Alice and bob both moderate the community, but only bob opted in to the email
+    let bob_form = PersonInsertForm {
+      local: Some(true),
+      ..PersonInsertForm::test_form(data.alice.instance_id, "bob")
+    };
+    let bob = Person::create(pool, &bob_form).await?;
+    let bob_local_user_form = LocalUserInsertForm {
+      email: Some("bob@my_domain.tld".to_string()),
+      send_notifications_to_email: Some(true),
+      email_pending_community_followers: Some(true),
+      ..LocalUserInsertForm::test_form(bob.id)
+    };
+    LocalUser::create(pool, &bob_local_user_form, vec![]).await?;
+
+    for person_id in [data.alice.id, bob.id] {
+      let form = CommunityModeratorForm::new(community.id, person_id);
+      CommunityActions::join(pool, &form).await?;
+    }
+
+    let mods = LocalUserView::list_mods_for_pending_follower_email(pool, community.id).await?;
+    assert_length!(1, mods);
+    assert_eq!(mods[0].person.id, bob.id);
+
+    cleanup(data, pool).await
+  }
 }
@@ -25,3 +25,20 @@ pub struct MarkNotificationAsRead {
   pub notification_id: NotificationId,
   pub read: bool,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Mark multiple notifications as read or unread at once.
+pub struct MarkNotificationsAsRead {
+  pub notification_ids: Vec<NotificationId>,
+  pub read: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The number of notifications which were marked.
+pub struct MarkNotificationsAsReadResponse {
+  pub count: i64,
+}
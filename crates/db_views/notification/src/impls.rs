@@ -85,7 +85,19 @@ impl NotificationView {
       .with_lemmy_type(LemmyErrorType::NotFound)?;
     // TODO: should pass this in as param
     let hide_modlog_names = true;
-    map_to_enum(res, hide_modlog_names).ok_or(LemmyErrorType::NotFound.into())
+    let mut res = map_to_enum(res, hide_modlog_names).ok_or(LemmyErrorType::NotFound)?;
+    CommentView::fill_creator_instance_blocked(res.to_comment_view_mut(), Some(my_person.id), conn)
+      .await?;
+    Ok(res)
+  }
+
+  /// Useful in combination with filter_map
+  fn to_comment_view_mut(&mut self) -> Option<&mut CommentView> {
+    if let NotificationData::Comment(v) = &mut self.data {
+      Some(v)
+    } else {
+      None
+    }
   }
 }
 
@@ -193,10 +205,18 @@ impl NotificationQuery {
       .await?;
 
     let hide_modlog_names = self.hide_modlog_names.unwrap_or_default();
-    let res = res
+    let mut res = res
       .into_iter()
       .filter_map(|r| map_to_enum(r, hide_modlog_names))
-      .collect();
+      .collect::<Vec<_>>();
+    CommentView::fill_creator_instance_blocked(
+      res
+        .iter_mut()
+        .filter_map(NotificationView::to_comment_view_mut),
+      Some(my_person.id),
+      conn,
+    )
+    .await?;
     paginate_response(res, limit, self.page_cursor)
   }
 }
@@ -220,23 +240,37 @@ fn map_to_enum(v: NotificationViewInternal, hide_modlog_name: bool) -> Option<No
     v.community.clone(),
     v.creator.clone(),
   ) {
-    NotificationData::Comment(CommentView {
-      comment,
-      post,
-      community,
-      creator,
-      community_actions: v.community_actions,
-      person_actions: v.person_actions,
-      comment_actions: v.comment_actions,
-      post_tags: v.post_tags,
-      creator_banned_from_community: v.creator_banned_from_community,
-      creator_community_ban_expires_at: v.creator_community_ban_expires_at,
-      creator_is_admin: v.creator_is_admin,
-      can_mod: v.can_mod,
-      creator_banned: v.creator_banned,
-      creator_ban_expires_at: v.creator_ban_expires_at,
-      creator_is_moderator: v.creator_is_moderator,
-    })
+    NotificationData::Comment(
+      CommentView {
+        comment,
+        post,
+        community,
+        creator,
+        community_actions: v.community_actions,
+        person_actions: v.person_actions,
+        comment_actions: v.comment_actions,
+        post_tags: v.post_tags,
+        comment_tags: Default::default(),
+        creator_banned_from_community: v.creator_banned_from_community,
+        creator_community_ban_expires_at: v.creator_community_ban_expires_at,
+        creator_is_admin: v.creator_is_admin,
+        can_mod: v.can_mod,
+        creator_banned: v.creator_banned,
+        creator_ban_expires_at: v.creator_ban_expires_at,
+        creator_is_moderator: v.creator_is_moderator,
+        creator_instance_domain: None,
+        federation_state: None,
+        content_source: None,
+        viewer_has_replied: false,
+        collapsed_suggested: false,
+        pinned_in_community: false,
+        removed_by: None,
+        removed_reason: None,
+        crosspost_of: None,
+        reactions: Default::default(),
+      }
+      .hide_scores_until_voted(),
+    )
   } else if let (Some(post), Some(community), Some(creator)) =
     (v.post.clone(), v.community.clone(), v.creator.clone())
   {
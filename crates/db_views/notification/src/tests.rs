@@ -1,5 +1,6 @@
 use crate::{NotificationData, NotificationView, impls::NotificationQuery};
 use lemmy_db_schema::{
+  NotificationDataType,
   assert_length,
   source::{
     comment::{Comment, CommentInsertForm},
@@ -197,3 +198,125 @@ async fn test_modlog() -> LemmyResult<()> {
 
   cleanup(data, pool).await
 }
+
+#[tokio::test]
+#[serial]
+async fn test_mixed_comment_notifications() -> LemmyResult<()> {
+  let pool = &build_db_pool_for_tests();
+  let pool = &mut pool.into();
+  let data = init_data(pool).await?;
+
+  let form = CommunityInsertForm::new(
+    data.alice.instance_id,
+    "mixed".to_string(),
+    "mixed".to_string(),
+    String::new(),
+  );
+  let community = Community::create(pool, &form).await?;
+  let form = PostInsertForm::new("post".to_string(), data.alice.id, community.id);
+  let post = Post::create(pool, &form).await?;
+
+  // Bob replies to alice, mentions her, and comments in a thread she subscribed to
+  let mut expected = vec![];
+  for kind in [
+    NotificationType::Reply,
+    NotificationType::Mention,
+    NotificationType::Subscribed,
+  ] {
+    let form = CommentInsertForm::new(data.bob.id, post.id, kind.to_string());
+    let comment = Comment::create(pool, &form, None).await?;
+    let form = NotificationInsertForm::new_comment(comment.id, data.alice.id, kind);
+    Notification::create(pool, &[form]).await?;
+    expected.push((kind, comment.id));
+  }
+
+  // All of them are returned in one feed, newest first, each tagged with its kind
+  let notifs = NotificationQuery::default().list(pool, &data.alice).await?;
+  assert_length!(3, notifs);
+  expected.reverse();
+  let kinds = notifs
+    .iter()
+    .map(|n| {
+      let NotificationData::Comment(c) = &n.data else {
+        panic!();
+      };
+      (n.notification.kind, c.comment.id)
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(expected, kinds);
+
+  // Reading one of them only removes it from the unread feed
+  Notification::mark_read_by_id_and_person(pool, notifs[0].notification.id, data.alice.id, true)
+    .await?;
+  let unread = NotificationQuery {
+    unread_only: Some(true),
+    ..Default::default()
+  }
+  .list(pool, &data.alice)
+  .await?;
+  assert_length!(2, unread);
+  assert_eq!(
+    3,
+    NotificationQuery::default()
+      .list(pool, &data.alice)
+      .await?
+      .len()
+  );
+
+  // The feed can be narrowed down to a single kind
+  let mentions = NotificationQuery {
+    type_: Some(NotificationDataType::Mention),
+    ..Default::default()
+  }
+  .list(pool, &data.alice)
+  .await?;
+  assert_length!(1, mentions);
+  assert_eq!(NotificationType::Mention, mentions[0].notification.kind);
+
+  cleanup(data, pool).await
+}
+
+#[tokio::test]
+#[serial]
+async fn test_mark_multiple_read() -> LemmyResult<()> {
+  let pool = &build_db_pool_for_tests();
+  let pool = &mut pool.into();
+  let data = init_data(pool).await?;
+
+  let mut ids = vec![];
+  for i in 0..3 {
+    let form = &PrivateMessageInsertForm::new(data.bob.id, data.alice.id, format!("message {i}"));
+    let pm = PrivateMessage::create(pool, form).await?;
+    let form = NotificationInsertForm::new_private_message(&pm);
+    ids.push(Notification::create(pool, &[form]).await?[0].id);
+  }
+  // A notification for bob which alice must not be able to mark
+  let form = &PrivateMessageInsertForm::new(data.alice.id, data.bob.id, "reply".to_string());
+  let pm = PrivateMessage::create(pool, form).await?;
+  let form = NotificationInsertForm::new_private_message(&pm);
+  let bob_notification = Notification::create(pool, &[form]).await?[0].id;
+
+  let marked = Notification::mark_read_by_ids_and_person(
+    pool,
+    &[ids[0], ids[1], bob_notification],
+    data.alice.id,
+    true,
+  )
+  .await?;
+  assert_eq!(2, marked);
+
+  let unread = NotificationQuery {
+    unread_only: Some(true),
+    ..Default::default()
+  }
+  .list(pool, &data.alice)
+  .await?;
+  assert_length!(1, unread);
+  assert_eq!(ids[2], unread[0].notification.id);
+  assert_eq!(
+    1,
+    NotificationView::get_unread_count(pool, &data.bob, false).await?
+  );
+
+  cleanup(data, pool).await
+}
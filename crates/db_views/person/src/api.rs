@@ -1,5 +1,5 @@
-use crate::PersonView;
-use lemmy_db_schema::source::site::Site;
+use crate::{CommunityActiveUser, PersonCommunityKarma, PersonView};
+use lemmy_db_schema::{newtypes::CommunityId, source::site::Site};
 use lemmy_db_schema_file::PersonId;
 use lemmy_db_views_community::MultiCommunityView;
 use lemmy_db_views_community_moderator::CommunityModeratorView;
@@ -83,6 +83,49 @@ pub struct GetPersonDetailsResponse {
   pub multi_communities_created: Vec<MultiCommunityView>,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Gets a breakdown of where a person's karma comes from.
+pub struct GetPersonKarmaBreakdown {
+  pub person_id: PersonId,
+  /// The number of communities to return, defaults to 20.
+  pub limit: Option<i64>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Gets the people who recently posted or commented in a community.
+pub struct GetCommunityActiveUsers {
+  pub community_id: CommunityId,
+  /// Only count posts and comments from the last this many seconds, at most 90 days.
+  pub window_seconds: i32,
+  /// The number of people to return, defaults to 20.
+  pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetCommunityActiveUsersResponse {
+  /// Most active first.
+  pub active_users: Vec<CommunityActiveUser>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A person's karma breakdown response.
+pub struct GetPersonKarmaBreakdownResponse {
+  pub post_score: i32,
+  pub comment_score: i32,
+  /// The communities where the person has the highest comment score, best first.
+  pub communities: Vec<PersonCommunityKarma>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -93,6 +136,24 @@ pub struct PurgePerson {
   pub reason: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Shows how much content would be removed by purging a person. Admins only.
+pub struct PreviewPurgePerson {
+  pub person_id: PersonId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct PreviewPurgePersonResponse {
+  pub posts: i64,
+  pub comments: i64,
+  /// Reports on the person's posts and comments, which are purged together with them.
+  pub reports: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -1,20 +1,35 @@
-use crate::PersonView;
-use diesel::{ExpressionMethods, QueryDsl, SelectableHelper};
+use crate::{CommunityActiveUser, PersonCommunityKarma, PersonView};
+use chrono::{TimeDelta, Utc};
+use diesel::{
+  BoolExpressionMethods,
+  ExpressionMethods,
+  QueryDsl,
+  SelectableHelper,
+  dsl::{count_star, sum},
+};
 use diesel_async::RunQueryDsl;
 use i_love_jesus::SortDirection;
 use lemmy_db_schema::{
-  source::person::{Person, person_keys as key},
+  impls::local_user::LocalUserOptionHelper,
+  newtypes::CommunityId,
+  source::{
+    community::Community,
+    local_user::LocalUser,
+    person::{Person, person_keys as key},
+  },
   utils::limit_fetch,
 };
 use lemmy_db_schema_file::{
   InstanceId,
   PersonId,
+  enums::{CommunityFollowerState, CommunityVisibility},
   joins::{
     creator_home_instance_actions_join,
     creator_local_instance_actions_join,
+    my_community_actions_join,
     my_person_actions_join,
   },
-  schema::{local_user, person},
+  schema::{comment, community, community_actions, local_user, person, post},
 };
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -26,6 +41,7 @@ use lemmy_diesel_utils::{
     paginate_response,
   },
   traits::Crud,
+  utils::functions::coalesce,
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -125,6 +141,128 @@ impl PersonQuery {
   }
 }
 
+impl PersonCommunityKarma {
+  /// The communities where the person's comments have the highest total score, best first.
+  /// Communities which the viewer isn't allowed to see are left out.
+  pub async fn list_for_person(
+    pool: &mut DbPool<'_>,
+    person_id: PersonId,
+    my_local_user: Option<&LocalUser>,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let limit = limit_fetch(limit, None)?;
+    let comment_score = sum(comment::score);
+
+    let mut query = comment::table
+      .inner_join(post::table)
+      .inner_join(community::table.on(post::community_id.eq(community::id)))
+      .left_join(my_community_actions_join(my_local_user.person_id()))
+      .filter(comment::creator_id.eq(person_id))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false))
+      .filter(community::deleted.eq(false))
+      .filter(community::removed.eq(false))
+      .group_by(community::id)
+      .select((Community::as_select(), comment_score))
+      .order_by(comment_score.desc())
+      .then_order_by(community::id)
+      .limit(limit)
+      .into_boxed();
+
+    query = my_local_user.visible_communities_only(query);
+    if !my_local_user.is_admin() {
+      query = query.filter(
+        community::visibility
+          .ne(CommunityVisibility::Private)
+          .or(community_actions::follow_state.eq(CommunityFollowerState::Accepted)),
+      );
+    }
+
+    let res = query
+      .load::<(Community, Option<i64>)>(conn)
+      .await
+      .with_lemmy_type(LemmyErrorType::NotFound)?;
+    Ok(
+      res
+        .into_iter()
+        .map(|(community, comment_score)| PersonCommunityKarma {
+          community,
+          comment_score: comment_score.unwrap_or_default(),
+        })
+        .collect(),
+    )
+  }
+}
+
+impl CommunityActiveUser {
+  /// People who posted or commented in the community within the given number of seconds, most
+  /// active first. Deleted and removed content isn't counted.
+  pub async fn list_for_community(
+    pool: &mut DbPool<'_>,
+    community_id: CommunityId,
+    window_seconds: i32,
+    my_person_id: Option<PersonId>,
+    local_instance_id: InstanceId,
+    limit: Option<i64>,
+  ) -> LemmyResult<Vec<Self>> {
+    let limit = limit_fetch(limit, None)?;
+    let since = Utc::now() - TimeDelta::seconds(window_seconds.into());
+    let conn = &mut get_conn(pool).await?;
+
+    let recent_posts = post::table
+      .filter(post::community_id.eq(community_id))
+      .filter(post::published_at.ge(since))
+      .filter(post::deleted.eq(false))
+      .filter(post::removed.eq(false));
+    let recent_comments = comment::table
+      .inner_join(post::table)
+      .filter(post::community_id.eq(community_id))
+      .filter(comment::published_at.ge(since))
+      .filter(comment::deleted.eq(false))
+      .filter(comment::removed.eq(false));
+
+    let post_count = coalesce(
+      recent_posts
+        .filter(post::creator_id.eq(person::id))
+        .select(count_star())
+        .single_value(),
+      0,
+    );
+    let comment_count = coalesce(
+      recent_comments
+        .filter(comment::creator_id.eq(person::id))
+        .select(count_star())
+        .single_value(),
+      0,
+    );
+
+    let res = PersonView::joins(my_person_id, local_instance_id)
+      .filter(
+        person::id
+          .eq_any(recent_posts.select(post::creator_id))
+          .or(person::id.eq_any(recent_comments.select(comment::creator_id))),
+      )
+      .filter(person::deleted.eq(false))
+      .select((PersonView::as_select(), post_count, comment_count))
+      .order_by((post_count + comment_count).desc())
+      .then_order_by(person::id)
+      .limit(limit)
+      .load::<(PersonView, i64, i64)>(conn)
+      .await?
+      .into_iter()
+      .map(
+        |(person_view, post_count, comment_count)| CommunityActiveUser {
+          person_view,
+          post_count,
+          comment_count,
+        },
+      )
+      .collect();
+    Ok(res)
+  }
+}
+
 #[cfg(test)]
 #[expect(clippy::indexing_slicing)]
 mod tests {
@@ -133,9 +271,12 @@ mod tests {
   use lemmy_db_schema::{
     assert_length,
     source::{
+      comment::{Comment, CommentInsertForm},
+      community::CommunityInsertForm,
       instance::Instance,
       local_user::{LocalUser, LocalUserInsertForm, LocalUserUpdateForm},
       person::{Person, PersonActions, PersonInsertForm, PersonNoteForm, PersonUpdateForm},
+      post::{Post, PostInsertForm},
     },
   };
   use lemmy_diesel_utils::{
@@ -278,4 +419,149 @@ mod tests {
 
     cleanup(data, pool).await
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn karma_breakdown() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let rust_form = CommunityInsertForm::new(
+      data.alice.instance_id,
+      "karma_rust".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let rust = Community::create(pool, &rust_form).await?;
+    let secret_form = CommunityInsertForm {
+      visibility: Some(CommunityVisibility::Private),
+      ..CommunityInsertForm::new(
+        data.alice.instance_id,
+        "karma_secret".to_string(),
+        "nada".to_owned(),
+        "pubkey".to_string(),
+      )
+    };
+    let secret = Community::create(pool, &secret_form).await?;
+
+    let rust_post =
+      Post::create(pool, &PostInsertForm::new("a".into(), data.bob.id, rust.id)).await?;
+    let secret_post = Post::create(
+      pool,
+      &PostInsertForm::new("b".into(), data.bob.id, secret.id),
+    )
+    .await?;
+
+    let mut rust_score = 0;
+    for _ in 0..2 {
+      let form = CommentInsertForm::new(data.bob.id, rust_post.id, "rust comment".into());
+      rust_score += i64::from(Comment::create(pool, &form, None).await?.score);
+    }
+    let form = CommentInsertForm::new(data.bob.id, secret_post.id, "secret comment".into());
+    let secret_score = i64::from(Comment::create(pool, &form, None).await?.score);
+
+    // An admin sees both communities
+    let admin = LocalUser {
+      admin: true,
+      ..data.alice_local_user.clone()
+    };
+    let karma =
+      PersonCommunityKarma::list_for_person(pool, data.bob.id, Some(&admin), None).await?;
+    assert_length!(2, karma);
+    let scores = karma
+      .iter()
+      .map(|k| (k.community.id, k.comment_score))
+      .collect::<Vec<_>>();
+    assert!(scores.contains(&(rust.id, rust_score)));
+    assert!(scores.contains(&(secret.id, secret_score)));
+
+    // A user who doesn't follow the private community only sees the public one
+    let karma =
+      PersonCommunityKarma::list_for_person(pool, data.bob.id, Some(&data.alice_local_user), None)
+        .await?;
+    assert_length!(1, karma);
+    assert_eq!(rust.id, karma[0].community.id);
+    assert_eq!(rust_score, karma[0].comment_score);
+
+    // The limit applies to the number of communities
+    let karma =
+      PersonCommunityKarma::list_for_person(pool, data.bob.id, Some(&admin), Some(1)).await?;
+    assert_length!(1, karma);
+
+    cleanup(data, pool).await
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn community_active_users() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+    let instance_id = data.alice.instance_id;
+    let week = 7 * 24 * 60 * 60;
+
+    let form = CommunityInsertForm::new(
+      instance_id,
+      "active_users".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &form).await?;
+
+    // Bob is active now, Alice only commented a month ago
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("active".into(), data.bob.id, community.id),
+    )
+    .await?;
+    for _ in 0..2 {
+      let form = CommentInsertForm::new(data.bob.id, post.id, "recent".into());
+      Comment::create(pool, &form, None).await?;
+    }
+    let form = CommentInsertForm {
+      published_at: Some(Utc::now() - TimeDelta::days(30)),
+      ..CommentInsertForm::new(data.alice.id, post.id, "old".into())
+    };
+    Comment::create(pool, &form, None).await?;
+
+    let active =
+      CommunityActiveUser::list_for_community(pool, community.id, week, None, instance_id, None)
+        .await?;
+    assert_length!(1, active);
+    assert_eq!(data.bob.id, active[0].person_view.person.id);
+    assert_eq!(1, active[0].post_count);
+    assert_eq!(2, active[0].comment_count);
+
+    // A longer window includes Alice as well, the most active person comes first
+    let active = CommunityActiveUser::list_for_community(
+      pool,
+      community.id,
+      8 * week,
+      None,
+      instance_id,
+      None,
+    )
+    .await?;
+    assert_length!(2, active);
+    assert_eq!(data.bob.id, active[0].person_view.person.id);
+    assert_eq!(data.alice.id, active[1].person_view.person.id);
+    assert_eq!(0, active[1].post_count);
+    assert_eq!(1, active[1].comment_count);
+
+    // The limit keeps only the most active people
+    let active = CommunityActiveUser::list_for_community(
+      pool,
+      community.id,
+      8 * week,
+      None,
+      instance_id,
+      Some(1),
+    )
+    .await?;
+    assert_length!(1, active);
+    assert_eq!(data.bob.id, active[0].person_view.person.id);
+
+    cleanup(data, pool).await
+  }
 }
@@ -1,5 +1,8 @@
 use chrono::{DateTime, Utc};
-use lemmy_db_schema::source::person::{Person, PersonActions};
+use lemmy_db_schema::source::{
+  community::Community,
+  person::{Person, PersonActions},
+};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "full")]
 use {
@@ -49,3 +52,22 @@ pub struct PersonView {
   )]
   pub ban_expires_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The total score of a person's comments within a single community.
+pub struct PersonCommunityKarma {
+  pub community: Community,
+  pub comment_score: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A person who recently posted or commented in a community.
+pub struct CommunityActiveUser {
+  pub person_view: PersonView,
+  pub post_count: i64,
+  pub comment_count: i64,
+}
@@ -35,6 +35,7 @@ use lemmy_db_schema_file::{
   },
   schema::{comment, person, person_content_combined, post},
 };
+use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_post_comment_combined::{
   PostCommentCombinedView,
   PostCommentCombinedViewInternal,
@@ -205,9 +206,20 @@ impl PersonContentCombinedQuery {
       .await?;
 
     // Map the query results to the enum
-    let out = res
+    let mut out = res
       .into_iter()
       .filter_map(InternalToCombinedView::map_to_enum)
+      .collect::<Vec<_>>();
+    CommentView::fill_creator_instance_blocked(
+      out
+        .iter_mut()
+        .filter_map(PostCommentCombinedView::to_comment_view_mut),
+      my_person_id,
+      conn,
+    )
+    .await?;
+    let out = out
+      .into_iter()
       .map(PostCommentCombinedViewWrapper)
       .collect();
 
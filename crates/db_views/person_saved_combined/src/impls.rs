@@ -34,6 +34,7 @@ use lemmy_db_schema_file::{
   },
   schema::{comment, person, person_saved_combined, post},
 };
+use lemmy_db_views_comment::CommentView;
 use lemmy_db_views_post_comment_combined::{
   PostCommentCombinedView,
   PostCommentCombinedViewInternal,
@@ -195,9 +196,20 @@ impl PersonSavedCombinedQuery {
       .await?;
 
     // Map the query results to the enum
-    let out = res
+    let mut out = res
       .into_iter()
       .filter_map(InternalToCombinedView::map_to_enum)
+      .collect::<Vec<_>>();
+    CommentView::fill_creator_instance_blocked(
+      out
+        .iter_mut()
+        .filter_map(PostCommentCombinedView::to_comment_view_mut),
+      Some(my_person_id),
+      conn,
+    )
+    .await?;
+    let out = out
+      .into_iter()
       .map(PostCommentCombinedViewWrapper)
       .collect();
 
@@ -1,4 +1,4 @@
-use crate::PostView;
+use crate::{CommentActivityBucket, PostCommentActivity, PostView};
 use lemmy_db_schema::{
   PostFeatureType,
   newtypes::{CommunityId, LanguageId, MultiCommunityId, PostId, TagId},
@@ -94,6 +94,26 @@ pub struct FeaturePost {
   pub feature_type: PostFeatureType,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the number of comments on a post over time, grouped by hour or day.
+pub struct GetPostCommentActivity {
+  pub post_id: PostId,
+  /// Defaults to day.
+  pub bucket: Option<CommentActivityBucket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct GetPostCommentActivityResponse {
+  /// Oldest first. Hours or days without comments are left out.
+  pub activity: Vec<PostCommentActivity>,
+}
+
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -101,6 +121,17 @@ pub struct FeaturePost {
 pub struct UpdatePostNotifications {
   pub post_id: PostId,
   pub mode: PostNotificationsMode,
+  /// Get only one notification for multiple comments by the same person in a short time.
+  pub coalesce: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Temporarily silence notifications for a post, without changing the notification settings.
+pub struct MutePostNotifications {
+  pub post_id: PostId,
+  pub muted: bool,
 }
 
 #[skip_serializing_none]
@@ -1,4 +1,4 @@
-use crate::PostView;
+use crate::{CommentActivityBucket, PostCommentActivity, PostView};
 use diesel::{
   self,
   BoolExpressionMethods,
@@ -12,6 +12,8 @@ use diesel::{
   dsl::{exists, not},
   pg::Pg,
   query_builder::AsQuery,
+  sql_query,
+  sql_types::{Bool, Integer, Text},
 };
 use diesel_async::RunQueryDsl;
 use i_love_jesus::{SortDirection, asc_if};
@@ -107,6 +109,43 @@ impl PaginationCursorConversion for PostViewDummy {
   }
 }
 
+impl PostCommentActivity {
+  /// Counts the comments on the post per hour or day. Removed comments and comments waiting for
+  /// approval are only counted if `include_removed` is set. Deleted and scheduled comments are
+  /// never counted, neither are comments with a pending delete.
+  pub async fn list_for_post(
+    pool: &mut DbPool<'_>,
+    post_id: PostId,
+    bucket: CommentActivityBucket,
+    include_removed: bool,
+  ) -> LemmyResult<Vec<Self>> {
+    let conn = &mut get_conn(pool).await?;
+    let bucket = match bucket {
+      CommentActivityBucket::Hour => "hour",
+      CommentActivityBucket::Day => "day",
+    };
+
+    // Diesel can't select an expression which is also used for grouping, so use raw sql here.
+    sql_query(
+      "SELECT date_trunc($2, published_at) AS bucket_start, count(*) AS comments
+      FROM comment
+      WHERE post_id = $1
+        AND ($3 OR NOT (removed OR pending_approval))
+        AND NOT deleted
+        AND pending_delete_until IS NULL
+        AND scheduled_publish_time_at IS NULL
+      GROUP BY bucket_start
+      ORDER BY bucket_start",
+    )
+    .bind::<Integer, _>(post_id.0)
+    .bind::<Text, _>(bucket)
+    .bind::<Bool, _>(include_removed)
+    .load::<Self>(conn)
+    .await
+    .with_lemmy_type(LemmyErrorType::NotFound)
+  }
+}
+
 impl PostView {
   // TODO while we can abstract the joins into a function, the selects are currently impossible to
   // do, because they rely on a few types that aren't yet publicly exported in diesel:
@@ -14,7 +14,7 @@ mod test;
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
 use {
-  diesel::{Queryable, Selectable},
+  diesel::{Queryable, QueryableByName, Selectable},
   lemmy_db_schema::utils::queries::selects::post_select_remove_deletes,
   lemmy_db_schema::utils::queries::selects::{
     CreatorLocalHomeBanExpiresType,
@@ -108,3 +108,28 @@ pub struct PostView {
   )]
   pub creator_community_ban_expires_at: Option<DateTime<Utc>>,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+/// The time span which comment activity is grouped by.
+pub enum CommentActivityBucket {
+  Hour,
+  #[default]
+  Day,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "full", derive(QueryableByName))]
+#[cfg_attr(feature = "full", diesel(check_for_backend(diesel::pg::Pg)))]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The number of comments on a post which were published in a single hour or day.
+pub struct PostCommentActivity {
+  /// Start of the hour or day.
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::Timestamptz))]
+  pub bucket_start: DateTime<Utc>,
+  #[cfg_attr(feature = "full", diesel(sql_type = diesel::sql_types::BigInt))]
+  pub comments: i64,
+}
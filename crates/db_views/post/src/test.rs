@@ -1,6 +1,6 @@
 #![expect(clippy::indexing_slicing, clippy::expect_used)]
 
-use crate::{PostView, impls::PostQuery};
+use crate::{CommentActivityBucket, PostCommentActivity, PostView, impls::PostQuery};
 use chrono::{DateTime, Days, Utc};
 use diesel_async::SimpleAsyncConnection;
 use diesel_uplete::UpleteCount;
@@ -2294,3 +2294,54 @@ async fn post_listing_multi_community(data: &mut Data) -> LemmyResult<()> {
 
   Ok(())
 }
+
+#[test_context(Data)]
+#[tokio::test]
+#[serial]
+async fn post_comment_activity(data: &mut Data) -> LemmyResult<()> {
+  let pool = &data.pool();
+  let pool = &mut pool.into();
+
+  // Three comments on the first day, one of them removed, then one on each of the next days.
+  // On the last day there is also a deleted comment, and one waiting for approval.
+  let fixture = [
+    ("2025-03-01T12:10:00Z", false, false, false),
+    ("2025-03-01T12:40:00Z", true, false, false),
+    ("2025-03-01T15:00:00Z", false, false, false),
+    ("2025-03-02T12:00:00Z", false, false, false),
+    ("2025-03-04T12:30:00Z", false, false, false),
+    ("2025-03-04T13:00:00Z", false, true, false),
+    ("2025-03-04T13:30:00Z", false, false, true),
+  ];
+  for (published_at, removed, deleted, pending_approval) in fixture {
+    let form = CommentInsertForm {
+      published_at: Some(DateTime::parse_from_rfc3339(published_at)?.into()),
+      removed: Some(removed),
+      deleted: Some(deleted),
+      pending_approval: Some(pending_approval),
+      ..CommentInsertForm::new(data.john.person.id, data.post.id, "activity".into())
+    };
+    Comment::create(pool, &form, None).await?;
+  }
+
+  let counts = |activity: Vec<PostCommentActivity>| {
+    activity.into_iter().map(|a| a.comments).collect::<Vec<_>>()
+  };
+
+  let by_day =
+    PostCommentActivity::list_for_post(pool, data.post.id, CommentActivityBucket::Day, false)
+      .await?;
+  assert_eq!(vec![2, 1, 1], counts(by_day));
+
+  let by_day_with_removed =
+    PostCommentActivity::list_for_post(pool, data.post.id, CommentActivityBucket::Day, true)
+      .await?;
+  assert_eq!(vec![3, 1, 2], counts(by_day_with_removed));
+
+  let by_hour =
+    PostCommentActivity::list_for_post(pool, data.post.id, CommentActivityBucket::Hour, true)
+      .await?;
+  assert_eq!(vec![2, 1, 1, 1, 1], counts(by_hour));
+
+  Ok(())
+}
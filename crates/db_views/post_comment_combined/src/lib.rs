@@ -89,23 +89,37 @@ impl InternalToCombinedView for PostCommentCombinedViewInternal {
     let v = self;
 
     if let Some(comment) = v.comment {
-      Some(PostCommentCombinedView::Comment(CommentView {
-        comment,
-        post: v.post,
-        community: v.community,
-        creator: v.item_creator,
-        community_actions: v.community_actions,
-        comment_actions: v.comment_actions,
-        person_actions: v.person_actions,
-        creator_is_admin: v.item_creator_is_admin,
-        post_tags: v.post_tags,
-        can_mod: v.can_mod,
-        creator_banned: v.creator_banned,
-        creator_ban_expires_at: v.creator_ban_expires_at,
-        creator_is_moderator: v.creator_is_moderator,
-        creator_banned_from_community: v.creator_banned_from_community,
-        creator_community_ban_expires_at: v.creator_community_ban_expires_at,
-      }))
+      Some(PostCommentCombinedView::Comment(
+        CommentView {
+          comment,
+          post: v.post,
+          community: v.community,
+          creator: v.item_creator,
+          community_actions: v.community_actions,
+          comment_actions: v.comment_actions,
+          person_actions: v.person_actions,
+          creator_is_admin: v.item_creator_is_admin,
+          post_tags: v.post_tags,
+          comment_tags: Default::default(),
+          can_mod: v.can_mod,
+          creator_banned: v.creator_banned,
+          creator_ban_expires_at: v.creator_ban_expires_at,
+          creator_is_moderator: v.creator_is_moderator,
+          creator_banned_from_community: v.creator_banned_from_community,
+          creator_community_ban_expires_at: v.creator_community_ban_expires_at,
+          creator_instance_domain: None,
+          federation_state: None,
+          content_source: None,
+          viewer_has_replied: false,
+          collapsed_suggested: false,
+          pinned_in_community: false,
+          removed_by: None,
+          removed_reason: None,
+          crosspost_of: None,
+          reactions: Default::default(),
+        }
+        .hide_scores_until_voted(),
+      ))
     } else {
       Some(PostCommentCombinedView::Post(PostView {
         post: v.post,
@@ -137,4 +151,13 @@ impl PostCommentCombinedView {
       None
     }
   }
+
+  /// Useful in combination with filter_map
+  pub fn to_comment_view_mut(&mut self) -> Option<&mut CommentView> {
+    if let Self::Comment(v) = self {
+      Some(v)
+    } else {
+      None
+    }
+  }
 }
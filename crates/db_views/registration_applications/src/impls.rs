@@ -51,7 +51,7 @@ impl PaginationCursorConversion for RegistrationApplicationView {
 
 impl RegistrationApplicationView {
   #[diesel::dsl::auto_type(no_type_alias)]
-  fn joins() -> _ {
+  pub fn joins() -> _ {
     let local_user_join =
       local_user::table.on(registration_application::local_user_id.eq(local_user::id));
 
@@ -246,6 +246,11 @@ mod tests {
         send_notifications_to_email: sara_local_user.send_notifications_to_email,
         show_bot_accounts: sara_local_user.show_bot_accounts,
         show_read_posts: sara_local_user.show_read_posts,
+        show_read_comments: sara_local_user.show_read_comments,
+        email_pending_community_followers: sara_local_user.email_pending_community_followers,
+        receive_community_welcome_messages: sara_local_user.receive_community_welcome_messages,
+        collapse_comments_account_age_days: sara_local_user.collapse_comments_account_age_days,
+        collapse_comments_below_score: sara_local_user.collapse_comments_below_score,
         email_verified: sara_local_user.email_verified,
         accepted_application: sara_local_user.accepted_application,
         totp_2fa_secret: sara_local_user.totp_2fa_secret,
@@ -291,6 +296,7 @@ mod tests {
         post_score: 0,
         comment_count: 0,
         comment_score: 0,
+        verified_links: vec![],
       },
       admin: None,
     };
@@ -365,6 +371,7 @@ mod tests {
       post_score: 0,
       comment_count: 0,
       comment_score: 0,
+      verified_links: vec![],
     });
     assert_eq!(read_sara_app_view_after_approve, expected_sara_app_view);
 
@@ -1,5 +1,7 @@
 use crate::{CommentReportView, CommunityReportView, PostReportView, PrivateMessageReportView};
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
+  ModQueueType,
   ReportType,
   newtypes::{
     CommentId,
@@ -36,6 +38,24 @@ pub struct ListReports {
   pub show_community_rule_violations: Option<bool>,
   /// If true, view all your created reports. Works for non-admins/mods also.
   pub my_reports_only: Option<bool>,
+  /// Sort by how many of the reporters' earlier reports were valid, highest first, instead of by
+  /// time.
+  pub sort_by_reporter_trust: Option<bool>,
+  /// Also show comment reports which a mod snoozed.
+  pub show_snoozed: Option<bool>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Get the unresolved post and comment reports of all communities you moderate, oldest first.
+/// Admins also get the unread registration applications.
+pub struct GetModQueue {
+  /// Filter the type of items.
+  pub type_: Option<ModQueueType>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -64,6 +84,26 @@ pub struct CreateCommentReport {
   pub violates_instance_rules: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Report a comment together with all its replies, eg when a thread is brigaded. The number of
+/// reported comments is limited by the server, and comments which you already reported are
+/// skipped.
+pub struct ReportCommentSubtree {
+  pub comment_id: CommentId,
+  pub reason: String,
+  pub violates_instance_rules: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+pub struct ReportCommentSubtreeResponse {
+  /// The number of created reports.
+  pub reports: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -110,6 +150,17 @@ pub struct ResolveCommentReport {
   pub resolved: bool,
 }
 
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Hide a comment report from the report queue until the given time, without resolving it (only
+/// doable by mods). Leave out `until` to show it again right away.
+pub struct SnoozeCommentReport {
+  pub report_id: CommentReportId,
+  pub until: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
@@ -128,6 +179,23 @@ pub struct ResolvePostReport {
   pub resolved: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// Resolve all open comment and post reports in a community (mods only).
+pub struct ResolveAllCommunityReports {
+  pub community_id: CommunityId,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The number of reports which were resolved.
+pub struct ResolveAllCommunityReportsResponse {
+  pub comment_reports: i64,
+  pub post_reports: i64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
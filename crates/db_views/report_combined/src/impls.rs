@@ -2,6 +2,7 @@ use crate::{
   CommentReportView,
   CommunityReportView,
   LocalUserView,
+  ModQueueView,
   PostReportView,
   PrivateMessageReportView,
   ReportCombinedView,
@@ -18,6 +19,7 @@ use diesel::{
 use diesel_async::RunQueryDsl;
 use i_love_jesus::asc_if;
 use lemmy_db_schema::{
+  ModQueueType,
   ReportType,
   newtypes::{
     CommentReportId,
@@ -26,10 +28,13 @@ use lemmy_db_schema::{
     PostId,
     PostReportId,
     PrivateMessageReportId,
+    RegistrationApplicationId,
+    ReportCombinedId,
   },
   source::{
     combined::report::{ReportCombined, report_combined_keys as key},
     person::Person,
+    registration_application::RegistrationApplication,
   },
   traits::InternalToCombinedView,
   utils::limit_fetch,
@@ -41,13 +46,17 @@ use lemmy_db_schema_file::{
     community,
     community_actions,
     community_report,
+    local_user,
     person,
+    person_report_stats,
     post,
     post_report,
     private_message_report,
+    registration_application,
     report_combined,
   },
 };
+use lemmy_db_views_registration_applications::RegistrationApplicationView;
 use lemmy_db_views_report_combined_sql::report_combined_joins;
 use lemmy_diesel_utils::{
   connection::{DbPool, get_conn},
@@ -56,8 +65,11 @@ use lemmy_diesel_utils::{
     PagedResponse,
     PaginationCursor,
     PaginationCursorConversion,
+    ReverseCursor,
     paginate_response,
   },
+  traits::Crud,
+  utils::functions::coalesce,
 };
 use lemmy_utils::error::{LemmyErrorExt, LemmyErrorType, LemmyResult};
 
@@ -150,6 +162,7 @@ impl ReportCombinedViewInternal {
 
     let mut query = report_combined_joins(user.person.id, user.person.instance_id)
       .filter(report_is_not_resolved())
+      .filter(report_is_not_snoozed(Utc::now()))
       .select(count(report_combined::id))
       .into_boxed();
 
@@ -221,6 +234,10 @@ pub struct ReportCombinedQuery {
   pub show_community_rule_violations: Option<bool>,
   pub page_cursor: Option<PaginationCursor>,
   pub my_reports_only: Option<bool>,
+  /// Sort by the trust score of the reporters, highest first, instead of by time.
+  pub sort_by_reporter_trust: Option<bool>,
+  /// Also show comment reports which are snoozed
+  pub show_snoozed: Option<bool>,
   pub limit: Option<i64>,
 }
 
@@ -285,18 +302,66 @@ impl ReportCombinedQuery {
       query = query.filter(report_is_not_resolved())
     };
 
-    // Sorting by published
-    let paginated_query =
-      ReportCombinedView::paginate(query, &self.page_cursor, sort_direction, pool, None)
-        .await?
-        .then_order_by(key::published_at)
-        // Tie breaker
-        .then_order_by(key::id);
+    if !self.show_snoozed.unwrap_or_default() {
+      query = query.filter(report_is_not_snoozed(Utc::now()));
+    }
 
-    let conn = &mut get_conn(pool).await?;
-    let res = paginated_query
-      .load::<ReportCombinedViewInternal>(conn)
-      .await?;
+    let res = if self.sort_by_reporter_trust.unwrap_or_default() {
+      // The trust score isn't part of the combined report, so it can't be used as a cursor key.
+      // Instead the cursor only points to the report, and its score is read here.
+      let (cursor_data, reverse_cursor) = ReverseCursor::new(self.page_cursor.clone())?;
+      let cursor = match cursor_data {
+        Some(data) => Some(ReportCombinedView::from_cursor(data, pool).await?.id),
+        None => None,
+      };
+
+      let conn = &mut get_conn(pool).await?;
+      // Reporters without any resolved reports rank in the middle
+      let trust_score = || coalesce(person_report_stats::trust_score, 0.5_f32);
+      let descending = reverse_cursor.load_descending(true);
+      if let Some(cursor) = cursor {
+        let cursor_score = report_combined_joins(user.person.id, user.person.instance_id)
+          .filter(report_combined::id.eq(cursor))
+          .select(trust_score())
+          .first::<f32>(conn)
+          .await?;
+        let same_score = trust_score().eq(cursor_score);
+        query = if descending {
+          query.filter(
+            trust_score()
+              .lt(cursor_score)
+              .or(same_score.and(report_combined::id.lt(cursor))),
+          )
+        } else {
+          query.filter(
+            trust_score()
+              .gt(cursor_score)
+              .or(same_score.and(report_combined::id.gt(cursor))),
+          )
+        };
+      }
+      query = if descending {
+        query.order_by((trust_score().desc(), report_combined::id.desc()))
+      } else {
+        query.order_by((trust_score().asc(), report_combined::id.asc()))
+      };
+
+      let res = query.load::<ReportCombinedViewInternal>(conn).await?;
+      reverse_cursor.restore_order(res)
+    } else {
+      // Sorting by published
+      let paginated_query =
+        ReportCombinedView::paginate(query, &self.page_cursor, sort_direction, pool, None)
+          .await?
+          .then_order_by(key::published_at)
+          // Tie breaker
+          .then_order_by(key::id);
+
+      let conn = &mut get_conn(pool).await?;
+      paginated_query
+        .load::<ReportCombinedViewInternal>(conn)
+        .await?
+    };
 
     // Map the query results to the enum
     let out = res
@@ -308,6 +373,216 @@ impl ReportCombinedQuery {
   }
 }
 
+/// Position of an item in the mod queue: its publish time, whether it is a registration
+/// application, and its id. Reports come before applications published at the same time.
+type ModQueueKey = (DateTime<Utc>, bool, i32);
+
+impl PaginationCursorConversion for ModQueueView {
+  type PaginatedType = ModQueueKey;
+
+  fn to_cursor(&self) -> CursorData {
+    let (prefix, id) = match &self {
+      ModQueueView::Comment(v) => ('C', v.comment_report.id.0),
+      ModQueueView::Post(v) => ('P', v.post_report.id.0),
+      ModQueueView::RegistrationApplication(v) => ('R', v.registration_application.id.0),
+    };
+    CursorData::new_with_prefix(prefix, id)
+  }
+
+  async fn from_cursor(
+    cursor: CursorData,
+    pool: &mut DbPool<'_>,
+  ) -> LemmyResult<Self::PaginatedType> {
+    let (prefix, id) = cursor.clone().id_and_prefix()?;
+    if prefix == 'R' {
+      let application = RegistrationApplication::read(pool, RegistrationApplicationId(id)).await?;
+      Ok((application.published_at, true, application.id.0))
+    } else {
+      let report = ReportCombinedView::from_cursor(cursor, pool).await?;
+      Ok((report.published_at, false, report.id.0))
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct ModQueueQuery {
+  pub type_: Option<ModQueueType>,
+  /// Only include registration applications of users with a verified email
+  pub verified_email_only: Option<bool>,
+  pub page_cursor: Option<PaginationCursor>,
+  pub limit: Option<i64>,
+}
+
+impl ModQueueQuery {
+  /// Lists unresolved post and comment reports from the communities the user moderates, and for
+  /// admins also the reports meant for them and unread registration applications. Oldest first.
+  ///
+  /// Reports and applications are in different tables, so each is loaded starting from the
+  /// cursor, and the results are merged afterwards.
+  pub async fn list(
+    self,
+    pool: &mut DbPool<'_>,
+    user: &LocalUserView,
+  ) -> LemmyResult<PagedResponse<ModQueueView>> {
+    let limit = limit_fetch(self.limit, None)?;
+    let type_ = self.type_.unwrap_or(ModQueueType::All);
+
+    let (cursor, back) = match self.page_cursor.clone() {
+      Some(page_cursor) => {
+        let (data, back) = page_cursor.into_data()?;
+        (Some(ModQueueView::from_cursor(data, pool).await?), back)
+      }
+      None => (None, false),
+    };
+
+    let mut items: Vec<(ModQueueKey, ModQueueView)> = vec![];
+
+    if type_ != ModQueueType::RegistrationApplications {
+      let mut query = report_combined_joins(user.person.id, user.person.instance_id)
+        .filter(report_is_not_resolved())
+        .filter(report_is_not_snoozed(Utc::now()))
+        .select(ReportCombinedViewInternal::as_select())
+        .limit(limit)
+        .into_boxed();
+
+      query = match type_ {
+        ModQueueType::Posts => query.filter(report_combined::post_report_id.is_not_null()),
+        ModQueueType::Comments => query.filter(report_combined::comment_report_id.is_not_null()),
+        _ => query.filter(
+          report_combined::post_report_id
+            .is_not_null()
+            .or(report_combined::comment_report_id.is_not_null()),
+        ),
+      };
+
+      if user.local_user.admin {
+        query = query.filter(filter_admin_reports(Utc::now() - Days::new(3)));
+      } else {
+        query = query.filter(filter_mod_reports());
+      }
+
+      if let Some((published_at, is_application, id)) = cursor {
+        let published = report_combined::published_at;
+        let same_time = published.eq(published_at);
+        let id = ReportCombinedId(id);
+        query = match (back, is_application) {
+          (false, false) => query.filter(
+            published
+              .gt(published_at)
+              .or(same_time.and(report_combined::id.gt(id))),
+          ),
+          (false, true) => query.filter(published.gt(published_at)),
+          (true, false) => query.filter(
+            published
+              .lt(published_at)
+              .or(same_time.and(report_combined::id.lt(id))),
+          ),
+          (true, true) => query.filter(published.le(published_at)),
+        };
+      }
+
+      query = if back {
+        query.order_by((
+          report_combined::published_at.desc(),
+          report_combined::id.desc(),
+        ))
+      } else {
+        query.order_by((
+          report_combined::published_at.asc(),
+          report_combined::id.asc(),
+        ))
+      };
+
+      let conn = &mut get_conn(pool).await?;
+      let res = query.load::<ReportCombinedViewInternal>(conn).await?;
+      items.extend(res.into_iter().filter_map(|r| {
+        let key = (
+          r.report_combined.published_at,
+          false,
+          r.report_combined.id.0,
+        );
+        match r.map_to_enum()? {
+          ReportCombinedView::Post(v) => Some((key, ModQueueView::Post(v))),
+          ReportCombinedView::Comment(v) => Some((key, ModQueueView::Comment(v))),
+          _ => None,
+        }
+      }));
+    }
+
+    let show_applications = matches!(
+      type_,
+      ModQueueType::All | ModQueueType::RegistrationApplications
+    );
+    if user.local_user.admin && show_applications {
+      let mut query = RegistrationApplicationView::joins()
+        .filter(RegistrationApplication::is_unread())
+        .select(RegistrationApplicationView::as_select())
+        .limit(limit)
+        .into_boxed();
+
+      if self.verified_email_only.unwrap_or_default() {
+        query = query.filter(local_user::email_verified.eq(true))
+      }
+
+      if let Some((published_at, is_application, id)) = cursor {
+        let published = registration_application::published_at;
+        let same_time = published.eq(published_at);
+        let id = RegistrationApplicationId(id);
+        query = match (back, is_application) {
+          (false, false) => query.filter(published.ge(published_at)),
+          (false, true) => query.filter(
+            published
+              .gt(published_at)
+              .or(same_time.and(registration_application::id.gt(id))),
+          ),
+          (true, false) => query.filter(published.lt(published_at)),
+          (true, true) => query.filter(
+            published
+              .lt(published_at)
+              .or(same_time.and(registration_application::id.lt(id))),
+          ),
+        };
+      }
+
+      query = if back {
+        query.order_by((
+          registration_application::published_at.desc(),
+          registration_application::id.desc(),
+        ))
+      } else {
+        query.order_by((
+          registration_application::published_at.asc(),
+          registration_application::id.asc(),
+        ))
+      };
+
+      let conn = &mut get_conn(pool).await?;
+      let res = query.load::<RegistrationApplicationView>(conn).await?;
+      items.extend(res.into_iter().map(|a| {
+        let key = (
+          a.registration_application.published_at,
+          true,
+          a.registration_application.id.0,
+        );
+        (key, ModQueueView::RegistrationApplication(a))
+      }));
+    }
+
+    // Merge both lists, and only keep the items closest to the cursor
+    items.sort_by_key(|(key, _)| *key);
+    let limit_usize: usize = limit.try_into().unwrap_or_default();
+    if back {
+      let skip = items.len().saturating_sub(limit_usize);
+      items.drain(..skip);
+    } else {
+      items.truncate(limit_usize);
+    }
+    let out = items.into_iter().map(|(_, item)| item).collect();
+
+    paginate_response(out, limit, self.page_cursor)
+  }
+}
+
 /// Mods can only see reports for posts/comments inside of communities where they are moderator,
 /// and which have `violates_instance_rules == false`.
 #[diesel::dsl::auto_type]
@@ -349,6 +624,14 @@ fn report_is_not_resolved() -> _ {
     .is_distinct_from(true)
 }
 
+/// Comment reports which a mod snoozed are hidden until the time has passed.
+#[diesel::dsl::auto_type]
+fn report_is_not_snoozed(now: DateTime<Utc>) -> _ {
+  comment_report::snoozed_until
+    .is_null()
+    .or(comment_report::snoozed_until.lt(now))
+}
+
 impl InternalToCombinedView for ReportCombinedViewInternal {
   type CombinedView = ReportCombinedView;
 
@@ -368,6 +651,7 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
         community,
         post_creator,
         creator: v.report_creator,
+        reporter_trust_score: v.reporter_trust_score,
         resolver: v.resolver,
         community_actions: v.community_actions,
         post_actions: v.post_actions,
@@ -399,6 +683,7 @@ impl InternalToCombinedView for ReportCombinedViewInternal {
         community,
         creator: v.report_creator,
         comment_creator,
+        reporter_trust_score: v.reporter_trust_score,
         resolver: v.resolver,
         community_actions: v.community_actions,
         comment_actions: v.comment_actions,
@@ -453,14 +738,16 @@ mod tests {
 
   use crate::{
     LocalUserView,
+    ModQueueView,
     ReportCombinedView,
     ReportCombinedViewInternal,
-    impls::ReportCombinedQuery,
+    impls::{ModQueueQuery, ReportCombinedQuery},
   };
   use chrono::{Days, Utc};
   use diesel::{ExpressionMethods, QueryDsl, update};
   use diesel_async::RunQueryDsl;
   use lemmy_db_schema::{
+    ModQueueType,
     ReportType,
     assert_length,
     source::{
@@ -471,16 +758,18 @@ mod tests {
       instance::{Instance, InstanceActions, InstanceBanForm},
       local_user::{LocalUser, LocalUserInsertForm},
       person::{Person, PersonInsertForm},
-      post::{Post, PostInsertForm},
+      post::{Post, PostInsertForm, PostUpdateForm},
       post_report::{PostReport, PostReportForm},
       private_message::{PrivateMessage, PrivateMessageInsertForm},
       private_message_report::{PrivateMessageReport, PrivateMessageReportForm},
+      registration_application::{RegistrationApplication, RegistrationApplicationInsertForm},
     },
     traits::{Bannable, Reportable},
   };
-  use lemmy_db_schema_file::schema::report_combined;
+  use lemmy_db_schema_file::schema::{person_report_stats, report_combined};
   use lemmy_diesel_utils::{
     connection::{DbPool, build_db_pool_for_tests, get_conn},
+    pagination::PagedResponse,
     traits::Crud,
   };
   use lemmy_utils::error::LemmyResult;
@@ -1294,6 +1583,111 @@ mod tests {
     Ok(())
   }
 
+  #[tokio::test]
+  #[serial]
+  async fn sort_by_reporter_trust() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Sara's report is valid, as the post gets removed
+    let sara_post_report_form = PostReportForm {
+      creator_id: data.sara.id,
+      post_id: data.post.id,
+      original_post_name: "Orig post".into(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "from sara".into(),
+      violates_instance_rules: false,
+    };
+    PostReport::report(pool, &sara_post_report_form).await?;
+    let form = PostUpdateForm {
+      removed: Some(true),
+      ..Default::default()
+    };
+    Post::update(pool, data.post.id, &form).await?;
+    PostReport::resolve_all_for_object(pool, data.post.id, data.timmy.id).await?;
+
+    // Jessica's report is dismissed
+    let jessica_post_report_form = PostReportForm {
+      creator_id: data.jessica.id,
+      post_id: data.post_2.id,
+      reason: "from jessica".into(),
+      ..sara_post_report_form
+    };
+    let jessica_post_report = PostReport::report(pool, &jessica_post_report_form).await?;
+    PostReport::update_resolved(pool, jessica_post_report.id, data.timmy.id, true).await?;
+
+    // Resolving the same report again doesn't count it twice
+    PostReport::update_resolved(pool, jessica_post_report.id, data.timmy.id, false).await?;
+    PostReport::update_resolved(pool, jessica_post_report.id, data.timmy.id, true).await?;
+    let jessica_stats = person_report_stats::table
+      .find(data.jessica.id)
+      .select((
+        person_report_stats::resolved_count,
+        person_report_stats::valid_count,
+      ))
+      .first::<(i32, i32)>(&mut get_conn(pool).await?)
+      .await?;
+    assert_eq!((1, 0), jessica_stats);
+
+    // Both report the comment, sara first
+    for (creator_id, reason) in [
+      (data.sara.id, "from sara"),
+      (data.jessica.id, "from jessica"),
+    ] {
+      let form = CommentReportForm {
+        creator_id,
+        comment_id: data.comment.id,
+        original_comment_text: "A test comment rv".into(),
+        reason: reason.into(),
+        violates_instance_rules: false,
+      };
+      CommentReport::report(pool, &form).await?;
+    }
+
+    let reporters = |reports: &PagedResponse<ReportCombinedView>| {
+      reports
+        .iter()
+        .map(|r| match r {
+          ReportCombinedView::Comment(v) => (v.creator.id, v.reporter_trust_score),
+          _ => panic!("wrong type"),
+        })
+        .collect::<Vec<_>>()
+    };
+
+    // By default the newest report is first
+    let reports = ReportCombinedQuery {
+      type_: Some(ReportType::Comments),
+      ..Default::default()
+    }
+    .list(pool, &data.timmy_view)
+    .await?;
+    assert_eq!(
+      vec![(data.jessica.id, Some(0.0)), (data.sara.id, Some(1.0))],
+      reporters(&reports)
+    );
+
+    // Sorting by trust puts sara's report first, also when paging
+    let list = |page_cursor| ReportCombinedQuery {
+      type_: Some(ReportType::Comments),
+      sort_by_reporter_trust: Some(true),
+      page_cursor,
+      limit: Some(1),
+      ..Default::default()
+    };
+    let page_1 = list(None).list(pool, &data.timmy_view).await?;
+    assert_eq!(vec![(data.sara.id, Some(1.0))], reporters(&page_1));
+    let page_2 = list(page_1.next_page.clone())
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_eq!(vec![(data.jessica.id, Some(0.0))], reporters(&page_2));
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
   #[tokio::test]
   #[serial]
   async fn ensure_creator_data_is_correct() -> LemmyResult<()> {
@@ -1331,4 +1725,193 @@ mod tests {
 
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn mod_queue() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    // Sara reports the post, and jessica the comment
+    let sara_post_report_form = PostReportForm {
+      creator_id: data.sara.id,
+      post_id: data.post.id,
+      original_post_name: "Orig post".into(),
+      original_post_url: None,
+      original_post_body: None,
+      reason: "from sara".into(),
+      violates_instance_rules: false,
+    };
+    let sara_post_report = PostReport::report(pool, &sara_post_report_form).await?;
+    let jessica_comment_report_form = CommentReportForm {
+      creator_id: data.jessica.id,
+      comment_id: data.comment.id,
+      original_comment_text: "A test comment rv".into(),
+      reason: "from jessica".into(),
+      violates_instance_rules: false,
+    };
+    let jessica_comment_report = CommentReport::report(pool, &jessica_comment_report_form).await?;
+
+    // A resolved report doesn't show up
+    let resolved_report_form = PostReportForm {
+      creator_id: data.jessica.id,
+      post_id: data.post_2.id,
+      ..sara_post_report_form.clone()
+    };
+    let resolved_report = PostReport::report(pool, &resolved_report_form).await?;
+    PostReport::update_resolved(pool, resolved_report.id, data.timmy.id, true).await?;
+
+    // A report which is only for admins
+    let instance_report_form = PostReportForm {
+      post_id: data.post_2.id,
+      violates_instance_rules: true,
+      ..sara_post_report_form
+    };
+    let instance_report = PostReport::report(pool, &instance_report_form).await?;
+
+    // A new user applies
+    let applicant_form = PersonInsertForm::test_form(data.instance.id, "applicant_mq");
+    let applicant = Person::create(pool, &applicant_form).await?;
+    let applicant_local_user_form = LocalUserInsertForm::test_form(applicant.id);
+    let applicant_local_user = LocalUser::create(pool, &applicant_local_user_form, vec![]).await?;
+    let application_form = RegistrationApplicationInsertForm {
+      local_user_id: applicant_local_user.id,
+      answer: "let me in".into(),
+    };
+    let application = RegistrationApplication::create(pool, &application_form).await?;
+
+    let items = |queue: &PagedResponse<ModQueueView>| {
+      queue
+        .iter()
+        .map(|i| match i {
+          ModQueueView::Post(v) => ('P', v.post_report.id.0),
+          ModQueueView::Comment(v) => ('C', v.comment_report.id.0),
+          ModQueueView::RegistrationApplication(v) => ('R', v.registration_application.id.0),
+        })
+        .collect::<Vec<_>>()
+    };
+
+    // The mod sees the reports of their community, oldest first
+    let mod_queue = ModQueueQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_eq!(
+      vec![
+        ('P', sara_post_report.id.0),
+        ('C', jessica_comment_report.id.0)
+      ],
+      items(&mod_queue)
+    );
+
+    // The admin isn't a mod, so only sees the report for admins and the application
+    let admin_queue = ModQueueQuery::default()
+      .list(pool, &data.admin_view)
+      .await?;
+    assert_eq!(
+      vec![('P', instance_report.id.0), ('R', application.id.0)],
+      items(&admin_queue)
+    );
+
+    // Paging goes through the merged list, in both directions
+    let list = |page_cursor| ModQueueQuery {
+      page_cursor,
+      limit: Some(1),
+      ..Default::default()
+    };
+    let page_1 = list(None).list(pool, &data.admin_view).await?;
+    assert_eq!(vec![('P', instance_report.id.0)], items(&page_1));
+    let page_2 = list(page_1.next_page.clone())
+      .list(pool, &data.admin_view)
+      .await?;
+    assert_eq!(vec![('R', application.id.0)], items(&page_2));
+    let page_3 = list(page_2.next_page.clone())
+      .list(pool, &data.admin_view)
+      .await?;
+    assert_length!(0, page_3);
+    let page_1_back = list(page_2.prev_page.clone())
+      .list(pool, &data.admin_view)
+      .await?;
+    assert_eq!(items(&page_1), items(&page_1_back));
+
+    // Filter by type
+    let applications = ModQueueQuery {
+      type_: Some(ModQueueType::RegistrationApplications),
+      ..Default::default()
+    };
+    let admin_applications = applications.list(pool, &data.admin_view).await?;
+    assert_eq!(vec![('R', application.id.0)], items(&admin_applications));
+    let comments = ModQueueQuery {
+      type_: Some(ModQueueType::Comments),
+      ..Default::default()
+    }
+    .list(pool, &data.timmy_view)
+    .await?;
+    assert_eq!(vec![('C', jessica_comment_report.id.0)], items(&comments));
+
+    // Only admins see registration applications
+    let mod_applications = ModQueueQuery {
+      type_: Some(ModQueueType::RegistrationApplications),
+      ..Default::default()
+    }
+    .list(pool, &data.timmy_view)
+    .await?;
+    assert_length!(0, mod_applications);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn snoozed_comment_reports() -> LemmyResult<()> {
+    let pool = &build_db_pool_for_tests();
+    let pool = &mut pool.into();
+    let data = init_data(pool).await?;
+
+    let sara_report_form = CommentReportForm {
+      creator_id: data.sara.id,
+      comment_id: data.comment.id,
+      original_comment_text: "A test comment rv".into(),
+      reason: "from sara".into(),
+      violates_instance_rules: false,
+    };
+    let report = CommentReport::report(pool, &sara_report_form).await?;
+
+    // The snoozed report is hidden from the queue and the report count
+    CommentReport::update_snoozed(pool, report.id, Some(Utc::now() + Days::new(1))).await?;
+    let reports = ReportCombinedQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_length!(0, reports);
+    let mod_queue = ModQueueQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_length!(0, mod_queue);
+    let count = ReportCombinedViewInternal::get_report_count(pool, &data.timmy_view, None).await?;
+    assert_eq!(0, count);
+
+    // Unless explicitly requested
+    let reports = ReportCombinedQuery {
+      show_snoozed: Some(true),
+      ..Default::default()
+    }
+    .list(pool, &data.timmy_view)
+    .await?;
+    assert_length!(1, reports);
+
+    // Once the snooze expires, it shows up again
+    CommentReport::update_snoozed(pool, report.id, Some(Utc::now() - Days::new(1))).await?;
+    let reports = ReportCombinedQuery::default()
+      .list(pool, &data.timmy_view)
+      .await?;
+    assert_length!(1, reports);
+    let count = ReportCombinedViewInternal::get_report_count(pool, &data.timmy_view, None).await?;
+    assert_eq!(1, count);
+
+    cleanup(data, pool).await?;
+
+    Ok(())
+  }
 }
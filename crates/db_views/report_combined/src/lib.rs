@@ -11,6 +11,7 @@ use lemmy_db_schema::source::{
   private_message::PrivateMessage,
   private_message_report::PrivateMessageReport,
 };
+use lemmy_db_views_registration_applications::RegistrationApplicationView;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 #[cfg(feature = "full")]
@@ -28,6 +29,7 @@ use {
     person2_select,
   },
   lemmy_db_schema::{Person1AliasAllColumnsTuple, Person2AliasAllColumnsTuple},
+  lemmy_db_schema_file::schema::person_report_stats,
   lemmy_db_views_local_user::LocalUserView,
 };
 
@@ -55,6 +57,8 @@ pub struct ReportCombinedViewInternal {
     select_expression = person1_select()
   )]
   pub report_creator: Person,
+  #[diesel(select_expression = person_report_stats::trust_score.nullable())]
+  pub reporter_trust_score: Option<f32>,
   #[diesel(embed)]
   pub comment: Option<Comment>,
   #[diesel(embed)]
@@ -106,6 +110,17 @@ pub enum ReportCombinedView {
   Community(CommunityReportView),
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(export))]
+#[serde(tag = "type_", rename_all = "snake_case")]
+/// An item waiting in the mod queue.
+pub enum ModQueueView {
+  Post(PostReportView),
+  Comment(CommentReportView),
+  RegistrationApplication(RegistrationApplicationView),
+}
+
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -134,6 +149,9 @@ pub struct CommentReportView {
   pub community: Community,
   pub creator: Person,
   pub comment_creator: Person,
+  /// The share of the reporter's resolved reports which were valid, between 0 and 1. None if none
+  /// of their reports were resolved yet.
+  pub reporter_trust_score: Option<f32>,
   pub comment_actions: Option<CommentActions>,
   pub resolver: Option<Person>,
   pub person_actions: Option<PersonActions>,
@@ -175,6 +193,9 @@ pub struct PostReportView {
   pub community: Community,
   pub creator: Person,
   pub post_creator: Person,
+  /// The share of the reporter's resolved reports which were valid, between 0 and 1. None if none
+  /// of their reports were resolved yet.
+  pub reporter_trust_score: Option<f32>,
   pub community_actions: Option<CommunityActions>,
   pub post_actions: Option<PostActions>,
   pub person_actions: Option<PersonActions>,
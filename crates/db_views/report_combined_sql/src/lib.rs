@@ -25,6 +25,7 @@ use lemmy_db_schema_file::{
     local_user,
     person,
     person_actions,
+    person_report_stats,
     post,
     post_actions,
     post_report,
@@ -74,6 +75,9 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
       .or(private_message::creator_id.eq(item_creator)),
   );
 
+  let report_creator_stats_join =
+    person_report_stats::table.on(person_report_stats::person_id.eq(report_creator));
+
   let resolver_join = aliases::person2.on(
     private_message_report::resolver_id
       .eq(resolver)
@@ -131,6 +135,7 @@ pub fn report_combined_joins(my_person_id: PersonId, local_instance_id: Instance
     .left_join(private_message_report::table)
     .left_join(community_report::table)
     .inner_join(report_creator_join)
+    .left_join(report_creator_stats_join)
     .left_join(comment_join)
     .left_join(private_message_join)
     .left_join(post_join)
@@ -174,6 +174,15 @@ impl SearchCombinedView {
       None
     }
   }
+
+  /// Useful in combination with filter_map
+  pub fn to_comment_view_mut(&mut self) -> Option<&mut CommentView> {
+    if let Self::Comment(v) = self {
+      Some(v)
+    } else {
+      None
+    }
+  }
 }
 
 impl PaginationCursorConversion for SearchCombinedView {
@@ -403,10 +412,18 @@ impl SearchCombinedQuery {
       .await?;
 
     // Map the query results to the enum
-    let out = res
+    let mut out = res
       .into_iter()
       .filter_map(InternalToCombinedView::map_to_enum)
-      .collect();
+      .collect::<Vec<_>>();
+    CommentView::fill_creator_instance_blocked(
+      out
+        .iter_mut()
+        .filter_map(SearchCombinedView::to_comment_view_mut),
+      my_person_id,
+      conn,
+    )
+    .await?;
 
     paginate_response(out, limit, self.page_cursor)
   }
@@ -425,23 +442,37 @@ impl InternalToCombinedView for SearchCombinedViewInternal {
       v.post.clone(),
       v.community.clone(),
     ) {
-      Some(SearchCombinedView::Comment(CommentView {
-        comment,
-        post,
-        community,
-        creator,
-        community_actions: v.community_actions,
-        person_actions: v.person_actions,
-        comment_actions: v.comment_actions,
-        creator_is_admin: v.item_creator_is_admin,
-        post_tags: v.post_tags,
-        can_mod: v.can_mod,
-        creator_banned: v.creator_banned,
-        creator_ban_expires_at: v.creator_ban_expires_at,
-        creator_is_moderator: v.creator_is_moderator,
-        creator_banned_from_community: v.creator_banned_from_community,
-        creator_community_ban_expires_at: v.creator_community_ban_expires_at,
-      }))
+      Some(SearchCombinedView::Comment(
+        CommentView {
+          comment,
+          post,
+          community,
+          creator,
+          community_actions: v.community_actions,
+          person_actions: v.person_actions,
+          comment_actions: v.comment_actions,
+          creator_is_admin: v.item_creator_is_admin,
+          post_tags: v.post_tags,
+          comment_tags: Default::default(),
+          can_mod: v.can_mod,
+          creator_banned: v.creator_banned,
+          creator_ban_expires_at: v.creator_ban_expires_at,
+          creator_is_moderator: v.creator_is_moderator,
+          creator_banned_from_community: v.creator_banned_from_community,
+          creator_community_ban_expires_at: v.creator_community_ban_expires_at,
+          creator_instance_domain: None,
+          federation_state: None,
+          content_source: None,
+          viewer_has_replied: false,
+          collapsed_suggested: false,
+          pinned_in_community: false,
+          removed_by: None,
+          removed_reason: None,
+          crosspost_of: None,
+          reactions: Default::default(),
+        }
+        .hide_scores_until_voted(),
+      ))
     } else if let (Some(post), Some(creator), Some(community)) =
       (v.post, v.item_creator.clone(), v.community.clone())
     {
@@ -468,6 +499,7 @@ impl InternalToCombinedView for SearchCombinedViewInternal {
         community_actions: v.community_actions,
         can_mod: v.can_mod,
         post_tags: v.community_post_tags,
+        comments_last_week: None,
       }))
     } else if let (Some(multi), Some(creator)) = (v.multi_community, &v.item_creator) {
       Some(SearchCombinedView::MultiCommunity(MultiCommunityView {
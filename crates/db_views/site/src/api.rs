@@ -1,4 +1,5 @@
 use crate::{ReadableFederationState, SiteView};
+use chrono::{DateTime, Utc};
 use lemmy_db_schema::{
   newtypes::{LanguageId, MultiCommunityId, OAuthProviderId, TaglineId},
   source::{
@@ -13,6 +14,7 @@ use lemmy_db_schema::{
     person::Person,
     post::Post,
     private_message::PrivateMessage,
+    settings_import_failure::SettingsImportFailure,
     tagline::Tagline,
   },
 };
@@ -123,6 +125,7 @@ pub struct CreateSite {
   pub default_post_listing_mode: Option<PostListingMode>,
   pub default_post_sort_type: Option<PostSortType>,
   pub default_post_time_range_seconds: Option<i32>,
+  pub max_comment_depth: Option<i32>,
   pub default_comment_sort_type: Option<CommentSortType>,
   pub legal_information: Option<String>,
   pub application_email_admins: Option<bool>,
@@ -216,6 +219,8 @@ pub struct EditSite {
   pub default_post_time_range_seconds: Option<i32>,
   /// A default fetch limit for number of items returned.
   pub default_items_per_page: Option<i32>,
+  /// The maximum nesting depth of comment replies, up to the hardcoded limit.
+  pub max_comment_depth: Option<i32>,
   /// The default comment sort, usually "hot"
   pub default_comment_sort_type: Option<CommentSortType>,
   /// An optional page of legal information
@@ -244,7 +249,8 @@ pub struct EditSite {
   /// The number of searches allowed in a given time frame.
   pub rate_limit_search_max_requests: Option<i32>,
   pub rate_limit_search_interval_seconds: Option<i32>,
-  /// The number of settings imports or exports allowed in a given time frame.
+  /// The number of settings imports or exports allowed in a given time frame. User and community
+  /// settings are counted separately.
   pub rate_limit_import_user_settings_max_requests: Option<i32>,
   pub rate_limit_import_user_settings_interval_seconds: Option<i32>,
   /// Whether to enable federation.
@@ -520,12 +526,18 @@ pub struct SaveUserSettings {
   pub show_avatars: Option<bool>,
   /// Sends notifications to your email.
   pub send_notifications_to_email: Option<bool>,
+  /// Sends an email when a new follower is waiting for approval in a community you moderate.
+  pub email_pending_community_followers: Option<bool>,
+  /// Whether communities may reply to your first comment there with their welcome message.
+  pub receive_community_welcome_messages: Option<bool>,
   /// Whether this account is a bot account. Users can hide these accounts easily if they wish.
   pub bot_account: Option<bool>,
   /// Whether to show bot accounts.
   pub show_bot_accounts: Option<bool>,
   /// Whether to show read posts.
   pub show_read_posts: Option<bool>,
+  /// Whether to show comments on posts which you already read.
+  pub show_read_comments: Option<bool>,
   /// A list of languages you are able to see discussion in.
   pub discussion_languages: Option<Vec<LanguageId>>,
   // A list of keywords used for blocking posts having them in title,url or body.
@@ -541,6 +553,12 @@ pub struct SaveUserSettings {
   pub enable_private_messages: Option<bool>,
   /// Whether to auto-collapse bot comments.
   pub collapse_bot_comments: Option<bool>,
+  /// Suggest to collapse comments from accounts younger than this many days. Zero removes the
+  /// setting.
+  pub collapse_comments_account_age_days: Option<i32>,
+  /// Suggest to collapse comments from accounts with a lower comment score than this. Zero
+  /// removes the setting.
+  pub collapse_comments_below_score: Option<i32>,
   /// Some vote display mode settings
   pub show_score: Option<bool>,
   pub show_upvotes: Option<bool>,
@@ -720,6 +738,16 @@ pub struct UserSettingsBackup {
   pub discussion_languages: Vec<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// The items from your last settings import which couldn't be fetched. These are only stored
+/// once the import is complete, and can be fetched again with the import retry endpoint.
+pub struct SettingsImportStatusResponse {
+  pub count: i64,
+  pub failed: Vec<SettingsImportFailure>,
+}
+
 #[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
@@ -732,6 +760,24 @@ pub struct ExportDataResponse {
   pub liked: Vec<Url>,
   pub moderates: Vec<Url>,
   pub settings: UserSettingsBackup,
+  /// Reports which you filed about posts.
+  pub post_reports: Vec<ExportedReport>,
+  /// Reports which you filed about comments.
+  pub comment_reports: Vec<ExportedReport>,
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-rs", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-rs", ts(optional_fields, export))]
+/// A report which you filed. Who resolved it isn't included.
+pub struct ExportedReport {
+  /// The reported post or comment.
+  pub target: Url,
+  pub reason: String,
+  pub resolved: bool,
+  pub published_at: DateTime<Utc>,
+  pub updated_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
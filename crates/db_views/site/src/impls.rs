@@ -93,6 +93,25 @@ pub async fn user_backup_list_to_user_settings_backup(
   pool: &mut DbPool<'_>,
 ) -> LemmyResult<UserSettingsBackup> {
   let lists = LocalUser::export_backup(pool, local_user_view.person.id).await?;
+  let vec_into = |vec: Vec<_>| vec.into_iter().map(Into::into).collect();
+  Ok(UserSettingsBackup {
+    followed_communities: vec_into(lists.followed_communities),
+    blocked_communities: vec_into(lists.blocked_communities),
+    blocked_users: vec_into(lists.blocked_users),
+    saved_posts: vec_into(lists.saved_posts),
+    saved_comments: vec_into(lists.saved_comments),
+    ..user_settings_backup_without_lists(local_user_view, pool).await?
+  })
+}
+
+/// The settings backup without the lists of followed communities, saved items and blocked
+/// users. These can be very long, so streaming exports fetch them separately in chunks.
+pub async fn user_settings_backup_without_lists(
+  local_user_view: LocalUserView,
+  pool: &mut DbPool<'_>,
+) -> LemmyResult<UserSettingsBackup> {
+  let (blocked_instances_communities, blocked_instances_persons) =
+    LocalUser::export_blocked_instances(pool, local_user_view.person.id).await?;
   let blocking_keywords = LocalUserKeywordBlock::read(pool, local_user_view.local_user.id).await?;
   let discussion_languages = LocalUserLanguage::read(pool, local_user_view.local_user.id).await?;
 
@@ -105,7 +124,6 @@ pub async fn user_backup_list_to_user_settings_backup(
     .iter()
     .flat_map(|d| all_languages.get(d).cloned())
     .collect();
-  let vec_into = |vec: Vec<_>| vec.into_iter().map(Into::into).collect();
   Ok(UserSettingsBackup {
     display_name: local_user_view.person.display_name,
     bio: local_user_view.person.bio,
@@ -114,15 +132,11 @@ pub async fn user_backup_list_to_user_settings_backup(
     matrix_id: local_user_view.person.matrix_user_id,
     bot_account: local_user_view.person.bot_account.into(),
     settings: Some(local_user_view.local_user),
-    followed_communities: vec_into(lists.followed_communities),
-    blocked_communities: vec_into(lists.blocked_communities),
-    blocked_instances_communities: lists.blocked_instances_communities,
-    blocked_instances_persons: lists.blocked_instances_persons,
-    blocked_users: vec_into(lists.blocked_users),
-    saved_posts: vec_into(lists.saved_posts),
-    saved_comments: vec_into(lists.saved_comments),
+    blocked_instances_communities,
+    blocked_instances_persons,
     blocking_keywords,
     discussion_languages,
+    ..Default::default()
   })
 }
 
@@ -258,6 +272,7 @@ mod tests {
       last_successful_id: None,
       last_successful_published_time_at: None,
       last_retry_at: None,
+      last_error: None,
     };
     FederationQueueState::upsert(pool, &queue_state).await?;
 
@@ -93,6 +93,8 @@ FROM (
         select_old_and_new_rows AS old_and_new_rows
     WHERE
         r.is_counted (comment)
+        -- Scheduled comments are counted once they are published
+        AND (comment).scheduled_publish_time_at IS NULL
     GROUP BY
         (comment).creator_id) AS diff
 WHERE
@@ -129,7 +131,9 @@ FROM (
             parent_id
         FROM
             select_old_and_new_rows AS old_and_new_rows,
-            LATERAL r.parent_comment_ids ((comment).path) AS parent_id) AS expanded_old_and_new_rows
+            LATERAL r.parent_comment_ids ((comment).path) AS parent_id
+        WHERE
+            (comment).scheduled_publish_time_at IS NULL) AS expanded_old_and_new_rows
     GROUP BY
         parent_id) AS diff
 WHERE
@@ -157,6 +161,7 @@ FROM
     LEFT JOIN post ON post.id = (comment).post_id
 WHERE
     r.is_counted (comment)
+    AND (comment).scheduled_publish_time_at IS NULL
 GROUP BY
     post.id) AS diff
 WHERE
@@ -177,6 +182,7 @@ FROM (
         select_old_and_new_rows AS old_and_new_rows
     WHERE
         r.is_counted (comment)
+        AND (comment).scheduled_publish_time_at IS NULL
         AND (comment).local) AS diff
 WHERE
     diff.comments != 0;
@@ -324,6 +330,71 @@ WHERE (diff.report_count, diff.unresolved_report_count) != (0, 0)
 RETURN NULL;
 END;
 $$);
+-- Count how many reports of each person were resolved by local mods, and how many of those were
+-- valid. A report counts as valid if the reported content is removed. The stats are recalculated
+-- whenever a report is resolved or unresolved, so that resolving a report again doesn't count it
+-- twice.
+CREATE FUNCTION r.update_person_report_stats (for_person_id int)
+    RETURNS void
+    LANGUAGE sql
+    AS $$
+    INSERT INTO person_report_stats (person_id, resolved_count, valid_count)
+    SELECT
+        for_person_id,
+        count(*),
+        count(*) FILTER (WHERE removed)
+    FROM (
+        SELECT
+            post.removed
+        FROM
+            post_report
+            INNER JOIN post ON post.id = post_report.post_id
+            INNER JOIN person ON person.id = post_report.resolver_id
+        WHERE
+            post_report.creator_id = for_person_id
+            AND post_report.resolved
+            AND person.local
+        UNION ALL
+        SELECT
+            comment.removed
+        FROM
+            comment_report
+            INNER JOIN comment ON comment.id = comment_report.comment_id
+            INNER JOIN person ON person.id = comment_report.resolver_id
+        WHERE
+            comment_report.creator_id = for_person_id
+            AND comment_report.resolved
+            AND person.local) AS resolved_report
+ON CONFLICT (person_id)
+    DO UPDATE SET
+        resolved_count = excluded.resolved_count,
+        valid_count = excluded.valid_count;
+$$;
+CREATE PROCEDURE r.create_reporter_stats_trigger (table_name text)
+LANGUAGE plpgsql
+AS $a$
+BEGIN
+    EXECUTE replace($b$ CREATE FUNCTION r.reporter_stats_thing_report_update ( )
+            RETURNS TRIGGER
+            LANGUAGE plpgsql
+            AS $$
+            BEGIN
+                PERFORM
+                    r.update_person_report_stats (NEW.creator_id);
+                RETURN NULL;
+            END $$;
+    CREATE TRIGGER reporter_stats
+        AFTER UPDATE OF resolved ON thing_report
+        FOR EACH ROW
+        WHEN (NEW.resolved IS DISTINCT FROM OLD.resolved)
+        EXECUTE FUNCTION r.reporter_stats_thing_report_update ( );
+        $b$,
+        'thing',
+        table_name);
+END;
+$a$;
+CALL r.create_reporter_stats_trigger ('post');
+CALL r.create_reporter_stats_trigger ('comment');
 -- Change the order of some cascading deletions to make deletion triggers run before the deletion of rows that the triggers need to read
 CREATE FUNCTION r.delete_follow_before_person ()
     RETURNS TRIGGER
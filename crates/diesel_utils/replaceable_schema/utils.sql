@@ -233,6 +233,52 @@ BEGIN
 END;
 $$;
 
+-- Community aggregate function for the number of comments which weren't deleted or removed.
+-- Communities without comments in the interval get a zero count, if their previous count wasn't
+-- zero already.
+CREATE OR REPLACE FUNCTION r.community_aggregates_comments (i text)
+    RETURNS TABLE (
+        count_ integer,
+        community_id_ integer)
+    LANGUAGE plpgsql
+    AS $$
+BEGIN
+    RETURN query WITH counts AS (
+        SELECT
+            count(*)::integer AS count_,
+            p.community_id AS community_id_
+        FROM
+            comment c
+            INNER JOIN post p ON c.post_id = p.id
+        WHERE
+            c.published_at >= (CURRENT_TIMESTAMP - i::interval)
+            AND NOT c.deleted
+            AND NOT c.removed
+            AND c.scheduled_publish_time_at IS NULL
+        GROUP BY
+            p.community_id
+)
+    SELECT
+        counts.count_,
+        counts.community_id_
+    FROM
+        counts
+    UNION ALL
+    SELECT
+        0,
+        co.id
+    FROM
+        community co
+    WHERE
+        co.comments_day > 0
+        AND co.id NOT IN (
+            SELECT
+                counts.community_id_
+            FROM
+                counts);
+END;
+$$;
+
 -- Edit site aggregates to include voters and people who have read posts as active users
 CREATE OR REPLACE FUNCTION r.site_aggregates_activity (i text)
     RETURNS integer
@@ -159,6 +159,49 @@ impl PaginationCursor {
   pub fn is_back(self) -> LemmyResult<bool> {
     Ok(self.into_internal()?.back)
   }
+
+  /// The cursor data and whether it points back, for queries which can't be paginated with
+  /// cursor keys.
+  pub fn into_data(self) -> LemmyResult<(CursorData, bool)> {
+    let internal = self.into_internal()?;
+    Ok((internal.data, internal.back))
+  }
+}
+
+/// Paging for queries which are ordered by a value that can't be a cursor key, for example a column
+/// of a joined table. The cursor only points to an item, and the query reads its sort value again.
+/// To go back, the previous items are loaded in reverse order and flipped again afterwards.
+#[cfg(feature = "full")]
+pub struct ReverseCursor {
+  back: bool,
+}
+
+#[cfg(feature = "full")]
+impl ReverseCursor {
+  /// Splits the page cursor into the data of the item it points to, and the paging direction.
+  pub fn new(page_cursor: Option<PaginationCursor>) -> LemmyResult<(Option<CursorData>, Self)> {
+    match page_cursor {
+      Some(page_cursor) => {
+        let (data, back) = page_cursor.into_data()?;
+        Ok((Some(data), Self { back }))
+      }
+      None => Ok((None, Self { back: false })),
+    }
+  }
+
+  /// Whether items need to be loaded in descending order, so that the page ends up in the
+  /// requested order.
+  pub fn load_descending(&self, descending: bool) -> bool {
+    descending != self.back
+  }
+
+  /// Puts the loaded items back into the requested order.
+  pub fn restore_order<T>(&self, mut items: Vec<T>) -> Vec<T> {
+    if self.back {
+      items.reverse();
+    }
+    items
+  }
 }
 
 /// The actual data which is stored inside a cursor, not accessible outside this file.
@@ -150,6 +150,16 @@ pub fn fuzzy_search(q: &str) -> String {
   format!("%{replaced}%")
 }
 
+/// Matches the text literally anywhere in a string with `ILIKE`. Unlike [fuzzy_search], words
+/// separated by spaces have to be next to each other.
+pub fn substring_search(q: &str) -> String {
+  let escaped = q
+    .replace('\\', "\\\\")
+    .replace('%', "\\%")
+    .replace('_', "\\_");
+  format!("%{escaped}%")
+}
+
 /// Takes an API optional text input, and converts it to an optional diesel DB update.
 pub fn diesel_string_update(opt: Option<&str>) -> Option<Option<String>> {
   match opt {
@@ -281,6 +291,15 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_substring_search() {
+    let test = "This %is% _a_ substring search";
+    assert_eq!(
+      substring_search(test),
+      "%This \\%is\\% \\_a\\_ substring search%".to_string()
+    );
+  }
+
   #[test]
   fn test_diesel_option_overwrite() {
     assert_eq!(diesel_string_update(None), None);
@@ -0,0 +1,39 @@
+use crate::{send::send_email, user_language};
+use lemmy_db_schema::source::community::Community;
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_diesel_utils::connection::DbPool;
+use lemmy_utils::{error::LemmyResult, settings::structs::Settings};
+
+/// Notify the moderators of a community who opted in that a new follower is waiting for approval.
+/// Returns the number of emails which were queued.
+pub async fn send_new_pending_follower_email_to_mods(
+  follower_username: &str,
+  community: &Community,
+  pool: &mut DbPool<'_>,
+  settings: &'static Settings,
+) -> LemmyResult<usize> {
+  let mods = LocalUserView::list_mods_for_pending_follower_email(pool, community.id).await?;
+
+  let community_link = &format!(
+    "{}/c/{}",
+    settings.get_protocol_and_hostname(),
+    community.name
+  );
+
+  let mut sent = 0;
+  for mod_ in mods {
+    if mod_.banned {
+      continue;
+    }
+    let lang = user_language(&mod_.local_user);
+    if let Some(email) = mod_.local_user.email {
+      let subject =
+        lang.new_pending_follower_subject(&settings.hostname, &community.title, follower_username);
+      let body =
+        lang.new_pending_follower_body(community_link, &community.title, follower_username);
+      send_email(subject, email, mod_.person.name, body, settings);
+      sent += 1;
+    }
+  }
+  Ok(sent)
+}
@@ -10,6 +10,7 @@ use translations::Lang;
 
 pub mod account;
 pub mod admin;
+pub mod community;
 pub mod notifications;
 mod send;
 
@@ -633,6 +633,49 @@ fn create_modlog_items(modlog: Vec<ModlogView>, settings: &Settings) -> LemmyRes
           ),
           settings,
         ),
+        ModlogKind::ModResolveCommunityReports => build_modlog_item(
+          r,
+          &modlog_url,
+          format!("Resolved all reports in /c/{}", &target_community_name),
+          settings,
+        ),
+        ModlogKind::ModMarkCommentAsAnswer => build_modlog_item(
+          r,
+          &modlog_url,
+          format!(
+            "{} comment {} as answer",
+            if r.modlog.is_revert {
+              "Unmarked"
+            } else {
+              "Marked"
+            },
+            &&target_comment_content
+          ),
+          settings,
+        ),
+        ModlogKind::ModDistinguishComment => build_modlog_item(
+          r,
+          &modlog_url,
+          format!(
+            "{} comment {}",
+            if r.modlog.is_revert {
+              "Undistinguished"
+            } else {
+              "Distinguished"
+            },
+            &&target_comment_content
+          ),
+          settings,
+        ),
+        ModlogKind::ModMoveComment => build_modlog_item(
+          r,
+          &modlog_url,
+          format!(
+            "Moved comment {} to post {}",
+            &&target_comment_content, &&target_post_name
+          ),
+          settings,
+        ),
       }
     })
     .collect::<LemmyResult<Vec<Item>>>()?;
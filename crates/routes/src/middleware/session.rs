@@ -3,16 +3,82 @@ use actix_web::{
   HttpMessage,
   body::MessageBody,
   dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
-  http::header::{CACHE_CONTROL, HeaderValue},
+  http::{
+    Method,
+    header::{CACHE_CONTROL, HeaderValue},
+  },
 };
 use core::future::Ready;
 use futures_util::future::LocalBoxFuture;
 use lemmy_api_utils::{
   context::LemmyContext,
-  utils::{local_user_view_from_jwt, read_auth_token},
+  utils::{
+    check_community_mod_of_any_or_admin_action,
+    is_admin,
+    local_user_view_from_jwt,
+    read_auth_token,
+  },
 };
+use lemmy_db_views_local_user::LocalUserView;
+use lemmy_utils::rate_limit::RateLimitExempt;
 use std::{future::ready, rc::Rc};
 
+/// Moderation endpoints which are exempt from rate limiting for admins and for moderators of any
+/// community. The community of the request is only known once the handler parses it, so the
+/// handler still checks that the user moderates that specific community. Content creation is
+/// never exempt.
+const MODERATION_ACTIONS: &[&str] = &[
+  "/api/v4/community/remove",
+  "/api/v4/community/ban_user",
+  "/api/v4/post/remove",
+  "/api/v4/post/lock",
+  "/api/v4/post/feature",
+  "/api/v4/post/mod_update",
+  "/api/v4/post/report/resolve",
+  "/api/v4/comment/remove",
+  "/api/v4/comment/lock",
+  "/api/v4/comment/pin_in_community",
+  "/api/v4/comment/approve",
+  "/api/v4/comment/distinguish",
+  "/api/v4/comment/report/resolve",
+  "/api/v4/comment/report/snooze",
+  "/api/v4/report/resolve_all",
+];
+
+/// Endpoints which only admins can use, so they are only exempt for admins.
+const ADMIN_ACTIONS: &[&str] = &[
+  "/api/v4/admin/ban",
+  "/api/v4/admin/purge/person",
+  "/api/v4/admin/purge/community",
+  "/api/v4/admin/purge/post",
+  "/api/v4/admin/purge/comment",
+];
+
+fn is_moderation_action(req: &ServiceRequest) -> bool {
+  req.method() != Method::GET && MODERATION_ACTIONS.contains(&req.path())
+}
+
+fn is_admin_action(req: &ServiceRequest) -> bool {
+  req.method() != Method::GET && ADMIN_ACTIONS.contains(&req.path())
+}
+
+/// Whether the request is a moderation action which the user may send without rate limits.
+async fn is_rate_limit_exempt(
+  req: &ServiceRequest,
+  local_user_view: &LocalUserView,
+  context: &LemmyContext,
+) -> bool {
+  if is_admin_action(req) {
+    is_admin(local_user_view).is_ok()
+  } else if is_moderation_action(req) {
+    check_community_mod_of_any_or_admin_action(local_user_view, &mut context.pool())
+      .await
+      .is_ok()
+  } else {
+    false
+  }
+}
+
 #[derive(Clone)]
 pub struct SessionMiddleware {
   context: LemmyContext,
@@ -73,6 +139,9 @@ where
         // to use `/api/v4/account/validate_auth` for that.
         let local_user_view = local_user_view_from_jwt(jwt, &context).await.ok();
         if let Some(local_user_view) = local_user_view {
+          if is_rate_limit_exempt(&req, &local_user_view, &context).await {
+            req.extensions_mut().insert(RateLimitExempt);
+          }
           req.extensions_mut().insert(local_user_view);
         }
       }
@@ -100,15 +169,25 @@ where
 #[cfg(test)]
 mod tests {
 
-  use actix_web::test::TestRequest;
+  use super::{SessionMiddleware, is_admin_action, is_moderation_action};
+  use actix_web::{
+    App,
+    HttpMessage,
+    HttpRequest,
+    http::header::AUTHORIZATION,
+    test::{TestRequest, call_and_read_body, init_service},
+    web::post,
+  };
   use lemmy_api_utils::{claims::Claims, context::LemmyContext};
   use lemmy_db_schema::source::{
+    community::{Community, CommunityActions, CommunityInsertForm, CommunityModeratorForm},
     instance::Instance,
     local_user::{LocalUser, LocalUserInsertForm},
     person::{Person, PersonInsertForm},
   };
+  use lemmy_db_views_local_user::LocalUserView;
   use lemmy_diesel_utils::traits::Crud;
-  use lemmy_utils::error::LemmyResult;
+  use lemmy_utils::{error::LemmyResult, rate_limit::RateLimitExempt};
   use pretty_assertions::assert_eq;
   use serial_test::serial;
 
@@ -139,4 +218,86 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_is_moderation_action() {
+    let removal = TestRequest::post()
+      .uri("/api/v4/comment/remove")
+      .to_srv_request();
+    assert!(is_moderation_action(&removal));
+
+    // Creating comments stays rate limited for everyone
+    let comment = TestRequest::post().uri("/api/v4/comment").to_srv_request();
+    assert!(!is_moderation_action(&comment));
+    let purge = TestRequest::post()
+      .uri("/api/v4/admin/purge/person")
+      .to_srv_request();
+    assert!(!is_moderation_action(&purge));
+    assert!(is_admin_action(&purge));
+    let list = TestRequest::get()
+      .uri("/api/v4/admin/purge/person")
+      .to_srv_request();
+    assert!(!is_admin_action(&list));
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_rate_limit_exempt() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let admin = LocalUserView::create_test_user(pool, "exempt_admin", "", true).await?;
+    let moderator = LocalUserView::create_test_user(pool, "exempt_mod", "", false).await?;
+    let regular = LocalUserView::create_test_user(pool, "exempt_regular", "", false).await?;
+    let community_form = CommunityInsertForm::new(
+      moderator.person.instance_id,
+      "exempt_community".to_string(),
+      "nada".to_owned(),
+      "pubkey".to_string(),
+    );
+    let community = Community::create(pool, &community_form).await?;
+    let form = CommunityModeratorForm::new(community.id, moderator.person.id);
+    CommunityActions::join(pool, &form).await?;
+
+    // Reports whether the session middleware marked the request as exempt
+    let is_exempt =
+      |req: HttpRequest| async move { req.extensions().contains::<RateLimitExempt>().to_string() };
+    let app = init_service(
+      App::new()
+        .wrap(SessionMiddleware::new(context.clone()))
+        .route("/api/v4/comment/remove", post().to(is_exempt))
+        .route("/api/v4/admin/purge/person", post().to(is_exempt)),
+    )
+    .await;
+
+    // Moderators of any community are exempt from moderation actions, but not from admin actions
+    for (user, path, exempt) in [
+      (&admin, "/api/v4/comment/remove", true),
+      (&moderator, "/api/v4/comment/remove", true),
+      (&regular, "/api/v4/comment/remove", false),
+      (&admin, "/api/v4/admin/purge/person", true),
+      (&moderator, "/api/v4/admin/purge/person", false),
+      (&regular, "/api/v4/admin/purge/person", false),
+    ] {
+      let jwt = Claims::generate(
+        user.local_user.id,
+        None,
+        TestRequest::default().to_http_request(),
+        &context,
+      )
+      .await?;
+      let req = TestRequest::post()
+        .uri(path)
+        .insert_header((AUTHORIZATION, format!("Bearer {}", &*jwt)))
+        .to_request();
+      let body = call_and_read_body(&app, req).await;
+      assert_eq!(exempt.to_string(), body, "{path}");
+    }
+
+    Community::delete(pool, community.id).await?;
+    for user in [admin, moderator, regular] {
+      Person::delete(pool, user.person.id).await?;
+    }
+    Ok(())
+  }
 }
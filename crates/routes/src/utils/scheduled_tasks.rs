@@ -6,6 +6,7 @@ use diesel::{
   BoolExpressionMethods,
   ExpressionMethods,
   NullableExpressionMethods,
+  OptionalExtension,
   QueryDsl,
   QueryableByName,
   SelectableHelper,
@@ -18,15 +19,19 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use diesel_uplete::uplete;
 use lemmy_api_utils::{
   context::LemmyContext,
+  notify::NotifyData,
   send_activity::{ActivityChannel, SendActivityData},
   utils::send_webmention,
 };
 use lemmy_db_schema::{
   source::{
-    community::Community,
+    comment::{Comment, CommentUpdateForm},
+    community::{Community, CommunityActions},
     instance::{Instance, InstanceForm},
     local_user::LocalUser,
-    post::{Post, PostUpdateForm},
+    modlog::{Modlog, ModlogInsertForm},
+    person::Person,
+    post::{Post, PostActions, PostUpdateForm},
   },
   utils::DELETED_REPLACEMENT_TEXT,
 };
@@ -66,7 +71,9 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
   let mut scheduler = AsyncScheduler::with_tz(Utc);
 
   let context_1 = context.clone();
-  // Every 10 minutes update hot ranks, delete expired captchas and publish scheduled posts
+  // Every 10 minutes update hot ranks, delete expired captchas and coalesced notification records,
+  // publish scheduled posts and comments, finalize pending comment deletes and undistinguish
+  // disliked comments
   scheduler.every(CTimeUnits::minutes(10)).run(move || {
     let context = context_1.clone();
 
@@ -79,16 +86,46 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to delete expired captcha answers: {e}"))
         .ok();
+      PostActions::delete_expired_subscriber_notified(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to delete expired subscriber notifications: {e}"))
+        .ok();
       publish_scheduled_posts(&context)
         .await
         .inspect_err(|e| warn!("Failed to publish scheduled posts: {e}"))
         .ok();
+      publish_scheduled_comments(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to publish scheduled comments: {e}"))
+        .ok();
+      finalize_pending_comment_deletes(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to finalize pending comment deletes: {e}"))
+        .ok();
+      undistinguish_disliked_comments(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to undistinguish disliked comments: {e}"))
+        .ok();
+    }
+  });
+
+  let context_1 = context.clone();
+  // Every minute federate comments whose federation delay is over
+  scheduler.every(CTimeUnits::minutes(1)).run(move || {
+    let context = context_1.clone();
+
+    async move {
+      federate_delayed_comments(&context)
+        .await
+        .inspect_err(|e| warn!("Failed to federate delayed comments: {e}"))
+        .ok();
     }
   });
 
   let context_1 = context.clone();
   // Hourly tasks:
   // - Update active daily counts
+  // - Update daily comment counts
   // - Expired bans
   // - Expired instance blocks
   scheduler.every(CTimeUnits::hour(1)).run(move || {
@@ -99,6 +136,10 @@ pub async fn setup(context: Data<LemmyContext>) -> LemmyResult<()> {
         .await
         .inspect_err(|e| warn!("Failed to update active counts: {e}"))
         .ok();
+      comment_counts(&mut context.pool())
+        .await
+        .inspect_err(|e| warn!("Failed to update comment counts: {e}"))
+        .ok();
       update_banned_when_expired(&mut context.pool())
         .await
         .inspect_err(|e| warn!("Failed to update expired bans: {e}"))
@@ -408,6 +449,12 @@ async fn all_active_counts(pool: &mut DbPool<'_>) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Re-calculate the number of comments in each community during the last day
+async fn comment_counts(pool: &mut DbPool<'_>) -> LemmyResult<()> {
+  let conn = &mut get_conn(pool).await?;
+  process_community_aggregates(conn, ONE_DAY, "comments", "community_aggregates_comments").await
+}
+
 async fn process_site_aggregates(
   conn: &mut AsyncPgConnection,
   interval: (&str, &str),
@@ -618,6 +665,221 @@ async fn publish_scheduled_posts(context: &Data<LemmyContext>) -> LemmyResult<()
   Ok(())
 }
 
+/// Publishes comments whose scheduled time has passed, then notifies and federates them.
+async fn publish_scheduled_comments(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let site_view = SiteView::read_local(pool).await?;
+  let local_instance_id = site_view.instance.id;
+  let conn = &mut get_conn(pool).await?;
+
+  let not_community_banned_action = community_actions::table
+    .find((person::id, community::id))
+    .filter(community_actions::received_ban_at.is_not_null());
+
+  let not_local_banned_action = instance_actions::table
+    .find((person::id, local_instance_id))
+    .filter(instance_actions::received_ban_at.is_not_null());
+
+  let scheduled_comments: Vec<_> = comment::table
+    .inner_join(person::table)
+    .inner_join(post::table.inner_join(community::table))
+    .filter(comment::scheduled_publish_time_at.lt(now().nullable()))
+    // make sure the comment, post, person and community are still around
+    .filter(not(comment::deleted.or(comment::removed)))
+    .filter(not(post::deleted.or(post::removed)))
+    .filter(not(person::deleted))
+    .filter(not(community::removed.or(community::deleted)))
+    // ensure that user isnt banned from community
+    .filter(not(exists(not_community_banned_action)))
+    // ensure that user isnt banned from local
+    .filter(not(exists(not_local_banned_action)))
+    .select((
+      Comment::as_select(),
+      Person::as_select(),
+      Post::as_select(),
+      Community::as_select(),
+    ))
+    .get_results::<(Comment, Person, Post, Community)>(conn)
+    .await?;
+
+  for (comment, creator, post, community) in scheduled_comments {
+    // The comment counts as new from now on, so it isn't buried below older replies
+    let form = CommentUpdateForm {
+      scheduled_publish_time_at: Some(None),
+      published_at: Some(Utc::now()),
+      ..Default::default()
+    };
+    let comment = Comment::update(&mut context.pool(), comment.id, &form).await?;
+
+    // Comments held for approval are announced once a mod approves them
+    if comment.pending_approval {
+      continue;
+    }
+    NotifyData::new(
+      post,
+      Some(comment.clone()),
+      creator,
+      community,
+      !site_view.local_site.disable_email_notifications,
+    )
+    .send(context);
+    let send_activity = SendActivityData::CreateComment(comment);
+    ActivityChannel::submit_activity(send_activity, context)?;
+  }
+  Ok(())
+}
+
+/// Federates new comments whose federation delay is over, and notifies about them. Comments which
+/// were deleted or removed in the meantime are never federated.
+async fn federate_delayed_comments(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let local_site = SiteView::read_local(pool).await?.local_site;
+  let conn = &mut get_conn(pool).await?;
+
+  let delayed_comments: Vec<_> = comment::table
+    .inner_join(person::table)
+    .inner_join(post::table.inner_join(community::table))
+    .filter(comment::pending_federation_until.lt(now().nullable()))
+    .filter(not(comment::deleted.or(comment::removed)))
+    .select((
+      Comment::as_select(),
+      Person::as_select(),
+      Post::as_select(),
+      Community::as_select(),
+    ))
+    .get_results::<(Comment, Person, Post, Community)>(conn)
+    .await?;
+
+  for (comment, creator, post, community) in delayed_comments {
+    // Comments to remote communities stay pending until the community sends them back
+    let federation_pending = !community.local
+      && CommunityActions::check_accept_activity_in_community(&mut context.pool(), &community)
+        .await
+        .is_ok();
+    let form = CommentUpdateForm {
+      federation_pending: Some(federation_pending),
+      pending_federation_until: Some(None),
+      ..Default::default()
+    };
+    let comment = Comment::update(&mut context.pool(), comment.id, &form).await?;
+
+    // Comments held for approval are announced once a mod approves them
+    if comment.pending_approval {
+      continue;
+    }
+    NotifyData::new(
+      post,
+      Some(comment.clone()),
+      creator,
+      community,
+      !local_site.disable_email_notifications,
+    )
+    .send(context);
+    let send_activity = SendActivityData::CreateComment(comment);
+    ActivityChannel::submit_activity(send_activity, context)?;
+  }
+  Ok(())
+}
+
+/// Deletes comments whose grace period for restoring them is over, and federates the delete.
+async fn finalize_pending_comment_deletes(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+
+  let expired: Vec<_> = comment::table
+    .inner_join(person::table)
+    .inner_join(post::table.inner_join(community::table))
+    .filter(comment::pending_delete_until.le(now().nullable()))
+    .select((
+      Comment::as_select(),
+      Person::as_select(),
+      Community::as_select(),
+    ))
+    .get_results::<(Comment, Person, Community)>(conn)
+    .await?;
+
+  for (comment, creator, community) in expired {
+    let comment_id = comment.id;
+    finalize_pending_comment_delete(context, comment, creator, community)
+      .await
+      .inspect_err(|e| warn!("Failed to finalize pending delete of comment {comment_id}: {e}"))
+      .ok();
+  }
+  Ok(())
+}
+
+/// Marks a single comment as deleted, unless its pending delete was restored in the meantime.
+async fn finalize_pending_comment_delete(
+  context: &Data<LemmyContext>,
+  comment: Comment,
+  creator: Person,
+  community: Community,
+) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+
+  let form = CommentUpdateForm {
+    deleted: Some(true),
+    pending_delete_until: Some(None),
+    ..Default::default()
+  };
+  let comment = update(
+    comment::table
+      .find(comment.id)
+      .filter(comment::pending_delete_until.is_not_null())
+      .filter(comment::pending_delete_until.le(now().nullable())),
+  )
+  .set(&form)
+  .get_result::<Comment>(conn)
+  .await
+  .optional()?;
+
+  if let Some(comment) = comment {
+    let send_activity = SendActivityData::DeleteComment(comment, creator, community);
+    ActivityChannel::submit_activity(send_activity, context)?;
+  }
+  Ok(())
+}
+
+/// Undistinguishes local comments whose score dropped below the community's
+/// `auto_undistinguish_below_score`, and writes a modlog entry for each.
+async fn undistinguish_disliked_comments(context: &Data<LemmyContext>) -> LemmyResult<()> {
+  let pool = &mut context.pool();
+  let conn = &mut get_conn(pool).await?;
+
+  let disliked: Vec<_> = comment::table
+    .inner_join(post::table.inner_join(community::table))
+    .filter(comment::distinguished)
+    .filter(comment::local)
+    .filter(
+      comment::score
+        .nullable()
+        .lt(community::auto_undistinguish_below_score),
+    )
+    .select(Comment::as_select())
+    .get_results::<Comment>(conn)
+    .await?;
+
+  if disliked.is_empty() {
+    return Ok(());
+  }
+  let system_account = SiteView::read_system_account(&mut context.pool()).await?;
+
+  for comment in disliked {
+    let form = CommentUpdateForm {
+      distinguished: Some(false),
+      ..Default::default()
+    };
+    let comment = Comment::update(&mut context.pool(), comment.id, &form).await?;
+
+    let form = ModlogInsertForm::mod_distinguish_comment(system_account.id, &comment, false);
+    Modlog::create(&mut context.pool(), &[form]).await?;
+
+    ActivityChannel::submit_activity(SendActivityData::UpdateComment(comment), context)?;
+  }
+  Ok(())
+}
+
 /// Updates the instance software and version.
 ///
 /// Does so using the /.well-known/nodeinfo protocol described here:
@@ -711,9 +973,12 @@ async fn build_update_instance_form(
 mod tests {
 
   use super::*;
+  use chrono::TimeDelta;
   use lemmy_api_utils::request::client_builder;
   use lemmy_db_schema::{
+    CommunitySortType,
     source::{
+      comment::CommentInsertForm,
       community::{Community, CommunityInsertForm},
       person::{Person, PersonInsertForm},
       post::{Post, PostActions, PostInsertForm, PostLikeForm},
@@ -721,6 +986,8 @@ mod tests {
     test_data::TestData,
     traits::Likeable,
   };
+  use lemmy_db_schema_file::{enums::ModlogKind, schema::modlog};
+  use lemmy_db_views_community::impls::CommunityQuery;
   use lemmy_diesel_utils::traits::Crud;
   use lemmy_utils::{
     error::{LemmyErrorType, LemmyResult},
@@ -791,6 +1058,8 @@ mod tests {
     update_instance_software(pool, context.client()).await?;
     delete_expired_captcha_answers(pool).await?;
     publish_scheduled_posts(&context).await?;
+    publish_scheduled_comments(&context).await?;
+    finalize_pending_comment_deletes(&context).await?;
 
     let community_after = Community::read(pool, community.id).await?;
     assert_eq!(
@@ -809,4 +1078,355 @@ mod tests {
     data.delete(pool).await?;
     Ok(())
   }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_finalize_pending_comment_deletes() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let data = TestData::create(pool).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "pending_delete".to_owned(),
+        "title".to_owned(),
+        "pubkey".to_owned(),
+      ),
+    )
+    .await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "pending_deleter"),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("pending deletes".to_owned(), person.id, community.id),
+    )
+    .await?;
+
+    let form = CommentInsertForm {
+      pending_delete_until: Some(Utc::now() - TimeDelta::minutes(1)),
+      ..CommentInsertForm::new(person.id, post.id, "expired".to_owned())
+    };
+    let expired = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm {
+      pending_delete_until: Some(Utc::now() + TimeDelta::hours(1)),
+      ..CommentInsertForm::new(person.id, post.id, "in grace".to_owned())
+    };
+    let in_grace = Comment::create(pool, &form, None).await?;
+
+    finalize_pending_comment_deletes(&context).await?;
+
+    // The grace period is over, so the delete is final and can't be restored anymore
+    let expired = Comment::read(pool, expired.id).await?;
+    assert!(expired.deleted);
+    assert_eq!(None, expired.pending_delete_until);
+    assert!(
+      Comment::restore_pending_delete(pool, expired.id)
+        .await
+        .is_err()
+    );
+
+    // Still within the grace period, so the creator can restore it
+    let in_grace = Comment::read(pool, in_grace.id).await?;
+    assert!(!in_grace.deleted);
+    assert!(in_grace.pending_delete_until.is_some());
+    let restored = Comment::restore_pending_delete(pool, in_grace.id).await?;
+    assert!(!restored.deleted);
+    assert_eq!(None, restored.pending_delete_until);
+
+    // A restore that lands between the select and the update wins
+    finalize_pending_comment_delete(&context, restored, person.clone(), community.clone()).await?;
+    assert!(!Comment::read(pool, in_grace.id).await?.deleted);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_trending_comments() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let data = TestData::create(pool).await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "trending_commenter"),
+    )
+    .await?;
+
+    // Communities with one, three and zero recent comments, and two comments from last week
+    let mut communities = vec![];
+    for (name, recent, old) in [("quiet", 1, 2), ("busy", 3, 0), ("silent", 0, 0)] {
+      let community = Community::create(
+        pool,
+        &CommunityInsertForm::new(
+          data.instance.id,
+          format!("trending_{name}"),
+          "title".to_owned(),
+          "pubkey".to_owned(),
+        ),
+      )
+      .await?;
+      let post = Post::create(
+        pool,
+        &PostInsertForm::new(name.to_owned(), person.id, community.id),
+      )
+      .await?;
+      for i in 0..(recent + old) {
+        let form = CommentInsertForm {
+          published_at: (i >= recent).then(|| Utc::now() - TimeDelta::weeks(1)),
+          ..CommentInsertForm::new(person.id, post.id, format!("comment {i}"))
+        };
+        Comment::create(pool, &form, None).await?;
+      }
+      communities.push(community);
+    }
+
+    comment_counts(pool).await?;
+
+    let ids = CommunityQuery {
+      sort: Some(CommunitySortType::TrendingComments),
+      ..Default::default()
+    }
+    .list(&data.site, pool)
+    .await?
+    .items
+    .into_iter()
+    .filter(|c| c.community.name.starts_with("trending_"))
+    .map(|c| (c.community.name, c.community.comments_day))
+    .collect::<Vec<_>>();
+    assert_eq!(
+      vec![
+        ("trending_busy".to_owned(), 3),
+        ("trending_quiet".to_owned(), 1),
+        ("trending_silent".to_owned(), 0),
+      ],
+      ids
+    );
+
+    for community in communities {
+      Community::delete(pool, community.id).await?;
+    }
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_publish_scheduled_comments() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let data = TestData::create(pool).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "scheduled_comments".to_owned(),
+        "title".to_owned(),
+        "pubkey".to_owned(),
+      ),
+    )
+    .await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "comment_scheduler"),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("scheduled comments".to_owned(), person.id, community.id),
+    )
+    .await?;
+
+    let form = CommentInsertForm {
+      scheduled_publish_time_at: Some(Utc::now() - TimeDelta::minutes(1)),
+      ..CommentInsertForm::new(person.id, post.id, "due".to_owned())
+    };
+    let due = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm {
+      scheduled_publish_time_at: Some(Utc::now() + TimeDelta::hours(1)),
+      ..CommentInsertForm::new(person.id, post.id, "later".to_owned())
+    };
+    let later = Comment::create(pool, &form, None).await?;
+
+    // Scheduled comments don't count towards the post's comments yet
+    assert_eq!(0, Post::read(pool, post.id).await?.comments);
+
+    publish_scheduled_comments(&context).await?;
+
+    // The scheduled time is over, so the comment is published and counted
+    let due_before = due;
+    let due = Comment::read(pool, due.id).await?;
+    assert_eq!(None, due.scheduled_publish_time_at);
+    assert!(due.published_at > due_before.published_at);
+    assert_eq!(1, Post::read(pool, post.id).await?.comments);
+
+    // This one stays scheduled
+    let later = Comment::read(pool, later.id).await?;
+    assert!(later.scheduled_publish_time_at.is_some());
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_federate_delayed_comments() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let data = TestData::create(pool).await?;
+    let community = Community::create(
+      pool,
+      &CommunityInsertForm::new(
+        data.instance.id,
+        "delayed_comments".to_owned(),
+        "title".to_owned(),
+        "pubkey".to_owned(),
+      ),
+    )
+    .await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "comment_delayer"),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("delayed comments".to_owned(), person.id, community.id),
+    )
+    .await?;
+
+    let delayed_form = |content: &str, until| CommentInsertForm {
+      local: Some(true),
+      federation_pending: Some(true),
+      pending_federation_until: Some(until),
+      ..CommentInsertForm::new(person.id, post.id, content.to_owned())
+    };
+    let survivor = Comment::create(
+      pool,
+      &delayed_form("survivor", Utc::now() - TimeDelta::minutes(1)),
+      None,
+    )
+    .await?;
+    let deleted = Comment::create(
+      pool,
+      &delayed_form("deleted", Utc::now() - TimeDelta::minutes(1)),
+      None,
+    )
+    .await?;
+    let form = CommentUpdateForm {
+      deleted: Some(true),
+      ..Default::default()
+    };
+    Comment::update(pool, deleted.id, &form).await?;
+    let later = Comment::create(
+      pool,
+      &delayed_form("later", Utc::now() + TimeDelta::hours(1)),
+      None,
+    )
+    .await?;
+
+    federate_delayed_comments(&context).await?;
+
+    // The delay is over, so the comment is federated and visible to everyone
+    let survivor = Comment::read(pool, survivor.id).await?;
+    assert!(!survivor.federation_pending);
+    assert_eq!(None, survivor.pending_federation_until);
+
+    // Deleted within the delay, so it never federates
+    let deleted = Comment::read(pool, deleted.id).await?;
+    assert!(deleted.federation_pending);
+    assert!(deleted.pending_federation_until.is_some());
+
+    // This one is still delayed
+    let later = Comment::read(pool, later.id).await?;
+    assert!(later.federation_pending);
+    assert!(later.pending_federation_until.is_some());
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[serial]
+  async fn test_undistinguish_disliked_comments() -> LemmyResult<()> {
+    let context = LemmyContext::init_test_context().await;
+    let pool = &mut context.pool();
+
+    let data = TestData::create(pool).await?;
+    let community_form = CommunityInsertForm {
+      auto_undistinguish_below_score: Some(-2),
+      ..CommunityInsertForm::new(
+        data.instance.id,
+        "auto_undistinguish".to_owned(),
+        "title".to_owned(),
+        "pubkey".to_owned(),
+      )
+    };
+    let community = Community::create(pool, &community_form).await?;
+    let person = Person::create(
+      pool,
+      &PersonInsertForm::test_form(data.instance.id, "distinguisher"),
+    )
+    .await?;
+    let post = Post::create(
+      pool,
+      &PostInsertForm::new("distinguished comments".to_owned(), person.id, community.id),
+    )
+    .await?;
+
+    let form = CommentInsertForm {
+      distinguished: Some(true),
+      ..CommentInsertForm::new(person.id, post.id, "disliked".to_owned())
+    };
+    let disliked = Comment::create(pool, &form, None).await?;
+    let form = CommentInsertForm {
+      distinguished: Some(true),
+      ..CommentInsertForm::new(person.id, post.id, "tolerated".to_owned())
+    };
+    let tolerated = Comment::create(pool, &form, None).await?;
+
+    for (comment_id, score) in [(disliked.id, -3), (tolerated.id, -2)] {
+      update(comment::table.find(comment_id))
+        .set(comment::score.eq(score))
+        .execute(&mut get_conn(pool).await?)
+        .await?;
+    }
+
+    undistinguish_disliked_comments(&context).await?;
+
+    // The score is below the threshold, so the comment loses its distinction
+    let disliked = Comment::read(pool, disliked.id).await?;
+    assert!(!disliked.distinguished);
+    let modlog_entries = modlog::table
+      .filter(modlog::kind.eq(ModlogKind::ModDistinguishComment))
+      .filter(modlog::target_comment_id.eq(disliked.id))
+      .select(modlog::is_revert)
+      .get_results::<bool>(&mut get_conn(pool).await?)
+      .await?;
+    assert_eq!(vec![true], modlog_entries);
+
+    // Exactly at the threshold is still fine
+    let tolerated = Comment::read(pool, tolerated.id).await?;
+    assert!(tolerated.distinguished);
+
+    Community::delete(pool, community.id).await?;
+    Person::delete(pool, person.id).await?;
+    data.delete(pool).await?;
+    Ok(())
+  }
 }
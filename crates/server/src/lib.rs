@@ -192,7 +192,8 @@ pub async fn start_lemmy_server(args: CmdArgs) -> LemmyResult<()> {
   // Set up the rate limiter
   let rate_limit_config =
     local_site_rate_limit_to_rate_limit_config(&site_view.local_site_rate_limit);
-  let rate_limit_cell = RateLimit::new(rate_limit_config);
+  let rate_limit_cell =
+    RateLimit::new(rate_limit_config, SETTINGS.rate_limit_redis_url.as_deref())?;
 
   println!(
     "Starting HTTP server at {}:{}",
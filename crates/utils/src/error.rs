@@ -50,6 +50,8 @@ pub enum LemmyErrorType {
   HoneypotFailed,
   RegistrationApplicationIsPending,
   Locked,
+  /// The parent comment, or one of its ancestors, is locked
+  CommentLocked,
   MaxCommentDepthReached,
   NoCommentEditAllowed,
   OnlyAdminsCanCreateCommunities,
@@ -131,6 +133,49 @@ pub enum LemmyErrorType {
   CannotCombineCommunityIdAndMultiCommunityId,
   MultiCommunityEntryLimitReached,
   TooManyRequests,
+  /// Settings can only be imported into communities without any posts
+  CommunityNotEmpty,
+  UnsupportedBackupVersion,
+  /// The emoji isn't in the list of allowed comment reactions
+  ReactionNotAllowed,
+  InvalidMaxCommentDepth,
+  PostRemoved,
+  /// The grace period for restoring a deleted comment is over
+  CommentDeleteFinalized,
+  /// The post is older than the community allows for new comments
+  PostAutoLocked,
+  /// The community is read-only, only mods can add content
+  CommunityIsReadOnly,
+  /// The time for publishing a scheduled comment has already passed
+  CommentScheduleTimeMustBeInFuture,
+  /// Comments can only be scheduled up to a limited time ahead
+  CommentScheduleTimeTooFarInFuture,
+  /// There are too many scheduled comments which aren't published yet
+  TooManyScheduledComments,
+  /// The comment isn't scheduled anymore, so it can't be cancelled
+  CommentAlreadyPublished,
+  /// A comment can't be moved below one of its own replies
+  CannotMoveCommentBelowItself,
+  /// The new parent comment must be on the post which the comment is moved to
+  CommentParentNotOnPost,
+  /// Only members approved by a mod can comment in this community
+  NotACommunityMember,
+  /// The comment is shorter than the community's minimum comment length
+  CommentTooShort,
+  /// The content links to a domain which isn't on the community's list of allowed domains
+  LinkDomainNotAllowed,
+  /// The community doesn't allow downvotes on comments
+  DownvotesDisabled,
+  /// The community doesn't allow editing comments which already have replies
+  CommentEditLockedAfterReply,
+  /// New comments from this account need a solved challenge, see `SolveCommentChallenge`
+  CommentChallengeRequired,
+  /// Thresholds and time limits of a community can't be negative
+  NegativeValue,
+  /// The window for listing active users must be positive and at most 90 days
+  InvalidActiveUsersWindow,
+  /// Only comments by mods or admins can be pinned in a community
+  OnlyModCommentsCanBePinned,
 }
 
 /// These errors are only used for federation or internally and dont need to be translated.
@@ -49,6 +49,11 @@ pub const CACHE_DURATION_LARGEST_COMMUNITY: Duration = DAY;
 
 pub const MAX_COMMENT_DEPTH_LIMIT: usize = 50;
 
+/// The most discussion languages a community can have, unless all languages are allowed.
+pub const COMMUNITY_INLINE_LANGUAGES_LIMIT: usize = 50;
+/// The most moderators which are returned inline with a community, the longest serving ones first.
+pub const COMMUNITY_INLINE_MODERATORS_LIMIT: i64 = 50;
+
 /// Doing DB transactions of bigger batches than this tend to cause seq scans.
 pub const DB_BATCH_SIZE: i64 = 1000;
 
@@ -32,7 +32,20 @@ struct Value {
 }
 
 impl LemmyBackend {
+  #[cfg(test)]
   pub(crate) fn new(configs: EnumMap<ActionType, BucketConfig>, enable_gc: bool) -> Self {
+    Self::with_configs(Arc::new(RwLock::new(configs)), enable_gc)
+  }
+
+  /// Creates a backend with its own storage and garbage collector, which uses the same configs.
+  pub(crate) fn isolated(&self) -> Self {
+    Self::with_configs(self.configs.clone(), self.gc_handle.is_some())
+  }
+
+  pub(crate) fn with_configs(
+    configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+    enable_gc: bool,
+  ) -> Self {
     let map = Arc::new(DashMap::<LemmyInput, Value>::new());
     let gc_handle = enable_gc.then(|| {
       Arc::new(LemmyBackend::garbage_collector(
@@ -43,7 +56,7 @@ impl LemmyBackend {
     LemmyBackend {
       map,
       gc_handle,
-      configs: Arc::new(RwLock::new(configs)),
+      configs,
     }
   }
 
@@ -79,6 +92,15 @@ impl Backend<LemmyInput> for LemmyBackend {
     let interval = Duration::from_secs(config.interval.into());
 
     let now = Instant::now();
+    // Exempt requests are always allowed and don't count towards the limit
+    if input.2 {
+      let output = SimpleOutput {
+        limit: max_requests,
+        remaining: max_requests,
+        reset: now,
+      };
+      return Ok((Decision::from_allowed(true), output, input));
+    }
     let mut count = 1;
     let mut expiry = now
       .checked_add(interval)
@@ -104,6 +126,8 @@ impl Backend<LemmyInput> for LemmyBackend {
         count,
       });
     let allow = count <= max_requests;
+    // In a fixed window the bucket refills all at once when it expires, so the expiry is also
+    // what the middleware sends as `Retry-After` for denied requests.
     let output = SimpleOutput {
       limit: max_requests,
       remaining: max_requests.saturating_sub(count),
@@ -122,7 +146,10 @@ impl Backend<LemmyInput> for LemmyBackend {
 
 impl Drop for LemmyBackend {
   fn drop(&mut self) {
-    if let Some(handle) = &self.gc_handle {
+    // Backends are cloned for each action type, only stop the garbage collector with the last one
+    if let Some(handle) = &self.gc_handle
+      && Arc::strong_count(handle) == 1
+    {
       handle.abort();
     }
   }
@@ -132,9 +159,10 @@ impl Drop for LemmyBackend {
 mod tests {
   use super::*;
   use crate::{
-    error::LemmyResult,
-    rate_limit::{ActionType, input::raw_ip_key},
+    error::{LemmyErrorType, LemmyResult},
+    rate_limit::{ActionType, BackendKind, RateLimit, input::raw_ip_key},
   };
+  use actix_extensible_rate_limit::HeaderCompatibleOutput;
   use enum_map::enum_map;
 
   const MINUTE_SECS: u32 = 60;
@@ -170,6 +198,10 @@ mod tests {
           max_requests: 0,
           interval: 0,
         },
+        ActionType::CommunitySettings => BucketConfig {
+          max_requests: 0,
+          interval: 0,
+        },
     }
   }
 
@@ -178,7 +210,7 @@ mod tests {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.2"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, false);
     for _ in 0..5 {
       // First 5 should be allowed
       let (allow, _, _) = backend.request(input).await?;
@@ -194,7 +226,7 @@ mod tests {
   async fn test_reset() -> LemmyResult<()> {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), false);
-    let input = LemmyInput(raw_ip_key(Some("127.0.0.3")), ActionType::Message);
+    let input = LemmyInput(raw_ip_key(Some("127.0.0.3")), ActionType::Message, false);
     // Make first request, should be allowed
     let (decision, _, _) = backend.request(input).await?;
     assert!(decision.is_allowed());
@@ -214,8 +246,8 @@ mod tests {
   async fn test_garbage_collection() -> LemmyResult<()> {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), true);
-    let key1 = LemmyInput(raw_ip_key(Some("127.0.0.4")), ActionType::Message);
-    let key2 = LemmyInput(raw_ip_key(Some("127.0.0.5")), ActionType::Post);
+    let key1 = LemmyInput(raw_ip_key(Some("127.0.0.4")), ActionType::Message, false);
+    let key2 = LemmyInput(raw_ip_key(Some("127.0.0.5")), ActionType::Post, false);
     backend.request(key1).await?;
     backend.request(key2).await?;
     assert!(backend.map.contains_key(&key1));
@@ -233,7 +265,7 @@ mod tests {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 2), true);
     let key = raw_ip_key(Some("127.0.0.6"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, false);
     // First of 2 should be allowed.
     let (decision, output, _) = backend.request(input).await?;
     assert!(decision.is_allowed());
@@ -255,12 +287,34 @@ mod tests {
     Ok(())
   }
 
+  #[actix_web::test]
+  async fn test_retry_after() -> LemmyResult<()> {
+    tokio::time::pause();
+    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 1), false);
+    let input = LemmyInput(raw_ip_key(Some("127.0.0.8")), ActionType::Message, false);
+    let (decision, _, _) = backend.request(input).await?;
+    assert!(decision.is_allowed());
+    let (decision, output, _) = backend.request(input).await?;
+    assert!(decision.is_denied());
+    assert_eq!(60, output.seconds_until_reset());
+    // Retrying while still denied doesn't extend the window
+    tokio::time::advance(Duration::from_secs(20)).await;
+    let (decision, output, _) = backend.request(input).await?;
+    assert!(decision.is_denied());
+    assert_eq!(40, output.seconds_until_reset());
+    tokio::time::advance(Duration::from_millis(39_500)).await;
+    let (decision, output, _) = backend.request(input).await?;
+    assert!(decision.is_denied());
+    assert_eq!(1, output.seconds_until_reset());
+    Ok(())
+  }
+
   #[actix_web::test]
   async fn test_rollback() -> LemmyResult<()> {
     tokio::time::pause();
     let backend = LemmyBackend::new(test_config(MINUTE_SECS, 5), true);
     let key = raw_ip_key(Some("127.0.0.7"));
-    let input = LemmyInput(key, ActionType::Message);
+    let input = LemmyInput(key, ActionType::Message, false);
     let (_, output, rollback) = backend.request(input).await?;
     assert_eq!(output.remaining, 4);
     backend.rollback(rollback).await?;
@@ -269,4 +323,93 @@ mod tests {
     assert_eq!(output.remaining, 4);
     Ok(())
   }
+
+  #[actix_web::test]
+  async fn test_exempt() -> LemmyResult<()> {
+    tokio::time::pause();
+    let backend = LemmyBackend::new(test_config(MINUTE_SECS, 2), false);
+    let key = raw_ip_key(Some("127.0.0.10"));
+    let regular = LemmyInput(key, ActionType::Message, false);
+    let exempt = LemmyInput(key, ActionType::Message, true);
+
+    // Bulk moderation actions from the same address are never throttled
+    for _ in 0..10 {
+      let (decision, output, _) = backend.request(exempt).await?;
+      assert!(decision.is_allowed());
+      assert_eq!(output.remaining, 2);
+    }
+    assert!(!backend.map.contains_key(&exempt));
+
+    // Other requests are still limited
+    for _ in 0..2 {
+      let (decision, _, _) = backend.request(regular).await?;
+      assert!(decision.is_allowed());
+    }
+    let (decision, _, _) = backend.request(regular).await?;
+    assert!(decision.is_denied());
+    let (decision, _, _) = backend.request(exempt).await?;
+    assert!(decision.is_allowed());
+    Ok(())
+  }
+
+  #[actix_web::test]
+  async fn test_backend_kinds() -> LemmyResult<()> {
+    tokio::time::pause();
+    let rate_limit = RateLimit::with_backends(
+      test_config(MINUTE_SECS, 1),
+      enum_map! {
+        ActionType::Post => BackendKind::Isolated,
+        _ => BackendKind::Shared,
+      },
+      None,
+    )?;
+    let message = LemmyInput(raw_ip_key(Some("127.0.0.9")), ActionType::Message, false);
+    let post = LemmyInput(raw_ip_key(Some("127.0.0.9")), ActionType::Post, false);
+    rate_limit
+      .backend(ActionType::Message)
+      .request(message)
+      .await?;
+    rate_limit.backend(ActionType::Post).request(post).await?;
+
+    // Only the isolated backend stores requests for posts
+    let shared = rate_limit.backends[ActionType::Comment]
+      .memory()
+      .ok_or(LemmyErrorType::NotFound)?;
+    let isolated = rate_limit.backends[ActionType::Post]
+      .memory()
+      .ok_or(LemmyErrorType::NotFound)?;
+    assert!(shared.map.contains_key(&message));
+    assert!(!shared.map.contains_key(&post));
+    assert!(isolated.map.contains_key(&post));
+    assert!(!isolated.map.contains_key(&message));
+
+    // Config changes apply to both
+    rate_limit.set_config(test_config(MINUTE_SECS, 3));
+    let (_, output, _) = shared.request(message).await?;
+    assert_eq!(output.limit, 3);
+    #[expect(clippy::expect_used)]
+    let config = isolated.configs.read().expect("read rwlock")[ActionType::Message];
+    assert_eq!(config.max_requests, 3);
+    Ok(())
+  }
+
+  #[actix_web::test]
+  async fn test_action_types_independent() -> LemmyResult<()> {
+    tokio::time::pause();
+    let configs = EnumMap::from_fn(|_| BucketConfig {
+      max_requests: 1,
+      interval: MINUTE_SECS,
+    });
+    let rate_limit = RateLimit::new(configs, None)?;
+    let ip = raw_ip_key(Some("127.0.0.10"));
+
+    // Use up the limit of each action type in turn, the others are still allowed
+    for (action_type, _) in configs {
+      let input = LemmyInput(ip, action_type, false);
+      let backend = rate_limit.backend(action_type);
+      assert!(backend.request(input).await?.0.is_allowed());
+      assert!(backend.request(input).await?.0.is_denied());
+    }
+    Ok(())
+  }
 }
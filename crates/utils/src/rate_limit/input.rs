@@ -5,8 +5,13 @@ use std::{
   str::FromStr,
 };
 
+/// The last field is true for requests which are exempt from rate limiting.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub struct LemmyInput(pub(crate) RateLimitIpAddr, pub(crate) ActionType);
+pub struct LemmyInput(
+  pub(crate) RateLimitIpAddr,
+  pub(crate) ActionType,
+  pub(crate) bool,
+);
 
 pub(crate) type LemmyInputFuture = Ready<Result<LemmyInput, actix_web::Error>>;
 
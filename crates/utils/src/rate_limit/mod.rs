@@ -1,15 +1,32 @@
-use crate::rate_limit::{
-  backend::LemmyBackend,
-  input::{LemmyInput, LemmyInputFuture, raw_ip_key},
+#[cfg(feature = "redis")]
+use crate::rate_limit::redis_backend::LemmyRedisBackend;
+use crate::{
+  error::LemmyResult,
+  rate_limit::{
+    backend::LemmyBackend,
+    input::{LemmyInput, LemmyInputFuture, raw_ip_key},
+  },
 };
-use actix_extensible_rate_limit::{RateLimiter, backend::SimpleOutput};
-use actix_web::dev::ServiceRequest;
+use actix_extensible_rate_limit::{
+  RateLimiter,
+  backend::{Backend, Decision, SimpleOutput},
+};
+use actix_web::{HttpMessage, ResponseError, dev::ServiceRequest};
 use enum_map::{EnumMap, enum_map};
-use std::future::ready;
+use std::{
+  convert::Infallible,
+  fmt,
+  future::ready,
+  sync::{Arc, RwLock},
+};
 use strum::{AsRefStr, Display};
+#[cfg(feature = "redis")]
+use tracing::warn;
 
 mod backend;
 mod input;
+#[cfg(feature = "redis")]
+mod redis_backend;
 
 #[derive(Debug, enum_map::Enum, Copy, Clone, Display, AsRefStr, Eq, PartialEq, Hash)]
 pub enum ActionType {
@@ -20,72 +37,251 @@ pub enum ActionType {
   Comment,
   Search,
   ImportUserSettings,
+  /// Community settings export and import. Counted separately from [ActionType::ImportUserSettings]
+  /// so that moderating a community doesn't lock users out of their own settings backup.
+  CommunitySettings,
 }
 
+/// Request extension which exempts the request from rate limiting. Inserted for moderation
+/// actions by admins and moderators, so that they can clean up spam without getting throttled.
+#[derive(Debug, Copy, Clone)]
+pub struct RateLimitExempt;
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct BucketConfig {
   pub max_requests: u32,
   pub interval: u32,
 }
 
+/// Which backend counts the requests for an action type. Selected for each action type at
+/// startup.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum BackendKind {
+  /// Use the in-memory backend which is shared by all action types.
+  #[default]
+  Shared,
+  /// Use a separate in-memory backend with its own storage, so that busy action types don't slow
+  /// down the others. Bucket configs are still shared.
+  Isolated,
+  /// Count the requests in Redis, so that the limits are shared by all Lemmy processes. Needs a
+  /// Redis connection string.
+  #[cfg(feature = "redis")]
+  Redis,
+}
+
+/// Error from a rate limit backend, eg when Redis is unreachable.
+#[derive(Debug)]
+pub struct RateLimitBackendError(String);
+
+impl fmt::Display for RateLimitBackendError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Rate limit backend failed: {}", self.0)
+  }
+}
+
+impl std::error::Error for RateLimitBackendError {}
+
+impl ResponseError for RateLimitBackendError {}
+
+impl From<Infallible> for RateLimitBackendError {
+  fn from(e: Infallible) -> Self {
+    match e {}
+  }
+}
+
+#[cfg(feature = "redis")]
+impl From<redis::RedisError> for RateLimitBackendError {
+  fn from(e: redis::RedisError) -> Self {
+    Self(e.to_string())
+  }
+}
+
+#[cfg(feature = "redis")]
+impl From<tokio::time::error::Elapsed> for RateLimitBackendError {
+  fn from(e: tokio::time::error::Elapsed) -> Self {
+    Self(format!("Redis {e}"))
+  }
+}
+
+/// Where the requests of an action type are counted.
+#[derive(Clone)]
+pub enum RateLimitBackend {
+  Memory(LemmyBackend),
+  /// Falls back to the in-memory backend while Redis is unreachable, so that an outage doesn't
+  /// take down the whole API. Limits are only enforced per process in this case.
+  #[cfg(feature = "redis")]
+  Redis(LemmyRedisBackend, LemmyBackend),
+}
+
+impl RateLimitBackend {
+  #[cfg(test)]
+  fn memory(&self) -> Option<&LemmyBackend> {
+    match self {
+      Self::Memory(backend) => Some(backend),
+      #[cfg(feature = "redis")]
+      Self::Redis(..) => None,
+    }
+  }
+}
+
+impl Backend<LemmyInput> for RateLimitBackend {
+  type Output = SimpleOutput;
+  type RollbackToken = LemmyInput;
+  type Error = RateLimitBackendError;
+
+  async fn request(
+    &self,
+    input: LemmyInput,
+  ) -> Result<(Decision, Self::Output, Self::RollbackToken), Self::Error> {
+    match self {
+      Self::Memory(backend) => Ok(backend.request(input).await?),
+      #[cfg(feature = "redis")]
+      Self::Redis(backend, fallback) => match backend.request(input).await {
+        Ok(res) => Ok(res),
+        Err(e) => {
+          warn!("{e}, counting requests in memory instead");
+          Ok(fallback.request(input).await?)
+        }
+      },
+    }
+  }
+
+  async fn rollback(&self, token: Self::RollbackToken) -> Result<(), Self::Error> {
+    match self {
+      Self::Memory(backend) => Ok(backend.rollback(token).await?),
+      #[cfg(feature = "redis")]
+      Self::Redis(backend, _) => {
+        if let Err(e) = backend.rollback(token).await {
+          warn!("{e}");
+        }
+        Ok(())
+      }
+    }
+  }
+}
+
 #[derive(Clone)]
 pub struct RateLimit {
-  backend: LemmyBackend,
+  /// Shared by all backends, so that config changes apply to every action type.
+  configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+  backends: EnumMap<ActionType, RateLimitBackend>,
 }
 
 impl RateLimit {
-  pub fn new(configs: EnumMap<ActionType, BucketConfig>) -> Self {
-    Self {
-      backend: LemmyBackend::new(configs, true),
+  /// If a Redis connection string is given, all requests are counted in Redis instead of
+  /// memory, so that the limits are shared by all Lemmy processes. This requires the `redis`
+  /// feature.
+  pub fn new(
+    configs: EnumMap<ActionType, BucketConfig>,
+    redis_url: Option<&str>,
+  ) -> LemmyResult<Self> {
+    #[cfg(feature = "redis")]
+    let kinds = if redis_url.is_some() {
+      EnumMap::from_fn(|_| BackendKind::Redis)
+    } else {
+      default_backend_kinds()
+    };
+    #[cfg(not(feature = "redis"))]
+    let kinds = default_backend_kinds();
+    Self::with_backends(configs, kinds, redis_url)
+  }
+
+  pub fn with_backends(
+    configs: EnumMap<ActionType, BucketConfig>,
+    kinds: EnumMap<ActionType, BackendKind>,
+    redis_url: Option<&str>,
+  ) -> LemmyResult<Self> {
+    let configs = Arc::new(RwLock::new(configs));
+    let shared = LemmyBackend::with_configs(configs.clone(), true);
+    #[cfg(feature = "redis")]
+    let redis = redis_url
+      .map(|redis_url| LemmyRedisBackend::new(redis_url, configs.clone()))
+      .transpose()?;
+    #[cfg(not(feature = "redis"))]
+    if let Some(redis_url) = redis_url {
+      Err(anyhow::anyhow!(
+        "Using Redis at {redis_url} for rate limits requires the redis feature"
+      ))?
     }
+
+    let mut backends = EnumMap::from_fn(|_| RateLimitBackend::Memory(shared.clone()));
+    for (action_type, kind) in kinds {
+      backends[action_type] = match kind {
+        BackendKind::Shared => RateLimitBackend::Memory(shared.clone()),
+        BackendKind::Isolated => RateLimitBackend::Memory(shared.isolated()),
+        #[cfg(feature = "redis")]
+        BackendKind::Redis => RateLimitBackend::Redis(
+          redis.clone().ok_or_else(|| {
+            anyhow::anyhow!("Rate limit backend for {action_type} needs a Redis connection string")
+          })?,
+          shared.clone(),
+        ),
+      };
+    }
+    Ok(Self { configs, backends })
   }
 
-  pub fn with_debug_config() -> Self {
-    Self::new(enum_map! {
-      ActionType::Message => BucketConfig {
-        max_requests: 180,
-        interval: 60,
-      },
-      ActionType::Post => BucketConfig {
-        max_requests: 6,
-        interval: 300,
+  pub fn with_debug_config() -> LemmyResult<Self> {
+    Self::with_backends(
+      enum_map! {
+        ActionType::Message => BucketConfig {
+          max_requests: 180,
+          interval: 60,
+        },
+        ActionType::Post => BucketConfig {
+          max_requests: 6,
+          interval: 300,
+        },
+        ActionType::Register => BucketConfig {
+          max_requests: 3,
+          interval: 3600,
+        },
+        ActionType::Image => BucketConfig {
+          max_requests: 6,
+          interval: 3600,
+        },
+        ActionType::Comment => BucketConfig {
+          max_requests: 6,
+          interval: 600,
+        },
+        ActionType::Search => BucketConfig {
+          max_requests: 60,
+          interval: 600,
+        },
+        ActionType::ImportUserSettings => BucketConfig {
+          max_requests: 1,
+          interval: 24 * 60 * 60,
+        },
+        ActionType::CommunitySettings => BucketConfig {
+          max_requests: 1,
+          interval: 24 * 60 * 60,
+        },
       },
-      ActionType::Register => BucketConfig {
-        max_requests: 3,
-        interval: 3600,
-      },
-      ActionType::Image => BucketConfig {
-        max_requests: 6,
-        interval: 3600,
-      },
-      ActionType::Comment => BucketConfig {
-        max_requests: 6,
-        interval: 600,
-      },
-      ActionType::Search => BucketConfig {
-        max_requests: 60,
-        interval: 600,
-      },
-      ActionType::ImportUserSettings => BucketConfig {
-        max_requests: 1,
-        interval: 24 * 60 * 60,
-      },
-    })
+      default_backend_kinds(),
+      None,
+    )
   }
 
   #[allow(clippy::expect_used)]
   pub fn set_config(&self, configs: EnumMap<ActionType, BucketConfig>) {
-    *self.backend.configs.write().expect("write rwlock") = configs;
+    *self.configs.write().expect("write rwlock") = configs;
+  }
+
+  fn backend(&self, action_type: ActionType) -> RateLimitBackend {
+    self.backends[action_type].clone()
   }
 
   fn build_rate_limiter(
     &self,
     action_type: ActionType,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     let input = new_input(action_type);
 
-    RateLimiter::builder(self.backend.clone(), input)
+    RateLimiter::builder(self.backend(action_type), input)
       .add_headers()
       // rollback rate limit on any error 500
       .rollback_server_errors()
@@ -94,47 +290,85 @@ impl RateLimit {
 
   pub fn message(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Message)
   }
 
   pub fn search(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Search)
   }
   pub fn register(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Register)
   }
   pub fn post(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Post)
   }
   pub fn image(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Image)
   }
   pub fn comment(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::Comment)
   }
   pub fn import_user_settings(
     &self,
-  ) -> RateLimiter<LemmyBackend, SimpleOutput, impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static>
-  {
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
     self.build_rate_limiter(ActionType::ImportUserSettings)
   }
+  pub fn community_settings(
+    &self,
+  ) -> RateLimiter<
+    RateLimitBackend,
+    SimpleOutput,
+    impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static,
+  > {
+    self.build_rate_limiter(ActionType::CommunitySettings)
+  }
+}
+
+/// Image uploads are slow and bursty, so they get their own backend.
+fn default_backend_kinds() -> EnumMap<ActionType, BackendKind> {
+  enum_map! {
+    ActionType::Image => BackendKind::Isolated,
+    _ => BackendKind::Shared,
+  }
 }
 
 fn new_input(action_type: ActionType) -> impl Fn(&ServiceRequest) -> LemmyInputFuture + 'static {
@@ -142,8 +376,9 @@ fn new_input(action_type: ActionType) -> impl Fn(&ServiceRequest) -> LemmyInputF
     ready({
       let info = req.connection_info();
       let key = raw_ip_key(info.realip_remote_addr());
+      let exempt = req.extensions().contains::<RateLimitExempt>();
 
-      Ok(LemmyInput(key, action_type))
+      Ok(LemmyInput(key, action_type, exempt))
     })
   }
 }
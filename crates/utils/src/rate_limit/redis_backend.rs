@@ -0,0 +1,250 @@
+use crate::rate_limit::{
+  ActionType,
+  BucketConfig,
+  RateLimitBackendError,
+  input::{LemmyInput, RateLimitIpAddr},
+};
+use actix_extensible_rate_limit::backend::{Backend, Decision, SimpleOutput};
+use actix_web::rt::time::Instant;
+use enum_map::EnumMap;
+use redis::{Client, Script, aio::ConnectionManager};
+use std::{
+  sync::{Arc, LazyLock, RwLock},
+  time::Duration,
+};
+use tokio::{sync::OnceCell, time::timeout};
+
+/// How long to wait for Redis before falling back to counting in memory.
+const REDIS_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Increments the count of the bucket, and starts its window if it is new. Returns the count and
+/// the seconds until the window ends. Runs atomically, so concurrent requests from different
+/// Lemmy processes are counted correctly.
+static REQUEST_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+  Script::new(
+    r"
+local count = redis.call('INCR', KEYS[1])
+if count == 1 then
+  redis.call('EXPIRE', KEYS[1], ARGV[1])
+end
+return {count, redis.call('TTL', KEYS[1])}
+",
+  )
+});
+
+/// Decrements the count of the bucket, unless its window ended in the meantime.
+static ROLLBACK_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+  Script::new(
+    r"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+  return redis.call('DECR', KEYS[1])
+end
+return 0
+",
+  )
+});
+
+/// A Fixed Window rate limiter [Backend] which stores the counts in Redis, so that all Lemmy
+/// processes using the same Redis share their limits. Works the same as
+/// [LemmyBackend](super::backend::LemmyBackend) otherwise.
+#[derive(Clone)]
+pub struct LemmyRedisBackend {
+  client: Client,
+  /// Connected on the first request, and reconnects automatically afterwards.
+  connection: Arc<OnceCell<ConnectionManager>>,
+  pub(super) configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+}
+
+impl LemmyRedisBackend {
+  pub(crate) fn new(
+    redis_url: &str,
+    configs: Arc<RwLock<EnumMap<ActionType, BucketConfig>>>,
+  ) -> Result<Self, RateLimitBackendError> {
+    Ok(Self {
+      client: Client::open(redis_url)?,
+      connection: Arc::new(OnceCell::new()),
+      configs,
+    })
+  }
+
+  async fn connection(&self) -> Result<ConnectionManager, RateLimitBackendError> {
+    let connection = self
+      .connection
+      .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+      .await?;
+    Ok(connection.clone())
+  }
+}
+
+/// The Redis key for the bucket of the input. IPv6 addresses are already grouped into subnets.
+fn redis_key(input: &LemmyInput) -> String {
+  let ip = match input.0 {
+    RateLimitIpAddr::V4(addr) => addr.to_string(),
+    RateLimitIpAddr::V6(segments) => segments
+      .iter()
+      .map(|s| format!("{s:x}"))
+      .collect::<Vec<_>>()
+      .join(":"),
+  };
+  format!("lemmy:rate_limit:{}:{ip}", input.1)
+}
+
+impl Backend<LemmyInput> for LemmyRedisBackend {
+  type Output = SimpleOutput;
+  type RollbackToken = LemmyInput;
+  type Error = RateLimitBackendError;
+
+  #[expect(clippy::expect_used)]
+  async fn request(
+    &self,
+    input: LemmyInput,
+  ) -> Result<(Decision, Self::Output, Self::RollbackToken), Self::Error> {
+    let config = self.configs.read().expect("read rwlock")[input.1];
+    let max_requests: u64 = config.max_requests.into();
+
+    let now = Instant::now();
+    // Exempt requests are always allowed and don't count towards the limit
+    if input.2 {
+      let output = SimpleOutput {
+        limit: max_requests,
+        remaining: max_requests,
+        reset: now,
+      };
+      return Ok((Decision::from_allowed(true), output, input));
+    }
+
+    let (count, ttl): (u64, i64) = timeout(REDIS_TIMEOUT, async {
+      REQUEST_SCRIPT
+        .key(redis_key(&input))
+        .arg(config.interval)
+        .invoke_async(&mut self.connection().await?)
+        .await
+        .map_err(RateLimitBackendError::from)
+    })
+    .await??;
+    let ttl = Duration::from_secs(ttl.try_into().unwrap_or_default());
+    let output = SimpleOutput {
+      limit: max_requests,
+      remaining: max_requests.saturating_sub(count),
+      reset: now.checked_add(ttl).unwrap_or(now),
+    };
+    Ok((Decision::from_allowed(count <= max_requests), output, input))
+  }
+
+  async fn rollback(&self, token: Self::RollbackToken) -> Result<(), Self::Error> {
+    timeout(REDIS_TIMEOUT, async {
+      ROLLBACK_SCRIPT
+        .key(redis_key(&token))
+        .invoke_async::<()>(&mut self.connection().await?)
+        .await
+        .map_err(RateLimitBackendError::from)
+    })
+    .await?
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    error::LemmyResult,
+    rate_limit::{RateLimitBackend, backend::LemmyBackend, input::raw_ip_key},
+  };
+  use std::env;
+
+  /// Needs a running Redis, eg `docker run -p 6379:6379 redis`, so the tests using it are ignored
+  /// by default. Run them with `cargo test --features redis -- --ignored`.
+  fn test_backend(max_requests: u32, interval: u32) -> LemmyResult<LemmyRedisBackend> {
+    let redis_url =
+      env::var("LEMMY_REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let configs = EnumMap::from_fn(|_| BucketConfig {
+      max_requests,
+      interval,
+    });
+    Ok(LemmyRedisBackend::new(
+      &redis_url,
+      Arc::new(RwLock::new(configs)),
+    )?)
+  }
+
+  async fn clear(backend: &LemmyRedisBackend, input: &LemmyInput) -> LemmyResult<()> {
+    redis::cmd("DEL")
+      .arg(redis_key(input))
+      .exec_async(&mut backend.connection().await?)
+      .await?;
+    Ok(())
+  }
+
+  #[tokio::test]
+  #[ignore]
+  async fn test_redis_allow_deny() -> LemmyResult<()> {
+    let backend = test_backend(2, 60)?;
+    let input = LemmyInput(raw_ip_key(Some("127.0.1.2")), ActionType::Message, false);
+    clear(&backend, &input).await?;
+
+    for _ in 0..2 {
+      let (decision, _, _) = backend.request(input).await?;
+      assert!(decision.is_allowed());
+    }
+    let (decision, output, _) = backend.request(input).await?;
+    assert!(decision.is_denied());
+    assert_eq!(0, output.remaining);
+
+    // Another Lemmy process sees the same counts
+    let other = test_backend(2, 60)?;
+    let (decision, _, _) = other.request(input).await?;
+    assert!(decision.is_denied());
+
+    // Exempt requests and other action types are unaffected
+    let exempt = LemmyInput(input.0, input.1, true);
+    assert!(backend.request(exempt).await?.0.is_allowed());
+    let post = LemmyInput(input.0, ActionType::Post, false);
+    clear(&backend, &post).await?;
+    assert!(backend.request(post).await?.0.is_allowed());
+
+    clear(&backend, &input).await?;
+    clear(&backend, &post).await
+  }
+
+  #[tokio::test]
+  #[ignore]
+  async fn test_redis_reset_and_rollback() -> LemmyResult<()> {
+    let backend = test_backend(1, 2)?;
+    let input = LemmyInput(raw_ip_key(Some("127.0.1.3")), ActionType::Message, false);
+    clear(&backend, &input).await?;
+
+    let (decision, _, token) = backend.request(input).await?;
+    assert!(decision.is_allowed());
+    assert!(backend.request(input).await?.0.is_denied());
+
+    // Rolling back both requests frees the bucket again
+    backend.rollback(token).await?;
+    backend.rollback(token).await?;
+    assert!(backend.request(input).await?.0.is_allowed());
+    assert!(backend.request(input).await?.0.is_denied());
+
+    // Allowed again once the window is over
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    assert!(backend.request(input).await?.0.is_allowed());
+
+    clear(&backend, &input).await
+  }
+
+  #[tokio::test]
+  async fn test_redis_unreachable_falls_back_to_memory() -> LemmyResult<()> {
+    let configs = Arc::new(RwLock::new(EnumMap::from_fn(|_| BucketConfig {
+      max_requests: 1,
+      interval: 60,
+    })));
+    // Nothing listens on port 1
+    let redis = LemmyRedisBackend::new("redis://127.0.0.1:1", configs.clone())?;
+    let backend = RateLimitBackend::Redis(redis, LemmyBackend::with_configs(configs, false));
+    let input = LemmyInput(raw_ip_key(Some("127.0.1.4")), ActionType::Message, false);
+
+    let (decision, _, token) = backend.request(input).await?;
+    assert!(decision.is_allowed());
+    assert!(backend.request(input).await?.0.is_denied());
+    backend.rollback(token).await?;
+    Ok(())
+  }
+}
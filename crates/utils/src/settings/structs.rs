@@ -52,6 +52,28 @@ pub struct Settings {
   cors_origin: Vec<String>,
   /// Print logs in JSON format. You can also disable ANSI colors in logs with env var `NO_COLOR`.
   pub json_logging: bool,
+  /// Time in seconds during which users can restore comments they deleted. The delete is only
+  /// federated once this is over. Zero deletes comments immediately.
+  #[default(0)]
+  pub comment_delete_grace_seconds: u32,
+  /// Time in seconds during which new comments are only visible to their creator before they are
+  /// federated. Comments deleted within this time are never federated. Zero federates comments
+  /// immediately.
+  #[default(0)]
+  pub comment_federation_delay_seconds: u32,
+  /// Emojis which users can react to comments with.
+  #[default(vec!["👍".to_string(), "❤️".to_string(), "😂".to_string(), "🎉".to_string(), "😮".to_string(), "😢".to_string()])]
+  #[doku(example = "👍")]
+  pub allowed_comment_reactions: Vec<String>,
+  /// Maximum number of comments which are reported at once when reporting a comment together
+  /// with its replies.
+  #[default(50)]
+  pub max_subtree_reports: u32,
+  /// Redis connection string for storing rate limits, so that they are shared when running
+  /// multiple Lemmy processes. Requires compiling with the `redis` feature. Rate limits are
+  /// stored in memory if this isn't set, or while Redis is unreachable.
+  #[doku(example = "redis://localhost:6379")]
+  pub rate_limit_redis_url: Option<String>,
   /// Data for loading Lemmy plugins
   pub plugins: Vec<PluginSettings>,
 }
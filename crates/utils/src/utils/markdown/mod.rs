@@ -1,7 +1,9 @@
 use crate::error::{LemmyErrorType, LemmyResult};
+use image_links::{markdown_find_links, markdown_handle_title};
 use markdown_it::MarkdownIt;
-use regex::RegexSet;
+use regex::{Regex, RegexSet};
 use std::sync::LazyLock;
+use url::{ParseError, Url};
 
 mod identifier_rule;
 pub mod image_links;
@@ -33,6 +35,50 @@ pub fn markdown_check_for_blocked_urls(text: &str, blocklist: &RegexSet) -> Lemm
   Ok(())
 }
 
+/// Checks that all links in the text go to one of the allowed domains, or a subdomain of them. If
+/// no domains are given, all links are allowed. Relative links stay on this instance and are
+/// always allowed.
+pub fn markdown_check_for_allowed_domains(
+  text: &str,
+  allowed_domains: &[String],
+) -> LemmyResult<()> {
+  #[allow(clippy::expect_used)]
+  static LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)https?://[^\s<>()\[\]"']+"#).expect("compile regex"));
+
+  if allowed_domains.is_empty() {
+    return Ok(());
+  }
+  // Markdown link destinations don't need a scheme, eg `[text](//example.com)`, so they are read
+  // from the syntax tree in addition to the plain links.
+  let markdown_links = markdown_find_links(text)
+    .into_iter()
+    .map(|(start, end)| markdown_handle_title(text, start, end).0);
+  let plain_links = LINK_REGEX.find_iter(text).map(|l| l.as_str());
+  for link in markdown_links.chain(plain_links) {
+    let url = match Url::parse(link) {
+      Err(ParseError::RelativeUrlWithoutBase) if link.starts_with("//") => {
+        Url::parse(&format!("https:{link}"))
+      }
+      Err(ParseError::RelativeUrlWithoutBase) => continue,
+      url => url,
+    };
+    let allowed = url.ok().is_some_and(|u| {
+      // Links without a host such as `mailto:` don't lead to another site
+      u.host().is_none()
+        || u.domain().map(str::to_lowercase).is_some_and(|d| {
+          allowed_domains
+            .iter()
+            .any(|a| d == *a || d.ends_with(&format!(".{a}")))
+        })
+    });
+    if !allowed {
+      Err(LemmyErrorType::LinkDomainNotAllowed)?
+    }
+  }
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -41,6 +87,40 @@ mod tests {
   use pretty_assertions::assert_eq;
   use regex::escape;
 
+  #[test]
+  fn test_allowed_domains() -> LemmyResult<()> {
+    let allowed = vec!["nature.com".to_string(), "arxiv.org".to_string()];
+
+    markdown_check_for_allowed_domains("no links at all", &allowed)?;
+    markdown_check_for_allowed_domains("see [this](https://www.nature.com/articles/1)", &allowed)?;
+    markdown_check_for_allowed_domains("preprint: https://arxiv.org/abs/2101.1", &allowed)?;
+
+    let res = markdown_check_for_allowed_domains(
+      "see https://arxiv.org/abs/1 and [this](https://example.com/nature.com)",
+      &allowed,
+    );
+    assert_eq!(
+      Some(LemmyErrorType::LinkDomainNotAllowed),
+      res.err().map(|e| e.error_type)
+    );
+    let res = markdown_check_for_allowed_domains("https://notnature.com", &allowed);
+    assert!(res.is_err());
+
+    // Uppercase schemes and links without a scheme are checked as well
+    let res = markdown_check_for_allowed_domains("HTTPS://EXAMPLE.COM/page", &allowed);
+    assert!(res.is_err());
+    let res = markdown_check_for_allowed_domains("[this](//example.com/page)", &allowed);
+    assert!(res.is_err());
+    markdown_check_for_allowed_domains("[this](//nature.com/articles/1)", &allowed)?;
+
+    // Relative links stay on this instance
+    markdown_check_for_allowed_domains("see [the community](/c/science)", &allowed)?;
+
+    // Without allowed domains, everything goes
+    markdown_check_for_allowed_domains("https://example.com", &[])?;
+    Ok(())
+  }
+
   #[test]
   fn test_basic_markdown() {
     let tests: Vec<_> = vec![
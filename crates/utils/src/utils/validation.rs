@@ -114,6 +114,17 @@ pub fn is_valid_body_field(body: &str, post: bool) -> LemmyResult<()> {
   Ok(())
 }
 
+/// Checks a comment against the community's minimum length. Counts graphemes, so that eg an emoji
+/// made of several code points is a single character.
+pub fn is_valid_comment_min_length(content: &str, min_length: Option<i32>) -> LemmyResult<()> {
+  let min_length = usize::try_from(min_length.unwrap_or_default()).unwrap_or_default();
+  if content.trim().graphemes(true).count() < min_length {
+    Err(LemmyErrorType::CommentTooShort.into())
+  } else {
+    Ok(())
+  }
+}
+
 pub fn is_valid_bio_field(bio: &str) -> LemmyResult<()> {
   max_length_check(bio, BIO_MAX_LENGTH, LemmyErrorType::BioLengthOverflow)
 }
@@ -254,6 +265,22 @@ pub fn check_urls_are_valid(urls: &Vec<String>) -> LemmyResult<Vec<String>> {
   Ok(unique_urls)
 }
 
+/// Normalizes the allowed link domains of a community, and checks that they are plain domains.
+pub fn check_allowed_domains_are_valid(domains: &[String]) -> LemmyResult<Vec<String>> {
+  check_api_elements_count(domains.len())?;
+  let mut res = vec![];
+  for domain in domains {
+    let domain = domain.trim().to_lowercase();
+    let url =
+      Url::parse(&format!("https://{domain}/")).with_lemmy_type(LemmyErrorType::InvalidUrl)?;
+    if url.domain() != Some(&domain) || url.as_str() != format!("https://{domain}/") {
+      Err(LemmyErrorType::InvalidUrl)?
+    }
+    res.push(domain);
+  }
+  Ok(res.into_iter().unique().collect())
+}
+
 pub fn check_blocking_keywords_are_valid(blocking_keywords: &Vec<String>) -> LemmyResult<()> {
   for keyword in blocking_keywords {
     min_length_check(
@@ -360,17 +387,41 @@ pub fn check_api_elements_count(len: usize) -> LemmyResult<()> {
   }
   Ok(())
 }
+
+/// Checks that a threshold or time limit setting isn't negative.
+pub fn check_not_negative(value: Option<i32>) -> LemmyResult<()> {
+  if value.is_some_and(|v| v < 0) {
+    Err(LemmyErrorType::NegativeValue)?
+  }
+  Ok(())
+}
+
+/// The longest time window for which active users of a community can be listed.
+const ACTIVE_USERS_MAX_WINDOW_SECONDS: i32 = 90 * 24 * 60 * 60;
+
+/// Checks the time window for listing active users, so the counts stay cheap to compute.
+pub fn check_active_users_window(window_seconds: i32) -> LemmyResult<()> {
+  if !(1..=ACTIVE_USERS_MAX_WINDOW_SECONDS).contains(&window_seconds) {
+    Err(LemmyErrorType::InvalidActiveUsersWindow)?
+  }
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
   use crate::{
     error::{LemmyErrorType, LemmyResult},
     utils::validation::{
+      ACTIVE_USERS_MAX_WINDOW_SECONDS,
       BIO_MAX_LENGTH,
       SITE_DESCRIPTION_MAX_LENGTH,
       SITE_NAME_MAX_LENGTH,
       URL_MAX_LENGTH,
       build_and_check_regex,
+      check_active_users_window,
+      check_allowed_domains_are_valid,
+      check_not_negative,
       check_urls_are_valid,
       clean_url,
       clean_urls_in_text,
@@ -378,6 +429,7 @@ mod tests {
       is_url_blocked,
       is_valid_actor_name,
       is_valid_bio_field,
+      is_valid_comment_min_length,
       is_valid_display_name,
       is_valid_matrix_id,
       is_valid_post_title,
@@ -554,6 +606,21 @@ Line3",
     );
   }
 
+  #[test]
+  fn test_valid_comment_min_length() {
+    assert!(is_valid_comment_min_length("lol", None).is_ok());
+    assert!(is_valid_comment_min_length("12345", Some(5)).is_ok());
+    assert!(is_valid_comment_min_length("123456", Some(5)).is_ok());
+
+    let too_short = is_valid_comment_min_length("1234", Some(5));
+    assert!(too_short.is_err_and(|e| e.error_type.eq(&LemmyErrorType::CommentTooShort)));
+
+    // Surrounding whitespace doesn't count, and each emoji is a single character
+    assert!(is_valid_comment_min_length("  1234  ", Some(5)).is_err());
+    assert!(is_valid_comment_min_length("👨‍👩‍👧‍👦👨‍👩‍👧‍👦", Some(3)).is_err());
+    assert!(is_valid_comment_min_length("👨‍👩‍👧‍👦👨‍👩‍👧‍👦", Some(2)).is_ok());
+  }
+
   #[test]
   fn test_valid_site_description() {
     assert!(
@@ -687,6 +754,40 @@ Line3",
     Ok(())
   }
 
+  #[test]
+  fn test_allowed_domains_valid() -> LemmyResult<()> {
+    assert_eq!(
+      vec!["nature.com".to_string(), "arxiv.org".to_string()],
+      check_allowed_domains_are_valid(&[
+        " Nature.com".to_string(),
+        "arxiv.org".to_string(),
+        "nature.com".to_string(),
+      ])?
+    );
+
+    assert!(check_allowed_domains_are_valid(&["https://nature.com".to_string()]).is_err());
+    assert!(check_allowed_domains_are_valid(&["nature.com/articles".to_string()]).is_err());
+    assert!(check_allowed_domains_are_valid(&["nature .com".to_string()]).is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_check_not_negative() {
+    assert!(check_not_negative(None).is_ok());
+    assert!(check_not_negative(Some(0)).is_ok());
+    assert!(check_not_negative(Some(7)).is_ok());
+    assert!(check_not_negative(Some(-1)).is_err());
+  }
+
+  #[test]
+  fn test_check_active_users_window() {
+    assert!(check_active_users_window(60).is_ok());
+    assert!(check_active_users_window(ACTIVE_USERS_MAX_WINDOW_SECONDS).is_ok());
+    assert!(check_active_users_window(0).is_err());
+    assert!(check_active_users_window(-60).is_err());
+    assert!(check_active_users_window(i32::MAX).is_err());
+  }
+
   #[test]
   fn test_truncate() -> LemmyResult<()> {
     assert_eq!("Hell", truncate_for_db("Hello", 4));
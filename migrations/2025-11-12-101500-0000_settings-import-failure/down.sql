@@ -0,0 +1,4 @@
+DROP TABLE settings_import_failure;
+
+DROP TYPE settings_import_kind_enum;
+
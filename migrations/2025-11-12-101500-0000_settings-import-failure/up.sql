@@ -0,0 +1,17 @@
+-- Items which couldn't be fetched during a user settings import, so they can be retried later.
+CREATE TYPE settings_import_kind_enum AS enum (
+    'FollowedCommunity',
+    'SavedPost',
+    'SavedComment',
+    'BlockedCommunity',
+    'BlockedPerson'
+);
+
+CREATE TABLE settings_import_failure (
+    local_user_id int REFERENCES local_user ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    kind settings_import_kind_enum NOT NULL,
+    ap_id text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (local_user_id, kind, ap_id)
+);
+
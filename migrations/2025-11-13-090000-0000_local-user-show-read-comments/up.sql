@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    ADD COLUMN show_read_comments boolean NOT NULL DEFAULT TRUE;
+
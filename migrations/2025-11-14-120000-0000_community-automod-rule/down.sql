@@ -0,0 +1,4 @@
+DROP TABLE community_automod_rule;
+
+DROP TYPE automod_action_enum;
+
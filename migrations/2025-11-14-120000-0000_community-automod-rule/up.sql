@@ -0,0 +1,17 @@
+-- Keyword rules which community moderators can use to act on new comments automatically.
+CREATE TYPE automod_action_enum AS enum (
+    'Remove',
+    'Flag',
+    'RequireApproval'
+);
+
+CREATE TABLE community_automod_rule (
+    id serial PRIMARY KEY,
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    pattern text NOT NULL,
+    action automod_action_enum NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_community_automod_rule_community ON community_automod_rule (community_id);
+
@@ -0,0 +1,8 @@
+ALTER TABLE comment
+    DROP COLUMN pending_approval;
+
+ALTER TABLE community
+    DROP COLUMN require_comment_approval_for_new_members,
+    DROP COLUMN new_member_comment_threshold,
+    DROP COLUMN new_member_account_age_days;
+
@@ -0,0 +1,13 @@
+-- Communities can hold comments from new members until a mod approves them.
+ALTER TABLE community
+    ADD COLUMN require_comment_approval_for_new_members boolean NOT NULL DEFAULT FALSE,
+    ADD COLUMN new_member_comment_threshold int,
+    ADD COLUMN new_member_account_age_days int;
+
+ALTER TABLE comment
+    ADD COLUMN pending_approval boolean NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_comment_pending_approval ON comment (published_at)
+WHERE
+    pending_approval;
+
@@ -0,0 +1,33 @@
+DELETE FROM modlog
+WHERE kind = 'ModResolveCommunityReports';
+
+ALTER TYPE modlog_kind RENAME TO modlog_kind__;
+
+CREATE TYPE modlog_kind AS enum (
+    'AdminAdd',
+    'AdminBan',
+    'AdminAllowInstance',
+    'AdminBlockInstance',
+    'AdminPurgeComment',
+    'AdminPurgeCommunity',
+    'AdminPurgePerson',
+    'AdminPurgePost',
+    'ModAddToCommunity',
+    'ModBanFromCommunity',
+    'ModFeaturePostCommunity',
+    'AdminFeaturePostSite',
+    'ModChangeCommunityVisibility',
+    'ModLockPost',
+    'ModRemoveComment',
+    'AdminRemoveCommunity',
+    'ModRemovePost',
+    'ModTransferCommunity',
+    'ModLockComment'
+);
+
+ALTER TABLE modlog
+    ALTER COLUMN kind TYPE modlog_kind
+    USING kind::text::modlog_kind;
+
+DROP TYPE modlog_kind__;
+
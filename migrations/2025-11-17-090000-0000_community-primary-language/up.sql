@@ -0,0 +1,5 @@
+-- Default language for new comments in a community, when none is given.
+ALTER TABLE community
+    ADD COLUMN primary_language_id int REFERENCES
+    LANGUAGE ON UPDATE CASCADE ON DELETE SET NULL;
+
@@ -0,0 +1,3 @@
+ALTER TABLE comment
+    DROP COLUMN pending_delete_until;
+
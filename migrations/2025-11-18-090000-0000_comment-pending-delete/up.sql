@@ -0,0 +1,9 @@
+-- Comments deleted by their creator can be restored until this time, after which the delete is
+-- finalized.
+ALTER TABLE comment
+    ADD COLUMN pending_delete_until timestamptz;
+
+CREATE INDEX idx_comment_pending_delete_until ON comment (pending_delete_until)
+WHERE
+    pending_delete_until IS NOT NULL;
+
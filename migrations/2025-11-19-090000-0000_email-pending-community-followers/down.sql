@@ -0,0 +1,3 @@
+ALTER TABLE local_user
+    DROP COLUMN email_pending_community_followers;
+
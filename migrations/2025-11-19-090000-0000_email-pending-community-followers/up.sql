@@ -0,0 +1,4 @@
+-- Moderators can opt in to receiving an email when a new follower is waiting for approval.
+ALTER TABLE local_user
+    ADD COLUMN email_pending_community_followers boolean DEFAULT FALSE NOT NULL;
+
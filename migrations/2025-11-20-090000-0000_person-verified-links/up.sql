@@ -0,0 +1,4 @@
+-- Links from the bio which link back to the profile with rel="me".
+ALTER TABLE person
+    ADD COLUMN verified_links text[] NOT NULL DEFAULT '{}';
+
@@ -0,0 +1,9 @@
+-- Emoji reactions on comments, in addition to votes. Each person can add each emoji once.
+CREATE TABLE comment_reaction (
+    comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    emoji text NOT NULL,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (comment_id, person_id, emoji)
+);
+
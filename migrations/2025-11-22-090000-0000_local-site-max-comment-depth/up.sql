@@ -0,0 +1,4 @@
+-- Maximum nesting depth of comment replies, which admins can lower below the hardcoded limit.
+ALTER TABLE local_site
+    ADD COLUMN max_comment_depth integer DEFAULT 50 NOT NULL;
+
@@ -0,0 +1,11 @@
+-- In Q&A communities the post creator or a mod can mark one reply as the accepted answer.
+ALTER TABLE comment
+    ADD COLUMN is_answer boolean DEFAULT FALSE NOT NULL;
+
+CREATE UNIQUE INDEX idx_comment_is_answer_post ON comment (post_id)
+WHERE
+    is_answer;
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModMarkCommentAsAnswer';
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN auto_lock_posts_after_seconds;
+
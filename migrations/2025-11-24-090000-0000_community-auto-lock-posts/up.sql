@@ -0,0 +1,4 @@
+-- Communities can stop new comments on posts older than this, without locking them.
+ALTER TABLE community
+    ADD COLUMN auto_lock_posts_after_seconds int;
+
@@ -0,0 +1,2 @@
+DROP TABLE person_report_stats;
+
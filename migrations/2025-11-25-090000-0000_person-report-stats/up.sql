@@ -0,0 +1,40 @@
+-- How many reports of each person were resolved by local mods, and how many of those were valid.
+-- This is kept separate from the person table, as it's only visible to mods.
+CREATE TABLE person_report_stats (
+    person_id int PRIMARY KEY REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE,
+    resolved_count int NOT NULL DEFAULT 0,
+    valid_count int NOT NULL DEFAULT 0,
+    trust_score real GENERATED ALWAYS AS (valid_count::real / nullif(resolved_count, 0)) STORED
+);
+
+-- Backfill from the reports which were already resolved
+INSERT INTO person_report_stats (person_id, resolved_count, valid_count)
+SELECT
+    creator_id,
+    count(*),
+    count(*) FILTER (WHERE removed)
+FROM (
+    SELECT
+        post_report.creator_id,
+        post.removed
+    FROM
+        post_report
+        INNER JOIN post ON post.id = post_report.post_id
+        INNER JOIN person ON person.id = post_report.resolver_id
+    WHERE
+        post_report.resolved
+        AND person.local
+    UNION ALL
+    SELECT
+        comment_report.creator_id,
+        comment.removed
+    FROM
+        comment_report
+        INNER JOIN comment ON comment.id = comment_report.comment_id
+        INNER JOIN person ON person.id = comment_report.resolver_id
+    WHERE
+        comment_report.resolved
+        AND person.local) AS resolved_report
+GROUP BY
+    creator_id;
+
@@ -0,0 +1,7 @@
+ALTER TABLE federation_queue_state
+    DROP COLUMN last_error;
+
+DROP INDEX idx_sent_activity_published_at;
+
+DROP INDEX idx_sent_activity_object_ids;
+
@@ -0,0 +1,9 @@
+ALTER TABLE federation_queue_state
+    ADD COLUMN last_error text;
+
+CREATE INDEX idx_sent_activity_published_at ON sent_activity (published_at);
+
+-- The ids which an activity can refer to its object with, directly or nested in an undo or
+-- announce. Needs to match the expression in `SentActivity::list_for_object`.
+CREATE INDEX idx_sent_activity_object_ids ON sent_activity USING gin ((ARRAY[data ->> 'object', (data -> 'object') ->> 'id', (data -> 'object') ->> 'object', ((data -> 'object') -> 'object') ->> 'id']));
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN read_only bool NOT NULL DEFAULT FALSE;
+
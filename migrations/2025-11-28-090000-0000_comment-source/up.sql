@@ -0,0 +1,7 @@
+-- The markdown of local comments as submitted by their creator, before slurs were removed and links
+-- rewritten. Only visible to the creator, for editing.
+CREATE TABLE comment_source (
+    comment_id int PRIMARY KEY REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL
+);
+
@@ -0,0 +1,3 @@
+ALTER TABLE comment
+    DROP COLUMN scheduled_publish_time_at;
+
@@ -0,0 +1,7 @@
+ALTER TABLE comment
+    ADD COLUMN scheduled_publish_time_at timestamptz;
+
+CREATE INDEX idx_comment_scheduled_publish_time_at ON comment (scheduled_publish_time_at)
+WHERE
+    scheduled_publish_time_at IS NOT NULL;
+
@@ -0,0 +1,9 @@
+ALTER TABLE community
+    DROP COLUMN welcome_message;
+
+ALTER TABLE community_actions
+    DROP COLUMN welcomed_at;
+
+ALTER TABLE local_user
+    DROP COLUMN receive_community_welcome_messages;
+
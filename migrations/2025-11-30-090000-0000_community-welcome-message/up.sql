@@ -0,0 +1,9 @@
+ALTER TABLE community
+    ADD COLUMN welcome_message text;
+
+ALTER TABLE community_actions
+    ADD COLUMN welcomed_at timestamptz;
+
+ALTER TABLE local_user
+    ADD COLUMN receive_community_welcome_messages bool NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community_actions
+    ADD COLUMN comment_sort_type comment_sort_type_enum;
+
@@ -0,0 +1,5 @@
+DROP TABLE post_subscriber_notified;
+
+ALTER TABLE post_actions
+    DROP COLUMN coalesce_notifications;
+
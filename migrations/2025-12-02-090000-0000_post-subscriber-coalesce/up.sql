@@ -0,0 +1,13 @@
+-- Lets post subscribers get a single notification for a burst of comments by the same person.
+ALTER TABLE post_actions
+    ADD COLUMN coalesce_notifications boolean;
+
+-- When a subscriber was last notified about a comment by the given creator on the post.
+CREATE TABLE post_subscriber_notified (
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    creator_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    notified_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (post_id, person_id, creator_id)
+);
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN hide_scores_until_voted;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN hide_scores_until_voted bool NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,3 @@
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModMoveComment';
+
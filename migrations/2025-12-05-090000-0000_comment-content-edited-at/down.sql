@@ -0,0 +1,3 @@
+ALTER TABLE comment
+    DROP COLUMN content_edited_at;
+
@@ -0,0 +1,19 @@
+-- Unlike updated_at, this is only set when the comment content is edited, and not eg when it is
+-- removed or distinguished.
+ALTER TABLE comment
+    ADD COLUMN content_edited_at timestamptz;
+
+-- Best effort backfill, previous edits can't be distinguished from other updates.
+UPDATE
+    comment
+SET
+    content_edited_at = updated_at
+WHERE
+    updated_at IS NOT NULL
+    AND NOT deleted
+    AND NOT removed;
+
+CREATE INDEX idx_comment_content_edited_at ON comment (content_edited_at)
+WHERE
+    content_edited_at IS NOT NULL;
+
@@ -0,0 +1,12 @@
+-- Unpublished comments which are saved on the server, so they can be continued on another device.
+-- These are private to the author and never federated.
+CREATE TABLE comment_draft (
+    id serial PRIMARY KEY,
+    person_id int REFERENCES person ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    post_id int REFERENCES post ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    parent_id int REFERENCES comment ON UPDATE CASCADE ON DELETE CASCADE,
+    content text NOT NULL,
+    updated_at timestamptz NOT NULL DEFAULT now(),
+    UNIQUE NULLS NOT DISTINCT (person_id, post_id, parent_id)
+);
+
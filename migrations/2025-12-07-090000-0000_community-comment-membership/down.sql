@@ -0,0 +1,6 @@
+ALTER TABLE community
+    DROP COLUMN comments_require_membership;
+
+ALTER TABLE community_actions
+    DROP COLUMN became_member_at;
+
@@ -0,0 +1,8 @@
+-- Lets communities separate reading from participating. Accepted followers can still read, but
+-- only members approved by a mod can comment.
+ALTER TABLE community
+    ADD COLUMN comments_require_membership bool NOT NULL DEFAULT FALSE;
+
+ALTER TABLE community_actions
+    ADD COLUMN became_member_at timestamptz;
+
@@ -0,0 +1,39 @@
+ALTER TABLE community
+    DROP COLUMN auto_undistinguish_below_score;
+
+DELETE FROM modlog
+WHERE kind = 'ModDistinguishComment';
+
+ALTER TYPE modlog_kind RENAME TO modlog_kind__;
+
+CREATE TYPE modlog_kind AS enum (
+    'AdminAdd',
+    'AdminBan',
+    'AdminAllowInstance',
+    'AdminBlockInstance',
+    'AdminPurgeComment',
+    'AdminPurgeCommunity',
+    'AdminPurgePerson',
+    'AdminPurgePost',
+    'ModAddToCommunity',
+    'ModBanFromCommunity',
+    'ModFeaturePostCommunity',
+    'AdminFeaturePostSite',
+    'ModChangeCommunityVisibility',
+    'ModLockPost',
+    'ModRemoveComment',
+    'AdminRemoveCommunity',
+    'ModRemovePost',
+    'ModTransferCommunity',
+    'ModLockComment',
+    'ModResolveCommunityReports',
+    'ModMarkCommentAsAnswer',
+    'ModMoveComment'
+);
+
+ALTER TABLE modlog
+    ALTER COLUMN kind TYPE modlog_kind
+    USING kind::text::modlog_kind;
+
+DROP TYPE modlog_kind__;
+
@@ -0,0 +1,7 @@
+-- Distinguished comments whose score drops below this are undistinguished automatically.
+ALTER TABLE community
+    ADD COLUMN auto_undistinguish_below_score int;
+
+ALTER TYPE modlog_kind
+    ADD VALUE 'ModDistinguishComment';
+
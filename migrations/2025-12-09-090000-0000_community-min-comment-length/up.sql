@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN min_comment_length int;
+
@@ -0,0 +1,10 @@
+-- an association between a comment and a community tag, set by the comment author
+CREATE TABLE comment_tag (
+    comment_id int NOT NULL REFERENCES comment (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    tag_id int NOT NULL REFERENCES tag (id) ON UPDATE CASCADE ON DELETE CASCADE,
+    published_at timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY (comment_id, tag_id)
+);
+
+CREATE INDEX idx_comment_tag_tag ON comment_tag (tag_id);
+
@@ -0,0 +1,9 @@
+-- New local comments are only federated once this time is over, so that comments which are
+-- deleted right away never leave the instance.
+ALTER TABLE comment
+    ADD COLUMN pending_federation_until timestamptz;
+
+CREATE INDEX idx_comment_pending_federation_until ON comment (pending_federation_until)
+WHERE
+    pending_federation_until IS NOT NULL;
+
@@ -0,0 +1,3 @@
+ALTER TABLE post_actions
+    DROP COLUMN notifications_muted;
+
@@ -0,0 +1,4 @@
+-- Silences notifications for a post without changing the subscription itself.
+ALTER TABLE post_actions
+    ADD COLUMN notifications_muted boolean;
+
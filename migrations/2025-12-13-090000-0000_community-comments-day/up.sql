@@ -0,0 +1,5 @@
+-- Number of comments in the community during the last day, updated by a scheduled task. Used to
+-- list communities by trending comment activity.
+ALTER TABLE community
+    ADD COLUMN comments_day int NOT NULL DEFAULT 0;
+
@@ -0,0 +1,7 @@
+-- If a community has any allowed domains, comments by non-mods may only link to these domains.
+CREATE TABLE community_allowed_domain (
+    community_id int REFERENCES community ON UPDATE CASCADE ON DELETE CASCADE NOT NULL,
+    domain text NOT NULL,
+    PRIMARY KEY (community_id, domain)
+);
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN downvotes_enabled bool NOT NULL DEFAULT TRUE;
+
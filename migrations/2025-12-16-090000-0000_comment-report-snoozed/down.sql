@@ -0,0 +1,3 @@
+ALTER TABLE comment_report
+    DROP COLUMN snoozed_until;
+
@@ -0,0 +1,3 @@
+ALTER TABLE comment_report
+    ADD COLUMN snoozed_until timestamptz;
+
@@ -0,0 +1,3 @@
+ALTER TYPE comment_sort_type_enum
+    ADD VALUE 'Received';
+
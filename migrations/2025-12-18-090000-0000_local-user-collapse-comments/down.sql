@@ -0,0 +1,4 @@
+ALTER TABLE local_user
+    DROP COLUMN collapse_comments_account_age_days,
+    DROP COLUMN collapse_comments_below_score;
+
@@ -0,0 +1,4 @@
+ALTER TABLE local_user
+    ADD COLUMN collapse_comments_account_age_days int,
+    ADD COLUMN collapse_comments_below_score int;
+
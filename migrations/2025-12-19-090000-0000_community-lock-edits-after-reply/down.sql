@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN lock_edits_after_reply;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN lock_edits_after_reply bool NOT NULL DEFAULT FALSE;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN show_creator_instance bool NOT NULL DEFAULT TRUE;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    DROP COLUMN pinned_comment_id;
+
@@ -0,0 +1,3 @@
+ALTER TABLE community
+    ADD COLUMN pinned_comment_id int REFERENCES comment ON UPDATE CASCADE ON DELETE SET NULL;
+